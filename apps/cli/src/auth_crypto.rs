@@ -0,0 +1,96 @@
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{Context, Result, bail};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const KEY_ENV: &str = "ATLAS_AUTH_STORE_KEY";
+const KEYRING_SERVICE: &str = "atlas-cli";
+const KEYRING_USER: &str = "auth-store-key";
+const CIPHER_NAME: &str = "aes-256-gcm";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EncryptedPayload {
+    pub cipher: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+// `ATLAS_AUTH_STORE_KEY` wins so headless CI can supply a key; otherwise the key
+// lives in the OS keyring and is generated on first save when `create` is set.
+pub fn resolve_key(create: bool) -> Option<[u8; 32]> {
+    if let Some(value) = std::env::var(KEY_ENV)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+    {
+        return Some(Sha256::digest(value.trim().as_bytes()).into());
+    }
+
+    let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).ok()?;
+    match entry.get_password() {
+        Ok(encoded) => decode_key(&encoded),
+        Err(keyring::Error::NoEntry) if create => {
+            let mut key = [0u8; 32];
+            rand::thread_rng().fill_bytes(&mut key);
+            entry.set_password(&hex::encode(key)).ok()?;
+            Some(key)
+        }
+        Err(_) => None,
+    }
+}
+
+pub fn encrypt(key: &[u8; 32], plaintext: &[u8]) -> Result<EncryptedPayload> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt auth store"))?;
+    Ok(EncryptedPayload {
+        cipher: CIPHER_NAME.to_string(),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    })
+}
+
+pub fn decrypt(key: &[u8; 32], payload: &EncryptedPayload) -> Result<Vec<u8>> {
+    if payload.cipher != CIPHER_NAME {
+        bail!("Unsupported auth store cipher '{}'", payload.cipher);
+    }
+    let nonce = hex::decode(&payload.nonce).context("Invalid auth store nonce")?;
+    if nonce.len() != NONCE_LEN {
+        bail!("Invalid auth store nonce length");
+    }
+    let ciphertext = hex::decode(&payload.ciphertext).context("Invalid auth store ciphertext")?;
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "Failed to decrypt auth store. Check {} or the OS keyring, or run `atlas login` again.",
+                KEY_ENV
+            )
+        })
+}
+
+fn decode_key(encoded: &str) -> Option<[u8; 32]> {
+    hex::decode(encoded.trim()).ok()?.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decrypt, encrypt};
+
+    #[test]
+    fn encrypted_payload_round_trips() {
+        let key = [7u8; 32];
+        let payload = encrypt(&key, b"{\"sessions\":{}}").expect("encrypt");
+        assert_eq!(
+            decrypt(&key, &payload).expect("decrypt"),
+            b"{\"sessions\":{}}"
+        );
+        assert!(decrypt(&[8u8; 32], &payload).is_err());
+    }
+}
@@ -1,13 +1,15 @@
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result, bail};
-use atlas_client::device_code::{
-    DEFAULT_ATLAS_DEVICE_CLIENT_ID, DEFAULT_ATLAS_HUB_URL, normalize_hub_url,
-};
+use atlas_client::device_code::{DEFAULT_ATLAS_DEVICE_CLIENT_ID, DEFAULT_ATLAS_HUB_URL};
+use atlas_client::hub_url::normalize_hub_url_string;
 use serde::{Deserialize, Serialize};
 
+use crate::auth_crypto;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CliAuthSession {
     pub access_token: String,
@@ -21,11 +23,35 @@ pub struct CliAuthSession {
     pub created_at: u64,
 }
 
-pub fn resolve_hub_url(hub_url_override: Option<String>) -> String {
-    normalize_optional(hub_url_override)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CliAuthStore {
+    #[serde(default)]
+    pub active_hub: Option<String>,
+    pub sessions: BTreeMap<String, CliAuthSession>,
+}
+
+impl CliAuthStore {
+    fn from_legacy_session(session: CliAuthSession) -> Result<Self> {
+        let hub_url = normalize_hub_url_string(&session.hub_url)?;
+        let mut sessions = BTreeMap::new();
+        sessions.insert(hub_url.clone(), session);
+        Ok(Self {
+            active_hub: Some(hub_url),
+            sessions,
+        })
+    }
+}
+
+pub fn resolve_hub_url(hub_url_override: Option<String>) -> Result<String> {
+    let hub_url = normalize_optional(hub_url_override)
         .or_else(|| normalize_optional(std::env::var("ATLAS_HUB_URL").ok()))
-        .map(|value| normalize_hub_url(&value))
-        .unwrap_or_else(|| DEFAULT_ATLAS_HUB_URL.to_string())
+        .or_else(active_hub_url)
+        .unwrap_or_else(|| DEFAULT_ATLAS_HUB_URL.to_string());
+    Ok(normalize_hub_url_string(&hub_url)?)
+}
+
+pub fn active_hub_url() -> Option<String> {
+    load_cli_auth_store().ok()?.active_hub
 }
 
 pub fn resolve_device_client_id(client_id_override: Option<String>) -> String {
@@ -34,57 +60,172 @@ pub fn resolve_device_client_id(client_id_override: Option<String>) -> String {
         .unwrap_or_else(|| DEFAULT_ATLAS_DEVICE_CLIENT_ID.to_string())
 }
 
-pub fn load_cli_auth_session() -> Result<Option<CliAuthSession>> {
-    let path = auth_store_path()?;
+pub fn load_cli_auth_store() -> Result<CliAuthStore> {
+    load_store_at(&auth_store_path()?, || auth_crypto::resolve_key(false))
+}
+
+fn load_store_at(
+    path: &Path,
+    resolve_key: impl FnOnce() -> Option<[u8; 32]>,
+) -> Result<CliAuthStore> {
     if !path.exists() {
-        return Ok(None);
+        return Ok(CliAuthStore::default());
+    }
+    let mut bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut encrypted = false;
+    if let Ok(payload) = serde_json::from_slice::<auth_crypto::EncryptedPayload>(&bytes) {
+        let key = resolve_key().with_context(|| {
+            format!(
+                "{} is encrypted but no key is available. Set ATLAS_AUTH_STORE_KEY or unlock the OS keyring.",
+                path.display()
+            )
+        })?;
+        bytes = auth_crypto::decrypt(&key, &payload)?;
+        encrypted = true;
+    }
+    if let Ok(store) = serde_json::from_slice::<CliAuthStore>(&bytes) {
+        return Ok(store);
     }
-    let bytes = fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
-    let session = serde_json::from_slice::<CliAuthSession>(&bytes)
+
+    let legacy = serde_json::from_slice::<CliAuthSession>(&bytes)
         .with_context(|| format!("Failed to parse {}", path.display()))?;
-    Ok(Some(session))
+    let store = CliAuthStore::from_legacy_session(legacy)?;
+    if !encrypted {
+        save_cli_auth_store(&store)?;
+    }
+    Ok(store)
 }
 
-pub fn save_cli_auth_session(session: &CliAuthSession) -> Result<()> {
+pub fn save_cli_auth_store(store: &CliAuthStore) -> Result<()> {
     let path = auth_store_path()?;
+    if store.sessions.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        return Ok(());
+    }
+
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .with_context(|| format!("Failed to create {}", parent.display()))?;
     }
-    let payload =
-        serde_json::to_vec_pretty(session).context("Failed to serialize auth session file")?;
+    let mut payload =
+        serde_json::to_vec_pretty(store).context("Failed to serialize auth session file")?;
+    match auth_crypto::resolve_key(true) {
+        Some(key) => {
+            let encrypted = auth_crypto::encrypt(&key, &payload)?;
+            payload = serde_json::to_vec_pretty(&encrypted)
+                .context("Failed to serialize encrypted auth session file")?;
+        }
+        None => eprintln!(
+            "Warning: no OS keyring available; storing credentials unencrypted in {}. Set ATLAS_AUTH_STORE_KEY to encrypt them.",
+            path.display()
+        ),
+    }
     fs::write(&path, payload).with_context(|| format!("Failed to write {}", path.display()))?;
     Ok(())
 }
 
-pub fn remove_cli_auth_session() -> Result<()> {
-    let path = auth_store_path()?;
-    if path.exists() {
-        fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+pub fn load_cli_auth_session(hub_url: Option<&str>) -> Result<Option<CliAuthSession>> {
+    let mut store = load_cli_auth_store()?;
+    let hub_url = hub_url.map(normalize_hub_url_string).transpose()?;
+    let Some(hub_url) = hub_url.or(store.active_hub.clone()) else {
+        return Ok(None);
+    };
+    Ok(store.sessions.remove(&hub_url))
+}
+
+pub fn save_cli_auth_session(session: &CliAuthSession) -> Result<()> {
+    let mut store = load_store_for_update(&auth_store_path()?, || auth_crypto::resolve_key(false))?;
+    let hub_url = normalize_hub_url_string(&session.hub_url)?;
+    store.sessions.insert(hub_url.clone(), session.clone());
+    store.active_hub = Some(hub_url);
+    save_cli_auth_store(&store)
+}
+
+// A store that cannot be read (e.g. the key changed) is moved aside to `.bak`
+// instead of being overwritten, so the other hubs' sessions can be recovered.
+fn load_store_for_update(
+    path: &Path,
+    resolve_key: impl FnOnce() -> Option<[u8; 32]>,
+) -> Result<CliAuthStore> {
+    match load_store_at(path, resolve_key) {
+        Ok(store) => Ok(store),
+        Err(err) => {
+            let backup = path.with_extension("json.bak");
+            fs::rename(path, &backup).with_context(|| {
+                format!(
+                    "Failed to move unreadable {} to {}",
+                    path.display(),
+                    backup.display()
+                )
+            })?;
+            eprintln!(
+                "Warning: could not read {} ({err:#}); moved it to {} and starting a new store.",
+                path.display(),
+                backup.display()
+            );
+            Ok(CliAuthStore::default())
+        }
     }
-    Ok(())
 }
 
-pub fn require_access_token_for_hub(hub_url: &str) -> Result<String> {
-    let requested_hub = normalize_hub_url(hub_url);
-    let session =
-        load_cli_auth_session()?.context("No CLI auth session found. Run `atlas login` first.")?;
+pub fn remove_cli_auth_session(hub_url: Option<&str>) -> Result<Option<String>> {
+    let mut store = load_cli_auth_store()?;
+    let hub_url = hub_url.map(normalize_hub_url_string).transpose()?;
+    let Some(hub_url) = hub_url.or(store.active_hub.clone()) else {
+        return Ok(None);
+    };
+    if store.sessions.remove(&hub_url).is_none() {
+        return Ok(None);
+    }
 
-    if normalize_hub_url(&session.hub_url) != requested_hub {
+    if store.active_hub.as_deref() == Some(hub_url.as_str()) {
+        store.active_hub = store.sessions.keys().next().cloned();
+    }
+    save_cli_auth_store(&store)?;
+    Ok(Some(hub_url))
+}
+
+pub fn set_active_hub(hub_url: &str) -> Result<String> {
+    let mut store = load_cli_auth_store()?;
+    let hub_url = normalize_hub_url_string(hub_url)?;
+    if !store.sessions.contains_key(&hub_url) {
         bail!(
-            "CLI auth session is for {} but current hub is {}. Run `atlas login --hub-url {}`.",
-            session.hub_url,
-            requested_hub,
-            requested_hub
+            "No CLI auth session for {}. Run `atlas login --hub {}` first.",
+            hub_url,
+            hub_url
         );
     }
+    store.active_hub = Some(hub_url.clone());
+    save_cli_auth_store(&store)?;
+    Ok(hub_url)
+}
+
+pub fn require_access_token_for_hub(hub_url: &str) -> Result<String> {
+    let requested_hub = normalize_hub_url_string(hub_url)?;
+    let store = load_cli_auth_store()?;
+    if store.sessions.is_empty() {
+        bail!("No CLI auth session found. Run `atlas login` first.");
+    }
+    let session = store.sessions.get(&requested_hub).with_context(|| {
+        format!(
+            "No CLI auth session for {}. Run `atlas login --hub {}`.",
+            requested_hub, requested_hub
+        )
+    })?;
 
     let now = unix_timestamp();
     if session.expires_at > 0 && now + 30 >= session.expires_at {
-        bail!("CLI auth session expired. Run `atlas login` again.");
+        bail!(
+            "CLI auth session for {} expired. Run `atlas login --hub {}` again.",
+            requested_hub,
+            requested_hub
+        );
     }
 
-    Ok(session.access_token)
+    Ok(session.access_token.clone())
 }
 
 pub fn unix_timestamp() -> u64 {
@@ -109,3 +250,40 @@ fn normalize_optional(value: Option<String>) -> Option<String> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{CliAuthSession, CliAuthStore, load_store_at, load_store_for_update};
+    use crate::auth_crypto;
+
+    #[test]
+    fn legacy_session_file_is_not_mistaken_for_a_keyed_store() {
+        let legacy = br#"{"access_token":"t","token_type":"Bearer","expires_at":0,"hub_url":"https://hub.example.com/","client_id":"c","scope":null,"created_at":1}"#;
+
+        assert!(serde_json::from_slice::<CliAuthStore>(legacy).is_err());
+        let session = serde_json::from_slice::<CliAuthSession>(legacy).expect("legacy session");
+        let store = CliAuthStore::from_legacy_session(session).expect("valid hub url");
+        assert_eq!(store.active_hub.as_deref(), Some("https://hub.example.com"));
+        assert!(store.sessions.contains_key("https://hub.example.com"));
+    }
+
+    #[test]
+    fn store_encrypted_with_another_key_is_backed_up_before_replacing() {
+        let dir = std::env::temp_dir().join(format!("atlas-auth-store-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create dir");
+        let path = dir.join("cli-auth.json");
+        let payload = auth_crypto::encrypt(&[1u8; 32], br#"{"sessions":{}}"#).expect("encrypt");
+        let contents = serde_json::to_vec(&payload).expect("serialize");
+        std::fs::write(&path, &contents).expect("write store");
+
+        assert!(load_store_at(&path, || Some([2u8; 32])).is_err());
+        let store = load_store_for_update(&path, || Some([2u8; 32])).expect("backed up");
+        assert!(store.sessions.is_empty());
+        assert!(!path.exists());
+        assert_eq!(
+            std::fs::read(dir.join("cli-auth.json.bak")).expect("backup"),
+            contents
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
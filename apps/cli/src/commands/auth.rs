@@ -11,6 +11,7 @@ use atlas_client::device_code::{
 };
 use clap::{Args, Subcommand};
 use reqwest::blocking::Client;
+use serde::Serialize;
 
 use crate::auth_store::{self, CliAuthSession};
 
@@ -19,28 +20,50 @@ pub enum AuthCommand {
     #[command(alias = "login")]
     Signin(SignInArgs),
     #[command(alias = "logout")]
-    Signout,
-    Status,
+    Signout(SignOutArgs),
+    Status(StatusArgs),
+    Hub(HubArgs),
 }
 
 #[derive(Args)]
 pub struct SignInArgs {
-    #[arg(long)]
+    #[arg(long = "hub", alias = "hub-url", value_name = "URL")]
     hub_url: Option<String>,
     #[arg(long)]
     client_id: Option<String>,
 }
 
+#[derive(Args)]
+pub struct SignOutArgs {
+    #[arg(long = "hub", value_name = "URL")]
+    hub_url: Option<String>,
+}
+
+#[derive(Args)]
+pub struct StatusArgs {
+    #[arg(long = "hub", value_name = "URL")]
+    hub_url: Option<String>,
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Args)]
+pub struct HubArgs {
+    #[arg(value_name = "URL")]
+    hub_url: Option<String>,
+}
+
 pub fn run(command: AuthCommand) -> Result<()> {
     match command {
         AuthCommand::Signin(args) => signin(args),
-        AuthCommand::Signout => signout(),
-        AuthCommand::Status => status(),
+        AuthCommand::Signout(args) => signout(args),
+        AuthCommand::Status(args) => status(args),
+        AuthCommand::Hub(args) => hub(args),
     }
 }
 
 fn signin(args: SignInArgs) -> Result<()> {
-    let hub_url = auth_store::resolve_hub_url(args.hub_url);
+    let hub_url = auth_store::resolve_hub_url(args.hub_url)?;
     let client_id = auth_store::resolve_device_client_id(args.client_id);
     let client = Client::new();
 
@@ -105,26 +128,155 @@ fn signin(args: SignInArgs) -> Result<()> {
     }
 }
 
-fn signout() -> Result<()> {
-    auth_store::remove_cli_auth_session()?;
-    println!("Signed out.");
+/// Exchanges the stored refresh token for a new access token and saves it.
+/// Fails when the session has no refresh token or the hub rejects it.
+pub fn refresh_access_token(hub_url: &str) -> Result<String> {
+    let hub_url = atlas_client::hub_url::normalize_hub_url_string(hub_url)?;
+    let mut store = auth_store::load_cli_auth_store()?;
+    let session = store
+        .sessions
+        .get_mut(&hub_url)
+        .with_context(|| format!("No CLI auth session for {hub_url}."))?;
+    let refresh_token = session
+        .refresh_token
+        .clone()
+        .with_context(|| format!("CLI auth session for {hub_url} has no refresh token."))?;
+
+    let response = Client::new()
+        .post(format!("{hub_url}/api/auth/oauth2/token"))
+        .form(&[
+            ("client_id", session.client_id.as_str()),
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token.as_str()),
+        ])
+        .send()
+        .context("Failed to refresh CLI auth session")?
+        .error_for_status()
+        .context("Hub rejected the CLI refresh token")?;
+    let token: StandardDeviceTokenResponse = response
+        .json()
+        .context("Failed to parse refreshed CLI auth token")?;
+
+    let now = auth_store::unix_timestamp();
+    session.access_token = token.access_token.clone();
+    session.expires_at = now.saturating_add(token.expires_in);
+    session.refresh_token = token.refresh_token.or(Some(refresh_token));
+    auth_store::save_cli_auth_store(&store)?;
+    Ok(token.access_token)
+}
+
+fn signout(args: SignOutArgs) -> Result<()> {
+    match auth_store::remove_cli_auth_session(args.hub_url.as_deref())? {
+        Some(hub_url) => println!("Signed out of {}.", hub_url),
+        None => println!("Not signed in."),
+    }
     Ok(())
 }
 
-fn status() -> Result<()> {
-    let session = auth_store::load_cli_auth_session()?;
-    let Some(session) = session else {
-        println!("Not signed in.");
+fn hub(args: HubArgs) -> Result<()> {
+    if let Some(hub_url) = args.hub_url {
+        let active = auth_store::set_active_hub(&hub_url)?;
+        println!("Active hub set to {}.", active);
         return Ok(());
+    }
+
+    let store = auth_store::load_cli_auth_store()?;
+    if store.sessions.is_empty() {
+        println!("Not signed in to any hub.");
+        return Ok(());
+    }
+    for hub_url in store.sessions.keys() {
+        let marker = if store.active_hub.as_deref() == Some(hub_url.as_str()) {
+            "*"
+        } else {
+            " "
+        };
+        println!("{} {}", marker, hub_url);
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct StatusReport {
+    signed_in: bool,
+    active: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hub_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_kind: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    token_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_in: Option<u64>,
+    has_refresh_token: bool,
+}
+
+impl StatusReport {
+    fn signed_out() -> Self {
+        Self {
+            signed_in: false,
+            active: false,
+            hub_url: None,
+            client_id: None,
+            scope: None,
+            token_kind: None,
+            token_type: None,
+            expires_at: None,
+            expires_in: None,
+            has_refresh_token: false,
+        }
+    }
+
+    fn from_session(session: CliAuthSession, now: u64) -> Self {
+        let remaining = session.expires_at.saturating_sub(now);
+        Self {
+            signed_in: true,
+            active: session.expires_at == 0 || remaining > 0,
+            hub_url: Some(session.hub_url),
+            client_id: Some(session.client_id),
+            scope: session.scope,
+            token_kind: Some("user"),
+            token_type: Some(session.token_type),
+            expires_at: Some(session.expires_at),
+            expires_in: Some(remaining),
+            has_refresh_token: session
+                .refresh_token
+                .as_deref()
+                .is_some_and(|value| !value.trim().is_empty()),
+        }
+    }
+}
+
+fn status(args: StatusArgs) -> Result<()> {
+    let report = match auth_store::load_cli_auth_session(args.hub_url.as_deref())? {
+        Some(session) => StatusReport::from_session(session, auth_store::unix_timestamp()),
+        None => StatusReport::signed_out(),
     };
 
-    let now = auth_store::unix_timestamp();
-    let remaining = session.expires_at.saturating_sub(now);
-    let state = if remaining > 0 { "active" } else { "expired" };
-    println!("Status: {}", state);
-    println!("Hub: {}", session.hub_url);
-    println!("Client ID: {}", session.client_id);
-    println!("Expires in: {}s", remaining);
+    if args.json {
+        let payload =
+            serde_json::to_string_pretty(&report).context("Failed to serialize auth status")?;
+        println!("{}", payload);
+    } else if report.signed_in {
+        let state = if report.active { "active" } else { "expired" };
+        println!("Status: {}", state);
+        println!("Hub: {}", report.hub_url.as_deref().unwrap_or("-"));
+        println!("Client ID: {}", report.client_id.as_deref().unwrap_or("-"));
+        println!("Expires in: {}s", report.expires_in.unwrap_or_default());
+    }
+
+    if !report.signed_in {
+        bail!("Not signed in.");
+    }
+    if !report.active {
+        bail!("CLI auth session expired. Run `atlas login` again.");
+    }
     Ok(())
 }
 
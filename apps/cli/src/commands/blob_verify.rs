@@ -0,0 +1,155 @@
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use protocol::config::mods::parse_mod_toml;
+use protocol::{Dependency, HashAlgorithm, PackBlob};
+
+use crate::io;
+
+// There is no lockfile yet, so the pointers in the checkout are the record of
+// what the pack should ship.
+pub fn verify_against_checkout(root: &Path, blob: &PackBlob) -> Result<()> {
+    let mut files = BTreeMap::new();
+    io::insert_repo_text_files(&mut files, root)?;
+
+    let mismatches = find_mismatches(&files, blob);
+    if mismatches.is_empty() {
+        return Ok(());
+    }
+    bail!(
+        "The pack does not match the pointers in {}:\n  {}\nRebuild from this checkout or pass --no-verify to publish anyway.",
+        root.display(),
+        mismatches.join("\n  ")
+    );
+}
+
+fn find_mismatches(checkout: &BTreeMap<String, Vec<u8>>, blob: &PackBlob) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    let mut seen = HashSet::new();
+
+    for dependency in &blob.manifest.dependencies {
+        let path = dependency.pointer_path.as_str();
+        seen.insert(path);
+
+        if pointer_hash(blob.files.get(path), dependency.hash.algorithm).as_deref()
+            != Some(dependency.hash.hex.to_ascii_lowercase().as_str())
+        {
+            mismatches.push(format!(
+                "{}: manifest hash differs from the bundled pointer",
+                path
+            ));
+        }
+
+        match pointer_hash(checkout.get(path), dependency.hash.algorithm) {
+            _ if !checkout.contains_key(path) => {
+                mismatches.push(format!("{}: not in the checkout", path));
+            }
+            Some(hex) if hex == dependency.hash.hex.to_ascii_lowercase() => {}
+            Some(hex) => mismatches.push(format!(
+                "{}: {} {} does not match the checkout ({})",
+                path,
+                algorithm_label(dependency),
+                dependency.hash.hex,
+                hex
+            )),
+            None => mismatches.push(format!(
+                "{}: the checkout pointer has no {} hash",
+                path,
+                algorithm_label(dependency)
+            )),
+        }
+    }
+
+    for path in checkout.keys() {
+        if mod_resolver::pointer::is_pointer_path(path).is_some() && !seen.contains(path.as_str()) {
+            mismatches.push(format!("{}: missing from the pack", path));
+        }
+    }
+
+    mismatches
+}
+
+fn pointer_hash(bytes: Option<&Vec<u8>>, algorithm: HashAlgorithm) -> Option<String> {
+    let contents = std::str::from_utf8(bytes?).ok()?;
+    let hashes = parse_mod_toml(contents).ok()?.download.hashes?;
+    let hex = match algorithm {
+        HashAlgorithm::Sha1 => hashes.sha1,
+        HashAlgorithm::Sha256 => hashes.sha256,
+        HashAlgorithm::Sha512 => hashes.sha512,
+    }?;
+    Some(hex.trim().to_ascii_lowercase())
+}
+
+fn algorithm_label(dependency: &Dependency) -> &'static str {
+    match dependency.hash.algorithm {
+        HashAlgorithm::Sha1 => "sha1",
+        HashAlgorithm::Sha256 => "sha256",
+        HashAlgorithm::Sha512 => "sha512",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use protocol::{
+        Dependency, DependencyKind, DependencySide, Hash, HashAlgorithm, Loader, Manifest,
+        PackBlob, PackMetadata, PlatformFilter,
+    };
+
+    use super::find_mismatches;
+
+    fn pointer(sha512: &str) -> Vec<u8> {
+        format!(
+            "[download]\nsource = \"modrinth\"\nproject_id = \"AANobbMI\"\nversion = \"1.0\"\nurl = \"https://cdn.example/sodium.jar\"\n\n[download.hashes]\nsha512 = \"{sha512}\"\n"
+        )
+        .into_bytes()
+    }
+
+    fn blob(sha512: &str) -> PackBlob {
+        PackBlob {
+            metadata: PackMetadata {
+                pack_id: "pack".into(),
+                version: "1".into(),
+                minecraft_version: "1.21.1".into(),
+                loader: Loader::Fabric,
+                loader_version: "0.16.10".into(),
+                name: "Pack".into(),
+                description: String::new(),
+            },
+            manifest: Manifest {
+                dependencies: vec![Dependency {
+                    url: "https://cdn.example/sodium.jar".into(),
+                    hash: Hash {
+                        algorithm: HashAlgorithm::Sha512,
+                        hex: sha512.into(),
+                    },
+                    platform: PlatformFilter::default(),
+                    kind: DependencyKind::Mod,
+                    side: DependencySide::Both,
+                    pointer_path: "mods/sodium.mod.toml".into(),
+                    mirrors: Vec::new(),
+                }],
+            },
+            files: BTreeMap::from([("mods/sodium.mod.toml".to_string(), pointer(sha512))]),
+        }
+    }
+
+    #[test]
+    fn reports_hash_drift_and_missing_pointers() {
+        let mut checkout = BTreeMap::from([("mods/sodium.mod.toml".to_string(), pointer("aa"))]);
+        assert!(find_mismatches(&checkout, &blob("AA")).is_empty());
+
+        let drift = find_mismatches(&checkout, &blob("bb"));
+        assert_eq!(drift.len(), 1);
+        assert!(drift[0].contains("does not match the checkout"));
+
+        checkout.insert("mods/lithium.mod.toml".to_string(), pointer("cc"));
+        let missing = find_mismatches(&checkout, &blob("aa"));
+        assert_eq!(
+            missing,
+            vec!["mods/lithium.mod.toml: missing from the pack"]
+        );
+    }
+}
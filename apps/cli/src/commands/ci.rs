@@ -8,6 +8,10 @@ use reqwest::blocking::Client;
 use serde::Deserialize;
 
 use crate::auth_store;
+use crate::text_diff;
+
+const KEEP_BEGIN_MARKER: &str = "# atlas:keep-begin";
+const KEEP_END_MARKER: &str = "# atlas:keep-end";
 
 #[derive(Subcommand)]
 pub enum CiCommand {
@@ -23,6 +27,10 @@ pub struct CiSyncArgs {
     pack_id: Option<String>,
     #[arg(long)]
     hub_url: Option<String>,
+    #[arg(long, conflicts_with = "check")]
+    dry_run: bool,
+    #[arg(long)]
+    check: bool,
 }
 
 pub fn run(command: CiCommand) -> Result<()> {
@@ -37,7 +45,7 @@ fn run_sync(action: &str, args: CiSyncArgs) -> Result<()> {
         .input
         .canonicalize()
         .context("Failed to resolve input path")?;
-    let hub_url = auth_store::resolve_hub_url(args.hub_url);
+    let hub_url = auth_store::resolve_hub_url(args.hub_url)?;
 
     let mut client = HubClient::new(&hub_url)?;
     if let Ok(token) = auth_store::require_access_token_for_hub(&hub_url) {
@@ -49,16 +57,35 @@ fn run_sync(action: &str, args: CiSyncArgs) -> Result<()> {
     let workflow_path = workflow_response.workflow_path;
     let relative_path = sanitize_relative_path(&workflow_path)?;
     let target_path = root.join(relative_path);
-    let content = workflow_response.content;
+    let existing = std::fs::read_to_string(&target_path).ok();
+    let content = match existing.as_deref() {
+        Some(existing) => merge_keep_blocks(existing, &workflow_response.content),
+        None => workflow_response.content,
+    };
+    let updated = existing.as_deref() != Some(content.as_str());
+
+    if args.check {
+        if updated {
+            bail!(
+                "Atlas CI workflow is out of sync: {}. Run `atlas workflow update`.",
+                target_path.display()
+            );
+        }
+        println!("Atlas CI workflow is up to date: {}", target_path.display());
+        return Ok(());
+    }
+
+    if args.dry_run {
+        print_dry_run(&target_path, existing.as_deref(), &content, updated);
+        return Ok(());
+    }
 
-    let updated = std::fs::read_to_string(&target_path)
-        .map(|existing| existing != content)
-        .unwrap_or(true);
     if updated {
         if let Some(parent) = target_path.parent() {
             std::fs::create_dir_all(parent)
                 .with_context(|| format!("Failed to create {}", parent.display()))?;
         }
+        tracing::debug!("writing {}", target_path.display());
         std::fs::write(&target_path, content)
             .with_context(|| format!("Failed to write {}", target_path.display()))?;
     }
@@ -84,6 +111,83 @@ fn run_sync(action: &str, args: CiSyncArgs) -> Result<()> {
     Ok(())
 }
 
+fn print_dry_run(target_path: &Path, existing: Option<&str>, content: &str, updated: bool) {
+    match existing {
+        None => {
+            println!("Would create {}:", target_path.display());
+            print!("{}", text_diff::render_diff("", content));
+        }
+        Some(_) if !updated => {
+            println!("{} is already up to date.", target_path.display());
+        }
+        Some(existing) => {
+            println!("Would update {}:", target_path.display());
+            print!("{}", text_diff::render_diff(existing, content));
+        }
+    }
+}
+
+// Carries user content between `# atlas:keep-begin <name>` / `# atlas:keep-end <name>`
+// markers from the existing workflow into matching marker blocks of the template.
+fn merge_keep_blocks(existing: &str, generated: &str) -> String {
+    let kept = collect_keep_blocks(existing);
+    if kept.is_empty() {
+        return generated.to_string();
+    }
+
+    let mut merged = Vec::new();
+    let mut skipping: Option<&str> = None;
+    for line in generated.lines() {
+        if let Some(name) = skipping {
+            if keep_marker_name(line, KEEP_END_MARKER) == Some(name) {
+                merged.push(line.to_string());
+                skipping = None;
+            }
+            continue;
+        }
+
+        merged.push(line.to_string());
+        let kept_block = keep_marker_name(line, KEEP_BEGIN_MARKER)
+            .and_then(|name| kept.iter().find(|(kept_name, _)| *kept_name == name));
+        if let Some((name, body)) = kept_block {
+            merged.extend(body.iter().map(|value| value.to_string()));
+            skipping = Some(name);
+        }
+    }
+
+    let mut output = merged.join("\n");
+    if generated.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+fn collect_keep_blocks(content: &str) -> Vec<(&str, Vec<&str>)> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(&str, Vec<&str>)> = None;
+    for line in content.lines() {
+        match current.as_mut() {
+            Some((name, body)) => {
+                if keep_marker_name(line, KEEP_END_MARKER) == Some(*name) {
+                    blocks.extend(current.take());
+                } else {
+                    body.push(line);
+                }
+            }
+            None => {
+                if let Some(name) = keep_marker_name(line, KEEP_BEGIN_MARKER) {
+                    current = Some((name, Vec::new()));
+                }
+            }
+        }
+    }
+    blocks
+}
+
+fn keep_marker_name<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    line.trim().strip_prefix(marker).map(str::trim)
+}
+
 fn sanitize_relative_path(value: &str) -> Result<std::path::PathBuf> {
     let normalized = value.replace('\\', "/");
     if normalized.trim().is_empty() {
@@ -222,7 +326,7 @@ fn parse_owner_repo_path(path: &str) -> Option<(String, String)> {
 }
 
 fn request_linked_github_access_token(client: &HubClient) -> Result<Option<String>> {
-    client.blocking_get_github_token()
+    Ok(client.blocking_get_github_token()?)
 }
 
 fn set_repository_actions_permissions(
@@ -329,3 +433,19 @@ fn enable_repository_workflow(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::merge_keep_blocks;
+
+    #[test]
+    fn merge_preserves_user_content_inside_keep_markers() {
+        let existing = "steps:\n  # atlas:keep-begin extra\n  - run: echo custom\n  # atlas:keep-end extra\n  - run: atlas publish\n";
+        let generated = "steps:\n  - uses: actions/checkout@v4\n  # atlas:keep-begin extra\n  # atlas:keep-end extra\n  - run: atlas publish\n";
+
+        assert_eq!(
+            merge_keep_blocks(existing, generated),
+            "steps:\n  - uses: actions/checkout@v4\n  # atlas:keep-begin extra\n  - run: echo custom\n  # atlas:keep-end extra\n  - run: atlas publish\n"
+        );
+    }
+}
@@ -1,12 +1,14 @@
+use std::io::Read;
 use std::path::PathBuf;
 use std::process::Command;
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use atlas_client::hub::{CiCompleteRequest, HubClient};
 use clap::Args;
 use reqwest::blocking::Client;
 
 use crate::auth_store;
+use crate::commands::{blob_verify, release_diff};
 use crate::config;
 use crate::io;
 
@@ -26,10 +28,21 @@ pub struct DeployArgs {
     channel: Option<String>,
     #[arg(long)]
     commit_hash: Option<String>,
-    #[arg(long)]
-    input_file: Option<PathBuf>,
+    #[arg(
+        long,
+        alias = "input-file",
+        value_name = "PATH",
+        conflicts_with_all = ["stdin", "zstd_level"]
+    )]
+    artifact: Option<PathBuf>,
+    #[arg(long, conflicts_with = "zstd_level")]
+    stdin: bool,
     #[arg(long, default_value_t = protocol::DEFAULT_ZSTD_LEVEL)]
     zstd_level: i32,
+    #[arg(long, short = 'y')]
+    yes: bool,
+    #[arg(long)]
+    no_verify: bool,
 }
 
 pub fn run(args: DeployArgs) -> Result<()> {
@@ -41,16 +54,25 @@ pub fn run(args: DeployArgs) -> Result<()> {
     let ci_auth = resolve_ci_auth(args.oidc_token, args.deploy_token, &settings.hub_url)?;
     let commit_hash = resolve_commit_hash(&root, args.commit_hash)?;
     let commit_message = resolve_commit_message(&root, &commit_hash);
-    let build_context = resolve_build_context(&root);
     let derived_version = commit_hash.clone();
 
-    let (bytes, pack_id, version) = if let Some(input_file) = args.input_file.clone() {
-        let bytes = io::read_bytes(&input_file)?;
-        let pack_id = settings
-            .pack_id
-            .clone()
-            .context("pack_id is required (use --pack-id or ATLAS_PACK_ID)")?;
-        (bytes, pack_id, derived_version.clone())
+    let prebuilt = if let Some(artifact) = args.artifact.as_ref() {
+        Some(io::read_bytes(artifact)?)
+    } else if args.stdin {
+        Some(read_stdin_artifact()?)
+    } else {
+        None
+    };
+
+    let (bytes, pack_id, version, build_context) = if let Some(bytes) = prebuilt {
+        let metadata = validate_artifact(&bytes, settings.pack_id.as_deref())?;
+        let build_context = build_context_from_metadata(&metadata);
+        (
+            bytes,
+            metadata.pack_id,
+            metadata.version,
+            Some(build_context),
+        )
     } else {
         let build = config::build_pack_bytes(
             &root,
@@ -58,12 +80,32 @@ pub fn run(args: DeployArgs) -> Result<()> {
             Some(derived_version.clone()),
             args.zstd_level,
         )?;
-        (build.bytes, build.metadata.pack_id, build.metadata.version)
+        (
+            build.bytes,
+            build.metadata.pack_id,
+            build.metadata.version,
+            resolve_build_context(&root),
+        )
     };
     let artifact_size = bytes.len() as u64;
+    let blob = protocol::decode_blob(&bytes).context("Failed to decode built pack")?;
+    if !args.no_verify {
+        blob_verify::verify_against_checkout(&root, &blob)?;
+    }
+    let fingerprint = protocol::blob_fingerprint(&blob)?;
 
     let mut hub_client = HubClient::new(&settings.hub_url)?;
     apply_ci_auth_to_client(&mut hub_client, &ci_auth)?;
+
+    if release_diff::requires_confirmation(&settings.channel, args.yes) {
+        let current = release_diff::fetch_live_blob(&hub_client, &pack_id, &settings.channel);
+        release_diff::print_diff(&settings.channel, current.as_ref(), &blob);
+        if !release_diff::confirm(&settings.channel)? {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
     let presign = hub_client.blocking_presign_ci_upload(&pack_id)?;
 
     let upload_client = Client::new();
@@ -96,9 +138,37 @@ pub fn run(args: DeployArgs) -> Result<()> {
         "Published {} (version {}) to {}",
         pack_id, version, settings.channel
     );
+    println!("Fingerprint: {}", fingerprint);
     Ok(())
 }
 
+fn read_stdin_artifact() -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    std::io::stdin()
+        .lock()
+        .read_to_end(&mut bytes)
+        .context("Failed to read artifact from stdin")?;
+    if bytes.is_empty() {
+        bail!("No artifact bytes received on stdin.");
+    }
+    Ok(bytes)
+}
+
+fn validate_artifact(
+    bytes: &[u8],
+    expected_pack_id: Option<&str>,
+) -> Result<protocol::PackMetadata> {
+    let blob = protocol::decode_blob(bytes).context("Artifact is not a valid Atlas pack blob")?;
+    if let Some(expected) = expected_pack_id.filter(|expected| *expected != blob.metadata.pack_id) {
+        bail!(
+            "Artifact was built for pack '{}' but the publish target is '{}'.",
+            blob.metadata.pack_id,
+            expected
+        );
+    }
+    Ok(blob.metadata)
+}
+
 enum CiAuth {
     UserToken(String),
     OidcToken(String),
@@ -217,6 +287,19 @@ struct BuildContext {
     modloader_version: Option<String>,
 }
 
+fn build_context_from_metadata(metadata: &protocol::PackMetadata) -> BuildContext {
+    let modloader = match metadata.loader {
+        protocol::Loader::Fabric => "fabric",
+        protocol::Loader::Forge => "forge",
+        protocol::Loader::Neo => "neoforge",
+    };
+    BuildContext {
+        minecraft_version: metadata.minecraft_version.clone(),
+        modloader: modloader.to_string(),
+        modloader_version: normalize_optional(Some(metadata.loader_version.clone())),
+    }
+}
+
 fn resolve_build_context(root: &std::path::Path) -> Option<BuildContext> {
     let config = config::load_atlas_config(root).ok()?;
     Some(BuildContext {
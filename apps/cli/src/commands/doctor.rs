@@ -0,0 +1,309 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+use clap::Args;
+use reqwest::blocking::Client;
+use serde::Serialize;
+
+use crate::auth_store;
+use crate::config;
+use crate::version_catalog::VersionCatalog;
+
+const MODRINTH_API_URL: &str = "https://api.modrinth.com/v2";
+
+#[derive(Args)]
+pub struct DoctorArgs {
+    #[arg(long, default_value = ".")]
+    input: PathBuf,
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    name: String,
+    status: CheckStatus,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: impl Into<String>, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DoctorReport {
+    ok: bool,
+    checks: Vec<Check>,
+}
+
+pub fn run(args: DoctorArgs) -> Result<()> {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .context("Failed to create HTTP client")?;
+
+    let mut checks = vec![check_git()];
+    let pack = check_atlas_config(&args.input);
+    let hub_url = match pack.as_ref().map(|(_, config)| config) {
+        Some(Ok(_)) => config::resolve_cli_settings(&args.input, None, None, None)
+            .map(|settings| settings.hub_url),
+        _ => auth_store::resolve_hub_url(None),
+    };
+    match hub_url {
+        Ok(hub_url) => checks.push(check_reachable(&client, "hub", &hub_url)),
+        Err(err) => checks.push(Check::fail(
+            "hub",
+            format!("{err:#}"),
+            "Set a valid hub with `atlas hub <url>` or ATLAS_HUB_URL.",
+        )),
+    }
+    checks.push(check_reachable(&client, "modrinth", MODRINTH_API_URL));
+    checks.extend(check_auth());
+    if let Some((check, config)) = pack {
+        checks.push(check);
+        if let Ok(config) = config {
+            checks.push(check_versions(&config));
+        }
+    }
+
+    let ok = checks.iter().all(|check| check.status != CheckStatus::Fail);
+    if args.json {
+        let report = DoctorReport { ok, checks };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_checklist(&checks);
+    }
+
+    if !ok {
+        bail!("atlas doctor found problems that need attention.");
+    }
+    Ok(())
+}
+
+fn check_git() -> Check {
+    match Command::new("git").arg("--version").output() {
+        Ok(output) if output.status.success() => Check::pass(
+            "git",
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ),
+        _ => Check::warn(
+            "git",
+            "git was not found on PATH",
+            "Install git; `atlas commit` and workflow commands need it.",
+        ),
+    }
+}
+
+fn check_reachable(client: &Client, name: &str, url: &str) -> Check {
+    match client.get(url).send() {
+        Ok(response) if response.status().is_server_error() => Check::fail(
+            name,
+            format!("{url} answered HTTP {}", response.status().as_u16()),
+            "The service is having problems; try again later.",
+        ),
+        Ok(_) => Check::pass(name, format!("{url} is reachable")),
+        Err(err) => Check::fail(
+            name,
+            format!("Could not reach {url}: {err}"),
+            "Check your network connection, proxy and firewall settings.",
+        ),
+    }
+}
+
+fn check_auth() -> Vec<Check> {
+    let store = match auth_store::load_cli_auth_store() {
+        Ok(store) => store,
+        Err(err) => {
+            return vec![Check::fail(
+                "auth",
+                format!("{err:#}"),
+                "Run `atlas logout` and `atlas login` to recreate the auth store.",
+            )];
+        }
+    };
+    if store.sessions.is_empty() {
+        return vec![Check::warn(
+            "auth",
+            "Not signed in to any hub",
+            "Run `atlas login`.",
+        )];
+    }
+
+    let now = auth_store::unix_timestamp();
+    store
+        .sessions
+        .values()
+        .map(|session| {
+            let name = format!("auth {}", session.hub_url);
+            let has_refresh = session
+                .refresh_token
+                .as_deref()
+                .is_some_and(|value| !value.trim().is_empty());
+            if session.expires_at == 0 || session.expires_at > now {
+                Check::pass(name, "Signed in")
+            } else if has_refresh {
+                Check::warn(
+                    name,
+                    "Access token expired; it is refreshed when needed",
+                    format!(
+                        "Run `atlas login --hub {}` if commands still fail.",
+                        session.hub_url
+                    ),
+                )
+            } else {
+                Check::warn(
+                    name,
+                    "Session expired",
+                    format!("Run `atlas login --hub {}`.", session.hub_url),
+                )
+            }
+        })
+        .collect()
+}
+
+type LoadedConfig = Result<protocol::config::atlas::AtlasConfig>;
+
+/// `None` when the directory is not a pack, which is fine outside a pack repo.
+fn check_atlas_config(root: &Path) -> Option<(Check, LoadedConfig)> {
+    if !root.join("atlas.toml").exists() {
+        return None;
+    }
+    let config = config::load_atlas_config(root);
+    let check = match &config {
+        Ok(_) => Check::pass("atlas.toml", "Parsed successfully"),
+        Err(err) => Check::fail(
+            "atlas.toml",
+            format!("{err:#}"),
+            "Fix the syntax error, or run `atlas reinit` to regenerate it.",
+        ),
+    };
+    Some((check, config))
+}
+
+fn check_versions(config: &protocol::config::atlas::AtlasConfig) -> Check {
+    let versions = &config.versions;
+    let detail = format!(
+        "Minecraft {} with {} {}",
+        versions.mc, versions.modloader, versions.modloader_version
+    );
+    let Ok(loader) = protocol::config::atlas::parse_loader(&versions.modloader) else {
+        return Check::fail(
+            "versions",
+            format!("Unknown modloader \"{}\"", versions.modloader),
+            "Set versions.modloader to fabric, forge or neoforge in atlas.toml.",
+        );
+    };
+    let loader_versions = VersionCatalog::new().and_then(|catalog| {
+        catalog.fetch_published_loader_versions(catalog_loader_name(loader), &versions.mc)
+    });
+    match loader_versions {
+        Ok(available) if has_loader_version(&available, &versions.modloader_version) => {
+            Check::pass("versions", detail)
+        }
+        Ok(_) => Check::fail(
+            "versions",
+            format!("{detail} is not a published combination"),
+            "Pick a loader version for this Minecraft release with `atlas reinit`.",
+        ),
+        Err(err) if is_network_error(&err) => Check::warn(
+            "versions",
+            format!("{detail} could not be checked: {err:#}"),
+            "Check your network connection; the loader version lists could not be fetched.",
+        ),
+        Err(err) => Check::fail(
+            "versions",
+            format!("{detail} could not be resolved: {err:#}"),
+            "Check versions.mc and versions.modloader in atlas.toml.",
+        ),
+    }
+}
+
+fn catalog_loader_name(loader: protocol::Loader) -> &'static str {
+    match loader {
+        protocol::Loader::Fabric => "fabric",
+        protocol::Loader::Forge => "forge",
+        protocol::Loader::Neo => "neoforge",
+    }
+}
+
+fn has_loader_version(available: &[String], wanted: &str) -> bool {
+    available.iter().any(|value| value.trim() == wanted.trim())
+}
+
+fn is_network_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| cause.is::<reqwest::Error>())
+}
+
+fn print_checklist(checks: &[Check]) {
+    for check in checks {
+        let marker = match check.status {
+            CheckStatus::Pass => "[ok]  ",
+            CheckStatus::Warn => "[warn]",
+            CheckStatus::Fail => "[fail]",
+        };
+        println!("{marker} {}: {}", check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("       {hint}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use protocol::config::atlas::parse_loader;
+
+    use super::{catalog_loader_name, has_loader_version};
+
+    #[test]
+    fn loader_aliases_map_to_catalog_names() {
+        let name = |value: &str| catalog_loader_name(parse_loader(value).expect("known loader"));
+        assert_eq!(name("neo"), "neoforge");
+        assert_eq!(name("NeoForge"), "neoforge");
+        assert_eq!(name("Fabric"), "fabric");
+        assert_eq!(name("forge"), "forge");
+    }
+
+    #[test]
+    fn matches_loader_versions_ignoring_whitespace() {
+        let available = vec!["0.16.0".to_string(), "0.15.11".to_string()];
+        assert!(has_loader_version(&available, " 0.15.11 "));
+        assert!(!has_loader_version(&available, "0.15.1"));
+    }
+}
@@ -1,26 +1,37 @@
 use std::fs;
+use std::io::{self as stdio, IsTerminal};
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result, bail};
 use clap::Args;
 use dialoguer::{Confirm, Input, Select, theme::ColorfulTheme};
 use protocol::config::atlas::{AtlasConfig, CliConfig, MetadataConfig, VersionsConfig};
+use walkdir::WalkDir;
 
+use crate::commands::pack::mrpack;
 use crate::version_catalog::VersionCatalog;
 
 #[derive(Args)]
 pub struct InitArgs {
     #[arg(long, default_value = ".")]
     input: PathBuf,
+    #[arg(long = "from-mrpack", value_name = "MRPACK")]
+    from_mrpack: Option<PathBuf>,
 }
 
 #[derive(Args)]
 pub struct ReinitArgs {
     #[arg(long, default_value = ".")]
     input: PathBuf,
+    #[arg(long)]
+    force: bool,
 }
 
 pub fn run_init(args: InitArgs) -> Result<()> {
+    if let Some(mrpack_path) = args.from_mrpack.as_deref() {
+        return init_from_mrpack(&args.input, mrpack_path);
+    }
+
     let root = resolve_root(&args.input, true)?;
     let atlas_path = root.join("atlas.toml");
 
@@ -38,6 +49,8 @@ pub fn run_init(args: InitArgs) -> Result<()> {
         metadata,
         versions,
         cli,
+        templates: None,
+        extra: Default::default(),
     };
 
     write_atlas_config(&atlas_path, &config)?;
@@ -47,6 +60,193 @@ pub fn run_init(args: InitArgs) -> Result<()> {
     Ok(())
 }
 
+fn init_from_mrpack(input: &Path, mrpack_path: &Path) -> Result<()> {
+    let mrpack_path = mrpack_path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve mrpack path: {}", mrpack_path.display()))?;
+    if !mrpack_path.is_file() {
+        bail!("Expected a file at {}", mrpack_path.display());
+    }
+    if input.exists() && !input.is_dir() {
+        bail!("Input path must be a directory: {}", input.display());
+    }
+    let atlas_path = input.join("atlas.toml");
+    if atlas_path.exists() {
+        bail!(
+            "{} already exists. Use `atlas pack import` to import into an existing pack.",
+            atlas_path.display()
+        );
+    }
+
+    let info = mrpack::read_pack_info(&mrpack_path)?;
+    validate_mrpack_versions(&info)?;
+
+    let name = info
+        .name
+        .clone()
+        .or_else(|| {
+            input
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| "Atlas Pack".to_string());
+    let cli = if stdio::stdin().is_terminal() && stdio::stdout().is_terminal() {
+        prompt_cli(&ColorfulTheme::default(), &name)?
+    } else {
+        None
+    };
+    let config = AtlasConfig {
+        metadata: MetadataConfig {
+            name: name.trim().to_string(),
+            version: Some(info.version.unwrap_or_else(|| "0.1.0".to_string())),
+            description: non_empty(info.summary),
+            extra: Default::default(),
+        },
+        versions: VersionsConfig {
+            mc: info.minecraft,
+            modloader: info.loader,
+            modloader_version: info.loader_version,
+            extra: Default::default(),
+        },
+        cli,
+        templates: None,
+        extra: Default::default(),
+    };
+
+    // Import into a staging directory first so a failed import never leaves a
+    // half-initialized pack behind.
+    let staging = std::env::temp_dir().join(format!("atlas-init-{}", std::process::id()));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)
+            .with_context(|| format!("Failed to clear {}", staging.display()))?;
+    }
+    fs::create_dir_all(&staging)
+        .with_context(|| format!("Failed to create {}", staging.display()))?;
+
+    let staged = write_atlas_config(&staging.join("atlas.toml"), &config)
+        .and_then(|_| ensure_seed_files(&staging))
+        .and_then(|_| mrpack::import_into(&staging, &mrpack_path));
+    if let Err(err) = staged {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(err.context(format!(
+            "Failed to import {}; {} was left untouched",
+            mrpack_path.display(),
+            input.display()
+        )));
+    }
+
+    let copied = ensure_no_collisions(&staging, input)
+        .and_then(|_| resolve_root(input, true))
+        .and_then(|root| copy_staged_files(&staging, &root).map(|_| root));
+    let _ = fs::remove_dir_all(&staging);
+    let root = copied?;
+
+    println!(
+        "Initialized {} from {}",
+        root.join("atlas.toml").display(),
+        mrpack_path.display()
+    );
+    Ok(())
+}
+
+fn validate_mrpack_versions(info: &mrpack::MrpackPackInfo) -> Result<()> {
+    let catalog = VersionCatalog::new()?;
+    let mc_versions = catalog.fetch_minecraft_versions()?;
+    if !mc_versions.iter().any(|version| version == &info.minecraft) {
+        bail!(
+            "Minecraft version {} from the mrpack is not a known release",
+            info.minecraft
+        );
+    }
+
+    let loader_versions = catalog.fetch_loader_versions(&info.loader, &info.minecraft)?;
+    if !loader_versions
+        .iter()
+        .any(|version| version == &info.loader_version)
+    {
+        eprintln!(
+            "Warning: {} {} is not listed for Minecraft {}; keeping it as declared by the mrpack.",
+            info.loader, info.loader_version, info.minecraft
+        );
+    }
+    Ok(())
+}
+
+fn staged_entries(staging: &Path) -> Result<Vec<(PathBuf, bool)>> {
+    let mut entries = Vec::new();
+    for entry in WalkDir::new(staging).min_depth(1) {
+        let entry = entry.context("Failed to read staged pack files")?;
+        let rel = entry
+            .path()
+            .strip_prefix(staging)
+            .context("Failed to resolve staged path")?;
+        entries.push((rel.to_path_buf(), entry.file_type().is_dir()));
+    }
+    Ok(entries)
+}
+
+fn ensure_no_collisions(staging: &Path, root: &Path) -> Result<()> {
+    let collisions = staged_entries(staging)?
+        .into_iter()
+        .filter(|(rel, is_dir)| {
+            let target = root.join(rel);
+            if *is_dir {
+                target.exists() && !target.is_dir()
+            } else {
+                target.symlink_metadata().is_ok()
+            }
+        })
+        .map(|(rel, _)| rel)
+        .collect::<Vec<_>>();
+    if collisions.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!(
+        "{} already contains files the import would overwrite:",
+        root.display()
+    );
+    for rel in &collisions {
+        eprintln!("  {}", rel.display());
+    }
+    bail!("Remove these files or run `atlas init --from-mrpack` in an empty directory.")
+}
+
+// Copies into `root` and removes everything it wrote if a copy fails, so the
+// directory is left as it was.
+fn copy_staged_files(staging: &Path, root: &Path) -> Result<()> {
+    let mut written = Vec::new();
+    let copied = staged_entries(staging).and_then(|entries| {
+        for (rel, is_dir) in entries {
+            let target = root.join(&rel);
+            if is_dir {
+                if target.is_dir() {
+                    continue;
+                }
+                fs::create_dir(&target)
+                    .with_context(|| format!("Failed to create {}", target.display()))?;
+            } else {
+                fs::copy(staging.join(&rel), &target)
+                    .with_context(|| format!("Failed to write {}", target.display()))?;
+            }
+            written.push((target, is_dir));
+        }
+        Ok(())
+    });
+    if let Err(err) = copied {
+        for (path, is_dir) in written.iter().rev() {
+            let _ = if *is_dir {
+                fs::remove_dir(path)
+            } else {
+                fs::remove_file(path)
+            };
+        }
+        return Err(err);
+    }
+    Ok(())
+}
+
 pub fn run_reinit(args: ReinitArgs) -> Result<()> {
     let root = resolve_root(&args.input, false)?;
     let atlas_path = root.join("atlas.toml");
@@ -55,14 +255,54 @@ pub fn run_reinit(args: ReinitArgs) -> Result<()> {
     }
 
     let mut config = crate::config::load_atlas_config(&root)?;
+    let existing = crate::io::read_to_string(&atlas_path)?;
+    let previous = render_atlas_config(&config)?;
     let theme = ColorfulTheme::default();
+    let extra = std::mem::take(&mut config.versions.extra);
     config.versions = prompt_versions(&theme, Some(&config.versions))?;
+    config.versions.extra = extra;
+    let next = render_atlas_config(&config)?;
 
-    write_atlas_config(&atlas_path, &config)?;
+    if existing == next {
+        println!("{} is already up to date.", atlas_path.display());
+        return Ok(());
+    }
+
+    println!("Changes to {}:", atlas_path.display());
+    print!("{}", crate::text_diff::render_diff(&existing, &next));
+
+    // A file that matches neither template was edited by hand; rewriting it
+    // would drop comments and formatting, so make the user opt in.
+    if existing != previous && !args.force && !confirm_user_modified(&atlas_path)? {
+        println!("Aborted.");
+        return Ok(());
+    }
+
+    tracing::debug!("writing {}", atlas_path.display());
+    fs::write(&atlas_path, next)
+        .with_context(|| format!("Failed to write {}", atlas_path.display()))?;
     println!("Updated versions in {}", atlas_path.display());
     Ok(())
 }
 
+fn confirm_user_modified(path: &Path) -> Result<bool> {
+    if !stdio::stdin().is_terminal() || !stdio::stdout().is_terminal() {
+        bail!(
+            "{} has local edits that reinit would overwrite. Pass --force in non-interactive mode.",
+            path.display()
+        );
+    }
+
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!(
+            "{} has local edits that will be lost. Overwrite?",
+            path.display()
+        ))
+        .default(false)
+        .interact()
+        .context("Failed to read confirmation")
+}
+
 fn resolve_root(input: &Path, create_if_missing: bool) -> Result<PathBuf> {
     if create_if_missing && !input.exists() {
         fs::create_dir_all(input)
@@ -131,6 +371,7 @@ fn prompt_metadata(theme: &ColorfulTheme, root: &Path) -> Result<MetadataConfig>
         name: name.trim().to_string(),
         version: Some(version.trim().to_string()),
         description: non_empty(Some(description)),
+        extra: Default::default(),
     })
 }
 
@@ -179,6 +420,7 @@ fn prompt_versions(
         mc,
         modloader,
         modloader_version,
+        extra: Default::default(),
     })
 }
 
@@ -324,6 +566,7 @@ fn prompt_cli(theme: &ColorfulTheme, pack_name: &str) -> Result<Option<CliConfig
         pack_id: non_empty(Some(pack_id)),
         hub_url: non_empty(Some(hub_url)),
         default_channel: non_empty(Some(default_channel)),
+        extra: Default::default(),
     };
 
     if cli.pack_id.is_none() && cli.hub_url.is_none() && cli.default_channel.is_none() {
@@ -370,9 +613,14 @@ fn slugify(value: &str) -> String {
     }
 }
 
-fn write_atlas_config(path: &Path, config: &AtlasConfig) -> Result<()> {
+fn render_atlas_config(config: &AtlasConfig) -> Result<String> {
     let contents = toml::to_string(config).context("Failed to serialize atlas config")?;
-    fs::write(path, format!("{}\n", contents))
+    Ok(format!("{}\n", contents))
+}
+
+fn write_atlas_config(path: &Path, config: &AtlasConfig) -> Result<()> {
+    tracing::debug!("writing {}", path.display());
+    fs::write(path, render_atlas_config(config)?)
         .with_context(|| format!("Failed to write {}", path.display()))
 }
 
@@ -408,3 +656,33 @@ fn non_empty(value: Option<String>) -> Option<String> {
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{copy_staged_files, ensure_no_collisions};
+
+    #[test]
+    fn staged_import_refuses_to_overwrite_existing_files() {
+        let base = std::env::temp_dir().join(format!("atlas-init-test-{}", std::process::id()));
+        let staging = base.join("staging");
+        let root = base.join("root");
+        fs::create_dir_all(staging.join("mods")).unwrap();
+        fs::write(staging.join("atlas.toml"), "staged").unwrap();
+        fs::write(staging.join("mods").join("sodium.toml"), "staged").unwrap();
+        fs::create_dir_all(root.join("mods")).unwrap();
+        fs::write(root.join("mods").join("sodium.toml"), "existing").unwrap();
+
+        assert!(ensure_no_collisions(&staging, &root).is_err());
+
+        fs::remove_file(root.join("mods").join("sodium.toml")).unwrap();
+        ensure_no_collisions(&staging, &root).unwrap();
+        copy_staged_files(&staging, &root).unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("mods").join("sodium.toml")).unwrap(),
+            "staged"
+        );
+        let _ = fs::remove_dir_all(&base);
+    }
+}
@@ -1,9 +1,13 @@
 pub mod auth;
+pub mod blob_verify;
 pub mod ci;
 pub mod completion;
 pub mod deploy;
+pub mod doctor;
 pub mod init;
 pub mod pack;
 pub mod promote;
 pub mod pull;
 pub mod push;
+pub mod release_diff;
+pub mod sync_state;
@@ -0,0 +1,163 @@
+use std::collections::{BTreeSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{AddArgs, QueuedResolution};
+
+/// Dependency walk state for `atlas mod add`, saved before each resolution so
+/// an interrupted add can pick up where it stopped.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct AddProgress {
+    pub queue: VecDeque<QueuedResolution>,
+    pub visited_projects: BTreeSet<String>,
+    pub added_count: usize,
+    pub skipped_existing_count: usize,
+    pub skipped_pinned_count: usize,
+}
+
+impl AddProgress {
+    pub fn new(first: QueuedResolution) -> Self {
+        let mut visited_projects = BTreeSet::new();
+        visited_projects.insert(first.project_id.clone());
+        Self {
+            queue: VecDeque::from([first]),
+            visited_projects,
+            added_count: 0,
+            skipped_existing_count: 0,
+            skipped_pinned_count: 0,
+        }
+    }
+}
+
+// Lives in the temp dir, keyed by the pack root and the add arguments, so only
+// an identical re-run offers to resume.
+pub(super) struct AddResumeFile {
+    path: PathBuf,
+}
+
+impl AddResumeFile {
+    pub fn for_add(root: &Path, args: &AddArgs) -> Self {
+        let key = [
+            root.to_string_lossy().as_ref(),
+            args.source.as_str(),
+            args.query.as_deref().unwrap_or_default(),
+            args.slug.as_deref().unwrap_or_default(),
+            args.version.as_deref().unwrap_or_default(),
+            args.dependencies.as_str(),
+            args.dependency_versions.as_str(),
+            args.asset_type.as_str(),
+        ]
+        .join("\n");
+        let digest = hex::encode(Sha256::digest(key.as_bytes()));
+        Self {
+            path: std::env::temp_dir().join(format!("atlas-add-{}.json", &digest[..16])),
+        }
+    }
+
+    pub fn load(&self) -> Option<AddProgress> {
+        let contents = fs::read_to_string(&self.path).ok()?;
+        match serde_json::from_str::<AddProgress>(&contents) {
+            Ok(progress) if !progress.queue.is_empty() => Some(progress),
+            Ok(_) => None,
+            Err(err) => {
+                eprintln!("Ignoring unreadable {}: {}", self.path.display(), err);
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, progress: &AddProgress) -> Result<()> {
+        let contents =
+            serde_json::to_string(progress).context("Failed to serialize add progress")?;
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, contents).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, &self.path)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    pub fn remove(&self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{AddArgs, AddProgress, AddResumeFile, QueuedResolution};
+
+    fn add_args(root: &Path, version: Option<&str>) -> AddArgs {
+        AddArgs {
+            input: root.to_path_buf(),
+            source: "mr".to_string(),
+            query: None,
+            slug: Some("sodium".to_string()),
+            version: version.map(str::to_string),
+            dependencies: "auto".to_string(),
+            dependency_versions: "required".to_string(),
+            asset_type: "mod".to_string(),
+            fresh: false,
+        }
+    }
+
+    fn queued(project_id: &str) -> QueuedResolution {
+        QueuedResolution {
+            project_id: project_id.to_string(),
+            desired_version: None,
+            preferred_name: None,
+            preferred_project_url: None,
+        }
+    }
+
+    fn temp_root(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("atlas-add-resume-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn saved_progress_resumes_only_the_same_add() {
+        let root = temp_root("resume");
+        let file = AddResumeFile::for_add(&root, &add_args(&root, None));
+        let mut progress = AddProgress::new(queued("sodium"));
+        progress.queue.push_back(queued("fabric-api"));
+        progress.added_count = 1;
+        file.save(&progress).expect("save progress");
+
+        let resumed = file.load().expect("resume saved progress");
+        let queue: Vec<&str> = resumed
+            .queue
+            .iter()
+            .map(|item| item.project_id.as_str())
+            .collect();
+        assert_eq!(queue, ["sodium", "fabric-api"]);
+        assert_eq!(resumed.added_count, 1);
+        assert!(resumed.visited_projects.contains("sodium"));
+
+        let other = AddResumeFile::for_add(&root, &add_args(&root, Some("0.5.0")));
+        assert_ne!(other.path, file.path);
+        assert!(other.load().is_none());
+
+        file.remove();
+        assert!(file.load().is_none());
+    }
+
+    #[test]
+    fn partially_written_progress_is_ignored() {
+        let root = temp_root("partial");
+        let file = AddResumeFile::for_add(&root, &add_args(&root, None));
+        let contents = serde_json::to_string(&AddProgress::new(queued("sodium"))).unwrap();
+        std::fs::write(&file.path, &contents[..contents.len() / 2]).unwrap();
+        assert!(file.load().is_none());
+
+        let mut finished = AddProgress::new(queued("sodium"));
+        finished.queue.clear();
+        file.save(&finished).expect("save progress");
+        assert!(file.load().is_none());
+
+        file.remove();
+    }
+}
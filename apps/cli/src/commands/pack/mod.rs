@@ -1,21 +1,28 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{self as stdio, IsTerminal};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
 use anyhow::{Context, Result, bail};
-use clap::{Args, Subcommand};
-use dialoguer::{Select, theme::ColorfulTheme};
-use mod_resolver::{Provider, SearchCandidate};
+use clap::{Args, Subcommand, ValueEnum};
+use dialoguer::{Confirm, Select, theme::ColorfulTheme};
+use indicatif::{ProgressBar, ProgressFinish, ProgressStyle};
+use mod_resolver::{Provider, SearchCandidate, VersionCache};
+use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 
+use add_resume::{AddProgress, AddResumeFile};
+
 use crate::auth_store;
 use crate::commands::{init, pull, push};
 use crate::config;
 use crate::io;
 
-mod mrpack;
+mod add_resume;
+pub(crate) mod mrpack;
 
 const SEARCH_PAGE_SIZE: usize = 5;
 
@@ -29,6 +36,10 @@ pub enum PackCommand {
     Import(ImportArgs),
     #[command(alias = "remove")]
     Rm(RmArgs),
+    #[command(alias = "move")]
+    Mv(MvArgs),
+    Pin(PinArgs),
+    Unpin(PinArgs),
     List(ListArgs),
     Pull(pull::PullArgs),
     Push(push::PushArgs),
@@ -50,6 +61,8 @@ pub struct BuildArgs {
     format: String,
     #[arg(long, default_value_t = protocol::DEFAULT_ZSTD_LEVEL)]
     zstd_level: i32,
+    #[arg(long, value_name = "SIZE", value_parser = parse_size)]
+    max_size: Option<u64>,
 }
 
 #[derive(Args)]
@@ -93,6 +106,8 @@ pub struct AddArgs {
         value_parser = ["mod", "shader", "shaderpack", "resourcepack", "other"]
     )]
     asset_type: String,
+    #[arg(long)]
+    fresh: bool,
 }
 
 #[derive(Args)]
@@ -113,6 +128,13 @@ pub struct ValidateArgs {
         value_parser = ["strict", "off"]
     )]
     check_dependency_versions: String,
+    #[arg(
+        long = "check-resources",
+        default_value = "on",
+        value_name = "MODE",
+        value_parser = ["on", "off"]
+    )]
+    check_resources: String,
 }
 
 #[derive(Args)]
@@ -146,14 +168,58 @@ pub struct RmArgs {
     all: bool,
 }
 
+#[derive(Args)]
+pub struct MvArgs {
+    #[arg(long, default_value = ".")]
+    input: PathBuf,
+    #[arg(value_name = "QUERY")]
+    query: String,
+    #[arg(value_name = "DIR")]
+    destination: PathBuf,
+    #[arg(
+        long = "type",
+        value_name = "TYPE",
+        default_value = "any",
+        value_parser = ["any", "mod", "resource"]
+    )]
+    asset_type: String,
+    #[arg(long)]
+    all: bool,
+}
+
+#[derive(Args)]
+pub struct PinArgs {
+    #[arg(long, default_value = ".")]
+    input: PathBuf,
+    #[arg(value_name = "QUERY")]
+    query: String,
+    #[arg(
+        long = "type",
+        value_name = "TYPE",
+        default_value = "any",
+        value_parser = ["any", "mod", "resource"]
+    )]
+    asset_type: String,
+    #[arg(long)]
+    all: bool,
+}
+
 #[derive(Args)]
 pub struct CommitArgs {
     #[arg(long, default_value = ".")]
     input: PathBuf,
+    #[arg(long, value_enum, default_value = "pack")]
+    scope: CommitScope,
     #[arg(value_name = "MESSAGE")]
     message: String,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+pub enum CommitScope {
+    Pack,
+    All,
+}
+
 pub fn run(command: PackCommand) -> Result<()> {
     match command {
         PackCommand::Init(args) => init::run_init(args),
@@ -163,6 +229,9 @@ pub fn run(command: PackCommand) -> Result<()> {
         PackCommand::Add(args) => add(args),
         PackCommand::Import(args) => mrpack::import(args),
         PackCommand::Rm(args) => rm(args),
+        PackCommand::Mv(args) => mv(args),
+        PackCommand::Pin(args) => set_pinned(args, true),
+        PackCommand::Unpin(args) => set_pinned(args, false),
         PackCommand::List(args) => list(args),
         PackCommand::Pull(args) => pull::run(args),
         PackCommand::Push(args) => push::run(args),
@@ -183,12 +252,13 @@ fn set_channel(args: ChannelArgs) -> Result<()> {
 
     let config_text = io::read_to_string(&atlas_path)?;
     let mut config = protocol::config::atlas::parse_config(&config_text)
-        .map_err(|_| anyhow::anyhow!("atlas.toml is invalid"))?;
+        .with_context(|| format!("{} is invalid", atlas_path.display()))?;
 
     let cli = config.cli.get_or_insert_with(Default::default);
     cli.default_channel = Some(args.channel.clone());
 
     let contents = toml::to_string(&config).context("Failed to serialize atlas config")?;
+    tracing::debug!("writing {}", atlas_path.display());
     fs::write(&atlas_path, format!("{contents}\n"))
         .with_context(|| format!("Failed to write {}", atlas_path.display()))?;
 
@@ -209,8 +279,28 @@ fn build(args: BuildArgs) -> Result<()> {
         "atlas" => {
             let build =
                 config::build_pack_bytes(&root, args.pack_id, args.version, args.zstd_level)?;
-            io::write_output(&args.output, &build.bytes)?;
-            println!("Wrote {}", args.output.display());
+            let report = format_build_report(&build.stats);
+            if io::is_stdout_path(&args.output) {
+                eprintln!("{report}");
+            } else {
+                println!("{report}");
+            }
+            if let Some(max_size) = args.max_size
+                && build.stats.compressed_bytes > max_size
+            {
+                bail!(
+                    "Pack is {} compressed, over the --max-size limit of {}.",
+                    format_size(build.stats.compressed_bytes),
+                    format_size(max_size)
+                );
+            }
+            if io::is_stdout_path(&args.output) {
+                io::write_stdout(&build.bytes)?;
+                eprintln!("Wrote {} bytes to stdout", build.bytes.len());
+            } else {
+                io::write_output(&args.output, &build.bytes)?;
+                println!("Wrote {}", args.output.display());
+            }
         }
         "mrpack" => mrpack::build(&args, &root)?,
         other => bail!("Unsupported build format '{}'. Use atlas or mrpack.", other),
@@ -218,6 +308,65 @@ fn build(args: BuildArgs) -> Result<()> {
     Ok(())
 }
 
+fn format_build_report(stats: &protocol::pack::BuildStats) -> String {
+    let artifacts = match stats.dependencies_with_size {
+        0 => "unknown".to_string(),
+        known if known == stats.dependencies => format_size(stats.dependency_bytes),
+        known => format!(
+            "{} ({} of {} dependencies report a size)",
+            format_size(stats.dependency_bytes),
+            known,
+            stats.dependencies
+        ),
+    };
+    format!(
+        "Bundled files: {} ({})\nDependencies: {}\nArtifact size: {}\nCompressed blob: {} ({:.1}% of {})",
+        stats.bundled_files,
+        format_size(stats.bundled_bytes),
+        stats.dependencies,
+        artifacts,
+        format_size(stats.compressed_bytes),
+        stats.compression_ratio() * 100.0,
+        format_size(stats.uncompressed_bytes)
+    )
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+// Accepts plain bytes or a binary K/M/G suffix, e.g. `500M` or `1GiB`.
+fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (digits, suffix) = value.split_at(split);
+    let number = digits
+        .parse::<u64>()
+        .map_err(|_| format!("invalid size '{value}'"))?;
+    let multiplier: u64 = match suffix.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1024,
+        "m" | "mb" | "mib" => 1024 * 1024,
+        "g" | "gb" | "gib" => 1024 * 1024 * 1024,
+        other => return Err(format!("unknown size unit '{other}'")),
+    };
+    number
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("size '{value}' is too large"))
+}
+
 fn add(args: AddArgs) -> Result<()> {
     let root = args
         .input
@@ -234,77 +383,105 @@ fn add(args: AddArgs) -> Result<()> {
     let pack_type = asset_kind.resolver_pack_type();
 
     let provider = Provider::from_short_code(&args.source).context("source must be cf or mr")?;
-    let curseforge_auth = match provider {
+    let mut curseforge_auth = match provider {
         Provider::CurseForge => {
             let settings = config::resolve_cli_settings(&root, None, None, None)?;
-            let access_token = auth_store::require_access_token_for_hub(&settings.hub_url)?;
-            Some(CurseForgeAuth {
-                hub_url: settings.hub_url,
-                access_token,
-            })
+            Some(CurseForgeAuth::new(settings.hub_url)?)
         }
         Provider::Modrinth => None,
     };
 
-    let selected = if let Some(slug) = args.slug.as_deref() {
-        resolve_slug_candidate(
-            provider,
-            pack_type,
-            slug,
-            &loader,
-            &minecraft_version,
-            curseforge_auth.as_ref(),
-        )?
+    // One cache per `add` so the picker's version list is reused for resolution.
+    let version_cache = VersionCache::new();
+    let resume_file = AddResumeFile::for_add(&root, &args);
+    let resumed = if args.fresh {
+        resume_file.remove();
+        None
     } else {
-        if !stdio::stdin().is_terminal() || !stdio::stdout().is_terminal() {
-            bail!(
-                "Search UI requires an interactive terminal. Use `--slug=<provider-slug>` in non-interactive mode."
-            );
-        }
-
-        let query = args
-            .query
-            .as_deref()
-            .context("query is required unless --slug is set")?;
-        prompt_search_selection(
-            provider,
-            pack_type,
-            query,
-            &loader,
-            &minecraft_version,
-            curseforge_auth.as_ref(),
-        )?
+        resume_file
+            .load()
+            .map(|state| confirm_resume(&resume_file, state))
+            .transpose()?
+            .flatten()
     };
-    let Some(selected) = selected else {
-        println!("Cancelled.");
-        return Ok(());
+
+    let mut state = match resumed {
+        Some(state) => state,
+        None => {
+            let selected = if let Some(slug) = args.slug.as_deref() {
+                resolve_slug_candidate(
+                    provider,
+                    pack_type,
+                    slug,
+                    &loader,
+                    &minecraft_version,
+                    curseforge_auth.as_ref(),
+                )?
+            } else {
+                if !stdio::stdin().is_terminal() || !stdio::stdout().is_terminal() {
+                    bail!(
+                        "Search UI requires an interactive terminal. Use `--slug=<provider-slug>` in non-interactive mode."
+                    );
+                }
+
+                let query = args
+                    .query
+                    .as_deref()
+                    .context("query is required unless --slug is set")?;
+                prompt_search_selection(
+                    provider,
+                    pack_type,
+                    query,
+                    &loader,
+                    &minecraft_version,
+                    curseforge_auth.as_ref(),
+                )?
+            };
+            let Some(selected) = selected else {
+                println!("Cancelled.");
+                return Ok(());
+            };
+            let pinned_version = resolve_pinned_version(
+                &version_cache,
+                provider,
+                pack_type,
+                &selected,
+                &loader,
+                &minecraft_version,
+                desired_version.as_deref(),
+                curseforge_auth.as_ref(),
+            )?;
+            AddProgress::new(QueuedResolution {
+                project_id: selected.project_id,
+                desired_version: pinned_version,
+                preferred_name: Some(selected.title),
+                preferred_project_url: selected.project_url,
+            })
+        }
     };
-    let pinned_version = resolve_pinned_version(
-        provider,
-        pack_type,
-        &selected,
-        &loader,
-        &minecraft_version,
-        desired_version.as_deref(),
-        curseforge_auth.as_ref(),
-    )?;
 
     let mut existing = load_existing_mod_keys(&root)?;
-    let mut visited_projects = HashSet::new();
-    let mut queue = VecDeque::new();
-    queue.push_back(QueuedResolution {
-        project_id: selected.project_id.clone(),
-        desired_version: pinned_version,
-        preferred_name: Some(selected.title),
-        preferred_project_url: selected.project_url,
-    });
-    visited_projects.insert(selected.project_id);
+    let pinned = load_pinned_mod_keys(&root)?;
 
-    let mut added_count = 0usize;
-    let mut skipped_existing_count = 0usize;
+    let progress = resolution_progress();
+    if let Some(auth) = curseforge_auth.as_mut() {
+        auth.progress = progress.clone();
+    }
+    let mut resolved_count = 0usize;
 
-    while let Some(next) = queue.pop_front() {
+    loop {
+        resume_file.save(&state)?;
+        let Some(next) = state.queue.pop_front() else {
+            break;
+        };
+        resolved_count += 1;
+        progress.set_message(format!(
+            "Resolving {}/{} dependencies...",
+            resolved_count,
+            resolved_count + state.queue.len()
+        ));
         let resolved = resolve_project(
+            &version_cache,
             provider,
             pack_type,
             &next.project_id,
@@ -328,6 +505,7 @@ fn add(args: AddArgs) -> Result<()> {
                     version: dependency.desired_version.clone(),
                 })
                 .collect(),
+            extra: std::mem::take(&mut entry.compat.extra),
         };
         if entry.metadata.name.trim().is_empty() {
             if let Some(name) = next.preferred_name.filter(|value| !value.trim().is_empty()) {
@@ -347,22 +525,29 @@ fn add(args: AddArgs) -> Result<()> {
         }
 
         let key = mod_key(&entry.download.source, &entry.download.project_id);
+        // The resolved version may differ from the pinned one, so its dependencies
+        // are not followed either.
+        if pinned.contains(&key) {
+            state.skipped_pinned_count += 1;
+            progress.suspend(|| println!("Skipped pinned {}", mod_reference_for_entry(&entry)));
+            continue;
+        }
         if existing.insert(key) {
             if asset_kind == AssetKind::Mod {
                 io::write_mod_entry(&root, &entry)?;
             } else {
                 io::write_resource_entry(&root, &entry, asset_kind.resource_pointer_directory())?;
             }
-            added_count += 1;
-            println!("Added {}", mod_reference_for_entry(&entry));
+            state.added_count += 1;
+            progress.suspend(|| println!("Added {}", mod_reference_for_entry(&entry)));
         } else {
-            skipped_existing_count += 1;
+            state.skipped_existing_count += 1;
         }
 
         if asset_kind == AssetKind::Mod && dependency_install_mode == DependencyInstallMode::Auto {
             for dependency in resolved.dependencies {
-                if visited_projects.insert(dependency.project_id.clone()) {
-                    queue.push_back(QueuedResolution {
+                if state.visited_projects.insert(dependency.project_id.clone()) {
+                    state.queue.push_back(QueuedResolution {
                         project_id: dependency.project_id,
                         desired_version: dependency_version_mode.select(dependency.desired_version),
                         preferred_name: None,
@@ -373,13 +558,19 @@ fn add(args: AddArgs) -> Result<()> {
         }
     }
 
-    if added_count == 0 {
+    progress.finish_and_clear();
+    resume_file.remove();
+
+    if state.added_count == 0 {
         println!("No new mods were added.");
     } else {
-        println!("Added {} mod(s).", added_count);
+        println!("Added {} mod(s).", state.added_count);
+    }
+    if state.skipped_existing_count > 0 {
+        println!("Skipped {} existing mod(s).", state.skipped_existing_count);
     }
-    if skipped_existing_count > 0 {
-        println!("Skipped {} existing mod(s).", skipped_existing_count);
+    if state.skipped_pinned_count > 0 {
+        println!("Skipped {} pinned mod(s).", state.skipped_pinned_count);
     }
     if asset_kind == AssetKind::Mod && dependency_install_mode == DependencyInstallMode::Off {
         println!("Dependency auto-install is disabled (--dependencies=off).");
@@ -541,6 +732,7 @@ fn prompt_search_selection(
     }
 }
 
+#[tracing::instrument(level = "debug", skip(curseforge_auth), err(level = "debug"))]
 fn search_candidates(
     provider: Provider,
     pack_type: &str,
@@ -563,21 +755,25 @@ fn search_candidates(
         ),
         Provider::CurseForge => {
             let auth = curseforge_auth.context("CurseForge authentication is required")?;
-            mod_resolver::search_curseforge_via_proxy_blocking(
-                &auth.hub_url,
-                &auth.access_token,
-                query,
-                loader,
-                minecraft_version,
-                pack_type,
-                offset,
-                limit,
-            )
+            auth.call(|access_token| {
+                mod_resolver::search_curseforge_via_proxy_blocking(
+                    &auth.hub_url,
+                    access_token,
+                    query,
+                    loader,
+                    minecraft_version,
+                    pack_type,
+                    offset,
+                    limit,
+                )
+            })
         }
     }
 }
 
+#[tracing::instrument(level = "debug", skip(cache, curseforge_auth), err(level = "debug"))]
 fn resolve_project(
+    cache: &VersionCache,
     provider: Provider,
     pack_type: &str,
     project_id: &str,
@@ -588,6 +784,7 @@ fn resolve_project(
 ) -> Result<mod_resolver::ResolvedMod> {
     match provider {
         Provider::Modrinth => mod_resolver::resolve_by_project_id_blocking(
+            cache,
             provider,
             project_id,
             loader,
@@ -597,20 +794,24 @@ fn resolve_project(
         ),
         Provider::CurseForge => {
             let auth = curseforge_auth.context("CurseForge authentication is required")?;
-            mod_resolver::resolve_curseforge_by_project_id_via_proxy_blocking(
-                &auth.hub_url,
-                &auth.access_token,
-                project_id,
-                loader,
-                minecraft_version,
-                desired_version,
-                pack_type,
-            )
+            auth.call(|access_token| {
+                mod_resolver::resolve_curseforge_by_project_id_via_proxy_blocking(
+                    cache,
+                    &auth.hub_url,
+                    access_token,
+                    project_id,
+                    loader,
+                    minecraft_version,
+                    desired_version,
+                    pack_type,
+                )
+            })
         }
     }
 }
 
 fn resolve_pinned_version(
+    cache: &VersionCache,
     provider: Provider,
     pack_type: &str,
     selected: &SearchCandidate,
@@ -627,6 +828,7 @@ fn resolve_pinned_version(
     }
 
     let compatible_versions = list_compatible_versions(
+        cache,
         provider,
         pack_type,
         &selected.project_id,
@@ -667,7 +869,9 @@ fn resolve_pinned_version(
         .context("Invalid version selection")
 }
 
+#[tracing::instrument(level = "debug", skip(cache, curseforge_auth), err(level = "debug"))]
 fn list_compatible_versions(
+    cache: &VersionCache,
     provider: Provider,
     pack_type: &str,
     project_id: &str,
@@ -677,6 +881,7 @@ fn list_compatible_versions(
 ) -> Result<Vec<mod_resolver::CompatibleVersion>> {
     match provider {
         Provider::Modrinth => mod_resolver::compatible_versions_by_project_id_blocking(
+            cache,
             provider,
             project_id,
             loader,
@@ -685,14 +890,17 @@ fn list_compatible_versions(
         ),
         Provider::CurseForge => {
             let auth = curseforge_auth.context("CurseForge authentication is required")?;
-            mod_resolver::compatible_curseforge_versions_by_project_id_via_proxy_blocking(
-                &auth.hub_url,
-                &auth.access_token,
-                project_id,
-                loader,
-                minecraft_version,
-                pack_type,
-            )
+            auth.call(|access_token| {
+                mod_resolver::compatible_curseforge_versions_by_project_id_via_proxy_blocking(
+                    cache,
+                    &auth.hub_url,
+                    access_token,
+                    project_id,
+                    loader,
+                    minecraft_version,
+                    pack_type,
+                )
+            })
         }
     }
 }
@@ -702,7 +910,7 @@ fn load_existing_mod_keys(root: &Path) -> Result<HashSet<String>> {
     for path in pointer_paths(root)? {
         let contents = io::read_to_string(&path)?;
         let parsed = protocol::config::mods::parse_mod_toml(&contents)
-            .map_err(|_| anyhow::anyhow!("Invalid pointer file: {}", path.display()))?;
+            .with_context(|| format!("Invalid pointer file: {}", path.display()))?;
         keys.insert(mod_key(
             &parsed.download.source,
             &parsed.download.project_id,
@@ -711,6 +919,19 @@ fn load_existing_mod_keys(root: &Path) -> Result<HashSet<String>> {
     Ok(keys)
 }
 
+fn load_pinned_mod_keys(root: &Path) -> Result<HashSet<String>> {
+    Ok(load_pointer_resources(root)?
+        .into_iter()
+        .filter(|pointer| pointer.entry.download.pinned)
+        .map(|pointer| {
+            mod_key(
+                &pointer.entry.download.source,
+                &pointer.entry.download.project_id,
+            )
+        })
+        .collect())
+}
+
 fn pointer_paths(root: &Path) -> Result<Vec<PathBuf>> {
     let mut paths = Vec::new();
     for entry in WalkDir::new(root)
@@ -856,55 +1077,11 @@ fn rm(args: RmArgs) -> Result<()> {
         bail!("No {} matched '{}'.", filter.label_plural(), query);
     }
 
-    let selected = if args.all {
-        matches
-    } else if matches.len() == 1 {
-        matches
-    } else if !stdio::stdin().is_terminal() || !stdio::stdout().is_terminal() {
-        let candidates = matches
-            .iter()
-            .map(|index| {
-                let pointer = &pointers[*index];
-                format!(
-                    "{} -> {}",
-                    pointer.rel_path,
-                    mod_reference_for_entry(&pointer.entry)
-                )
-            })
-            .collect::<Vec<_>>()
-            .join("\n");
-        bail!(
-            "Multiple matches for '{}'. Use --all or run in an interactive terminal.\n{}",
-            query,
-            candidates
-        );
-    } else {
-        let mut items = matches
-            .iter()
-            .map(|index| {
-                let pointer = &pointers[*index];
-                format!(
-                    "{} -> {}",
-                    pointer.rel_path,
-                    mod_reference_for_entry(&pointer.entry)
-                )
-            })
-            .collect::<Vec<_>>();
-        items.push("Cancel".to_string());
-
-        let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt(format!("Select {} to remove", filter.label_singular()))
-            .items(&items)
-            .default(0)
-            .interact()
-            .context("Failed to read removal selection")?;
-
-        if selection == items.len() - 1 {
-            println!("Cancelled.");
-            return Ok(());
-        }
-
-        vec![matches[selection]]
+    let Some(selected) =
+        select_pointer_matches(&pointers, matches, query, args.all, filter, "remove")?
+    else {
+        println!("Cancelled.");
+        return Ok(());
     };
 
     let mut removed = 0usize;
@@ -924,6 +1101,257 @@ fn rm(args: RmArgs) -> Result<()> {
     Ok(())
 }
 
+fn select_pointer_matches(
+    pointers: &[PointerResource],
+    matches: Vec<usize>,
+    query: &str,
+    all: bool,
+    filter: RemoveAssetFilter,
+    action: &str,
+) -> Result<Option<Vec<usize>>> {
+    if all || matches.len() == 1 {
+        return Ok(Some(matches));
+    }
+
+    let items = matches
+        .iter()
+        .map(|index| {
+            let pointer = &pointers[*index];
+            format!(
+                "{} -> {}",
+                pointer.rel_path,
+                mod_reference_for_entry(&pointer.entry)
+            )
+        })
+        .collect::<Vec<_>>();
+    if !stdio::stdin().is_terminal() || !stdio::stdout().is_terminal() {
+        bail!(
+            "Multiple matches for '{}'. Use --all or run in an interactive terminal.\n{}",
+            query,
+            items.join("\n")
+        );
+    }
+
+    let mut items = items;
+    items.push("Cancel".to_string());
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Select {} to {}", filter.label_singular(), action))
+        .items(&items)
+        .default(0)
+        .interact()
+        .with_context(|| format!("Failed to read {} selection", action))?;
+
+    if selection == items.len() - 1 {
+        return Ok(None);
+    }
+    Ok(Some(vec![matches[selection]]))
+}
+
+fn mv(args: MvArgs) -> Result<()> {
+    let root = args
+        .input
+        .canonicalize()
+        .context("Failed to resolve input path")?;
+    let query = args.query.trim();
+    if query.is_empty() {
+        bail!("query cannot be empty");
+    }
+    let destination = destination_rel_dir(&args.destination)?;
+
+    let filter = RemoveAssetFilter::from_input(&args.asset_type)?;
+    let pointers = load_pointer_resources(&root)?;
+    let candidates = pointers
+        .iter()
+        .enumerate()
+        .filter(|(_, pointer)| filter.matches(pointer.kind))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    if candidates.is_empty() {
+        bail!("No {} pointer files found.", filter.label_plural());
+    }
+
+    let matches = find_pointer_matches(&pointers, query)
+        .into_iter()
+        .filter(|index| candidates.contains(index))
+        .collect::<Vec<_>>();
+    if matches.is_empty() {
+        bail!("No {} matched '{}'.", filter.label_plural(), query);
+    }
+
+    let Some(selected) =
+        select_pointer_matches(&pointers, matches, query, args.all, filter, "move")?
+    else {
+        println!("Cancelled.");
+        return Ok(());
+    };
+
+    // Check every move before touching the tree so a conflict leaves nothing half-moved.
+    let mut moves: Vec<(usize, String, PathBuf)> = Vec::new();
+    for index in selected {
+        let pointer = &pointers[index];
+        let file_name = Path::new(&pointer.rel_path)
+            .file_name()
+            .map(|value| value.to_string_lossy().to_string())
+            .with_context(|| format!("Invalid pointer path: {}", pointer.rel_path))?;
+        let target_rel = if destination.is_empty() {
+            file_name
+        } else {
+            format!("{}/{}", destination, file_name)
+        };
+        if target_rel == pointer.rel_path {
+            println!(
+                "{} is already in {}",
+                pointer.rel_path,
+                args.destination.display()
+            );
+            continue;
+        }
+
+        let key = mod_key(
+            &pointer.entry.download.source,
+            &pointer.entry.download.project_id,
+        );
+        if let Some(existing) = pointers.iter().find(|other| {
+            other.path != pointer.path
+                && pointer_parent(&other.rel_path) == destination
+                && mod_key(
+                    &other.entry.download.source,
+                    &other.entry.download.project_id,
+                ) == key
+        }) {
+            bail!(
+                "{} already has a pointer for {} ({}).",
+                display_dir(&destination),
+                mod_reference_for_entry(&pointer.entry),
+                existing.rel_path
+            );
+        }
+        let target = root.join(&target_rel);
+        if target.exists() || moves.iter().any(|(_, rel, _)| *rel == target_rel) {
+            bail!("{} already exists.", target_rel);
+        }
+        moves.push((index, target_rel, target));
+    }
+
+    for (index, target_rel, target) in &moves {
+        let pointer = &pointers[*index];
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::rename(&pointer.path, target).with_context(|| {
+            format!(
+                "Failed to move {} to {}",
+                pointer.path.display(),
+                target.display()
+            )
+        })?;
+        println!("Moved {} -> {}", pointer.rel_path, target_rel);
+        if let Some(kind) = mod_resolver::pointer::is_pointer_path(target_rel) {
+            let installed = mod_resolver::pointer::destination_relative_path(
+                target_rel.as_str(),
+                kind,
+                pointer.entry.download.url.as_deref().unwrap_or_default(),
+            );
+            println!("  installs to {}", installed);
+        }
+    }
+
+    Ok(())
+}
+
+fn set_pinned(args: PinArgs, pinned: bool) -> Result<()> {
+    let root = args
+        .input
+        .canonicalize()
+        .context("Failed to resolve input path")?;
+    let query = args.query.trim();
+    if query.is_empty() {
+        bail!("query cannot be empty");
+    }
+
+    let filter = RemoveAssetFilter::from_input(&args.asset_type)?;
+    let pointers = load_pointer_resources(&root)?
+        .into_iter()
+        .filter(|pointer| filter.matches(pointer.kind))
+        .collect::<Vec<_>>();
+    if pointers.is_empty() {
+        bail!("No {} pointer files found.", filter.label_plural());
+    }
+
+    let matches = find_pointer_matches(&pointers, query);
+    if matches.is_empty() {
+        bail!("No {} matched '{}'.", filter.label_plural(), query);
+    }
+
+    let action = if pinned { "pin" } else { "unpin" };
+    let Some(selected) =
+        select_pointer_matches(&pointers, matches, query, args.all, filter, action)?
+    else {
+        println!("Cancelled.");
+        return Ok(());
+    };
+
+    for index in selected {
+        let pointer = &pointers[index];
+        if pointer.entry.download.pinned == pinned {
+            println!(
+                "{} is already {}.",
+                mod_reference_for_entry(&pointer.entry),
+                if pinned { "pinned" } else { "unpinned" }
+            );
+            continue;
+        }
+
+        let mut entry = pointer.entry.clone();
+        entry.download.pinned = pinned;
+        let content = entry
+            .to_toml_string()
+            .context("Failed to serialize mod entry")?;
+        tracing::debug!("writing {}", pointer.path.display());
+        std::fs::write(&pointer.path, content)
+            .with_context(|| format!("Failed to write {}", pointer.path.display()))?;
+        if pinned {
+            println!(
+                "Pinned {} at {}",
+                mod_reference_for_entry(&entry),
+                entry.download.version
+            );
+        } else {
+            println!("Unpinned {}", mod_reference_for_entry(&entry));
+        }
+    }
+
+    Ok(())
+}
+
+fn destination_rel_dir(destination: &Path) -> Result<String> {
+    let mut parts = Vec::new();
+    for component in destination.components() {
+        match component {
+            std::path::Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            std::path::Component::CurDir => {}
+            _ => bail!(
+                "Destination must be a directory inside the pack: {}",
+                destination.display()
+            ),
+        }
+    }
+    let rel = parts.join("/");
+    if is_excluded_path(&format!("{}/", rel)) {
+        bail!("Destination {} is excluded from the pack.", rel);
+    }
+    Ok(rel)
+}
+
+fn pointer_parent(rel_path: &str) -> &str {
+    rel_path.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+fn display_dir(rel: &str) -> &str {
+    if rel.is_empty() { "The pack root" } else { rel }
+}
+
 fn load_pointer_resources(root: &Path) -> Result<Vec<PointerResource>> {
     let mut pointers = Vec::new();
     for path in pointer_paths(root)? {
@@ -934,7 +1362,7 @@ fn load_pointer_resources(root: &Path) -> Result<Vec<PointerResource>> {
         let kind = pointer_kind_from_rel_path(&rel_path)?;
         let contents = io::read_to_string(&path)?;
         let entry = protocol::config::mods::parse_mod_toml(&contents)
-            .map_err(|_| anyhow::anyhow!("Invalid pointer file: {}", path.display()))?;
+            .with_context(|| format!("Invalid pointer file: {}", path.display()))?;
 
         pointers.push(PointerResource {
             path,
@@ -1061,7 +1489,7 @@ fn list(args: ListArgs) -> Result<()> {
         if rel_str.ends_with(".mod.toml") || rel_str.ends_with(".res.toml") {
             let contents = io::read_to_string(path)?;
             let parsed = protocol::config::mods::parse_mod_toml(&contents)
-                .map_err(|_| anyhow::anyhow!("Invalid pointer file: {}", path.display()))?;
+                .with_context(|| format!("Invalid pointer file: {}", path.display()))?;
             resources.push(format!(
                 "{}  ->  {}",
                 rel_str,
@@ -1093,13 +1521,14 @@ fn validate(args: ValidateArgs) -> Result<()> {
         .canonicalize()
         .context("Failed to resolve input path")?;
     let config_text = io::read_to_string(&root.join("atlas.toml"))?;
-    let config = protocol::config::atlas::parse_config(&config_text)
-        .map_err(|_| anyhow::anyhow!("atlas.toml is invalid"))?;
+    let config =
+        protocol::config::atlas::parse_config(&config_text).context("atlas.toml is invalid")?;
     validate_loader_version_against_minecraft(&config)?;
 
     let dependency_check_mode = DependencyCheckMode::from_input(&args.check_dependencies)?;
     let dependency_version_check_mode =
         DependencyVersionCheckMode::from_input(&args.check_dependency_versions)?;
+    let resource_check_mode = ResourceCheckMode::from_input(&args.check_resources)?;
 
     let pointers = load_pointer_resources(&root)?;
     let mod_pointers = pointers
@@ -1120,7 +1549,23 @@ fn validate(args: ValidateArgs) -> Result<()> {
         .collect::<HashMap<_, _>>();
 
     for pointer in &mod_pointers {
-        validate_mod_compatibility(pointer, &config)?;
+        if !pointer.entry.download.pinned {
+            validate_mod_compatibility(pointer, &config)?;
+        } else if let Err(err) = validate_mod_compatibility(pointer, &config) {
+            eprintln!(
+                "Warning: pinned version {} no longer fits the pack. {} Run `atlas mod unpin` to let it update.",
+                pointer.entry.download.version, err
+            );
+        }
+    }
+
+    if resource_check_mode == ResourceCheckMode::On {
+        for pointer in pointers
+            .iter()
+            .filter(|pointer| pointer.kind == PointerKind::Resource)
+        {
+            validate_resource_compatibility(pointer, &config)?;
+        }
     }
 
     if dependency_check_mode == DependencyCheckMode::On {
@@ -1147,6 +1592,7 @@ fn commit(args: CommitArgs) -> Result<()> {
     let status_output = Command::new("git")
         .arg("status")
         .arg("--porcelain")
+        .arg("--untracked-files=all")
         .current_dir(&root)
         .output()
         .context("Failed to run `git status --porcelain`")?;
@@ -1154,31 +1600,46 @@ fn commit(args: CommitArgs) -> Result<()> {
         bail!("Unable to check repository status.");
     }
 
-    let changed = !String::from_utf8_lossy(&status_output.stdout)
-        .trim()
-        .is_empty();
-    if !changed {
+    let status = String::from_utf8_lossy(&status_output.stdout).to_string();
+    if status.trim().is_empty() {
         println!("No changes to commit.");
         return Ok(());
     }
 
-    let add_status = Command::new("git")
-        .arg("add")
-        .arg("-A")
-        .current_dir(&root)
-        .status()
-        .context("Failed to run `git add -A`")?;
+    let pack_paths = match args.scope {
+        CommitScope::All => Vec::new(),
+        CommitScope::Pack => {
+            let paths = pack_relevant_changes(&status);
+            if paths.is_empty() {
+                println!("No pack changes to commit. Use `--scope all` to commit everything.");
+                return Ok(());
+            }
+            paths
+        }
+    };
+
+    let mut add = Command::new("git");
+    add.arg("add").current_dir(&root);
+    if args.scope == CommitScope::All {
+        add.arg("-A");
+    } else {
+        add.arg("--").args(&pack_paths);
+    }
+    let add_status = add.status().context("Failed to run `git add`")?;
     if !add_status.success() {
-        bail!("`git add -A` failed.");
+        bail!("`git add` failed.");
     }
 
-    let commit_status = Command::new("git")
+    let mut commit = Command::new("git");
+    commit
         .arg("commit")
         .arg("-m")
         .arg(message)
-        .current_dir(&root)
-        .status()
-        .context("Failed to run `git commit`")?;
+        .current_dir(&root);
+    if args.scope == CommitScope::Pack {
+        commit.arg("--").args(&pack_paths);
+    }
+    let commit_status = commit.status().context("Failed to run `git commit`")?;
     if !commit_status.success() {
         bail!("`git commit` failed.");
     }
@@ -1186,11 +1647,144 @@ fn commit(args: CommitArgs) -> Result<()> {
     Ok(())
 }
 
+// Pointer files, atlas.toml/atlas.lock and changes to already-tracked files
+// (overrides) are pack content; new untracked files outside those are not.
+fn pack_relevant_changes(porcelain: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    for line in porcelain.lines().filter(|line| line.len() > 3) {
+        let untracked = line.starts_with("??");
+        // Renames list both sides so the old path's removal is committed too.
+        for path in line[3..].split(" -> ") {
+            let path = path.trim_matches('"');
+            if is_excluded_path(path) {
+                continue;
+            }
+            let is_pack_file = path == "atlas.toml"
+                || path == "atlas.lock"
+                || path.ends_with(".mod.toml")
+                || path.ends_with(".res.toml");
+            if is_pack_file || !untracked {
+                paths.push(path.to_string());
+            }
+        }
+    }
+    paths
+}
+
 struct CurseForgeAuth {
     hub_url: String,
-    access_token: String,
+    access_token: RefCell<String>,
+    /// Spinner to clear while the re-sign-in prompt is shown.
+    progress: ProgressBar,
+}
+
+impl CurseForgeAuth {
+    fn new(hub_url: String) -> Result<Self> {
+        let access_token = auth_store::require_access_token_for_hub(&hub_url)?;
+        Ok(Self {
+            hub_url,
+            access_token: RefCell::new(access_token),
+            progress: ProgressBar::hidden(),
+        })
+    }
+
+    /// Runs a proxy call with the current access token. If the proxy rejects
+    /// the token, it is renewed once and the call retried, so a token that
+    /// expires partway through a dependency walk does not abort the add.
+    fn call<T>(&self, call: impl Fn(&str) -> Result<T>) -> Result<T> {
+        let token = self.access_token.borrow().clone();
+        match call(&token) {
+            Err(err) if mod_resolver::is_proxy_unauthorized(&err) => {}
+            result => return result,
+        }
+
+        let token = self.renew()?;
+        call(&token).map_err(|err| {
+            if mod_resolver::is_proxy_unauthorized(&err) {
+                err.context(format!(
+                    "CurseForge proxy still rejects the Atlas session. Run `atlas login --hub {}`.",
+                    self.hub_url
+                ))
+            } else {
+                err
+            }
+        })
+    }
+
+    fn renew(&self) -> Result<String> {
+        let token = match crate::commands::auth::refresh_access_token(&self.hub_url) {
+            Ok(token) => token,
+            Err(refresh_err) => {
+                if !stdio::stdin().is_terminal() || !stdio::stdout().is_terminal() {
+                    return Err(refresh_err.context(format!(
+                        "Atlas session expired. Run `atlas login --hub {}` and re-run the command.",
+                        self.hub_url
+                    )));
+                }
+                let resume = self.progress.suspend(|| {
+                    eprintln!(
+                        "Atlas session expired ({refresh_err:#}). Run `atlas login --hub {}` in another terminal.",
+                        self.hub_url
+                    );
+                    Confirm::with_theme(&ColorfulTheme::default())
+                        .with_prompt("Signed in again? Continue")
+                        .default(true)
+                        .interact()
+                        .context("Failed to read confirmation")
+                })?;
+                if !resume {
+                    bail!("Cancelled after the Atlas session expired.");
+                }
+                auth_store::require_access_token_for_hub(&self.hub_url)?
+            }
+        };
+        *self.access_token.borrow_mut() = token.clone();
+        Ok(token)
+    }
 }
 
+/// Spinner for the dependency walk in `add`. Hidden unless both stdout and
+/// stderr are terminals, so piped and CI output stays line-oriented.
+fn resolution_progress() -> ProgressBar {
+    if !stdio::stdout().is_terminal() || !stdio::stderr().is_terminal() {
+        return ProgressBar::hidden();
+    }
+    let progress = ProgressBar::new_spinner().with_finish(ProgressFinish::AndClear);
+    progress.set_style(
+        ProgressStyle::with_template("{spinner} {msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+    );
+    progress.enable_steady_tick(Duration::from_millis(100));
+    progress
+}
+
+/// Asks before resuming a saved walk; a declined resume discards it. Without
+/// a terminal the walk resumes, since re-running the same add is the retry.
+fn confirm_resume(resume_file: &AddResumeFile, state: AddProgress) -> Result<Option<AddProgress>> {
+    let remaining = state.queue.len();
+    if !stdio::stdin().is_terminal() || !stdio::stdout().is_terminal() {
+        println!(
+            "Resuming interrupted add ({remaining} project(s) left). Pass --fresh to start over."
+        );
+        return Ok(Some(state));
+    }
+    let resume = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!(
+            "A previous add with these arguments was interrupted with {remaining} project(s) left. Resume it?"
+        ))
+        .default(true)
+        .interact()
+        .context("Failed to read confirmation")?;
+    if resume {
+        Ok(Some(state))
+    } else {
+        resume_file.remove();
+        Ok(None)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct QueuedResolution {
     project_id: String,
     desired_version: Option<String>,
@@ -1250,6 +1844,12 @@ enum DependencyVersionCheckMode {
     Off,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResourceCheckMode {
+    On,
+    Off,
+}
+
 impl PointerIndexEntry {
     fn from_pointer(pointer: &PointerResource) -> Self {
         Self {
@@ -1320,6 +1920,19 @@ impl DependencyVersionCheckMode {
     }
 }
 
+impl ResourceCheckMode {
+    fn from_input(input: &str) -> Result<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "on" => Ok(Self::On),
+            "off" => Ok(Self::Off),
+            other => bail!(
+                "Unsupported check-resources mode '{}'. Use on or off.",
+                other
+            ),
+        }
+    }
+}
+
 fn validate_loader_version_against_minecraft(
     config: &protocol::config::atlas::AtlasConfig,
 ) -> Result<()> {
@@ -1392,6 +2005,64 @@ fn validate_mod_compatibility(
     Ok(())
 }
 
+// Loaders do not apply to shaders or resource packs, so only the Minecraft
+// version is checked. Resource packs also accept any Minecraft version that
+// shares the pack's resource pack format.
+fn validate_resource_compatibility(
+    pointer: &PointerResource,
+    config: &protocol::config::atlas::AtlasConfig,
+) -> Result<()> {
+    let compat = &pointer.entry.compat;
+    if compat.minecraft.is_empty() {
+        return Ok(());
+    }
+
+    let pack_mc = config.versions.mc.trim();
+    let is_resource_pack = pointer.rel_path.starts_with("resourcepacks/");
+    let pack_format = resource_pack_format(pack_mc);
+    let compatible = compat.minecraft.iter().any(|value| {
+        let value = value.trim();
+        value == pack_mc
+            || (is_resource_pack
+                && pack_format.is_some()
+                && resource_pack_format(value) == pack_format)
+    });
+    if !compatible {
+        bail!(
+            "{}: incompatible Minecraft version. Pointer supports {:?}, pack uses {}",
+            pointer.rel_path,
+            compat.minecraft,
+            config.versions.mc
+        );
+    }
+
+    Ok(())
+}
+
+// Resource pack format per Minecraft release, for versions Atlas packs target.
+fn resource_pack_format(minecraft_version: &str) -> Option<u32> {
+    let format = match minecraft_version.trim() {
+        "1.13" | "1.13.1" | "1.13.2" | "1.14" | "1.14.1" | "1.14.2" | "1.14.3" | "1.14.4" => 4,
+        "1.15" | "1.15.1" | "1.15.2" | "1.16" | "1.16.1" => 5,
+        "1.16.2" | "1.16.3" | "1.16.4" | "1.16.5" => 6,
+        "1.17" | "1.17.1" => 7,
+        "1.18" | "1.18.1" | "1.18.2" => 8,
+        "1.19" | "1.19.1" | "1.19.2" => 9,
+        "1.19.3" => 12,
+        "1.19.4" => 13,
+        "1.20" | "1.20.1" => 15,
+        "1.20.2" => 18,
+        "1.20.3" | "1.20.4" => 22,
+        "1.20.5" | "1.20.6" => 32,
+        "1.21" | "1.21.1" => 34,
+        "1.21.2" | "1.21.3" => 42,
+        "1.21.4" => 46,
+        "1.21.5" => 55,
+        _ => return None,
+    };
+    Some(format)
+}
+
 fn validate_mod_dependencies(
     pointer: &PointerResource,
     index_by_project: &HashMap<String, PointerIndexEntry>,
@@ -1533,7 +2204,10 @@ enum NavigationAction {
 
 #[cfg(test)]
 mod tests {
-    use super::AssetKind;
+    use super::{
+        AssetKind, destination_rel_dir, pack_relevant_changes, parse_size, resource_pack_format,
+    };
+    use std::path::Path;
 
     #[test]
     fn resource_pointer_directories_match_asset_type() {
@@ -1547,4 +2221,50 @@ mod tests {
         );
         assert_eq!(AssetKind::Other.resource_pointer_directory(), "resources");
     }
+
+    #[test]
+    fn pack_scope_skips_untracked_non_pack_files() {
+        let status = " M atlas.toml\n?? mods/sodium.mod.toml\n M config/foo.json\n?? .vscode/settings.json\n M dist/pack.atlas\nR  old.res.toml -> new.res.toml\n";
+        assert_eq!(
+            pack_relevant_changes(status),
+            vec![
+                "atlas.toml",
+                "mods/sodium.mod.toml",
+                "config/foo.json",
+                "old.res.toml",
+                "new.res.toml",
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_max_size_units() {
+        assert_eq!(parse_size("4096"), Ok(4096));
+        assert_eq!(parse_size("500M"), Ok(500 * 1024 * 1024));
+        assert_eq!(parse_size("1GiB"), Ok(1024 * 1024 * 1024));
+        assert!(parse_size("12 parsecs").is_err());
+    }
+
+    #[test]
+    fn resource_packs_share_formats_across_patch_releases() {
+        assert_eq!(resource_pack_format("1.20"), resource_pack_format("1.20.1"));
+        assert_ne!(
+            resource_pack_format("1.20.1"),
+            resource_pack_format("1.20.2")
+        );
+        assert_eq!(resource_pack_format("1.21.1"), Some(34));
+        assert_eq!(resource_pack_format("25w01a"), None);
+    }
+
+    #[test]
+    fn move_destination_stays_inside_the_pack() {
+        assert_eq!(
+            destination_rel_dir(Path::new("./mods/server-only/")).unwrap(),
+            "mods/server-only"
+        );
+        assert_eq!(destination_rel_dir(Path::new(".")).unwrap(), "");
+        assert!(destination_rel_dir(Path::new("../mods")).is_err());
+        assert!(destination_rel_dir(Path::new("/tmp/mods")).is_err());
+        assert!(destination_rel_dir(Path::new("dist")).is_err());
+    }
 }
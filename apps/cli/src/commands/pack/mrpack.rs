@@ -1,6 +1,6 @@
 use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::io::{Read, Write};
+use std::io::{Cursor, Read, Write};
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result, bail};
@@ -28,13 +28,17 @@ pub(super) fn import(args: ImportArgs) -> Result<()> {
         bail!("Expected a file at {}", mrpack_path.display());
     }
 
-    let config = crate::config::load_atlas_config(&root)?;
+    import_into(&root, &mrpack_path)
+}
+
+pub(crate) fn import_into(root: &Path, mrpack_path: &Path) -> Result<()> {
+    let config = crate::config::load_atlas_config(root)?;
     let loader = config.versions.modloader;
     let minecraft_version = config.versions.mc;
 
-    let mut archive = open_archive(&mrpack_path)?;
+    let mut archive = open_archive(mrpack_path)?;
     let index = load_mrpack_index(&mut archive)?;
-    let mut existing = super::load_existing_mod_keys(&root)?;
+    let mut existing = super::load_existing_mod_keys(root)?;
 
     let mut added_from_index = 0usize;
     let mut skipped_existing_index = 0usize;
@@ -54,9 +58,9 @@ pub(super) fn import(args: ImportArgs) -> Result<()> {
         let key = super::mod_key(&entry.download.source, &entry.download.project_id);
         if existing.insert(key) {
             if kind == AssetKind::Mod {
-                crate::io::write_mod_entry(&root, &entry)?;
+                crate::io::write_mod_entry(root, &entry)?;
             } else {
-                crate::io::write_resource_entry(&root, &entry, kind.resource_pointer_directory())?;
+                crate::io::write_resource_entry(root, &entry, kind.resource_pointer_directory())?;
             }
             added_from_index += 1;
         } else {
@@ -85,7 +89,7 @@ pub(super) fn import(args: ImportArgs) -> Result<()> {
                 Ok(Some(entry)) => {
                     let key = super::mod_key(&entry.download.source, &entry.download.project_id);
                     if existing.insert(key) {
-                        crate::io::write_mod_entry(&root, &entry)?;
+                        crate::io::write_mod_entry(root, &entry)?;
                         added_override_mods += 1;
                         println!(
                             "Added override mod {}",
@@ -111,7 +115,7 @@ pub(super) fn import(args: ImportArgs) -> Result<()> {
         file.read_to_end(&mut bytes)
             .with_context(|| format!("Failed to read {}", entry_name))?;
         if std::str::from_utf8(&bytes).is_ok() {
-            write_override_text_file(&root, &rel_path, &bytes)?;
+            write_override_text_file(root, &rel_path, &bytes)?;
             imported_override_text += 1;
         } else {
             skipped_binary_overrides += 1;
@@ -165,7 +169,7 @@ pub(super) fn build(args: &BuildArgs, root: &Path) -> Result<()> {
             .unwrap_or_else(|_| pointer_path.to_string_lossy().replace('\\', "/"));
         let contents = crate::io::read_to_string(&pointer_path)?;
         let entry = protocol::config::mods::parse_mod_toml(&contents)
-            .map_err(|_| anyhow::anyhow!("Invalid pointer file: {}", pointer_path.display()))?;
+            .with_context(|| format!("Invalid pointer file: {}", pointer_path.display()))?;
 
         let Some(download_url) = entry
             .download
@@ -226,8 +230,10 @@ pub(super) fn build(args: &BuildArgs, root: &Path) -> Result<()> {
                 client: side_env_value(entry.metadata.side, true),
                 server: side_env_value(entry.metadata.side, false),
             }),
-            downloads: vec![download_url],
-            file_size: None,
+            downloads: std::iter::once(download_url)
+                .chain(entry.download.mirrors.iter().cloned())
+                .collect(),
+            file_size: entry.download.size,
         });
     }
 
@@ -263,14 +269,10 @@ pub(super) fn build(args: &BuildArgs, root: &Path) -> Result<()> {
 
     let index_json =
         serde_json::to_vec_pretty(&index).context("Failed to serialize mrpack index")?;
-    if let Some(parent) = output.parent() {
-        fs::create_dir_all(parent)
-            .with_context(|| format!("Failed to create {}", parent.display()))?;
-    }
 
-    let output_file = fs::File::create(&output)
-        .with_context(|| format!("Failed to create {}", output.display()))?;
-    let mut zip = ZipWriter::new(output_file);
+    // The zip writer needs a seekable sink, so the archive is assembled in memory
+    // and then flushed to either the output file or stdout.
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
     let options = SimpleFileOptions::default()
         .compression_method(CompressionMethod::Deflated)
         .compression_level(Some(9));
@@ -288,8 +290,17 @@ pub(super) fn build(args: &BuildArgs, root: &Path) -> Result<()> {
             .with_context(|| format!("Failed to write {}", archive_path))?;
     }
 
-    zip.finish().context("Failed to finalize mrpack")?;
-    println!("Wrote {}", output.display());
+    let bytes = zip
+        .finish()
+        .context("Failed to finalize mrpack")?
+        .into_inner();
+    if crate::io::is_stdout_path(&output) {
+        crate::io::write_stdout(&bytes)?;
+        eprintln!("Wrote {} bytes to stdout", bytes.len());
+    } else {
+        crate::io::write_output(&output, &bytes)?;
+        println!("Wrote {}", output.display());
+    }
     Ok(())
 }
 
@@ -330,6 +341,51 @@ fn collect_override_text_files(root: &Path) -> Result<Vec<(String, Vec<u8>)>> {
     Ok(files)
 }
 
+pub(crate) fn read_pack_info(path: &Path) -> Result<MrpackPackInfo> {
+    let mut archive = open_archive(path)?;
+    let index = load_mrpack_index(&mut archive)?;
+
+    let minecraft = index
+        .dependencies
+        .get("minecraft")
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .context("modrinth.index.json does not declare a minecraft dependency")?;
+    let (loader, loader_version) = loader_from_dependencies(&index.dependencies)?;
+
+    Ok(MrpackPackInfo {
+        name: index.name.filter(|value| !value.trim().is_empty()),
+        version: index.version_id.filter(|value| !value.trim().is_empty()),
+        summary: index.summary.filter(|value| !value.trim().is_empty()),
+        minecraft,
+        loader: loader.to_string(),
+        loader_version,
+    })
+}
+
+fn loader_from_dependencies(
+    dependencies: &BTreeMap<String, String>,
+) -> Result<(&'static str, String)> {
+    for (key, value) in dependencies {
+        let loader = match key.as_str() {
+            "minecraft" => continue,
+            "fabric-loader" => "fabric",
+            "forge" => "forge",
+            "neoforge" => "neoforge",
+            other => bail!(
+                "Unsupported modloader '{}' in mrpack (supported: fabric, forge, neoforge)",
+                other
+            ),
+        };
+        let version = value.trim();
+        if version.is_empty() {
+            bail!("mrpack declares {} without a version", key);
+        }
+        return Ok((loader, version.to_string()));
+    }
+    bail!("modrinth.index.json does not declare a modloader dependency")
+}
+
 fn loader_dependency_key(loader: &str) -> Result<&'static str> {
     match loader.trim().to_ascii_lowercase().as_str() {
         "fabric" => Ok("fabric-loader"),
@@ -452,6 +508,7 @@ fn build_entry_from_mrpack_file(file: &MrpackFile, kind: AssetKind) -> Option<Mo
             side: map_mrpack_side(kind, file.env.as_ref()),
             project_url: project_url_for_source(&source, &project_id),
             disabled_client_oses: Vec::new(),
+            extra: Default::default(),
         },
         compat: protocol::config::mods::ModCompat::default(),
         download: ModDownload {
@@ -459,13 +516,23 @@ fn build_entry_from_mrpack_file(file: &MrpackFile, kind: AssetKind) -> Option<Mo
             project_id,
             version,
             file_id: parsed.as_ref().and_then(|value| value.file_id.clone()),
-            url: Some(download_url),
+            url: Some(download_url.clone()),
             hashes: Some(ModHashes {
                 sha1: get_hash(&file.hashes, "sha1"),
                 sha256: get_hash(&file.hashes, "sha256"),
                 sha512: get_hash(&file.hashes, "sha512"),
             }),
+            mirrors: file
+                .downloads
+                .iter()
+                .filter(|value| !value.trim().is_empty() && **value != download_url)
+                .cloned()
+                .collect(),
+            size: file.file_size,
+            pinned: false,
+            extra: Default::default(),
         },
+        extra: Default::default(),
     })
 }
 
@@ -586,6 +653,7 @@ fn resolve_override_with_provider(
     };
 
     match mod_resolver::resolve_by_project_id_blocking(
+        &mod_resolver::VersionCache::new(),
         provider,
         &candidate.project_id,
         loader,
@@ -748,8 +816,25 @@ fn path_segment_after(segments: &[String], needle: &str) -> Option<String> {
 
 #[derive(Debug, Deserialize)]
 struct MrpackIndex {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default, rename = "versionId")]
+    version_id: Option<String>,
+    #[serde(default)]
+    summary: Option<String>,
     #[serde(default)]
     files: Vec<MrpackFile>,
+    #[serde(default)]
+    dependencies: BTreeMap<String, String>,
+}
+
+pub(crate) struct MrpackPackInfo {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub summary: Option<String>,
+    pub minecraft: String,
+    pub loader: String,
+    pub loader_version: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -761,6 +846,8 @@ struct MrpackFile {
     downloads: Vec<String>,
     #[serde(default)]
     env: Option<MrpackEnv>,
+    #[serde(rename = "fileSize", default)]
+    file_size: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -814,7 +901,23 @@ struct MrpackBuildEnv {
 
 #[cfg(test)]
 mod tests {
-    use super::{override_query_for_path, strip_overrides_prefix};
+    use std::collections::BTreeMap;
+
+    use super::{loader_from_dependencies, override_query_for_path, strip_overrides_prefix};
+
+    #[test]
+    fn derives_loader_from_mrpack_dependencies() {
+        let mut dependencies = BTreeMap::new();
+        dependencies.insert("minecraft".to_string(), "1.20.1".to_string());
+        dependencies.insert("fabric-loader".to_string(), "0.15.11".to_string());
+        let (loader, version) = loader_from_dependencies(&dependencies).expect("loader");
+        assert_eq!(loader, "fabric");
+        assert_eq!(version, "0.15.11");
+
+        dependencies.remove("fabric-loader");
+        dependencies.insert("quilt-loader".to_string(), "0.26.0".to_string());
+        assert!(loader_from_dependencies(&dependencies).is_err());
+    }
 
     #[test]
     fn strips_supported_override_prefixes() {
@@ -7,6 +7,7 @@ use clap::Args;
 use dialoguer::{Select, theme::ColorfulTheme};
 
 use crate::auth_store;
+use crate::commands::release_diff;
 use crate::config;
 
 #[derive(Args)]
@@ -21,6 +22,8 @@ pub struct PromoteArgs {
     channel: Option<String>,
     #[arg(long, value_name = "BUILD_ID")]
     build_id: Option<String>,
+    #[arg(long, short = 'y')]
+    yes: bool,
 }
 
 pub fn run(args: PromoteArgs) -> Result<()> {
@@ -48,6 +51,14 @@ pub fn run(args: PromoteArgs) -> Result<()> {
     let selected_channel = resolve_channel(&args, &settings.channel, &client, &pack_id)?;
     let selected_build = resolve_build_id(&args, &client, &pack_id)?;
 
+    if release_diff::requires_confirmation(&selected_channel, args.yes) {
+        preview_promotion(&client, &pack_id, &selected_channel, &selected_build);
+        if !release_diff::confirm(&selected_channel)? {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
     client.blocking_promote_pack_channel(&pack_id, &selected_channel, &selected_build)?;
 
     println!(
@@ -57,6 +68,27 @@ pub fn run(args: PromoteArgs) -> Result<()> {
     Ok(())
 }
 
+fn preview_promotion(client: &HubClient, pack_id: &str, channel: &str, build_id: &str) {
+    let channels = client
+        .blocking_list_pack_channels(pack_id)
+        .unwrap_or_default();
+    let source_channel = channels.iter().find(|candidate| {
+        candidate.name != channel && candidate.build_id.as_deref() == Some(build_id)
+    });
+
+    let Some(next) = source_channel
+        .and_then(|source| release_diff::fetch_live_blob(client, pack_id, &source.name))
+    else {
+        println!(
+            "Build {} is not live on another channel, so its changes cannot be previewed.",
+            build_id
+        );
+        return;
+    };
+    let current = release_diff::fetch_live_blob(client, pack_id, channel);
+    release_diff::print_diff(channel, current.as_ref(), &next);
+}
+
 fn resolve_channel(
     args: &PromoteArgs,
     default_channel: &str,
@@ -1,5 +1,7 @@
+use std::collections::BTreeSet;
+use std::fs;
 use std::io::{self, IsTerminal};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{Context, Result, bail};
@@ -9,6 +11,7 @@ use dialoguer::{FuzzySelect, theme::ColorfulTheme};
 use serde::Deserialize;
 
 use crate::auth_store;
+use crate::commands::sync_state;
 
 #[derive(Args)]
 pub struct PullArgs {
@@ -20,6 +23,16 @@ pub struct PullArgs {
     hub_url: Option<String>,
     #[arg(long, value_name = "PATH")]
     output: Option<PathBuf>,
+    #[arg(long, conflicts_with = "ours")]
+    theirs: bool,
+    #[arg(long, conflicts_with = "theirs")]
+    ours: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Resolution {
+    Theirs,
+    Ours,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -32,7 +45,7 @@ struct RemotePack {
 }
 
 pub fn run(args: PullArgs) -> Result<()> {
-    let hub_url = auth_store::resolve_hub_url(args.hub_url.clone());
+    let hub_url = auth_store::resolve_hub_url(args.hub_url.clone())?;
     let access_token = auth_store::require_access_token_for_hub(&hub_url)?;
     let mut client = HubClient::new(&hub_url)?;
     client.set_token(access_token.clone());
@@ -60,14 +73,157 @@ pub fn run(args: PullArgs) -> Result<()> {
         .filter(|value| !value.is_empty())
         .context("Selected pack does not have an associated repository.")?;
 
+    let destination = args
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(default_clone_dir(repo_url)));
+    if destination.join(".git").exists() {
+        println!(
+            "Updating {} ({}) in {}",
+            selected.pack_name,
+            selected.pack_id,
+            destination.display()
+        );
+        let resolution = if args.theirs {
+            Some(Resolution::Theirs)
+        } else if args.ours {
+            Some(Resolution::Ours)
+        } else {
+            None
+        };
+        return update_checkout(&destination, resolution);
+    }
+
     println!(
         "Cloning {} ({}) from {}",
         selected.pack_name, selected.pack_id, repo_url
     );
-    run_git_clone(repo_url, args.output.as_ref())?;
+    run_git_clone(repo_url, Some(&destination))?;
+    if let Err(err) = record_upstream(&destination) {
+        eprintln!("Cloned, but failed to record sync state: {}", err);
+    }
     Ok(())
 }
 
+fn update_checkout(root: &Path, resolution: Option<Resolution>) -> Result<()> {
+    let upstream = sync_state::git_output(
+        root,
+        &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
+    )
+    .context("Current branch has no upstream to pull from.")?;
+    let (remote, _) = upstream
+        .split_once('/')
+        .with_context(|| format!("Unexpected upstream ref '{}'", upstream))?;
+    sync_state::git_output(root, &["fetch", remote])?;
+
+    let base = sync_state::git_output(root, &["merge-base", "HEAD", &upstream])?;
+    let incoming = sync_state::changed_files(root, &base, &upstream)?;
+    let dirty = sync_state::uncommitted_files(root)?;
+    let conflicts = incoming
+        .intersection(&dirty)
+        .cloned()
+        .collect::<BTreeSet<_>>();
+
+    let mut kept = Vec::new();
+    if !conflicts.is_empty() {
+        let Some(resolution) = resolution else {
+            eprintln!("Pulling would overwrite uncommitted local changes:");
+            sync_state::print_conflicts(&conflicts);
+            bail!("Pass --theirs to take the remote versions or --ours to keep your local edits.");
+        };
+        for path in &conflicts {
+            let full_path = root.join(path);
+            if resolution == Resolution::Ours {
+                kept.push((full_path.clone(), fs::read(&full_path).ok()));
+            }
+            discard_local_change(root, path)?;
+        }
+    }
+
+    let mut merge = vec!["merge", "--no-edit"];
+    match resolution {
+        Some(Resolution::Theirs) => merge.extend(["-X", "theirs"]),
+        Some(Resolution::Ours) => merge.extend(["-X", "ours"]),
+        None => {}
+    }
+    merge.push(&upstream);
+    sync_state::git_output(root, &merge)?;
+
+    for (path, contents) in kept {
+        match contents {
+            Some(bytes) => {
+                tracing::debug!("restoring {}", path.display());
+                fs::write(&path, bytes)
+                    .with_context(|| format!("Failed to restore {}", path.display()))?;
+            }
+            None => {
+                if path.exists() {
+                    fs::remove_file(&path)
+                        .with_context(|| format!("Failed to remove {}", path.display()))?;
+                }
+            }
+        }
+    }
+
+    record_upstream(root)?;
+    if conflicts.is_empty() {
+        println!(
+            "Pulled {} changed file(s) from {}.",
+            incoming.len(),
+            upstream
+        );
+    } else {
+        println!(
+            "Pulled {} changed file(s) from {}; resolved {} conflict(s) with --{}.",
+            incoming.len(),
+            upstream,
+            conflicts.len(),
+            if resolution == Some(Resolution::Ours) {
+                "ours"
+            } else {
+                "theirs"
+            }
+        );
+    }
+    Ok(())
+}
+
+fn discard_local_change(root: &Path, path: &str) -> Result<()> {
+    let tracked = Command::new("git")
+        .args(["cat-file", "-e", &format!("HEAD:{}", path)])
+        .current_dir(root)
+        .status()
+        .context("Failed to run git cat-file")?
+        .success();
+    if tracked {
+        sync_state::git_output(root, &["checkout", "HEAD", "--", path])?;
+    } else {
+        let full_path = root.join(path);
+        if full_path.exists() {
+            fs::remove_file(&full_path)
+                .with_context(|| format!("Failed to remove {}", full_path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+fn record_upstream(root: &Path) -> Result<()> {
+    let upstream = sync_state::git_output(
+        root,
+        &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
+    )?;
+    let (remote, branch) = upstream
+        .split_once('/')
+        .with_context(|| format!("Unexpected upstream ref '{}'", upstream))?;
+    sync_state::record(root, remote, branch)
+}
+
+fn default_clone_dir(repo_url: &str) -> String {
+    let trimmed = repo_url.trim_end_matches('/');
+    let name = trimmed.rsplit(['/', ':']).next().unwrap_or(trimmed);
+    name.strip_suffix(".git").unwrap_or(name).to_string()
+}
+
 fn fetch_remote_packs(client: &HubClient) -> Result<Vec<RemotePack>> {
     let packs = client.blocking_list_launcher_packs()?;
     Ok(packs
@@ -4,6 +4,8 @@ use std::process::Command;
 use anyhow::{Context, Result, bail};
 use clap::Args;
 
+use crate::commands::sync_state;
+
 #[derive(Args)]
 pub struct PushArgs {
     #[arg(long, default_value = ".")]
@@ -16,6 +18,8 @@ pub struct PushArgs {
     set_upstream: bool,
     #[arg(long)]
     force_with_lease: bool,
+    #[arg(long)]
+    force: bool,
 }
 
 pub fn run(args: PushArgs) -> Result<()> {
@@ -31,6 +35,13 @@ pub fn run(args: PushArgs) -> Result<()> {
         (false, value) => value,
     };
 
+    let target_branch = branch
+        .clone()
+        .or_else(|| resolve_current_branch(&root).ok());
+    if let Some(target) = target_branch.as_deref().filter(|_| !args.force) {
+        ensure_remote_unchanged(&root, &args.remote, target)?;
+    }
+
     println!(
         "Pushing {}{} via system git credentials ({})",
         args.remote,
@@ -46,10 +57,56 @@ pub fn run(args: PushArgs) -> Result<()> {
         branch.as_deref(),
         args.set_upstream,
         args.force_with_lease,
+        args.force,
     )?;
+    let recorded = target_branch.as_deref().map_or(Ok(()), |target| {
+        sync_state::record(&root, &args.remote, target)
+    });
+    if let Err(err) = recorded {
+        eprintln!("Pushed, but failed to record sync state: {}", err);
+    }
     Ok(())
 }
 
+// Refuses to push when the remote moved since the last pull/push and the new
+// remote commits touch files that were also changed locally.
+fn ensure_remote_unchanged(root: &Path, remote: &str, branch: &str) -> Result<()> {
+    let Some(state) =
+        sync_state::load(root)?.filter(|state| state.remote == remote && state.branch == branch)
+    else {
+        return Ok(());
+    };
+
+    sync_state::git_output(root, &["fetch", remote, branch])?;
+    let remote_ref = format!("{}/{}", remote, branch);
+    let Ok(remote_head) = sync_state::git_output(root, &["rev-parse", &remote_ref]) else {
+        return Ok(());
+    };
+    if remote_head == state.remote_head || sync_state::is_ancestor(root, &remote_head, "HEAD")? {
+        return Ok(());
+    }
+
+    let remote_changes = sync_state::changed_files(root, &state.remote_head, &remote_head)?;
+    let local_changes = sync_state::changed_files(root, &state.remote_head, "HEAD")?;
+    let conflicts = remote_changes
+        .intersection(&local_changes)
+        .cloned()
+        .collect::<std::collections::BTreeSet<_>>();
+
+    if conflicts.is_empty() {
+        bail!(
+            "{} has new commits since the last pull. Run `atlas pull` first, or pass --force to overwrite.",
+            remote_ref
+        );
+    }
+    eprintln!(
+        "{} changed since the last pull; these files were edited on both sides:",
+        remote_ref
+    );
+    sync_state::print_conflicts(&conflicts);
+    bail!("Run `atlas pull` to resolve the conflicts, or pass --force to overwrite the remote.")
+}
+
 fn resolve_remote_url(root: &Path, remote: &str) -> Result<String> {
     let output = Command::new("git")
         .arg("remote")
@@ -100,26 +157,71 @@ fn run_git_push(
     branch: Option<&str>,
     set_upstream: bool,
     force_with_lease: bool,
+    force: bool,
 ) -> Result<()> {
-    let mut command = Command::new("git");
-    command.current_dir(root);
+    let status = Command::new("git")
+        .current_dir(root)
+        .args(push_args(
+            remote,
+            branch,
+            set_upstream,
+            force_with_lease,
+            force,
+        ))
+        .status()
+        .context("Failed to run git push")?;
+    if !status.success() {
+        bail!("git push failed.");
+    }
+
+    Ok(())
+}
 
-    command.arg("push");
-    if force_with_lease {
-        command.arg("--force-with-lease");
+// `--force` only skips the sync-state check; the push itself still uses a
+// lease so commits git has not fetched yet are never overwritten.
+fn push_args(
+    remote: &str,
+    branch: Option<&str>,
+    set_upstream: bool,
+    force_with_lease: bool,
+    force: bool,
+) -> Vec<String> {
+    let mut args = vec!["push".to_string()];
+    if force || force_with_lease {
+        args.push("--force-with-lease".to_string());
     }
     if set_upstream {
-        command.arg("--set-upstream");
+        args.push("--set-upstream".to_string());
     }
-    command.arg(remote);
+    args.push(remote.to_string());
     if let Some(value) = branch {
-        command.arg(value);
+        args.push(value.to_string());
     }
+    args
+}
 
-    let status = command.status().context("Failed to run git push")?;
-    if !status.success() {
-        bail!("git push failed.");
-    }
+#[cfg(test)]
+mod tests {
+    use super::push_args;
 
-    Ok(())
+    #[test]
+    fn force_pushes_with_a_lease() {
+        let expected = ["push", "--force-with-lease", "origin", "main"];
+        assert_eq!(
+            push_args("origin", Some("main"), false, false, true),
+            expected
+        );
+        assert_eq!(
+            push_args("origin", Some("main"), false, true, true),
+            expected
+        );
+        assert_eq!(
+            push_args("origin", Some("main"), false, true, false),
+            expected
+        );
+        assert_eq!(
+            push_args("origin", None, false, false, false),
+            ["push", "origin"]
+        );
+    }
 }
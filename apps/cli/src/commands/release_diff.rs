@@ -0,0 +1,98 @@
+use std::io::{self, IsTerminal};
+
+use anyhow::{Context, Result, bail};
+use atlas_client::hub::HubClient;
+use dialoguer::{Confirm, theme::ColorfulTheme};
+use protocol::{Dependency, PackBlob, PackMetadata};
+
+const CONFIRMED_CHANNEL: &str = "production";
+
+pub fn requires_confirmation(channel: &str, assume_yes: bool) -> bool {
+    !assume_yes && channel == CONFIRMED_CHANNEL
+}
+
+pub fn fetch_live_blob(client: &HubClient, pack_id: &str, channel: &str) -> Option<PackBlob> {
+    let result = client
+        .blocking_get_build_blob(pack_id, channel)
+        .map_err(anyhow::Error::from)
+        .and_then(|build| protocol::decode_blob(&build.bytes).map_err(anyhow::Error::from));
+    match result {
+        Ok(blob) => Some(blob),
+        Err(err) => {
+            eprintln!("Could not load the live {} build: {}", channel, err);
+            None
+        }
+    }
+}
+
+pub fn print_diff(channel: &str, current: Option<&PackBlob>, next: &PackBlob) {
+    let Some(current) = current else {
+        println!(
+            "No live build on {}; {} (version {}) with {} dependencies will become its first build.",
+            channel,
+            next.metadata.pack_id,
+            next.metadata.version,
+            next.manifest.dependencies.len()
+        );
+        return;
+    };
+
+    println!("Changes to {}:", channel);
+    print_metadata_changes(&current.metadata, &next.metadata);
+
+    let diff = current.manifest.diff(&next.manifest);
+    if diff.is_empty() {
+        println!("  No dependency changes.");
+        return;
+    }
+    for dependency in &diff.added {
+        println!("  + {}", dependency_label(dependency));
+    }
+    for dependency in &diff.removed {
+        println!("  - {}", dependency_label(dependency));
+    }
+    for change in &diff.updated {
+        println!("  ~ {}", dependency_label(&change.after));
+    }
+    println!(
+        "  {} added, {} removed, {} updated",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.updated.len()
+    );
+}
+
+pub fn confirm(channel: &str) -> Result<bool> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        bail!(
+            "Releasing to {} requires confirmation. Pass --yes in non-interactive mode.",
+            channel
+        );
+    }
+
+    Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Release to {}?", channel))
+        .default(false)
+        .interact()
+        .context("Failed to read confirmation")
+}
+
+fn print_metadata_changes(current: &PackMetadata, next: &PackMetadata) {
+    println!("  version: {} -> {}", current.version, next.version);
+    if current.minecraft_version != next.minecraft_version {
+        println!(
+            "  minecraft: {} -> {}",
+            current.minecraft_version, next.minecraft_version
+        );
+    }
+    if current.loader != next.loader || current.loader_version != next.loader_version {
+        println!(
+            "  loader: {:?} {} -> {:?} {}",
+            current.loader, current.loader_version, next.loader, next.loader_version
+        );
+    }
+}
+
+fn dependency_label(dependency: &Dependency) -> &str {
+    dependency.pointer_path.as_str()
+}
@@ -0,0 +1,104 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+const SYNC_STATE_FILE: &str = "atlas-sync.json";
+
+// Lives inside the git directory so it is per-checkout and never committed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncState {
+    pub remote: String,
+    pub branch: String,
+    pub remote_head: String,
+}
+
+pub fn load(root: &Path) -> Result<Option<SyncState>> {
+    let path = state_path(root)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = crate::io::read_to_string(&path)?;
+    match serde_json::from_str::<SyncState>(&contents) {
+        Ok(state) => Ok(Some(state)),
+        Err(err) => {
+            eprintln!("Ignoring unreadable {}: {}", path.display(), err);
+            Ok(None)
+        }
+    }
+}
+
+pub fn record(root: &Path, remote: &str, branch: &str) -> Result<()> {
+    let remote_head = git_output(root, &["rev-parse", &format!("{}/{}", remote, branch)])?;
+    let state = SyncState {
+        remote: remote.to_string(),
+        branch: branch.to_string(),
+        remote_head,
+    };
+    let path = state_path(root)?;
+    let contents =
+        serde_json::to_string_pretty(&state).context("Failed to serialize sync state")?;
+    tracing::debug!("writing {}", path.display());
+    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+pub fn changed_files(root: &Path, from: &str, to: &str) -> Result<BTreeSet<String>> {
+    let output = git_output(root, &["diff", "--name-only", from, to])?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+pub fn uncommitted_files(root: &Path) -> Result<BTreeSet<String>> {
+    let output = run_git(root, &["status", "--porcelain"])?;
+    Ok(output
+        .lines()
+        .filter(|line| line.len() > 3)
+        .map(|line| {
+            let path = &line[3..];
+            path.rsplit_once(" -> ")
+                .map(|(_, renamed)| renamed)
+                .unwrap_or(path)
+                .to_string()
+        })
+        .collect())
+}
+
+pub fn is_ancestor(root: &Path, ancestor: &str, descendant: &str) -> Result<bool> {
+    let status = Command::new("git")
+        .args(["merge-base", "--is-ancestor", ancestor, descendant])
+        .current_dir(root)
+        .status()
+        .context("Failed to run git merge-base")?;
+    Ok(status.success())
+}
+
+pub fn git_output(root: &Path, args: &[&str]) -> Result<String> {
+    Ok(run_git(root, args)?.trim().to_string())
+}
+
+fn run_git(root: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(root)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        bail!("git {} failed: {}", args.join(" "), stderr);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub fn print_conflicts(conflicts: &BTreeSet<String>) {
+    for path in conflicts {
+        eprintln!("  - {}", path);
+    }
+}
+
+fn state_path(root: &Path) -> Result<PathBuf> {
+    let git_dir = git_output(root, &["rev-parse", "--git-dir"])?;
+    Ok(root.join(git_dir).join(SYNC_STATE_FILE))
+}
@@ -2,10 +2,12 @@ use std::collections::BTreeMap;
 use std::path::Path;
 
 use anyhow::{Context, Result, bail};
-use atlas_client::device_code::{DEFAULT_ATLAS_HUB_URL, normalize_hub_url};
+use atlas_client::device_code::DEFAULT_ATLAS_HUB_URL;
+use atlas_client::hub_url::normalize_hub_url_string;
 use protocol::config::atlas::AtlasConfig;
 use protocol::pack::{BuildInput, BuildOutput, build_pack_bytes as build_binary};
 
+use crate::auth_store;
 use crate::io;
 
 pub struct CliSettings {
@@ -23,7 +25,7 @@ pub fn load_atlas_config(root: &Path) -> Result<AtlasConfig> {
 
     let config_text = io::read_to_string(&config_path)?;
     let config = protocol::config::atlas::parse_config(&config_text)
-        .map_err(|_| anyhow::anyhow!("Failed to parse atlas.toml"))?;
+        .with_context(|| format!("Failed to parse {}", config_path.display()))?;
     Ok(config)
 }
 
@@ -42,6 +44,7 @@ pub fn resolve_cli_settings(
     let hub_url = normalize_optional(hub_url_override)
         .or_else(|| normalize_optional(std::env::var("ATLAS_HUB_URL").ok()))
         .or_else(|| normalize_optional(cli_config.hub_url))
+        .or_else(auth_store::active_hub_url)
         .unwrap_or_else(|| DEFAULT_ATLAS_HUB_URL.to_string());
 
     let channel = normalize_optional(channel_override)
@@ -50,7 +53,7 @@ pub fn resolve_cli_settings(
 
     Ok(CliSettings {
         pack_id,
-        hub_url: normalize_hub_url(&hub_url),
+        hub_url: normalize_hub_url_string(&hub_url)?,
         channel,
     })
 }
@@ -84,6 +87,7 @@ pub fn build_pack_bytes(
     Ok(BuildOutput {
         bytes: build.bytes,
         metadata: build.metadata,
+        stats: build.stats,
     })
 }
 
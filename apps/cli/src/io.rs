@@ -1,5 +1,6 @@
 use std::collections::BTreeMap;
 use std::fs;
+use std::io::{self as stdio, Write};
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
@@ -17,9 +18,22 @@ pub fn write_output(path: &Path, bytes: &[u8]) -> Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).context("Failed to create output directory")?;
     }
+    tracing::debug!("writing {} ({} bytes)", path.display(), bytes.len());
     fs::write(path, bytes).context("Failed to write output file")
 }
 
+pub fn is_stdout_path(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+pub fn write_stdout(bytes: &[u8]) -> Result<()> {
+    let mut stdout = stdio::stdout().lock();
+    stdout
+        .write_all(bytes)
+        .context("Failed to write output to stdout")?;
+    stdout.flush().context("Failed to flush stdout")
+}
+
 pub fn insert_file(files: &mut BTreeMap<String, Vec<u8>>, root: &Path, name: &str) -> Result<()> {
     let path = root.join(name);
     if !path.exists() {
@@ -85,6 +99,7 @@ fn write_pointer_entry(
     let content = entry
         .to_toml_string()
         .context("Failed to serialize mod entry")?;
+    tracing::debug!("writing {}", file_path.display());
     fs::write(&file_path, content)
         .with_context(|| format!("Failed to write {}", file_path.display()))?;
     Ok(())
@@ -1,17 +1,22 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{ArgAction, Parser, Subcommand};
+use tracing_subscriber::EnvFilter;
 
+mod auth_crypto;
 mod auth_store;
 mod commands;
 mod config;
 mod io;
+mod text_diff;
 mod version_catalog;
 
-use commands::{auth, ci, completion, deploy, init, pack, promote, pull, push};
+use commands::{auth, ci, completion, deploy, doctor, init, pack, promote, pull, push};
 
 #[derive(Parser)]
 #[command(name = "atlas", version, about = "Atlas pack tooling")]
 struct Cli {
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
     #[command(subcommand)]
     command: Commands,
 }
@@ -19,8 +24,9 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     Login(auth::SignInArgs),
-    Logout,
-    Status,
+    Logout(auth::SignOutArgs),
+    Status(auth::StatusArgs),
+    Hub(auth::HubArgs),
     Init(init::InitArgs),
     Reinit(init::ReinitArgs),
     Pull(pull::PullArgs),
@@ -39,12 +45,16 @@ enum Commands {
         command: WorkflowCommands,
     },
     Completion(completion::CompletionArgs),
+    Doctor(doctor::DoctorArgs),
 }
 
 #[derive(Subcommand)]
 enum ModCommands {
     Add(pack::AddArgs),
     Remove(pack::RmArgs),
+    Move(pack::MvArgs),
+    Pin(pack::PinArgs),
+    Unpin(pack::PinArgs),
     List(pack::ListArgs),
     Import(pack::ImportArgs),
 }
@@ -57,11 +67,13 @@ enum WorkflowCommands {
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    init_tracing(cli.verbose);
 
     match cli.command {
         Commands::Login(args) => auth::run(auth::AuthCommand::Signin(args)),
-        Commands::Logout => auth::run(auth::AuthCommand::Signout),
-        Commands::Status => auth::run(auth::AuthCommand::Status),
+        Commands::Logout(args) => auth::run(auth::AuthCommand::Signout(args)),
+        Commands::Status(args) => auth::run(auth::AuthCommand::Status(args)),
+        Commands::Hub(args) => auth::run(auth::AuthCommand::Hub(args)),
         Commands::Init(args) => init::run_init(args),
         Commands::Reinit(args) => init::run_reinit(args),
         Commands::Pull(args) => pull::run(args),
@@ -74,6 +86,9 @@ fn main() -> Result<()> {
         Commands::Mod { command } => match command {
             ModCommands::Add(args) => pack::run(pack::PackCommand::Add(args)),
             ModCommands::Remove(args) => pack::run(pack::PackCommand::Rm(args)),
+            ModCommands::Move(args) => pack::run(pack::PackCommand::Mv(args)),
+            ModCommands::Pin(args) => pack::run(pack::PackCommand::Pin(args)),
+            ModCommands::Unpin(args) => pack::run(pack::PackCommand::Unpin(args)),
             ModCommands::List(args) => pack::run(pack::PackCommand::List(args)),
             ModCommands::Import(args) => pack::run(pack::PackCommand::Import(args)),
         },
@@ -82,5 +97,26 @@ fn main() -> Result<()> {
             WorkflowCommands::Update(args) => ci::run(ci::CiCommand::Update(args)),
         },
         Commands::Completion(args) => completion::run(args),
+        Commands::Doctor(args) => doctor::run(args),
     }
 }
+
+/// `ATLAS_LOG` takes an `EnvFilter` directive and wins over `-v`. Logs go to
+/// stderr so `--json` and `-o -` output stays parseable.
+fn init_tracing(verbose: u8) {
+    let filter = std::env::var("ATLAS_LOG")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .and_then(|value| EnvFilter::try_new(value).ok())
+        .unwrap_or_else(|| {
+            EnvFilter::new(match verbose {
+                0 => "warn",
+                1 => "warn,atlas=debug,atlas_client=debug,mod_resolver=debug",
+                _ => "debug,atlas=trace,atlas_client=trace,mod_resolver=trace",
+            })
+        });
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(|| protocol::redact::RedactingWriter::new(std::io::stderr()))
+        .init();
+}
@@ -51,20 +51,33 @@ impl VersionCatalog {
 
     pub fn fetch_loader_versions(&self, modloader: &str, mc_version: &str) -> Result<Vec<String>> {
         match modloader {
-            "fabric" => self.fetch_fabric_versions(mc_version),
+            "fabric" => self.fetch_fabric_versions(mc_version, true),
             "forge" => self.fetch_forge_versions(mc_version),
             "neoforge" => self.fetch_neoforge_versions(mc_version),
             _ => bail!("Unsupported modloader: {}", modloader),
         }
     }
 
-    fn fetch_fabric_versions(&self, mc_version: &str) -> Result<Vec<String>> {
+    /// Like [`Self::fetch_loader_versions`], but keeps Fabric loaders that are
+    /// not marked stable, for checking a version that is already pinned.
+    pub fn fetch_published_loader_versions(
+        &self,
+        modloader: &str,
+        mc_version: &str,
+    ) -> Result<Vec<String>> {
+        match modloader {
+            "fabric" => self.fetch_fabric_versions(mc_version, false),
+            _ => self.fetch_loader_versions(modloader, mc_version),
+        }
+    }
+
+    fn fetch_fabric_versions(&self, mc_version: &str, stable_only: bool) -> Result<Vec<String>> {
         let url = format!("{}/{}", FABRIC_LOADER_URL_TEMPLATE, mc_version);
         let versions = self.get_json::<Vec<FabricLoaderVersion>>(&url)?;
 
         let mut stable = versions
             .iter()
-            .filter(|entry| entry.loader.stable.unwrap_or(true))
+            .filter(|entry| !stable_only || entry.loader.stable.unwrap_or(true))
             .map(|entry| entry.loader.version.clone())
             .collect::<Vec<_>>();
 
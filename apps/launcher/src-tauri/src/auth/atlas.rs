@@ -1,7 +1,8 @@
 use crate::telemetry;
 use atlas_client::device_code::{
     hub_device_code_endpoint, hub_device_token_endpoint, parse_device_token_poll_body,
-    DeviceCodeRequest, DeviceTokenPollStatus, DeviceTokenRequest, StandardDeviceTokenResponse,
+    DeviceCodeRequest, DevicePollInterval, DeviceTokenPollStatus, DeviceTokenRequest,
+    StandardDeviceTokenResponse,
 };
 use atlas_client::oauth as client_oauth;
 use std::time::{Duration, Instant};
@@ -52,14 +53,14 @@ pub(crate) async fn poll_device_token(
 ) -> Result<StandardDeviceTokenResponse, AuthError> {
     let poll_url = hub_device_token_endpoint(hub_url);
     let request = DeviceTokenRequest::new(client_id, device_code);
-    let mut interval = Duration::from_secs(interval_seconds.max(1));
+    let mut interval = DevicePollInterval::new(interval_seconds);
     let start = Instant::now();
     let timeout = Duration::from_secs(900);
     let mut attempts: u64 = 0;
 
     telemetry::info(format!(
         "Atlas device token polling started (hub_url={hub_url}, client_id={client_id}, interval={}s).",
-        interval.as_secs()
+        interval.get().as_secs()
     ));
 
     loop {
@@ -104,16 +105,16 @@ pub(crate) async fn poll_device_token(
                         start.elapsed().as_secs()
                     ));
                 }
-                sleep(interval).await;
+                sleep(interval.get()).await;
             }
             DeviceTokenPollStatus::SlowDown => {
-                interval += Duration::from_secs(5);
+                let next = interval.slow_down();
                 telemetry::warn(format!(
                     "Atlas device token polling slow_down (attempt={}, new_interval={}s).",
                     attempts,
-                    interval.as_secs()
+                    next.as_secs()
                 ));
-                sleep(interval).await;
+                sleep(next).await;
             }
             DeviceTokenPollStatus::ExpiredToken => {
                 telemetry::warn(format!(
@@ -1,4 +1,4 @@
-use crate::models::AuthSession;
+use crate::models::{AuthSession, Profile};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use super::error::AuthError;
@@ -24,8 +24,15 @@ pub(crate) async fn session_from_ms_token<H: HttpClient + ?Sized>(
         .ok_or_else(|| "Missing Xbox user hash".to_string())?;
     let mc = minecraft::login(http, &xsts.token, &uhs).await?;
 
-    let profile = minecraft::profile(http, &mc.access_token).await?;
-    minecraft::verify_entitlements(http, &mc.access_token).await?;
+    let owns_minecraft = minecraft::owns_minecraft(http, &mc.access_token).await?;
+    let profile = match minecraft::profile(http, &mc.access_token).await {
+        Ok(profile) => Profile {
+            demo: !owns_minecraft,
+            ..profile
+        },
+        Err(_) if !owns_minecraft => minecraft::demo_profile(&uhs),
+        Err(err) => return Err(err),
+    };
     let refresh_token = refresh_token
         .or(fallback_refresh_token)
         .ok_or_else(|| "Missing refresh token from Microsoft login.".to_string())?;
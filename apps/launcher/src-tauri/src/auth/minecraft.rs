@@ -1,6 +1,7 @@
 use crate::models::Profile;
 use serde::Deserialize;
 use serde_json::json;
+use sha1::{Digest, Sha1};
 
 use super::error::AuthError;
 use crate::net::http::HttpClient;
@@ -39,17 +40,29 @@ pub async fn profile<H: HttpClient + ?Sized>(
     Ok(http.get_json(MC_PROFILE_URL, Some(access_token)).await?)
 }
 
-pub async fn verify_entitlements<H: HttpClient + ?Sized>(
+pub async fn owns_minecraft<H: HttpClient + ?Sized>(
     http: &H,
     access_token: &str,
-) -> Result<(), AuthError> {
+) -> Result<bool, AuthError> {
     let entitlements: EntitlementsResponse = http
         .get_json(MC_ENTITLEMENTS_URL, Some(access_token))
         .await?;
 
-    if entitlements.items.is_empty() {
-        return Err(AuthError::MissingMinecraftEntitlement);
-    }
+    Ok(!entitlements.items.is_empty())
+}
 
-    Ok(())
+// Accounts that never bought the game have no Minecraft profile, so the demo runs
+// under an offline name with a UUID derived from the Xbox user hash.
+pub fn demo_profile(uhs: &str) -> Profile {
+    let digest = Sha1::digest(format!("OfflinePlayer:{uhs}").as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x50;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    Profile {
+        id: hex::encode(bytes),
+        name: "Player".to_string(),
+        demo: true,
+    }
 }
@@ -42,9 +42,10 @@ pub async fn start_atlas_device_code(
 pub async fn complete_device_code(
     client_id: &str,
     device_code: &str,
+    interval_seconds: u64,
 ) -> Result<AuthSession, AuthError> {
     let http = ReqwestHttpClient::new();
-    let token = ms::poll_device_token(&http, client_id, device_code).await?;
+    let token = ms::poll_device_token(&http, client_id, device_code, interval_seconds).await?;
     let refresh_token = token.refresh_token.clone();
     flow::session_from_ms_token(&http, client_id, &token.access_token, refresh_token, None).await
 }
@@ -1,6 +1,6 @@
 use crate::models::DeviceCodeResponse;
 use atlas_client::device_code::{
-    parse_device_token_poll_json, DeviceTokenPollStatus, DEVICE_CODE_GRANT_TYPE,
+    parse_device_token_poll_json, DevicePollInterval, DeviceTokenPollStatus, DEVICE_CODE_GRANT_TYPE,
 };
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use base64::Engine;
@@ -54,8 +54,9 @@ pub(crate) async fn poll_device_token<H: HttpClient + ?Sized>(
     http: &H,
     client_id: &str,
     device_code: &str,
+    interval_seconds: u64,
 ) -> Result<DeviceTokenResponse, AuthError> {
-    let mut interval = Duration::from_secs(5);
+    let mut interval = DevicePollInterval::new(interval_seconds);
     let start = Instant::now();
     let timeout = Duration::from_secs(900);
 
@@ -79,11 +80,10 @@ pub(crate) async fn poll_device_token<H: HttpClient + ?Sized>(
         match status {
             DeviceTokenPollStatus::Success(token) => return Ok(token),
             DeviceTokenPollStatus::AuthorizationPending => {
-                sleep(interval).await;
+                sleep(interval.get()).await;
             }
             DeviceTokenPollStatus::SlowDown => {
-                interval += Duration::from_secs(5);
-                sleep(interval).await;
+                sleep(interval.slow_down()).await;
             }
             DeviceTokenPollStatus::ExpiredToken => {
                 return Err("Device code expired. Start login again.".to_string().into())
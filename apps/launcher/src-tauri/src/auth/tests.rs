@@ -8,6 +8,7 @@ use std::sync::Mutex;
 struct MockHttp {
     responses: Mutex<Vec<serde_json::Value>>,
     form_calls: Mutex<Vec<(String, Vec<(String, String)>)>>,
+    call_times: Mutex<Vec<tokio::time::Instant>>,
 }
 
 impl MockHttp {
@@ -15,6 +16,7 @@ impl MockHttp {
         Self {
             responses: Mutex::new(responses),
             form_calls: Mutex::new(Vec::new()),
+            call_times: Mutex::new(Vec::new()),
         }
     }
 }
@@ -26,6 +28,10 @@ impl HttpClient for MockHttp {
         url: &str,
         params: &[(&str, &str)],
     ) -> Result<T, HttpError> {
+        self.call_times
+            .lock()
+            .unwrap()
+            .push(tokio::time::Instant::now());
         self.form_calls.lock().unwrap().push((
             url.to_string(),
             params
@@ -108,6 +114,34 @@ async fn refresh_token_uses_form_params() {
         .any(|(k, v)| k == "grant_type" && v == "refresh_token"));
 }
 
+#[tokio::test(start_paused = true)]
+async fn device_token_polling_backs_off_on_slow_down() {
+    let http = MockHttp::new(vec![
+        json!({ "error": "slow_down" }),
+        json!({ "error": "slow_down" }),
+        json!({ "error": "authorization_pending" }),
+        json!({
+          "access_token": "access",
+          "refresh_token": "refresh",
+          "expires_in": 3600,
+          "token_type": "Bearer",
+          "scope": "XboxLive.signin offline_access"
+        }),
+    ]);
+
+    let token = ms::poll_device_token(&http, "client", "device", 5)
+        .await
+        .unwrap();
+    assert_eq!(token.access_token, "access");
+
+    let times = http.call_times.lock().unwrap();
+    let gaps: Vec<u64> = times
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).as_secs())
+        .collect();
+    assert_eq!(gaps, vec![10, 15, 15]);
+}
+
 #[test]
 fn parse_auth_callback_accepts_valid_response() {
     let url = "atlas://auth?code=abc123&state=state1";
@@ -149,6 +149,7 @@ pub async fn complete_deeplink_login(
 pub async fn complete_device_code(
     state: tauri::State<'_, AppState>,
     device_code: String,
+    interval_seconds: u64,
 ) -> Result<Profile, String> {
     let settings = state
         .settings
@@ -156,7 +157,7 @@ pub async fn complete_device_code(
         .map_err(|_| "Settings lock poisoned".to_string())?
         .clone();
     let client_id = config::resolve_client_id(&settings);
-    let session = auth::complete_device_code(&client_id, &device_code)
+    let session = auth::complete_device_code(&client_id, &device_code, interval_seconds)
         .await
         .map_err(|err| err.to_string())?;
     let profile = session.profile.clone();
@@ -481,7 +482,14 @@ pub async fn complete_launcher_link_session(
         .complete_launcher_link_session(&payload)
         .await
         .map(LauncherLinkComplete::from)
-        .map_err(|err| err.to_string())?;
+        .map_err(|err| {
+            // The frontend keeps the link code and reconnects on this prefix.
+            if err.is_transient() {
+                format!("Atlas Hub unreachable: {err}")
+            } else {
+                err.to_string()
+            }
+        })?;
 
     let mut warning: Option<String> = None;
     let session = match auth::load_atlas_session() {
@@ -3,7 +3,7 @@ use crate::auth::AuthError;
 use crate::diagnostics;
 use crate::models::{
     AtlasSession, AuthSession, FixAction, FixResult, LaunchReadinessReport, RepairResult,
-    SupportBundleResult, TroubleshooterReport,
+    SupportBundleResult, TroubleshooterReport, VersionValidation,
 };
 use crate::settings;
 use crate::state::AppState;
@@ -33,17 +33,12 @@ async fn load_auth_session_fresh(
                 *guard2 = Some(fresh.clone());
                 Ok(Some(fresh))
             }
-            Err(err) => match err {
-                AuthError::MissingMinecraftEntitlement => {
-                    Err(AuthError::MissingMinecraftEntitlement)
-                }
-                _ => {
-                    let _ = auth::clear_session();
-                    let mut guard2 = state.auth.lock().map_err(|_| AuthError::LockPoisoned)?;
-                    *guard2 = None;
-                    Ok(None)
-                }
-            },
+            Err(_) => {
+                let _ = auth::clear_session();
+                let mut guard2 = state.auth.lock().map_err(|_| AuthError::LockPoisoned)?;
+                *guard2 = None;
+                Ok(None)
+            }
         };
     }
 
@@ -56,15 +51,10 @@ async fn load_auth_session_fresh(
                 *guard = Some(fresh.clone());
                 Ok(Some(fresh))
             }
-            Err(err) => match err {
-                AuthError::MissingMinecraftEntitlement => {
-                    Err(AuthError::MissingMinecraftEntitlement)
-                }
-                _ => {
-                    let _ = auth::clear_session();
-                    Ok(None)
-                }
-            },
+            Err(_) => {
+                let _ = auth::clear_session();
+                Ok(None)
+            }
         },
         None => Ok(None),
     }
@@ -237,6 +227,17 @@ pub async fn apply_fix(
     .await
 }
 
+#[tauri::command]
+pub async fn validate_installed_version(
+    window: tauri::Window,
+    state: tauri::State<'_, AppState>,
+    game_dir: String,
+    id: String,
+) -> Result<VersionValidation, String> {
+    let settings = load_settings(&state)?;
+    diagnostics::validate_installed_version(&window, &settings, &game_dir, &id).await
+}
+
 #[tauri::command]
 pub async fn repair_installation(
     window: tauri::Window,
@@ -1,14 +1,15 @@
 use crate::auth;
 use crate::config;
 use crate::launcher;
-use crate::models::LaunchOptions;
+use crate::models::{InstanceStats, LaunchOptions, LaunchRecord};
+use crate::paths::normalize_path;
 use crate::state::AppState;
 
 #[tauri::command]
 pub async fn launch_minecraft(
     window: tauri::Window,
     state: tauri::State<'_, AppState>,
-    options: LaunchOptions,
+    mut options: LaunchOptions,
 ) -> Result<(), String> {
     let settings = state
         .settings
@@ -45,7 +46,10 @@ pub async fn launch_minecraft(
         *guard = Some(session.clone());
     }
 
-    launcher::launch_minecraft(&window, &options, &session)
+    // Accounts without the game can still play the demo instead of failing.
+    options.demo |= session.profile.demo;
+
+    launcher::launch_minecraft(&window, &options, &session, settings.post_launch_behavior)
         .await
         .map_err(|err| err.to_string())
 }
@@ -59,3 +63,18 @@ pub async fn download_minecraft_files(
         .await
         .map_err(|err| err.to_string())
 }
+
+#[tauri::command]
+pub fn get_launch_history(
+    game_dir: String,
+    limit: Option<usize>,
+) -> Result<Vec<LaunchRecord>, String> {
+    launcher::history::read_launch_history(&normalize_path(&game_dir), limit.unwrap_or(20))
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn get_instance_stats(game_dir: String) -> Result<InstanceStats, String> {
+    launcher::history::read_instance_stats(&normalize_path(&game_dir))
+        .map_err(|err| err.to_string())
+}
@@ -3,7 +3,7 @@ use crate::config;
 use crate::library;
 use crate::models::{
     AtlasPackSyncResult, AtlasRemotePack, AtlasSession, FabricLoaderVersion, LaunchEvent, ModEntry,
-    VersionManifestSummary,
+    ScreenshotEntry, VersionManifestSummary, WorldBackup, WorldEntry,
 };
 use crate::state::AppState;
 use crate::telemetry;
@@ -53,6 +53,46 @@ pub fn delete_mod(game_dir: String, file_name: String) -> Result<(), String> {
     library::delete_mod(&game_dir, &file_name).map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+pub fn list_instance_screenshots(
+    game_dir: String,
+    limit: Option<usize>,
+    include_previews: Option<bool>,
+) -> Result<Vec<ScreenshotEntry>, String> {
+    library::list_instance_screenshots(&game_dir, limit, include_previews.unwrap_or(false))
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn open_screenshot(game_dir: String, file_name: String) -> Result<(), String> {
+    library::open_screenshot(&game_dir, &file_name).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn delete_screenshot(game_dir: String, file_name: String) -> Result<(), String> {
+    library::delete_screenshot(&game_dir, &file_name).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn list_worlds(game_dir: String) -> Result<Vec<WorldEntry>, String> {
+    library::list_worlds(&game_dir).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn list_world_backups(game_dir: String) -> Result<Vec<WorldBackup>, String> {
+    library::list_world_backups(&game_dir).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn backup_world(game_dir: String, world: String) -> Result<WorldBackup, String> {
+    library::backup_world(&game_dir, &world).map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn restore_world(game_dir: String, backup: String) -> Result<String, String> {
+    library::restore_world(&game_dir, &backup).map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 pub fn uninstall_instance_data(
     game_dir: String,
@@ -1,7 +1,9 @@
-use crate::models::AppSettings;
+use crate::launcher::java;
+use crate::models::{AppSettings, JavaInfo};
 use crate::paths;
 use crate::settings;
 use crate::state::AppState;
+use crate::telemetry;
 use sysinfo::System;
 
 #[tauri::command]
@@ -9,6 +11,11 @@ pub fn get_default_game_dir() -> String {
     paths::default_game_dir().to_string_lossy().to_string()
 }
 
+#[tauri::command]
+pub fn get_storage_error(state: tauri::State<'_, AppState>) -> Option<String> {
+    state.storage_error.clone()
+}
+
 #[tauri::command]
 pub fn get_system_memory_mb() -> Result<u64, String> {
     let mut system = System::new();
@@ -20,6 +27,20 @@ pub fn get_system_memory_mb() -> Result<u64, String> {
     Ok(total_mb)
 }
 
+#[tauri::command]
+pub async fn probe_java(path: String) -> Result<JavaInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || java::probe_java(&path))
+        .await
+        .map_err(|err| format!("Java probe failed: {err}"))?
+}
+
+#[tauri::command]
+pub async fn discover_java_runtimes() -> Result<Vec<JavaInfo>, String> {
+    tauri::async_runtime::spawn_blocking(java::discover_java_runtimes)
+        .await
+        .map_err(|err| format!("Java discovery failed: {err}"))
+}
+
 #[tauri::command]
 pub fn get_settings(state: tauri::State<'_, AppState>) -> Result<AppSettings, String> {
     let guard = state
@@ -35,6 +56,8 @@ pub fn update_settings(
     settings: AppSettings,
 ) -> Result<(), String> {
     settings::save_settings(&settings)?;
+    telemetry::set_log_level(settings.launcher_log_level.as_deref());
+    paths::set_game_dir_override(settings.game_dir_override.as_deref());
     let mut guard = state
         .settings
         .lock()
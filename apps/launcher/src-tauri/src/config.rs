@@ -1,4 +1,9 @@
+use std::time::Duration;
+
+use atlas_client::hub_url::normalize_hub_url_string;
+
 use crate::models::AppSettings;
+use crate::net::http::HttpTimeouts;
 
 pub const DEFAULT_MS_CLIENT_ID: &str = match option_env!("ATLAS_MS_CLIENT_ID") {
     Some(value) => value,
@@ -18,11 +23,13 @@ pub fn resolve_client_id(settings: &AppSettings) -> String {
 }
 
 pub fn resolve_atlas_hub_url(settings: &AppSettings) -> String {
+    // An invalid override is passed through so `HubClient::new` reports it.
     settings
         .atlas_hub_url
         .as_ref()
-        .map(|value| value.trim().trim_end_matches('/').to_string())
+        .map(|value| value.trim())
         .filter(|value| !value.is_empty())
+        .map(|value| normalize_hub_url_string(value).unwrap_or_else(|_| value.to_string()))
         .unwrap_or_else(|| DEFAULT_ATLAS_HUB_URL.to_string())
 }
 
@@ -37,3 +44,17 @@ pub fn resolve_atlas_redirect_uri() -> String {
 pub fn resolve_atlas_auth_base_url(settings: &AppSettings) -> String {
     format!("{}/api/auth", resolve_atlas_hub_url(settings))
 }
+
+pub fn resolve_http_timeouts(settings: &AppSettings) -> HttpTimeouts {
+    let defaults = HttpTimeouts::default();
+    let secs = |value: Option<u64>, default: Duration| {
+        value
+            .filter(|value| *value > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(default)
+    };
+    HttpTimeouts {
+        connect: secs(settings.network_connect_timeout_secs, defaults.connect),
+        read: secs(settings.network_read_timeout_secs, defaults.read),
+    }
+}
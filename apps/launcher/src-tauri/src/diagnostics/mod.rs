@@ -1,11 +1,15 @@
+use crate::launcher::manifest::{VersionManifest, VERSION_MANIFEST_URL};
 use crate::models::{
     AppSettings, AtlasSession, AuthSession, FixAction, FixResult, InstanceSource, LaunchOptions,
     LaunchReadinessReport, ModLoaderConfig, ReadinessItem, RepairResult, SupportBundleResult,
-    TroubleshooterFinding, TroubleshooterReport,
+    TroubleshooterFinding, TroubleshooterReport, VersionValidation,
 };
-use crate::paths::{auth_store_dir, normalize_path};
+use crate::net::http::fetch_json_shared;
+use crate::paths::{auth_store_dir, normalize_path, safe_join};
 use crate::{launcher, library};
+use protocol::redact;
 use serde_json::{json, Value};
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -199,6 +203,29 @@ pub fn run_troubleshooter(input: TroubleshooterInput) -> TroubleshooterReport {
             suggested_actions: vec![FixAction::ResyncPack, FixAction::FullRepair],
         });
     }
+    if haystack.contains("not found in manifest")
+        || haystack.contains("version inheritance loop")
+        || haystack.contains("failed to resolve version data")
+    {
+        findings.push(TroubleshooterFinding {
+            code: "installed_version_mismatch".to_string(),
+            title: "Installed version files don’t match".to_string(),
+            detail: "The installed Minecraft or loader version could not be resolved. Repairing the version files reinstalls them."
+                .to_string(),
+            confidence: 80,
+            suggested_actions: vec![FixAction::RepairVersion, FixAction::FullRepair],
+        });
+    }
+    if haystack.contains("timed out") || haystack.contains("download stalled") {
+        findings.push(TroubleshooterFinding {
+            code: "network_stall".to_string(),
+            title: "Downloads are timing out".to_string(),
+            detail: "A server stopped responding while game files were downloading. Check your connection and try again; completed files are kept."
+                .to_string(),
+            confidence: 80,
+            suggested_actions: vec![FixAction::RepairRuntime],
+        });
+    }
     if input.readiness.files_installed
         && (haystack.contains("client jar is missing")
             || haystack.contains("launch failed")
@@ -316,6 +343,42 @@ pub async fn apply_fix(window: &tauri::Window, input: ApplyFixInput) -> Result<F
                 message: "Runtime repair completed.".to_string(),
             }
         }
+        FixAction::RepairVersion => {
+            let game_dir = input
+                .game_dir
+                .as_deref()
+                .ok_or_else(|| "gameDir is required for version repair.".to_string())?;
+            let versions_dir = versions_dir_for_game_dir(game_dir);
+            let ids = fs::read_dir(&versions_dir)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .filter(|entry| entry.path().is_dir())
+                        .map(|entry| entry.file_name().to_string_lossy().to_string())
+                        // Installer scratch folders such as fabric-installer hold no version json.
+                        .filter(|id| has_json_file(&versions_dir.join(id)))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let results = repair_installed_versions(window, &input.settings, game_dir, ids).await?;
+            let broken = results
+                .iter()
+                .filter(|result| !result.problems.is_empty())
+                .count();
+            FixResult {
+                action,
+                applied: broken > 0,
+                message: if broken == 0 {
+                    "Installed versions are consistent.".to_string()
+                } else {
+                    format!(
+                        "Reinstalled {} of {} inconsistent version(s).",
+                        results.iter().filter(|result| result.repaired).count(),
+                        broken
+                    )
+                },
+            }
+        }
         FixAction::FullRepair => {
             let game_dir = input
                 .game_dir
@@ -353,6 +416,132 @@ pub async fn apply_fix(window: &tauri::Window, input: ApplyFixInput) -> Result<F
     Ok(output)
 }
 
+pub async fn validate_installed_version(
+    window: &tauri::Window,
+    settings: &AppSettings,
+    game_dir: &str,
+    id: &str,
+) -> Result<VersionValidation, String> {
+    let mut results =
+        repair_installed_versions(window, settings, game_dir, vec![id.to_string()]).await?;
+    results
+        .pop()
+        .ok_or_else(|| format!("Version {id} was not checked."))
+}
+
+// Broken version folders are moved aside and the instance runtime is downloaded again,
+// which rewrites the vanilla json and reinstalls the loader profile. Folders the
+// download does not recreate (other versions in the instance) are put back.
+async fn repair_installed_versions(
+    window: &tauri::Window,
+    settings: &AppSettings,
+    game_dir: &str,
+    ids: Vec<String>,
+) -> Result<Vec<VersionValidation>, String> {
+    let versions_dir = versions_dir_for_game_dir(game_dir);
+    let manifest: VersionManifest = fetch_json_shared(VERSION_MANIFEST_URL)
+        .await
+        .map_err(|err| format!("Failed to fetch version manifest: {err}"))?;
+    let known_remote = manifest
+        .versions
+        .into_iter()
+        .map(|version| version.id)
+        .collect::<HashSet<_>>();
+
+    let mut results = ids
+        .into_iter()
+        .map(|id| VersionValidation {
+            problems: launcher::versions::check_installed_version(
+                &versions_dir,
+                &id,
+                &known_remote,
+            ),
+            id,
+            repaired: false,
+        })
+        .collect::<Vec<_>>();
+    if results.iter().all(|result| result.problems.is_empty()) {
+        return Ok(results);
+    }
+
+    let options = build_launch_options_for_game_dir(settings, Some(game_dir))
+        .ok_or_else(|| "Unable to build launch options for version repair.".to_string())?;
+    let backup_root = normalize_path(game_dir)
+        .join(".minecraft")
+        .join("tmp")
+        .join("version-repair");
+    let mut moved = Vec::new();
+    for result in results.iter().filter(|result| !result.problems.is_empty()) {
+        let version_dir = safe_join(&versions_dir, &result.id)?;
+        if !version_dir.is_dir() {
+            continue;
+        }
+        let backup = safe_join(&backup_root, &result.id)?;
+        if let Err(err) = move_dir(&version_dir, &backup) {
+            restore_version_dirs(&moved, false)?;
+            return Err(err);
+        }
+        moved.push((version_dir, backup));
+    }
+
+    let downloaded = launcher::download_minecraft_files(window, &options)
+        .await
+        .map_err(|err| err.to_string());
+    restore_version_dirs(&moved, downloaded.is_ok())?;
+    downloaded?;
+
+    for result in results
+        .iter_mut()
+        .filter(|result| !result.problems.is_empty())
+    {
+        result.repaired =
+            launcher::versions::check_installed_version(&versions_dir, &result.id, &known_remote)
+                .is_empty();
+    }
+    Ok(results)
+}
+
+fn move_dir(from: &Path, to: &Path) -> Result<(), String> {
+    if to.exists() {
+        fs::remove_dir_all(to)
+            .map_err(|err| format!("Failed to remove {}: {err}", to.display()))?;
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    fs::rename(from, to).map_err(|err| format!("Failed to move {}: {err}", from.display()))
+}
+
+// Puts moved version folders back, except those the download recreated when
+// `keep_downloaded` is set.
+fn restore_version_dirs(moved: &[(PathBuf, PathBuf)], keep_downloaded: bool) -> Result<(), String> {
+    for (version_dir, backup) in moved {
+        if keep_downloaded && version_dir.is_dir() {
+            let _ = fs::remove_dir_all(backup);
+            continue;
+        }
+        if version_dir.exists() {
+            fs::remove_dir_all(version_dir)
+                .map_err(|err| format!("Failed to remove {}: {err}", version_dir.display()))?;
+        }
+        move_dir(backup, version_dir)?;
+    }
+    Ok(())
+}
+
+fn versions_dir_for_game_dir(game_dir: &str) -> PathBuf {
+    normalize_path(game_dir).join(".minecraft").join("versions")
+}
+
+fn has_json_file(dir: &Path) -> bool {
+    fs::read_dir(dir).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+    })
+}
+
 pub async fn repair_installation(
     window: &tauri::Window,
     input: RepairInput,
@@ -583,12 +772,19 @@ fn build_launch_options_for_game_dir(
     };
 
     Some(LaunchOptions {
+        instance_id: Some(instance.id.clone()),
         game_dir: instance.game_dir.clone(),
         java_path: instance.java_path.clone(),
         memory_mb,
         jvm_args,
         version: instance.version.clone(),
         loader,
+        width: None,
+        height: None,
+        fullscreen: false,
+        demo: false,
+        quick_play: None,
+        shared_storage: settings.shared_game_storage,
     })
 }
 
@@ -600,147 +796,13 @@ fn read_text_if_exists(path: &Path) -> Option<String> {
 }
 
 fn redact_sensitive(input: &str) -> String {
-    if let Ok(mut value) = serde_json::from_str::<Value>(input) {
-        redact_json_value(&mut value);
-        if let Ok(text) = serde_json::to_string_pretty(&value) {
+    if let Ok(value) = serde_json::from_str::<Value>(input) {
+        if let Ok(text) = serde_json::to_string_pretty(&redact::redact_json(value)) {
             return text;
         }
     }
 
-    input
-        .lines()
-        .map(redact_line)
-        .collect::<Vec<_>>()
-        .join("\n")
-}
-
-fn redact_json_value(value: &mut Value) {
-    match value {
-        Value::Object(map) => {
-            let keys: Vec<String> = map.keys().cloned().collect();
-            for key in keys {
-                if let Some(next) = map.get_mut(&key) {
-                    if is_sensitive_key(&key) {
-                        *next = Value::String("[REDACTED]".to_string());
-                    } else {
-                        redact_json_value(next);
-                    }
-                }
-            }
-        }
-        Value::Array(items) => {
-            for item in items {
-                redact_json_value(item);
-            }
-        }
-        Value::String(text) => {
-            *text = redact_line(text);
-        }
-        _ => {}
-    }
-}
-
-fn redact_line(line: &str) -> String {
-    let mut redacted = line.to_string();
-    redacted = redact_bearer_token(&redacted);
-    redacted = redact_kv_pairs(&redacted);
-    redacted
-}
-
-fn redact_bearer_token(line: &str) -> String {
-    let marker = "bearer ";
-    let lower = line.to_ascii_lowercase();
-    let Some(start) = lower.find(marker) else {
-        return line.to_string();
-    };
-    let value_start = start + marker.len();
-    let token_end = line[value_start..]
-        .find(|c: char| c.is_whitespace() || c == ',' || c == '"' || c == '\'')
-        .map(|offset| value_start + offset)
-        .unwrap_or(line.len());
-    let mut out = String::new();
-    out.push_str(&line[..value_start]);
-    out.push_str("[REDACTED]");
-    out.push_str(&line[token_end..]);
-    out
-}
-
-fn redact_kv_pairs(line: &str) -> String {
-    let mut out = line.to_string();
-    for key in sensitive_keys() {
-        out = redact_kv_value(&out, key);
-    }
-    out
-}
-
-fn redact_kv_value(line: &str, key: &str) -> String {
-    let lower = line.to_ascii_lowercase();
-    let key_lower = key.to_ascii_lowercase();
-    let mut cursor = 0usize;
-    let mut out = String::new();
-
-    while let Some(found) = lower[cursor..].find(&key_lower) {
-        let key_start = cursor + found;
-        let key_end = key_start + key_lower.len();
-        out.push_str(&line[cursor..key_end]);
-
-        let mut value_start = key_end;
-        while let Some(ch) = line[value_start..].chars().next() {
-            if ch.is_whitespace() {
-                value_start += ch.len_utf8();
-                continue;
-            }
-            if ch == ':' || ch == '=' {
-                value_start += ch.len_utf8();
-                break;
-            }
-            out.push_str(&line[key_end..]);
-            return out;
-        }
-
-        while let Some(ch) = line[value_start..].chars().next() {
-            if ch.is_whitespace() {
-                value_start += ch.len_utf8();
-            } else {
-                break;
-            }
-        }
-
-        let value_end = line[value_start..]
-            .find(|c: char| c.is_whitespace() || c == ',' || c == ';' || c == '"' || c == '\'')
-            .map(|offset| value_start + offset)
-            .unwrap_or(line.len());
-        out.push_str(&line[key_end..value_start]);
-        out.push_str("[REDACTED]");
-        cursor = value_end;
-    }
-
-    out.push_str(&line[cursor..]);
-    out
-}
-
-fn is_sensitive_key(key: &str) -> bool {
-    let lower = key.to_ascii_lowercase();
-    sensitive_keys()
-        .iter()
-        .any(|candidate| lower.contains(candidate))
-}
-
-fn sensitive_keys() -> &'static [&'static str] {
-    &[
-        "access_token",
-        "refresh_token",
-        "authorization",
-        "id_token",
-        "service_token",
-        "deploy_key",
-        "proof",
-        "linkcode",
-        "password",
-        "secret",
-        "apikey",
-        "api_key",
-    ]
+    redact::redact_text(input)
 }
 
 fn build_summary(ready_to_launch: bool, recent_status: &str, analysis: &Value) -> String {
@@ -22,6 +22,12 @@ fn sample_settings() -> AppSettings {
         first_launch_completed_at: None,
         first_launch_notice_dismissed_at: None,
         default_memory_profile_v1_applied: false,
+        launcher_log_level: None,
+        post_launch_behavior: Default::default(),
+        shared_game_storage: false,
+        network_connect_timeout_secs: None,
+        network_read_timeout_secs: None,
+        game_dir_override: None,
     }
 }
 
@@ -48,6 +54,7 @@ fn auth_session_with_uuid(uuid: &str) -> AuthSession {
         profile: Profile {
             id: uuid.to_string(),
             name: "PlayerOne".to_string(),
+            demo: false,
         },
         refresh_token: Some("refresh".to_string()),
         access_token_expires_at: 9_999_999_999,
@@ -160,6 +167,35 @@ fn troubleshooter_classifies_memory_and_runtime_metadata_signals() {
     ));
 }
 
+#[test]
+fn troubleshooter_classifies_network_stalls_apart_from_parse_errors() {
+    let readiness = LaunchReadinessReport {
+        atlas_logged_in: true,
+        microsoft_logged_in: true,
+        accounts_linked: true,
+        files_installed: false,
+        java_ready: true,
+        ready_to_launch: false,
+        checklist: vec![],
+    };
+    let stalled = run_troubleshooter(TroubleshooterInput {
+        readiness: readiness.clone(),
+        recent_status: Some(
+            "Request to https://piston-meta.mojang.com/v1/packages/1.21.1.json timed out after 4 attempt(s): operation timed out"
+                .to_string(),
+        ),
+        recent_logs: vec![],
+    });
+    assert!(finding_exists(&stalled.findings, "network_stall"));
+
+    let malformed = run_troubleshooter(TroubleshooterInput {
+        readiness,
+        recent_status: Some("Failed to parse JSON: expected value at line 1 column 1".to_string()),
+        recent_logs: vec![],
+    });
+    assert!(!finding_exists(&malformed.findings, "network_stall"));
+}
+
 #[test]
 fn troubleshooter_does_not_flag_install_corruption_when_files_not_installed() {
     let readiness = LaunchReadinessReport {
@@ -208,7 +244,10 @@ fn infer_pack_id_returns_only_for_matching_atlas_instance() {
                 build_id: None,
                 build_version: None,
                 artifact_key: None,
+                metadata: None,
             }),
+            last_played_unix: None,
+            total_playtime_seconds: None,
         },
         InstanceConfig {
             id: "local-1".to_string(),
@@ -224,6 +263,8 @@ fn infer_pack_id_returns_only_for_matching_atlas_instance() {
             jvm_args: None,
             source: InstanceSource::Local,
             atlas_pack: None,
+            last_played_unix: None,
+            total_playtime_seconds: None,
         },
     ];
 
@@ -257,7 +298,10 @@ fn repair_plan_prefers_local_strategy_for_local_instance_even_with_pack_id() {
             build_id: None,
             build_version: None,
             artifact_key: None,
+            metadata: None,
         }),
+        last_played_unix: None,
+        total_playtime_seconds: None,
     }];
 
     let plan = resolve_repair_plan(&RepairInput {
@@ -295,7 +339,10 @@ fn repair_plan_uses_atlas_strategy_for_atlas_instance() {
             build_id: None,
             build_version: None,
             artifact_key: None,
+            metadata: None,
         }),
+        last_played_unix: None,
+        total_playtime_seconds: None,
     }];
 
     let plan = resolve_repair_plan(&RepairInput {
@@ -349,6 +396,8 @@ fn readiness_java_check_rejects_directory_override() {
         jvm_args: None,
         source: InstanceSource::Local,
         atlas_pack: None,
+        last_played_unix: None,
+        total_playtime_seconds: None,
     }];
 
     assert!(!resolve_java_ready(&settings, Some(&dir.to_string_lossy())));
@@ -372,6 +421,51 @@ fn readiness_java_check_accepts_runtime_binary_layout() {
     let _ = fs::remove_dir_all(&game_dir);
 }
 
+#[test]
+fn version_repair_restores_folders_the_download_did_not_recreate() {
+    let root = unique_temp_path("version-repair");
+    let versions = root.join("versions");
+    let backups = root.join("backups");
+    let mut moved = Vec::new();
+    for id in ["1.20.1", "fabric-loader-0.16.14-1.20.1"] {
+        let version_dir = versions.join(id);
+        fs::create_dir_all(&version_dir).expect("create version dir");
+        fs::write(version_dir.join("old.txt"), id).expect("write version file");
+        let backup = backups.join(id);
+        move_dir(&version_dir, &backup).expect("move version aside");
+        moved.push((version_dir, backup));
+    }
+
+    fs::create_dir_all(versions.join("1.20.1")).expect("recreate version dir");
+    fs::write(versions.join("1.20.1").join("new.txt"), "new").expect("write new file");
+    restore_version_dirs(&moved, true).expect("restore versions");
+
+    assert!(versions.join("1.20.1").join("new.txt").is_file());
+    assert!(!versions.join("1.20.1").join("old.txt").exists());
+    assert!(versions
+        .join("fabric-loader-0.16.14-1.20.1")
+        .join("old.txt")
+        .is_file());
+    assert!(!backups.join("1.20.1").exists());
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn failed_version_repair_restores_every_folder() {
+    let root = unique_temp_path("version-repair-failed");
+    let version_dir = root.join("versions").join("1.20.1");
+    let backup = root.join("backups").join("1.20.1");
+    fs::create_dir_all(&version_dir).expect("create version dir");
+    fs::write(version_dir.join("old.txt"), "old").expect("write version file");
+    move_dir(&version_dir, &backup).expect("move version aside");
+
+    fs::create_dir_all(&version_dir).expect("recreate partial version dir");
+    restore_version_dirs(&[(version_dir.clone(), backup)], false).expect("restore versions");
+
+    assert!(version_dir.join("old.txt").is_file());
+    let _ = fs::remove_dir_all(&root);
+}
+
 fn unique_temp_path(prefix: &str) -> PathBuf {
     let nanos = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -1,28 +1,149 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
 
-use super::manifest::{ArgValue, Argument, Rule, VersionData};
+use regex::Regex;
+
+use super::manifest::{ArgValue, Argument, Rule, RuleOs, VersionData};
+use super::quick_play;
+use crate::models::{LaunchOptions, QuickPlayKind};
+
+/// Launch choices that `arguments` rules can be gated on. Features Atlas does
+/// not support (quick play realms and path logging) always evaluate to false.
+#[derive(Debug, Clone, Default)]
+pub struct LaunchFeatures {
+    pub is_demo_user: bool,
+    pub has_custom_resolution: bool,
+    pub fullscreen: bool,
+    pub quick_play: Option<QuickPlayKind>,
+}
+
+impl LaunchFeatures {
+    pub fn from_options(options: &LaunchOptions) -> Self {
+        Self {
+            is_demo_user: options.demo,
+            has_custom_resolution: options.width.is_some() && options.height.is_some(),
+            fullscreen: options.fullscreen,
+            quick_play: options
+                .quick_play
+                .as_ref()
+                .map(|quick_play| quick_play.kind),
+        }
+    }
+
+    fn get(&self, feature: &str) -> bool {
+        match feature {
+            "is_demo_user" => self.is_demo_user,
+            "has_custom_resolution" => self.has_custom_resolution,
+            _ => self
+                .quick_play
+                .is_some_and(|kind| feature == quick_play::feature(kind)),
+        }
+    }
+}
 
 pub fn build_arguments(
     version: &VersionData,
     replacements: &HashMap<&str, String>,
+    features: &LaunchFeatures,
 ) -> Result<(Vec<String>, Vec<String>), String> {
-    if let Some(arguments) = &version.arguments {
-        let jvm = expand_args(&arguments.jvm, replacements);
-        let game = expand_args(&arguments.game, replacements);
-        return Ok((jvm, game));
+    let (jvm, mut game) = if let Some(arguments) = &version.arguments {
+        let jvm = expand_args(&arguments.jvm, replacements, features);
+        let game = expand_args(&arguments.game, replacements, features);
+        (jvm, game)
+    } else {
+        let raw = version
+            .minecraft_arguments
+            .clone()
+            .ok_or_else(|| "Missing arguments in version metadata".to_string())?;
+        let mut game = raw
+            .split_whitespace()
+            .map(|arg| replace_tokens(arg, replacements))
+            .collect::<Vec<_>>();
+        // Legacy metadata has no resolution rule, but the game still accepts the flags.
+        if features.has_custom_resolution {
+            for arg in [
+                "--width",
+                "${resolution_width}",
+                "--height",
+                "${resolution_height}",
+            ] {
+                game.push(replace_tokens(arg, replacements));
+            }
+        }
+        (Vec::new(), game)
+    };
+
+    // Modern metadata already carries `--demo` behind the `is_demo_user` rule.
+    if features.is_demo_user && !game.iter().any(|arg| arg == "--demo") {
+        game.push("--demo".to_string());
+    }
+    if features.fullscreen && !game.iter().any(|arg| arg == "--fullscreen") {
+        game.push("--fullscreen".to_string());
     }
+    if let Some(kind) = features.quick_play {
+        let argument = quick_play::argument(kind);
+        if !game.iter().any(|arg| arg == argument) {
+            game.push(argument.to_string());
+            game.push(replace_tokens(
+                &format!("${{{}}}", quick_play::token(kind)),
+                replacements,
+            ));
+        }
+    }
+    Ok((jvm, game))
+}
 
-    let raw = version
-        .minecraft_arguments
-        .clone()
-        .ok_or_else(|| "Missing arguments in version metadata".to_string())?;
-    let game = raw
-        .split_whitespace()
-        .map(|arg| replace_tokens(arg, replacements))
-        .collect::<Vec<_>>();
+// CreateProcess caps the whole command line; Linux caps each argument
+// (MAX_ARG_STRLEN), which the classpath hits first.
+#[cfg(target_os = "windows")]
+pub const COMMAND_LINE_LIMIT: usize = 32_767;
+#[cfg(not(target_os = "windows"))]
+pub const COMMAND_LINE_LIMIT: usize = 128 * 1024;
 
-    Ok((Vec::new(), game))
+// `@argfile` support arrived in Java 9.
+const MIN_ARGFILE_JAVA_MAJOR: u32 = 9;
+
+/// Moves the JVM options into `argfile` when the command line would exceed
+/// `limit`, returning the options to pass instead. Short command lines, and
+/// Java 8 which cannot read argfiles, keep the options inline.
+pub fn apply_argfile_fallback(
+    program: &str,
+    jvm_args: Vec<String>,
+    trailing_args: &[String],
+    java_major: u32,
+    limit: usize,
+    argfile: &Path,
+) -> Result<Vec<String>, String> {
+    let length = std::iter::once(program)
+        .chain(jvm_args.iter().map(String::as_str))
+        .chain(trailing_args.iter().map(String::as_str))
+        // Room for a separating space and quotes around each argument.
+        .map(|arg| arg.len() + 3)
+        .sum::<usize>();
+    if length <= limit || java_major < MIN_ARGFILE_JAVA_MAJOR {
+        return Ok(jvm_args);
+    }
+
+    std::fs::write(argfile, render_argfile(&jvm_args))
+        .map_err(|err| format!("Failed to write {}: {err}", argfile.display()))?;
+    Ok(vec![format!("@{}", argfile.display())])
+}
+
+// Every argument is quoted; inside quotes the JVM treats backslash as an escape.
+fn render_argfile(args: &[String]) -> String {
+    let mut out = String::new();
+    for arg in args {
+        out.push('"');
+        for ch in arg.chars() {
+            if ch == '\\' || ch == '"' {
+                out.push('\\');
+            }
+            out.push(ch);
+        }
+        out.push_str("\"\n");
+    }
+    out
 }
 
 pub fn split_jvm_args(raw: &str) -> Vec<String> {
@@ -45,13 +166,17 @@ pub fn unresolved_tokens(args: &[String]) -> Vec<String> {
     values
 }
 
-fn expand_args(args: &[Argument], replacements: &HashMap<&str, String>) -> Vec<String> {
+fn expand_args(
+    args: &[Argument],
+    replacements: &HashMap<&str, String>,
+    features: &LaunchFeatures,
+) -> Vec<String> {
     let mut expanded = Vec::new();
     for arg in args {
         match arg {
             Argument::String(value) => expanded.push(replace_tokens(value, replacements)),
             Argument::Rule { rules, value } => {
-                if rules_allow(&Some(rules.clone())) {
+                if rules_allow_with(Some(rules), features) {
                     match value {
                         ArgValue::String(value) => {
                             expanded.push(replace_tokens(value, replacements))
@@ -115,53 +240,72 @@ fn extract_tokens(input: &str) -> Vec<String> {
 }
 
 pub fn rules_allow(rules: &Option<Vec<Rule>>) -> bool {
+    rules_allow_with(rules.as_deref(), &LaunchFeatures::default())
+}
+
+fn rules_allow_with(rules: Option<&[Rule]>, features: &LaunchFeatures) -> bool {
     let Some(rules) = rules else {
         return true;
     };
 
     let mut allowed = false;
     for rule in rules {
-        let os_applies = rule
-            .os
-            .as_ref()
-            .and_then(|os| os.name.as_ref())
-            .map(|name| name == current_os_key())
-            .unwrap_or(true);
+        let os_applies = rule.os.as_ref().is_none_or(os_matches);
+        let features_applies = rule.features.as_ref().is_none_or(|required| {
+            required
+                .iter()
+                .all(|(feature, expected)| features.get(feature) == *expected)
+        });
 
-        let features_applies = features_match(rule.features.as_ref());
-        let applies = os_applies && features_applies;
-
-        if applies {
+        if os_applies && features_applies {
             allowed = rule.action == "allow";
         }
     }
     allowed
 }
 
-fn features_match(features: Option<&HashMap<String, bool>>) -> bool {
-    let Some(features) = features else {
-        return true;
-    };
+fn os_matches(os: &RuleOs) -> bool {
+    os.name
+        .as_deref()
+        .is_none_or(|name| name == current_os_key())
+        && os.arch.as_deref().is_none_or(arch_matches)
+        && os.version.as_deref().is_none_or(|pattern| {
+            let Ok(pattern) = Regex::new(pattern) else {
+                return false;
+            };
+            current_os_version().is_some_and(|version| pattern.is_match(&version))
+        })
+}
 
-    let supported = current_features();
-    for (key, expected) in features {
-        let actual = supported.get(key).copied().unwrap_or(false);
-        if actual != *expected {
-            return false;
-        }
+// Rules use Java's `os.arch` names.
+fn arch_matches(arch: &str) -> bool {
+    match std::env::consts::ARCH {
+        "x86" => arch == "x86",
+        "x86_64" => matches!(arch, "x86_64" | "amd64"),
+        "aarch64" => matches!(arch, "aarch64" | "arm64"),
+        other => arch == other,
     }
-    true
 }
 
-fn current_features() -> HashMap<String, bool> {
-    let mut features = HashMap::new();
-    features.insert("is_demo_user".to_string(), false);
-    features.insert("has_custom_resolution".to_string(), false);
-    features.insert("has_quick_plays_support".to_string(), false);
-    features.insert("is_quick_play_singleplayer".to_string(), false);
-    features.insert("is_quick_play_multiplayer".to_string(), false);
-    features.insert("is_quick_play_realms".to_string(), false);
-    features
+// Mirrors Java's `os.version`, which version rules are written against. Java
+// reports 10.0 for both Windows 10 and 11.
+fn current_os_version() -> Option<String> {
+    if cfg!(target_os = "windows") {
+        let major = sysinfo::System::os_version()?
+            .split(|ch: char| !ch.is_ascii_digit())
+            .next()?
+            .parse::<u32>()
+            .ok()?;
+        Some(if major >= 10 {
+            "10.0".to_string()
+        } else {
+            format!("{major}.0")
+        })
+    } else if cfg!(target_os = "macos") {
+        sysinfo::System::os_version()
+    } else {
+        sysinfo::System::kernel_version()
+    }
 }
 
 fn current_os_key() -> &'static str {
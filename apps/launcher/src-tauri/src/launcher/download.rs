@@ -99,17 +99,70 @@ pub async fn download_raw(
     download_raw_with_retry_events(client, url, path, expected_size, allow_resume, |_| {}).await
 }
 
+enum AttemptError {
+    Stalled(reqwest::Error),
+    Failed(String),
+}
+
+impl From<String> for AttemptError {
+    fn from(err: String) -> Self {
+        Self::Failed(err)
+    }
+}
+
 pub async fn download_raw_with_retry_events<F>(
     client: &Client,
     url: &str,
     path: &Path,
     expected_size: Option<u64>,
-    allow_resume: bool,
+    mut allow_resume: bool,
     mut on_retry: F,
 ) -> Result<(), String>
 where
     F: FnMut(DownloadRetryEvent),
 {
+    let mut backoff = Duration::from_millis(250);
+    for attempt in 0..=DOWNLOAD_MAX_RETRIES {
+        match download_raw_attempt(
+            client,
+            url,
+            path,
+            expected_size,
+            allow_resume,
+            &mut on_retry,
+        )
+        .await
+        {
+            Ok(()) => return Ok(()),
+            Err(AttemptError::Failed(err)) => return Err(err),
+            Err(AttemptError::Stalled(err)) if attempt < DOWNLOAD_MAX_RETRIES => {
+                let delay = with_jitter(backoff);
+                on_retry(DownloadRetryEvent {
+                    attempt: attempt + 1,
+                    max_attempts: DOWNLOAD_MAX_RETRIES + 1,
+                    delay_ms: delay.as_millis() as u64,
+                    reason: format!("stalled mid-download: {err}"),
+                });
+                sleep(delay).await;
+                backoff = (backoff * 2).min(Duration::from_secs(2));
+                // Keep the bytes that already arrived and ask for the rest.
+                allow_resume = true;
+            }
+            Err(AttemptError::Stalled(err)) => return Err(stalled_error(url, &err)),
+        }
+    }
+
+    Err("Download failed after retries.".to_string())
+}
+
+async fn download_raw_attempt(
+    client: &Client,
+    url: &str,
+    path: &Path,
+    expected_size: Option<u64>,
+    allow_resume: bool,
+    on_retry: &mut impl FnMut(DownloadRetryEvent),
+) -> Result<(), AttemptError> {
     let mut existing = if allow_resume && file_exists(path) {
         std::fs::metadata(path).map(|m| m.len()).unwrap_or(0)
     } else {
@@ -134,7 +187,7 @@ where
         } else {
             None
         },
-        &mut on_retry,
+        on_retry,
     )
     .await?;
 
@@ -148,14 +201,14 @@ where
                     }
                 }
                 existing = 0;
-                response = send_with_retries(client, url, None, &mut on_retry).await?;
+                response = send_with_retries(client, url, None, on_retry).await?;
             }
             status if status.is_success() => {
                 existing = 0;
             }
             status => {
                 let text = response.text().await.unwrap_or_default();
-                return Err(format!("Download failed ({status}): {text}"));
+                return Err(format!("Download failed ({status}): {text}").into());
             }
         }
     }
@@ -163,7 +216,7 @@ where
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(format!("Download failed ({status}): {text}"));
+        return Err(format!("Download failed ({status}): {text}").into());
     }
 
     let mut file =
@@ -181,7 +234,13 @@ where
 
     let mut stream = response.bytes_stream();
     while let Some(chunk) = stream.next().await {
-        let bytes = chunk.map_err(|err| format!("Failed to read download: {err}"))?;
+        let bytes = chunk.map_err(|err| {
+            if retryable_body_error(&err) {
+                AttemptError::Stalled(err)
+            } else {
+                AttemptError::Failed(format!("Failed to read download: {err}"))
+            }
+        })?;
         file.write_all(&bytes)
             .await
             .map_err(|err| format!("Failed to write file: {err}"))?;
@@ -196,7 +255,8 @@ where
             if actual != size {
                 return Err(format!(
                     "Download incomplete: expected {size} bytes, got {actual} bytes"
-                ));
+                )
+                .into());
             }
         }
     }
@@ -214,6 +274,18 @@ fn retryable_error(err: &reqwest::Error) -> bool {
     err.is_timeout() || err.is_connect() || err.is_request()
 }
 
+fn retryable_body_error(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_body() || err.is_decode()
+}
+
+fn stalled_error(url: &str, err: &reqwest::Error) -> String {
+    if err.is_timeout() {
+        format!("Download timed out for {url}: {err}")
+    } else {
+        format!("Download stalled for {url}: {err}")
+    }
+}
+
 fn with_jitter(base: Duration) -> Duration {
     let nanos = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -273,6 +345,9 @@ async fn send_with_retries(
                     backoff = (backoff * 2).min(Duration::from_secs(2));
                     continue;
                 }
+                if err.is_timeout() {
+                    return Err(stalled_error(url, &err));
+                }
                 return Err(format!("Download failed: {err}"));
             }
         }
@@ -0,0 +1,161 @@
+use crate::models::{InstanceStats, LaunchOutcome, LaunchRecord};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use super::error::LauncherError;
+
+const HISTORY_FILE: &str = "launch_history.jsonl";
+const HISTORY_LIMIT: usize = 50;
+const STATS_FILE: &str = "instance_stats.json";
+
+/// A spawned Minecraft process whose outcome is recorded once it exits.
+pub(crate) struct LaunchAttempt {
+    instance_dir: PathBuf,
+    instance_id: Option<String>,
+    version: String,
+    started_at_ms: u64,
+    started: Instant,
+    window_visible_at: Arc<OnceLock<SystemTime>>,
+}
+
+impl LaunchAttempt {
+    pub(crate) fn start(
+        instance_dir: PathBuf,
+        instance_id: Option<String>,
+        version: String,
+    ) -> Self {
+        let started_at_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        Self {
+            instance_dir,
+            instance_id,
+            version,
+            started_at_ms,
+            started: Instant::now(),
+            window_visible_at: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Set by the log forwarders when the game window first appears; playtime
+    /// is measured from then until the process exits.
+    pub(crate) fn window_visible_marker(&self) -> Arc<OnceLock<SystemTime>> {
+        self.window_visible_at.clone()
+    }
+
+    pub(crate) fn finish(
+        self,
+        window_visible: bool,
+        timed_out: bool,
+        exit_code: Option<i32>,
+    ) -> Result<(), LauncherError> {
+        let record = LaunchRecord {
+            instance_id: self.instance_id,
+            version: self.version,
+            started_at_ms: self.started_at_ms,
+            outcome: classify_outcome(window_visible, timed_out, exit_code),
+            exit_code,
+            duration_ms: self.started.elapsed().as_millis() as u64,
+        };
+        record_launch(&self.instance_dir, &record)?;
+
+        if let Some(visible_at) = self.window_visible_at.get() {
+            let played = SystemTime::now()
+                .duration_since(*visible_at)
+                .unwrap_or_default()
+                .as_secs();
+            let visible_unix = visible_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            record_playtime(&self.instance_dir, visible_unix, played)?;
+        }
+        Ok(())
+    }
+}
+
+// A window that came up still counts as a crash unless the game exited cleanly.
+pub(crate) fn classify_outcome(
+    window_visible: bool,
+    timed_out: bool,
+    exit_code: Option<i32>,
+) -> LaunchOutcome {
+    if timed_out {
+        LaunchOutcome::TimedOut
+    } else if window_visible && exit_code == Some(0) {
+        LaunchOutcome::WindowDetected
+    } else {
+        LaunchOutcome::Crashed
+    }
+}
+
+pub(crate) fn record_launch(
+    instance_dir: &Path,
+    record: &LaunchRecord,
+) -> Result<(), LauncherError> {
+    let path = instance_dir.join(HISTORY_FILE);
+    let mut lines = read_lines(&path)?;
+    lines.push(serde_json::to_string(record)?);
+    let keep_from = lines.len().saturating_sub(HISTORY_LIMIT);
+
+    let mut file = fs::File::create(&path)?;
+    for line in &lines[keep_from..] {
+        writeln!(file, "{line}")?;
+    }
+    Ok(())
+}
+
+/// Most recent launches first. Lines that fail to parse are skipped.
+pub fn read_launch_history(
+    instance_dir: &Path,
+    limit: usize,
+) -> Result<Vec<LaunchRecord>, LauncherError> {
+    let lines = read_lines(&instance_dir.join(HISTORY_FILE))?;
+    Ok(lines
+        .iter()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .take(limit)
+        .collect())
+}
+
+pub(crate) fn record_playtime(
+    instance_dir: &Path,
+    played_at_unix: u64,
+    seconds: u64,
+) -> Result<(), LauncherError> {
+    let mut stats = read_instance_stats(instance_dir)?;
+    stats.last_played_unix = Some(stats.last_played_unix.unwrap_or(0).max(played_at_unix));
+    stats.total_playtime_seconds = stats.total_playtime_seconds.saturating_add(seconds);
+
+    let path = instance_dir.join(STATS_FILE);
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(&stats)?)?;
+    fs::rename(&tmp, &path)?;
+    Ok(())
+}
+
+/// Empty stats when the instance has never been played.
+pub fn read_instance_stats(instance_dir: &Path) -> Result<InstanceStats, LauncherError> {
+    match fs::read(instance_dir.join(STATS_FILE)) {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(InstanceStats::default()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn read_lines(path: &Path) -> Result<Vec<String>, LauncherError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(str::to_string)
+            .collect()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -1,16 +1,19 @@
 use crate::launcher::error::LauncherError;
+use crate::models::JavaInfo;
 use crate::net::http::shared_client;
 use crate::paths::ensure_dir;
 use futures::stream::{self, StreamExt};
 use serde::Deserialize;
 use sha1::{Digest, Sha1};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
 use std::fs;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
 use super::download::{
     download_if_needed_with_retry_events, DownloadRetryEvent, DOWNLOAD_CONCURRENCY,
@@ -23,6 +26,11 @@ use crate::net::http::fetch_json;
 const JAVA_RUNTIME_MANIFEST_URL: &str =
   "https://launchermeta.mojang.com/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json";
 const RUNTIME_MANIFEST_MARKER_FILE: &str = "runtime_manifest_url.txt";
+const JAVA_DISCOVERY_TIMEOUT: Duration = Duration::from_secs(10);
+#[cfg(target_os = "windows")]
+const JAVA_BINARY_NAME: &str = "java.exe";
+#[cfg(not(target_os = "windows"))]
+const JAVA_BINARY_NAME: &str = "java";
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct JavaRuntimeFiles {
@@ -380,6 +388,145 @@ fn ensure_java_major_version(path: &Path, required_major: Option<u32>) -> Result
 }
 
 fn detect_java_major_version(java_binary: &str) -> Result<u32, String> {
+    run_java_version(java_binary, &["-version"]).map(|info| info.major)
+}
+
+/// Runs `<path> -version` with the system properties dump so the settings UI can
+/// show what a configured Java binary actually is before launch.
+pub(crate) fn probe_java(path: &str) -> Result<JavaInfo, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Java path is empty.".to_string());
+    }
+
+    let candidate = Path::new(trimmed);
+    let looks_like_path =
+        candidate.is_absolute() || trimmed.contains('/') || trimmed.contains('\\');
+    if looks_like_path {
+        if !candidate.exists() {
+            return Err(format!("Java binary does not exist: {trimmed}"));
+        }
+        if !is_usable_java_binary(candidate) {
+            return Err(format!("Java binary is not an executable file: {trimmed}"));
+        }
+    }
+
+    run_java_version(trimmed, &["-XshowSettings:properties", "-version"])
+}
+
+/// Finds JDKs in the usual package manager, vendor and version manager
+/// directories plus PATH, probing each one. The scan is shallow and probes that
+/// have not answered by the deadline are dropped.
+pub(crate) fn discover_java_runtimes() -> Vec<JavaInfo> {
+    let mut seen = HashSet::new();
+    let mut binaries = Vec::new();
+    let mut push = |binary: PathBuf| {
+        let key = fs::canonicalize(&binary).unwrap_or_else(|_| binary.clone());
+        if seen.insert(key) {
+            binaries.push(binary);
+        }
+    };
+
+    for (root, depth) in java_search_roots() {
+        collect_java_homes(&root, depth, &mut push);
+    }
+    if let Some(path_value) = std::env::var_os("PATH") {
+        for dir in std::env::split_paths(&path_value) {
+            let binary = dir.join(JAVA_BINARY_NAME);
+            if is_usable_java_binary(&binary) {
+                push(binary);
+            }
+        }
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let count = binaries.len();
+    for binary in binaries {
+        let sender = sender.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(probe_java(&binary.to_string_lossy()).ok());
+        });
+    }
+    drop(sender);
+
+    let deadline = Instant::now() + JAVA_DISCOVERY_TIMEOUT;
+    let mut runtimes = Vec::new();
+    for _ in 0..count {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match receiver.recv_timeout(remaining) {
+            Ok(Some(info)) => runtimes.push(info),
+            Ok(None) => {}
+            Err(_) => break,
+        }
+    }
+
+    runtimes.sort_by(|a, b| b.major.cmp(&a.major).then_with(|| a.path.cmp(&b.path)));
+    runtimes
+}
+
+fn java_search_roots() -> Vec<(PathBuf, usize)> {
+    let mut roots = Vec::new();
+    if let Some(home) = dirs::home_dir() {
+        roots.push((home.join(".sdkman/candidates/java"), 1));
+        roots.push((home.join(".asdf/installs/java"), 1));
+        roots.push((home.join(".jdks"), 1));
+        #[cfg(target_os = "macos")]
+        roots.push((home.join("Library/Java/JavaVirtualMachines"), 1));
+    }
+
+    #[cfg(target_os = "linux")]
+    for root in [
+        "/usr/lib/jvm",
+        "/usr/lib64/jvm",
+        "/usr/java",
+        "/opt/java",
+        "/opt/jdk",
+    ] {
+        roots.push((PathBuf::from(root), 1));
+    }
+    #[cfg(target_os = "macos")]
+    for root in [
+        "/Library/Java/JavaVirtualMachines",
+        "/opt/homebrew/opt",
+        "/usr/local/opt",
+    ] {
+        roots.push((PathBuf::from(root), 1));
+    }
+    #[cfg(target_os = "windows")]
+    for var in ["ProgramFiles", "ProgramFiles(x86)", "ProgramW6432"] {
+        if let Some(root) = std::env::var_os(var) {
+            roots.push((PathBuf::from(root), 2));
+        }
+    }
+
+    roots
+}
+
+pub(crate) fn collect_java_homes(dir: &Path, depth: usize, push: &mut impl FnMut(PathBuf)) {
+    for binary in [
+        dir.join("bin").join(JAVA_BINARY_NAME),
+        dir.join("Contents/Home/bin").join(JAVA_BINARY_NAME),
+    ] {
+        if is_usable_java_binary(&binary) {
+            push(binary);
+            return;
+        }
+    }
+    if depth == 0 {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_java_homes(&path, depth - 1, push);
+        }
+    }
+}
+
+fn run_java_version(java_binary: &str, args: &[&str]) -> Result<JavaInfo, String> {
     let mut attempts = vec![java_binary.to_string()];
     if let Some(fallback) = java_version_fallback_binary(java_binary) {
         if !attempts.contains(&fallback) {
@@ -389,7 +536,7 @@ fn detect_java_major_version(java_binary: &str) -> Result<u32, String> {
 
     let mut last_error = String::new();
     for candidate in attempts {
-        let output = match Command::new(&candidate).arg("-version").output() {
+        let output = match Command::new(&candidate).args(args).output() {
             Ok(output) => output,
             Err(err) => {
                 last_error = format!("Failed to run `{candidate} -version`: {err}");
@@ -410,12 +557,13 @@ fn detect_java_major_version(java_binary: &str) -> Result<u32, String> {
         }
         full.push_str(&String::from_utf8_lossy(&output.stderr));
 
-        if let Some(version) = parse_java_major_version(&full) {
-            return Ok(version);
+        if let Some(mut info) = parse_java_info(&full) {
+            info.path = candidate;
+            return Ok(info);
         }
 
         last_error =
-            format!("Unable to parse Java major version from `{candidate} -version` output.");
+            format!("`{candidate}` does not look like Java: no version in its `-version` output.");
     }
 
     if last_error.is_empty() {
@@ -425,6 +573,42 @@ fn detect_java_major_version(java_binary: &str) -> Result<u32, String> {
 }
 
 pub(crate) fn parse_java_major_version(output: &str) -> Option<u32> {
+    parse_java_info(output).map(|info| info.major)
+}
+
+/// Reads `-XshowSettings:properties` values when present and falls back to the
+/// `java version "..."` banner and the VM line for plain `-version` output.
+pub(crate) fn parse_java_info(output: &str) -> Option<JavaInfo> {
+    let property = |key: &str| {
+        output.lines().find_map(|line| {
+            let (name, value) = line.split_once('=')?;
+            let value = value.trim();
+            (name.trim() == key && !value.is_empty()).then(|| value.to_string())
+        })
+    };
+
+    let version = property("java.version").or_else(|| banner_version(output))?;
+    let major = parse_java_major_from_version_token(&version)?;
+    let arch = property("os.arch");
+    let is_64bit = match property("sun.arch.data.model") {
+        Some(model) => model == "64",
+        None => match arch.as_deref() {
+            Some(arch) => arch.contains("64"),
+            None => output.contains("64-Bit"),
+        },
+    };
+
+    Some(JavaInfo {
+        path: String::new(),
+        version,
+        major,
+        vendor: property("java.vendor"),
+        arch,
+        is_64bit,
+    })
+}
+
+fn banner_version(output: &str) -> Option<String> {
     for line in output.lines() {
         if !line.to_ascii_lowercase().contains("version") {
             continue;
@@ -437,8 +621,8 @@ pub(crate) fn parse_java_major_version(output: &str) -> Option<u32> {
             continue;
         };
         let quoted = &rest[..end];
-        if let Some(major) = parse_java_major_from_version_token(quoted) {
-            return Some(major);
+        if parse_java_major_from_version_token(quoted).is_some() {
+            return Some(quoted.to_string());
         }
     }
 
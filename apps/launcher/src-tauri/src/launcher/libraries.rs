@@ -1,6 +1,7 @@
 use crate::paths::ensure_dir;
+use sha2::{Digest, Sha256};
 use std::collections::HashSet;
-use std::fs::File;
+use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 use zip::ZipArchive;
 
@@ -138,7 +139,55 @@ pub async fn sync_libraries(
     Ok((library_paths, native_paths))
 }
 
-pub fn extract_natives(
+const NATIVES_MARKER: &str = ".atlas-natives";
+
+/// Extracts `native_jars` into a clean `natives_dir` unless the marker left by the
+/// last extraction matches the current jars. Returns whether it extracted.
+pub fn sync_natives(
+    native_jars: &[PathBuf],
+    natives_dir: &Path,
+    libraries: &[Library],
+) -> Result<bool, LauncherError> {
+    let fingerprint = natives_fingerprint(native_jars, libraries)?;
+    let marker = natives_dir.join(NATIVES_MARKER);
+    if fs::read_to_string(&marker).ok().as_deref() == Some(fingerprint.as_str()) {
+        return Ok(false);
+    }
+
+    if natives_dir.exists() {
+        fs::remove_dir_all(natives_dir).map_err(|err| format!("Failed to clear natives: {err}"))?;
+    }
+    ensure_dir(natives_dir)?;
+    for native in native_jars {
+        extract_natives(native, natives_dir, libraries)?;
+    }
+    // Written last so an interrupted extraction is redone on the next launch.
+    fs::write(&marker, fingerprint)?;
+    Ok(true)
+}
+
+fn natives_fingerprint(
+    native_jars: &[PathBuf],
+    libraries: &[Library],
+) -> Result<String, LauncherError> {
+    let mut hasher = Sha256::new();
+    for native in native_jars {
+        hasher.update(native.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        hasher.update(fs::read(native).map_err(|err| format!("Failed to read native jar: {err}"))?);
+    }
+    for lib in libraries {
+        if let Some(extract) = &lib.extract {
+            for pattern in &extract.exclude {
+                hasher.update(pattern.as_bytes());
+                hasher.update([0]);
+            }
+        }
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn extract_natives(
     path: &Path,
     natives_dir: &Path,
     libraries: &[Library],
@@ -182,12 +231,13 @@ pub fn extract_natives(
     Ok(())
 }
 
-pub fn build_classpath(libraries: &[PathBuf], client_jar: &Path) -> String {
-    let sep = if cfg!(target_os = "windows") {
-        ";"
-    } else {
-        ":"
-    };
+pub const CLASSPATH_SEPARATOR: &str = if cfg!(target_os = "windows") {
+    ";"
+} else {
+    ":"
+};
+
+pub fn build_classpath(libraries: &[PathBuf], client_jar: &Path, separator: &str) -> String {
     let mut seen = HashSet::new();
     let mut entries = Vec::new();
     for path in libraries {
@@ -200,7 +250,7 @@ pub fn build_classpath(libraries: &[PathBuf], client_jar: &Path) -> String {
     if seen.insert(client.clone()) {
         entries.push(client);
     }
-    entries.join(sep)
+    entries.join(separator)
 }
 
 pub(crate) fn library_path_from_name(name: &str) -> String {
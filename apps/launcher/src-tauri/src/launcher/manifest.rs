@@ -28,9 +28,9 @@ pub struct VersionRef {
 #[derive(Debug, Deserialize, Serialize)]
 pub struct VersionData {
     pub id: String,
-    #[serde(rename = "type")]
+    #[serde(default, rename = "type")]
     pub kind: String,
-    #[serde(rename = "mainClass")]
+    #[serde(default, rename = "mainClass")]
     pub main_class: String,
     #[serde(default)]
     pub arguments: Option<Arguments>,
@@ -156,4 +156,8 @@ pub struct Rule {
 pub struct RuleOs {
     #[serde(default)]
     pub name: Option<String>,
+    #[serde(default)]
+    pub arch: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
 }
@@ -1,20 +1,23 @@
 mod args;
 pub(crate) mod download;
 mod error;
+pub(crate) mod history;
 pub(crate) mod java;
 mod libraries;
 pub(crate) mod loaders;
 pub(crate) mod manifest;
-mod versions;
+mod quick_play;
+mod storage;
+pub(crate) mod versions;
 
-use crate::models::{AuthSession, LaunchEvent, LaunchOptions, ModLoaderKind};
+use crate::models::{AuthSession, LaunchEvent, LaunchOptions, ModLoaderKind, PostLaunchBehavior};
 use crate::net::http::{fetch_json, shared_client};
-use crate::paths::{ensure_dir, file_exists, normalize_path};
+use crate::paths::{ensure_dir, file_exists, normalize_path, safe_join, shared_game_storage_dir};
 use download::{download_if_needed, download_raw, DOWNLOAD_CONCURRENCY};
 use error::LauncherError;
 use futures::stream::{self, StreamExt};
 use java::resolve_java_path;
-use libraries::{build_classpath, extract_natives, sync_libraries};
+use libraries::{build_classpath, sync_libraries, sync_natives, CLASSPATH_SEPARATOR};
 use manifest::{AssetIndexData, Download, VersionManifest, VERSION_MANIFEST_URL};
 use serde::Serialize;
 use std::collections::HashMap;
@@ -23,11 +26,12 @@ use std::io::{BufRead, BufReader, Read, Write};
 use std::path::PathBuf;
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::{Arc, Mutex};
-use std::time::Duration;
-use tauri::{Emitter, Window};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+use tauri::{Emitter, Manager, Window};
 
 const WINDOW_DETECTION_TIMEOUT: Duration = Duration::from_secs(120);
+const JVM_ARGFILE: &str = "jvm_args.txt";
 type LaunchLogSink = Arc<Mutex<std::fs::File>>;
 
 struct PreparedMinecraft {
@@ -41,11 +45,24 @@ struct PreparedMinecraft {
     java_path: String,
 }
 
+#[tracing::instrument(
+    skip_all,
+    fields(
+        instance_id = options.instance_id.as_deref().unwrap_or("-"),
+        version = options.version.as_deref().unwrap_or("-")
+    )
+)]
 pub async fn launch_minecraft(
     window: &Window,
     options: &LaunchOptions,
     session: &AuthSession,
+    post_launch: PostLaunchBehavior,
 ) -> Result<(), LauncherError> {
+    if let Some(quick) = &options.quick_play {
+        let game_dir = normalize_path(&options.game_dir).join(".minecraft");
+        quick_play::validate(quick, &game_dir)?;
+    }
+
     let prepared = prepare_minecraft(window, options).await?;
     let instance_dir = prepared.instance_dir;
     let game_dir = prepared.game_dir;
@@ -56,8 +73,11 @@ pub async fn launch_minecraft(
     let natives_dir = prepared.natives_dir;
     let java_path = prepared.java_path;
 
+    if options.demo {
+        emit(window, "launch", "Launching in demo mode", None, None)?;
+    }
     emit(window, "launch", "Preparing JVM arguments", None, None)?;
-    let classpath = build_classpath(&library_paths, &client_jar_path);
+    let classpath = build_classpath(&library_paths, &client_jar_path, CLASSPATH_SEPARATOR);
 
     let mut replace_map = HashMap::new();
     replace_map.insert("auth_player_name", session.profile.name.clone());
@@ -67,14 +87,7 @@ pub async fn launch_minecraft(
         "library_directory",
         game_dir.join("libraries").to_string_lossy().to_string(),
     );
-    replace_map.insert(
-        "classpath_separator",
-        if cfg!(target_os = "windows") {
-            ";".to_string()
-        } else {
-            ":".to_string()
-        },
-    );
+    replace_map.insert("classpath_separator", CLASSPATH_SEPARATOR.to_string());
     replace_map.insert("assets_root", assets_dir.to_string_lossy().to_string());
     replace_map.insert("game_assets", assets_dir.to_string_lossy().to_string());
     let asset_index_id = version_data
@@ -100,7 +113,31 @@ pub async fn launch_minecraft(
     replace_map.insert("launcher_name", "atlas".to_string());
     replace_map.insert("launcher_version", env!("CARGO_PKG_VERSION").to_string());
 
-    let (mut jvm_args, game_args) = args::build_arguments(&version_data, &replace_map)?;
+    if let (Some(width), Some(height)) = (options.width, options.height) {
+        replace_map.insert("resolution_width", width.to_string());
+        replace_map.insert("resolution_height", height.to_string());
+    }
+
+    let mut features = args::LaunchFeatures::from_options(options);
+    if let Some(quick) = &options.quick_play {
+        let minecraft_version = options.version.as_deref().unwrap_or(&version_data.id);
+        if quick_play::supports_quick_play(minecraft_version) {
+            replace_map.insert(
+                quick_play::token(quick.kind),
+                quick.target.trim().to_string(),
+            );
+        } else {
+            features.quick_play = None;
+            emit(
+                window,
+                "launch",
+                format!("Quick play needs Minecraft 1.20 or newer; starting {minecraft_version} normally"),
+                None,
+                None,
+            )?;
+        }
+    }
+    let (mut jvm_args, game_args) = args::build_arguments(&version_data, &replace_map, &features)?;
     let mut unresolved = args::unresolved_tokens(&jvm_args);
     unresolved.extend(args::unresolved_tokens(&game_args));
     unresolved.sort();
@@ -129,6 +166,22 @@ pub async fn launch_minecraft(
         ));
     }
 
+    let java_major = version_data
+        .java_version
+        .as_ref()
+        .map_or(8, |java| java.major_version);
+    let trailing_args = std::iter::once(version_data.main_class.clone())
+        .chain(game_args)
+        .collect::<Vec<_>>();
+    let jvm_args = args::apply_argfile_fallback(
+        &java_path,
+        jvm_args,
+        &trailing_args,
+        java_major,
+        args::COMMAND_LINE_LIMIT,
+        &instance_dir.join(JVM_ARGFILE),
+    )?;
+
     emit(window, "launch", "Spawning Minecraft", None, None)?;
     let mut command = Command::new(java_path);
     command
@@ -136,15 +189,23 @@ pub async fn launch_minecraft(
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .args(&jvm_args)
-        .arg(&version_data.main_class)
-        .args(&game_args);
+        .args(&trailing_args);
 
-    let mut child = command
-        .spawn()
-        .map_err(|err| format!("Failed to launch Minecraft: {err}"))?;
+    let attempt = history::LaunchAttempt::start(
+        instance_dir.clone(),
+        options.instance_id.clone(),
+        version_data.id.clone(),
+    );
+    let mut child = command.spawn().map_err(|err| {
+        tracing::error!("failed to spawn Minecraft: {err}");
+        format!("Failed to launch Minecraft: {err}")
+    })?;
+    tracing::info!(pid = child.id(), "Minecraft process spawned");
 
     let window_visible = Arc::new(AtomicBool::new(false));
+    let window_visible_at = attempt.window_visible_marker();
     let launch_terminal = Arc::new(AtomicBool::new(false));
+    let timed_out = Arc::new(AtomicBool::new(false));
     let launch_log_sink = init_launch_log_sink(&instance_dir);
     if launch_log_sink.is_none() {
         let _ = emit_log(
@@ -166,8 +227,10 @@ pub async fn launch_minecraft(
             stdout,
             "stdout",
             window_visible.clone(),
+            window_visible_at.clone(),
             launch_terminal.clone(),
             launch_log_sink.clone(),
+            post_launch,
         );
     }
     if let Some(stderr) = child.stderr.take() {
@@ -176,22 +239,28 @@ pub async fn launch_minecraft(
             stderr,
             "stderr",
             window_visible.clone(),
+            window_visible_at,
             launch_terminal.clone(),
             launch_log_sink.clone(),
+            post_launch,
         );
     }
 
     spawn_minecraft_process_watcher(
         window.clone(),
         child,
+        attempt,
         window_visible.clone(),
         launch_terminal.clone(),
+        timed_out.clone(),
         launch_log_sink.clone(),
+        post_launch,
     );
     spawn_window_visible_timeout_failure(
         window.clone(),
         window_visible,
         launch_terminal,
+        timed_out,
         launch_log_sink,
     );
 
@@ -205,6 +274,13 @@ pub async fn launch_minecraft(
     Ok(())
 }
 
+#[tracing::instrument(
+    skip_all,
+    fields(
+        instance_id = options.instance_id.as_deref().unwrap_or("-"),
+        version = options.version.as_deref().unwrap_or("-")
+    )
+)]
 pub async fn download_minecraft_files(
     window: &Window,
     options: &LaunchOptions,
@@ -214,6 +290,13 @@ pub async fn download_minecraft_files(
     Ok(())
 }
 
+#[tracing::instrument(
+    skip_all,
+    fields(
+        instance_id = options.instance_id.as_deref().unwrap_or("-"),
+        version = options.version.as_deref().unwrap_or("-")
+    )
+)]
 async fn prepare_minecraft(
     window: &Window,
     options: &LaunchOptions,
@@ -227,7 +310,21 @@ async fn prepare_minecraft(
     let libraries_dir = game_dir.join("libraries");
     let assets_dir = game_dir.join("assets");
     ensure_dir(&versions_dir)?;
-    ensure_dir(&libraries_dir)?;
+    let shared_root = options.shared_storage.then(shared_game_storage_dir);
+    let mut shared_assets = false;
+    for name in storage::SHARED_DIRS {
+        let shared = storage::prepare_storage_dir(&game_dir, name, shared_root.as_deref())?;
+        if shared_root.is_some() && !shared {
+            emit(
+                window,
+                "setup",
+                format!("Shared storage is unavailable; keeping {name} in this profile"),
+                None,
+                None,
+            )?;
+        }
+        shared_assets |= shared && name == "assets";
+    }
     ensure_dir(&assets_dir.join("indexes"))?;
     ensure_dir(&assets_dir.join("objects"))?;
 
@@ -239,7 +336,7 @@ async fn prepare_minecraft(
     let java_path =
         resolve_java_path(window, &instance_dir, &version_data, &options.java_path).await?;
 
-    let version_folder = versions_dir.join(&version_data.id);
+    let version_folder = safe_join(&versions_dir, &version_data.id)?;
     ensure_dir(&version_folder)?;
 
     let version_json_path = version_folder.join(format!("{}.json", version_data.id));
@@ -263,13 +360,8 @@ async fn prepare_minecraft(
 
     emit(window, "natives", "Extracting natives", None, None)?;
     let natives_dir = version_folder.join("natives");
-    if natives_dir.exists() {
-        fs::remove_dir_all(&natives_dir)
-            .map_err(|err| format!("Failed to clear natives: {err}"))?;
-    }
-    ensure_dir(&natives_dir)?;
-    for native in native_jars {
-        extract_natives(&native, &natives_dir, &version_data.libraries)?;
+    if !sync_natives(&native_jars, &natives_dir, &version_data.libraries)? {
+        tracing::debug!("natives are up to date");
     }
 
     emit(window, "assets", "Syncing assets", None, None)?;
@@ -300,12 +392,15 @@ async fn prepare_minecraft(
 
     let total_assets = assets_index_data.objects.len() as u64;
     let mut processed_assets = 0u64;
-    let mut asset_jobs: Vec<(String, PathBuf, u64)> = Vec::new();
+    let mut asset_jobs: Vec<(String, PathBuf, u64, String)> = Vec::new();
     for (_name, asset) in assets_index_data.objects.iter() {
         let hash = &asset.hash;
         let sub = &hash[0..2];
         let object_path = assets_dir.join("objects").join(sub).join(hash);
-        if file_exists(&object_path) {
+        // Other instances write to the shared store too, so objects there are
+        // checked against their sha1 by `download_if_needed` instead of trusted.
+        let present = !shared_assets && file_exists(&object_path);
+        if present {
             processed_assets += 1;
             if processed_assets % 250 == 0 || processed_assets == total_assets {
                 emit(
@@ -319,13 +414,24 @@ async fn prepare_minecraft(
             continue;
         }
         let url = format!("https://resources.download.minecraft.net/{}/{}", sub, hash);
-        asset_jobs.push((url, object_path, asset.size));
+        asset_jobs.push((url, object_path, asset.size, hash.clone()));
     }
 
     if !asset_jobs.is_empty() {
-        let mut stream = stream::iter(asset_jobs.into_iter().map(|(url, path, size)| {
+        let mut stream = stream::iter(asset_jobs.into_iter().map(|(url, path, size, hash)| {
             let client = client.clone();
-            async move { download_raw(&client, &url, &path, Some(size), true).await }
+            async move {
+                if !shared_assets {
+                    return download_raw(&client, &url, &path, Some(size), true).await;
+                }
+                let download = Download {
+                    path: None,
+                    url,
+                    sha1: Some(hash),
+                    size: Some(size),
+                };
+                download_if_needed(&client, &download, &path).await
+            }
         }))
         .buffer_unordered(DOWNLOAD_CONCURRENCY);
 
@@ -434,12 +540,14 @@ fn emit_with_percent(
     total: Option<u64>,
     percent: Option<u64>,
 ) -> Result<(), LauncherError> {
+    let message = message.into();
+    tracing::debug!(phase, current, total, percent, "{message}");
     window
         .emit(
             "launch://status",
             LaunchEvent {
                 phase: phase.into(),
-                message: message.into(),
+                message,
                 current,
                 total,
                 percent,
@@ -503,8 +611,10 @@ fn spawn_minecraft_log_forwarder<R: Read + Send + 'static>(
     reader: R,
     stream: &'static str,
     window_visible: Arc<AtomicBool>,
+    window_visible_at: Arc<OnceLock<SystemTime>>,
     launch_terminal: Arc<AtomicBool>,
     launch_log_sink: Option<LaunchLogSink>,
+    post_launch: PostLaunchBehavior,
 ) {
     std::thread::spawn(move || {
         let buffered = BufReader::new(reader);
@@ -524,6 +634,7 @@ fn spawn_minecraft_log_forwarder<R: Read + Send + 'static>(
             let _ = emit_log(&window, stream, line.clone());
             append_launch_log(&launch_log_sink, stream, &line);
             if indicates_window_visible(&line) && !window_visible.swap(true, Ordering::SeqCst) {
+                let _ = window_visible_at.set(SystemTime::now());
                 if !launch_terminal.swap(true, Ordering::SeqCst) {
                     let _ = emit_with_percent(
                         &window,
@@ -534,6 +645,7 @@ fn spawn_minecraft_log_forwarder<R: Read + Send + 'static>(
                         Some(100),
                     );
                 }
+                apply_post_launch_behavior(&window, post_launch, &launch_log_sink);
             }
         }
     });
@@ -542,64 +654,132 @@ fn spawn_minecraft_log_forwarder<R: Read + Send + 'static>(
 fn spawn_minecraft_process_watcher(
     window: Window,
     mut child: std::process::Child,
+    attempt: history::LaunchAttempt,
     window_visible: Arc<AtomicBool>,
     launch_terminal: Arc<AtomicBool>,
+    timed_out: Arc<AtomicBool>,
     launch_log_sink: Option<LaunchLogSink>,
+    post_launch: PostLaunchBehavior,
 ) {
-    std::thread::spawn(move || match child.wait() {
-        Ok(status) => {
-            let status_line = if let Some(code) = status.code() {
-                format!("Minecraft process exited with code {code}.")
-            } else {
-                "Minecraft process exited.".to_string()
-            };
-            let _ = emit_log(&window, "system", status_line.clone());
-            append_launch_log(&launch_log_sink, "system", &status_line);
-
-            if !window_visible.load(Ordering::SeqCst)
-                && !launch_terminal.swap(true, Ordering::SeqCst)
-            {
-                let message = format!("Launch failed: {status_line}");
-                let _ =
-                    emit_with_percent(&window, "launch", message.clone(), None, None, Some(100));
+    let span = tracing::Span::current();
+    std::thread::spawn(move || {
+        let _entered = span.enter();
+        let exit_code = match child.wait() {
+            Ok(status) => {
+                let status_line = if let Some(code) = status.code() {
+                    format!("Minecraft process exited with code {code}.")
+                } else {
+                    "Minecraft process exited.".to_string()
+                };
+                tracing::info!(code = status.code(), "Minecraft process exited");
+                let _ = emit_log(&window, "system", status_line.clone());
+                append_launch_log(&launch_log_sink, "system", &status_line);
+
+                if !window_visible.load(Ordering::SeqCst)
+                    && !launch_terminal.swap(true, Ordering::SeqCst)
+                {
+                    let message = format!("Launch failed: {status_line}");
+                    let _ = emit_with_percent(
+                        &window,
+                        "launch",
+                        message.clone(),
+                        None,
+                        None,
+                        Some(100),
+                    );
+                    append_launch_log(&launch_log_sink, "system", &message);
+                }
+                status.code()
+            }
+            Err(err) => {
+                let message = format!("Failed to monitor Minecraft process: {err}");
+                tracing::error!("{message}");
+                let _ = emit_log(&window, "system", message.clone());
                 append_launch_log(&launch_log_sink, "system", &message);
+                if !window_visible.load(Ordering::SeqCst)
+                    && !launch_terminal.swap(true, Ordering::SeqCst)
+                {
+                    let launch_message = format!("Launch failed: {message}");
+                    let _ = emit_with_percent(
+                        &window,
+                        "launch",
+                        launch_message.clone(),
+                        None,
+                        None,
+                        Some(100),
+                    );
+                    append_launch_log(&launch_log_sink, "system", &launch_message);
+                }
+                None
             }
+        };
+
+        if let Err(err) = attempt.finish(
+            window_visible.load(Ordering::SeqCst),
+            timed_out.load(Ordering::SeqCst),
+            exit_code,
+        ) {
+            tracing::warn!("failed to record launch history: {err}");
         }
-        Err(err) => {
-            let message = format!("Failed to monitor Minecraft process: {err}");
-            let _ = emit_log(&window, "system", message.clone());
-            append_launch_log(&launch_log_sink, "system", &message);
-            if !window_visible.load(Ordering::SeqCst)
-                && !launch_terminal.swap(true, Ordering::SeqCst)
-            {
-                let launch_message = format!("Launch failed: {message}");
-                let _ = emit_with_percent(
-                    &window,
-                    "launch",
-                    launch_message.clone(),
-                    None,
-                    None,
-                    Some(100),
-                );
-                append_launch_log(&launch_log_sink, "system", &launch_message);
-            }
+        let _ = window.emit("launch://exited", ());
+        if window_visible.load(Ordering::SeqCst) {
+            restore_launcher_window(&window, post_launch);
         }
     });
 }
 
+// Runs after the on-screen status has been emitted, so `Close` only exits once
+// the launch has been reported as successful.
+fn apply_post_launch_behavior(
+    window: &Window,
+    behavior: PostLaunchBehavior,
+    launch_log_sink: &Option<LaunchLogSink>,
+) {
+    let result = match behavior {
+        PostLaunchBehavior::KeepOpen => return,
+        PostLaunchBehavior::Minimize => window.minimize(),
+        PostLaunchBehavior::Hide => window.hide(),
+        PostLaunchBehavior::Close => {
+            tracing::info!("closing launcher after successful launch");
+            append_launch_log(launch_log_sink, "system", "Closing launcher after launch.");
+            window.app_handle().exit(0);
+            return;
+        }
+    };
+    if let Err(err) = result {
+        tracing::warn!("failed to apply post-launch behavior {behavior:?}: {err}");
+    }
+}
+
+fn restore_launcher_window(window: &Window, behavior: PostLaunchBehavior) {
+    let result = match behavior {
+        PostLaunchBehavior::Minimize => window.unminimize(),
+        PostLaunchBehavior::Hide => window.show(),
+        PostLaunchBehavior::KeepOpen | PostLaunchBehavior::Close => return,
+    };
+    if let Err(err) = result.and_then(|_| window.set_focus()) {
+        tracing::warn!("failed to restore launcher window: {err}");
+    }
+}
+
 fn spawn_window_visible_timeout_failure(
     window: Window,
     window_visible: Arc<AtomicBool>,
     launch_terminal: Arc<AtomicBool>,
+    timed_out: Arc<AtomicBool>,
     launch_log_sink: Option<LaunchLogSink>,
 ) {
+    let span = tracing::Span::current();
     std::thread::spawn(move || {
+        let _entered = span.enter();
         std::thread::sleep(WINDOW_DETECTION_TIMEOUT);
         if !window_visible.load(Ordering::SeqCst) && !launch_terminal.swap(true, Ordering::SeqCst) {
             let message = format!(
                 "Launch failed: Minecraft window was not detected within {} seconds.",
                 WINDOW_DETECTION_TIMEOUT.as_secs()
             );
+            timed_out.store(true, Ordering::SeqCst);
+            tracing::warn!("{message}");
             let _ = emit_log(&window, "system", message.clone());
             append_launch_log(&launch_log_sink, "system", &message);
             let _ = emit_with_percent(&window, "launch", message, None, None, Some(100));
@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use crate::models::{QuickPlay, QuickPlayKind};
+use crate::paths::safe_join;
+
+// Quick play arguments first shipped in 1.20 (23w14a).
+const MIN_MINOR: u32 = 20;
+
+pub fn supports_quick_play(minecraft_version: &str) -> bool {
+    let mut parts = minecraft_version.split('.');
+    let Some(Ok(major)) = parts.next().map(str::parse::<u32>) else {
+        return false;
+    };
+    let Some(Ok(minor)) = parts.next().map(str::parse::<u32>) else {
+        return false;
+    };
+    // Year-based versions (26.1 and later) follow 1.21.x.
+    major > 1 || minor >= MIN_MINOR
+}
+
+pub fn validate(quick_play: &QuickPlay, game_dir: &Path) -> Result<(), String> {
+    let target = quick_play.target.trim();
+    match quick_play.kind {
+        QuickPlayKind::Multiplayer => {
+            if target.is_empty() || target.contains(char::is_whitespace) {
+                return Err(format!(
+                    "Quick play server address '{}' is not valid.",
+                    quick_play.target
+                ));
+            }
+        }
+        QuickPlayKind::Singleplayer => {
+            // A world is a single folder directly under saves.
+            let world_dir = if target.contains(['/', '\\']) {
+                None
+            } else {
+                safe_join(&game_dir.join("saves"), target).ok()
+            };
+            if !world_dir.is_some_and(|dir| dir.is_dir()) {
+                return Err(format!(
+                    "Quick play world '{}' was not found in saves.",
+                    quick_play.target
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn argument(kind: QuickPlayKind) -> &'static str {
+    match kind {
+        QuickPlayKind::Singleplayer => "--quickPlaySingleplayer",
+        QuickPlayKind::Multiplayer => "--quickPlayMultiplayer",
+    }
+}
+
+pub(crate) fn token(kind: QuickPlayKind) -> &'static str {
+    match kind {
+        QuickPlayKind::Singleplayer => "quickPlaySingleplayer",
+        QuickPlayKind::Multiplayer => "quickPlayMultiplayer",
+    }
+}
+
+pub(crate) fn feature(kind: QuickPlayKind) -> &'static str {
+    match kind {
+        QuickPlayKind::Singleplayer => "is_quick_play_singleplayer",
+        QuickPlayKind::Multiplayer => "is_quick_play_multiplayer",
+    }
+}
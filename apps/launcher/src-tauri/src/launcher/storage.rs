@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::Path;
+
+use crate::paths::ensure_dir;
+
+/// Game directories that may live in the shared store. Versions, mods and saves
+/// always stay inside the instance.
+pub(crate) const SHARED_DIRS: [&str; 2] = ["assets", "libraries"];
+
+/// Points `game_dir/<name>` at `shared_root/<name>` with a directory symlink, or
+/// back at a per-instance directory when `shared_root` is `None`. Files already
+/// in the instance are moved into the store the first time it is linked. Returns
+/// whether the directory ended up shared; a failed symlink (for example on Windows
+/// without the privilege) leaves the per-instance directory in place.
+pub(crate) fn prepare_storage_dir(
+    game_dir: &Path,
+    name: &str,
+    shared_root: Option<&Path>,
+) -> Result<bool, String> {
+    let local = game_dir.join(name);
+    let is_link = fs::symlink_metadata(&local)
+        .map(|meta| meta.file_type().is_symlink())
+        .unwrap_or(false);
+
+    let Some(shared_root) = shared_root else {
+        if is_link {
+            remove_link(&local)?;
+        }
+        ensure_dir(&local)?;
+        return Ok(false);
+    };
+
+    let target = shared_root.join(name);
+    ensure_dir(&target)?;
+    if is_link {
+        if fs::read_link(&local).is_ok_and(|current| current == target) {
+            return Ok(true);
+        }
+        remove_link(&local)?;
+    }
+
+    // Link under a temporary name first so a missing privilege never touches the
+    // instance's own files.
+    let staged = game_dir.join(format!(".{name}.atlas-link"));
+    if fs::symlink_metadata(&staged).is_ok() {
+        remove_link(&staged)?;
+    }
+    if let Err(err) = symlink_dir(&target, &staged) {
+        tracing::warn!(
+            "could not link {} to {}: {err}",
+            local.display(),
+            target.display()
+        );
+        ensure_dir(&local)?;
+        return Ok(false);
+    }
+
+    if local.is_dir() {
+        merge_into(&local, &target)?;
+        fs::remove_dir_all(&local)
+            .map_err(|err| format!("Failed to remove {}: {err}", local.display()))?;
+    }
+    fs::rename(&staged, &local)
+        .map_err(|err| format!("Failed to link {}: {err}", local.display()))?;
+    Ok(true)
+}
+
+// Files already in the store win; the launch re-validates them by hash.
+fn merge_into(source: &Path, destination: &Path) -> Result<(), String> {
+    let entries = fs::read_dir(source)
+        .map_err(|err| format!("Failed to read {}: {err}", source.display()))?;
+    for entry in entries.flatten() {
+        let from = entry.path();
+        let to = destination.join(entry.file_name());
+        let is_dir = entry.file_type().is_ok_and(|kind| kind.is_dir());
+        if is_dir {
+            ensure_dir(&to)?;
+            merge_into(&from, &to)?;
+        } else if fs::symlink_metadata(&to).is_err() && fs::rename(&from, &to).is_err() {
+            fs::copy(&from, &to).map_err(|err| {
+                format!(
+                    "Failed to move {} into shared storage: {err}",
+                    from.display()
+                )
+            })?;
+        }
+    }
+    Ok(())
+}
+
+// Directory symlinks are removed with `remove_dir` on Windows and `remove_file`
+// elsewhere; neither follows the link.
+fn remove_link(path: &Path) -> Result<(), String> {
+    fs::remove_file(path)
+        .or_else(|_| fs::remove_dir(path))
+        .map_err(|err| format!("Failed to remove link {}: {err}", path.display()))
+}
+
+#[cfg(unix)]
+fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink_dir(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_dir(target, link)
+}
@@ -1,8 +1,9 @@
 use super::*;
 use crate::launcher::manifest::{
-    ArgValue, Argument, Arguments, AssetIndex, Download, Library, Rule, VersionData,
+    ArgValue, Argument, Arguments, AssetIndex, Download, Library, Rule, RuleOs, VersionData,
     VersionDownloads,
 };
+use crate::models::{QuickPlay, QuickPlayKind};
 use std::collections::HashMap;
 use std::fs;
 #[cfg(unix)]
@@ -44,7 +45,8 @@ fn replaces_tokens_in_legacy_args() {
     let mut replacements = HashMap::new();
     replacements.insert("auth_player_name", "Steve".to_string());
 
-    let (_jvm, game) = args::build_arguments(&version, &replacements).unwrap();
+    let (_jvm, game) =
+        args::build_arguments(&version, &replacements, &args::LaunchFeatures::default()).unwrap();
     assert_eq!(game, vec!["--username".to_string(), "Steve".to_string()]);
 }
 
@@ -69,12 +71,108 @@ fn filters_feature_gated_args() {
         ],
     });
 
-    let (jvm, game) = args::build_arguments(&version, &HashMap::new()).unwrap();
+    let (jvm, game) =
+        args::build_arguments(&version, &HashMap::new(), &args::LaunchFeatures::default()).unwrap();
     assert!(jvm.is_empty());
     assert!(game.contains(&"--demo".to_string()));
     assert!(!game.contains(&"--quickPlay".to_string()));
 }
 
+#[test]
+fn demo_mode_adds_demo_flag_once() {
+    let demo = args::LaunchFeatures {
+        is_demo_user: true,
+        ..Default::default()
+    };
+
+    let mut legacy = base_version();
+    legacy.minecraft_arguments = Some("--username Steve".to_string());
+    let (_jvm, game) = args::build_arguments(&legacy, &HashMap::new(), &demo).unwrap();
+    assert_eq!(game, vec!["--username", "Steve", "--demo"]);
+
+    let mut modern = base_version();
+    modern.arguments = Some(Arguments {
+        jvm: vec![],
+        game: vec![Argument::Rule {
+            rules: vec![Rule {
+                action: "allow".to_string(),
+                os: None,
+                features: Some(HashMap::from([("is_demo_user".to_string(), true)])),
+            }],
+            value: ArgValue::String("--demo".to_string()),
+        }],
+    });
+    let (_jvm, game) = args::build_arguments(&modern, &HashMap::new(), &demo).unwrap();
+    assert_eq!(game, vec!["--demo"]);
+}
+
+#[test]
+fn custom_resolution_enables_resolution_args() {
+    let mut version = base_version();
+    version.arguments = Some(Arguments {
+        jvm: vec![],
+        game: vec![Argument::Rule {
+            rules: vec![Rule {
+                action: "allow".to_string(),
+                os: None,
+                features: Some(HashMap::from([("has_custom_resolution".to_string(), true)])),
+            }],
+            value: ArgValue::List(vec![
+                "--width".to_string(),
+                "${resolution_width}".to_string(),
+                "--height".to_string(),
+                "${resolution_height}".to_string(),
+            ]),
+        }],
+    });
+    let replacements = HashMap::from([
+        ("resolution_width", "1280".to_string()),
+        ("resolution_height", "720".to_string()),
+    ]);
+
+    let (_jvm, game) =
+        args::build_arguments(&version, &replacements, &args::LaunchFeatures::default()).unwrap();
+    assert!(game.is_empty());
+
+    let features = args::LaunchFeatures {
+        has_custom_resolution: true,
+        fullscreen: true,
+        ..Default::default()
+    };
+    let (_jvm, game) = args::build_arguments(&version, &replacements, &features).unwrap();
+    assert_eq!(
+        game,
+        vec!["--width", "1280", "--height", "720", "--fullscreen"]
+    );
+}
+
+#[test]
+fn os_rules_match_arch_and_version() {
+    let rule = |os: RuleOs| {
+        Some(vec![Rule {
+            action: "allow".to_string(),
+            os: Some(os),
+            features: None,
+        }])
+    };
+    let os = |arch: Option<&str>, version: Option<&str>| RuleOs {
+        name: None,
+        arch: arch.map(str::to_string),
+        version: version.map(str::to_string),
+    };
+
+    assert!(args::rules_allow(&rule(os(
+        Some(std::env::consts::ARCH),
+        None
+    ))));
+    assert!(!args::rules_allow(&rule(os(Some("sparc"), None))));
+    assert!(args::rules_allow(&rule(os(None, Some(".*")))));
+    assert!(!args::rules_allow(&rule(os(
+        None,
+        Some("^no-such-version$")
+    ))));
+}
+
 #[test]
 fn unresolved_tokens_are_reported() {
     let args = vec![
@@ -98,13 +196,8 @@ fn library_path_from_maven_coords() {
 #[test]
 fn classpath_joins_with_separator() {
     let libs = vec![PathBuf::from("/tmp/a.jar"), PathBuf::from("/tmp/b.jar")];
-    let classpath = libraries::build_classpath(&libs, PathBuf::from("/tmp/c.jar").as_path());
-    let sep = if cfg!(target_os = "windows") {
-        ";"
-    } else {
-        ":"
-    };
-    assert!(classpath.contains(sep));
+    let classpath = libraries::build_classpath(&libs, PathBuf::from("/tmp/c.jar").as_path(), ";");
+    assert!(classpath.contains(';'));
     assert!(classpath.contains("a.jar"));
     assert!(classpath.contains("c.jar"));
 }
@@ -116,12 +209,8 @@ fn classpath_deduplicates_library_entries() {
         PathBuf::from("/tmp/a.jar"),
         PathBuf::from("/tmp/b.jar"),
     ];
-    let classpath = libraries::build_classpath(&libs, PathBuf::from("/tmp/c.jar").as_path());
-    let sep = if cfg!(target_os = "windows") {
-        ";"
-    } else {
-        ":"
-    };
+    let sep = libraries::CLASSPATH_SEPARATOR;
+    let classpath = libraries::build_classpath(&libs, PathBuf::from("/tmp/c.jar").as_path(), sep);
     let entries: Vec<&str> = classpath.split(sep).collect();
     let a_count = entries
         .iter()
@@ -236,6 +325,24 @@ fn parse_java_major_returns_none_for_unexpected_output() {
     assert_eq!(java::parse_java_major_version(output), None);
 }
 
+#[test]
+fn parses_java_info_from_properties_and_banner_output() {
+    let output = "Property settings:\n    java.vendor = Eclipse Adoptium\n    java.vendor.url = https://adoptium.net/\n    java.version = 21.0.5\n    java.version.date = 2024-10-15\n    os.arch = aarch64\n    sun.arch.data.model = 64\n\nopenjdk version \"21.0.5\" 2024-10-15 LTS\n";
+    let info = java::parse_java_info(output).expect("java info");
+    assert_eq!(info.version, "21.0.5");
+    assert_eq!(info.major, 21);
+    assert_eq!(info.vendor.as_deref(), Some("Eclipse Adoptium"));
+    assert_eq!(info.arch.as_deref(), Some("aarch64"));
+    assert!(info.is_64bit);
+
+    let legacy =
+        "java version \"1.8.0_432\"\nJava HotSpot(TM) Client VM (build 25.432-b06, mixed mode)";
+    let info = java::parse_java_info(legacy).expect("legacy java info");
+    assert_eq!(info.major, 8);
+    assert_eq!(info.vendor, None);
+    assert!(!info.is_64bit);
+}
+
 #[test]
 fn normalize_java_override_rejects_missing_path() {
     let temp = unique_temp_dir("java-override-missing");
@@ -284,3 +391,400 @@ fn normalize_java_override_rejects_non_executable_file() {
     assert!(result.is_err());
     let _ = fs::remove_dir_all(temp);
 }
+
+#[cfg(unix)]
+#[test]
+fn collect_java_homes_finds_linux_and_macos_layouts_within_depth() {
+    let temp = unique_temp_dir("java-discovery");
+    let install = |relative: &str| {
+        let binary = temp.join(relative);
+        fs::create_dir_all(binary.parent().expect("parent")).expect("create java home");
+        fs::write(&binary, b"fake-java").expect("write java");
+        fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).expect("chmod java");
+    };
+    install("java-21-openjdk/bin/java");
+    install("temurin-17.jdk/Contents/Home/bin/java");
+    install("vendor/nested/jdk-8/bin/java");
+
+    let mut found = Vec::new();
+    java::collect_java_homes(&temp, 1, &mut |binary| found.push(binary));
+    found.sort();
+
+    assert_eq!(
+        found,
+        vec![
+            temp.join("java-21-openjdk/bin/java"),
+            temp.join("temurin-17.jdk/Contents/Home/bin/java"),
+        ]
+    );
+    let _ = fs::remove_dir_all(temp);
+}
+
+#[test]
+fn classifies_launch_outcomes() {
+    use crate::models::LaunchOutcome;
+
+    assert_eq!(
+        history::classify_outcome(true, false, Some(0)),
+        LaunchOutcome::WindowDetected
+    );
+    assert_eq!(
+        history::classify_outcome(true, false, Some(-1)),
+        LaunchOutcome::Crashed
+    );
+    assert_eq!(
+        history::classify_outcome(false, false, Some(0)),
+        LaunchOutcome::Crashed
+    );
+    assert_eq!(
+        history::classify_outcome(false, true, None),
+        LaunchOutcome::TimedOut
+    );
+}
+
+#[test]
+fn launch_history_is_capped_and_newest_first() {
+    use crate::models::{LaunchOutcome, LaunchRecord};
+
+    let temp = unique_temp_dir("launch-history");
+    fs::create_dir_all(&temp).expect("create temp dir");
+    for index in 0..60u64 {
+        let record = LaunchRecord {
+            instance_id: Some("default".to_string()),
+            version: "1.21.1".to_string(),
+            started_at_ms: index,
+            outcome: LaunchOutcome::WindowDetected,
+            exit_code: Some(0),
+            duration_ms: 1000,
+        };
+        history::record_launch(&temp, &record).expect("record launch");
+    }
+
+    let all = history::read_launch_history(&temp, usize::MAX).expect("read history");
+    assert_eq!(all.len(), 50);
+    assert_eq!(all[0].started_at_ms, 59);
+    assert_eq!(all[49].started_at_ms, 10);
+
+    let recent = history::read_launch_history(&temp, 3).expect("read history");
+    assert_eq!(recent.len(), 3);
+    let _ = fs::remove_dir_all(temp);
+}
+
+#[test]
+fn playtime_accumulates_across_launches() {
+    let temp = unique_temp_dir("instance-stats");
+    fs::create_dir_all(&temp).expect("create temp dir");
+    assert_eq!(
+        history::read_instance_stats(&temp).expect("read stats"),
+        Default::default()
+    );
+
+    history::record_playtime(&temp, 2_000, 600).expect("record playtime");
+    history::record_playtime(&temp, 1_000, 30).expect("record playtime");
+    let stats = history::read_instance_stats(&temp).expect("read stats");
+    assert_eq!(stats.last_played_unix, Some(2_000));
+    assert_eq!(stats.total_playtime_seconds, 630);
+    let _ = fs::remove_dir_all(temp);
+}
+
+#[test]
+fn long_classpath_falls_back_to_argfile() {
+    let temp = unique_temp_dir("jvm-argfile");
+    fs::create_dir_all(&temp).expect("create temp dir");
+    let argfile = temp.join("jvm_args.txt");
+    let trailing = vec!["net.minecraft.client.main.Main".to_string()];
+
+    let libs = (0..5000)
+        .map(|index| PathBuf::from(format!("C:\\Users\\Some One\\libraries\\lib-{index}.jar")))
+        .collect::<Vec<_>>();
+    let classpath = libraries::build_classpath(&libs, PathBuf::from("client.jar").as_path(), ";");
+    let jvm_args = vec!["-Xmx4096M".to_string(), "-cp".to_string(), classpath];
+
+    let inline = args::apply_argfile_fallback(
+        "java",
+        jvm_args.clone(),
+        &trailing,
+        8,
+        args::COMMAND_LINE_LIMIT,
+        &argfile,
+    )
+    .expect("java 8 keeps inline args");
+    assert_eq!(inline, jvm_args);
+    assert!(!argfile.exists());
+
+    let replaced = args::apply_argfile_fallback(
+        "java",
+        jvm_args,
+        &trailing,
+        21,
+        args::COMMAND_LINE_LIMIT,
+        &argfile,
+    )
+    .expect("write argfile");
+    assert_eq!(replaced, vec![format!("@{}", argfile.display())]);
+    let contents = fs::read_to_string(&argfile).expect("read argfile");
+    assert!(contents.starts_with("\"-Xmx4096M\"\n\"-cp\"\n"));
+    assert!(contents.contains("\"C:\\\\Users\\\\Some One\\\\libraries\\\\lib-0.jar;"));
+
+    let short = vec!["-Xmx4096M".to_string()];
+    let kept = args::apply_argfile_fallback(
+        "java",
+        short.clone(),
+        &trailing,
+        21,
+        args::COMMAND_LINE_LIMIT,
+        &argfile,
+    )
+    .expect("short args stay inline");
+    assert_eq!(kept, short);
+    let _ = fs::remove_dir_all(temp);
+}
+
+fn write_native_jar(path: &std::path::Path, contents: &[u8]) {
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    let mut jar = zip::ZipWriter::new(fs::File::create(path).expect("create native jar"));
+    jar.start_file("liblwjgl.so", SimpleFileOptions::default())
+        .expect("start native entry");
+    jar.write_all(contents).expect("write native entry");
+    jar.finish().expect("finish native jar");
+}
+
+#[test]
+fn natives_are_reextracted_only_when_jars_change() {
+    let temp = unique_temp_dir("natives-cache");
+    fs::create_dir_all(&temp).expect("create temp dir");
+    let jar = temp.join("lwjgl-natives.jar");
+    let natives_dir = temp.join("natives");
+    write_native_jar(&jar, b"v1");
+
+    let jars = vec![jar.clone()];
+    assert!(libraries::sync_natives(&jars, &natives_dir, &[]).expect("first extract"));
+    assert!(!libraries::sync_natives(&jars, &natives_dir, &[]).expect("cached"));
+
+    fs::write(natives_dir.join("stale.so"), b"stale").expect("write stale native");
+    write_native_jar(&jar, b"v2");
+    assert!(libraries::sync_natives(&jars, &natives_dir, &[]).expect("re-extract"));
+    assert_eq!(
+        fs::read(natives_dir.join("liblwjgl.so")).expect("read native"),
+        b"v2"
+    );
+    assert!(!natives_dir.join("stale.so").exists());
+    let _ = fs::remove_dir_all(temp);
+}
+
+#[test]
+fn quick_play_is_gated_on_version_and_validated() {
+    assert!(quick_play::supports_quick_play("1.20"));
+    assert!(quick_play::supports_quick_play("1.21.4"));
+    assert!(quick_play::supports_quick_play("26.1"));
+    assert!(!quick_play::supports_quick_play("1.19.4"));
+    assert!(!quick_play::supports_quick_play("23w14a"));
+
+    let game_dir = unique_temp_dir("quick-play");
+    fs::create_dir_all(game_dir.join("saves").join("My World")).unwrap();
+    let world = |target: &str| QuickPlay {
+        kind: QuickPlayKind::Singleplayer,
+        target: target.to_string(),
+    };
+    let server = |target: &str| QuickPlay {
+        kind: QuickPlayKind::Multiplayer,
+        target: target.to_string(),
+    };
+
+    assert!(quick_play::validate(&world("My World"), &game_dir).is_ok());
+    assert!(quick_play::validate(&world("Missing"), &game_dir).is_err());
+    assert!(quick_play::validate(&world("../saves"), &game_dir).is_err());
+    assert!(quick_play::validate(&server("play.example.net:25565"), &game_dir).is_ok());
+    assert!(quick_play::validate(&server("  "), &game_dir).is_err());
+
+    let mut version = base_version();
+    version.minecraft_arguments = Some("--username Steve".to_string());
+    let features = args::LaunchFeatures {
+        quick_play: Some(QuickPlayKind::Multiplayer),
+        ..Default::default()
+    };
+    let replacements = HashMap::from([("quickPlayMultiplayer", "play.example.net".to_string())]);
+    let (_jvm, game) = args::build_arguments(&version, &replacements, &features).unwrap();
+    assert_eq!(
+        game,
+        vec![
+            "--username",
+            "Steve",
+            "--quickPlayMultiplayer",
+            "play.example.net"
+        ]
+    );
+
+    let _ = fs::remove_dir_all(game_dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn shared_storage_links_and_unlinks_game_dirs() {
+    let root = unique_temp_dir("shared-storage");
+    let game_dir = root.join("instance").join(".minecraft");
+    let shared = root.join("shared");
+    fs::create_dir_all(game_dir.join("assets/objects/ab")).unwrap();
+    fs::write(game_dir.join("assets/objects/ab/abcd"), b"asset").unwrap();
+
+    assert!(storage::prepare_storage_dir(&game_dir, "assets", Some(&shared)).unwrap());
+    let link = fs::symlink_metadata(game_dir.join("assets")).unwrap();
+    assert!(link.file_type().is_symlink());
+    assert!(shared.join("assets/objects/ab/abcd").is_file());
+    assert!(storage::prepare_storage_dir(&game_dir, "assets", Some(&shared)).unwrap());
+
+    assert!(!storage::prepare_storage_dir(&game_dir, "assets", None).unwrap());
+    let local = fs::symlink_metadata(game_dir.join("assets")).unwrap();
+    assert!(local.is_dir());
+    assert!(shared.join("assets/objects/ab/abcd").is_file());
+
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn installed_version_check_follows_inheritance() {
+    let versions_dir = unique_temp_dir("installed-version");
+    let write = |folder: &str, json: &str| {
+        fs::create_dir_all(versions_dir.join(folder)).unwrap();
+        fs::write(
+            versions_dir.join(folder).join(format!("{folder}.json")),
+            json,
+        )
+        .unwrap();
+    };
+    let known_remote = std::collections::HashSet::from(["1.21.1".to_string()]);
+
+    write(
+        "fabric-loader-1.21.1",
+        r#"{"id":"fabric-loader-1.21.1","inheritsFrom":"1.21.1"}"#,
+    );
+    assert!(versions::check_installed_version(
+        &versions_dir,
+        "fabric-loader-1.21.1",
+        &known_remote
+    )
+    .is_empty());
+
+    write(
+        "neoforge-21.1.1",
+        r#"{"id":"neoforge-21.1.0","inheritsFrom":"1.99"}"#,
+    );
+    let problems =
+        versions::check_installed_version(&versions_dir, "neoforge-21.1.1", &known_remote);
+    assert_eq!(problems.len(), 2);
+    assert!(problems[0].contains("declares id neoforge-21.1.0"));
+    assert!(problems[1].contains("1.99/1.99.json is missing"));
+
+    assert!(!versions::check_installed_version(&versions_dir, "1.21.1", &known_remote).is_empty());
+
+    let _ = fs::remove_dir_all(versions_dir);
+}
+
+const VANILLA_1_21_1: &str = r#"{
+    "id": "1.21.1",
+    "type": "release",
+    "mainClass": "net.minecraft.client.main.Main",
+    "assetIndex": {"id": "17", "url": "https://example.invalid/17.json", "sha1": "aa", "size": 1},
+    "downloads": {"client": {"url": "https://example.invalid/client.jar", "sha1": "bb", "size": 2}},
+    "javaVersion": {"component": "java-runtime-delta", "majorVersion": 21},
+    "arguments": {
+        "game": ["--username", "${auth_player_name}"],
+        "jvm": ["-Djava.library.path=${natives_directory}", "-cp", "${classpath}"]
+    },
+    "libraries": [
+        {"name": "org.ow2.asm:asm:9.6"},
+        {"name": "com.mojang:brigadier:1.3.10"},
+        {"name": "org.lwjgl:lwjgl:3.3.3"},
+        {"name": "org.lwjgl:lwjgl:3.3.3:natives-linux"}
+    ]
+}"#;
+
+fn inheritance_chain(child: &str) -> VersionData {
+    let child: VersionData = serde_json::from_str(child).unwrap();
+    let vanilla: VersionData = serde_json::from_str(VANILLA_1_21_1).unwrap();
+    versions::merge_inheritance_chain(vec![child, vanilla]).unwrap()
+}
+
+fn library_names(version: &VersionData) -> Vec<&str> {
+    version
+        .libraries
+        .iter()
+        .map(|library| library.name.as_str())
+        .collect()
+}
+
+#[test]
+fn fabric_profile_merges_onto_vanilla() {
+    let merged = inheritance_chain(
+        r#"{
+            "id": "fabric-loader-0.16.10-1.21.1",
+            "inheritsFrom": "1.21.1",
+            "type": "release",
+            "mainClass": "net.fabricmc.loader.impl.launch.knot.KnotClient",
+            "arguments": {"game": [], "jvm": ["-DFabricMcEmu= net.minecraft.client.main.Main "]},
+            "libraries": [
+                {"name": "org.ow2.asm:asm:9.7.1", "url": "https://maven.fabricmc.net/"},
+                {"name": "net.fabricmc:fabric-loader:0.16.10", "url": "https://maven.fabricmc.net/"}
+            ]
+        }"#,
+    );
+
+    assert_eq!(merged.id, "fabric-loader-0.16.10-1.21.1");
+    assert_eq!(
+        merged.main_class,
+        "net.fabricmc.loader.impl.launch.knot.KnotClient"
+    );
+    assert_eq!(
+        library_names(&merged),
+        vec![
+            "org.ow2.asm:asm:9.7.1",
+            "net.fabricmc:fabric-loader:0.16.10",
+            "com.mojang:brigadier:1.3.10",
+            "org.lwjgl:lwjgl:3.3.3",
+            "org.lwjgl:lwjgl:3.3.3:natives-linux",
+        ]
+    );
+    assert_eq!(merged.asset_index.as_ref().unwrap().id, "17");
+    assert!(merged.downloads.is_some());
+    assert!(merged.inherits_from.is_none());
+    let jvm = &merged.arguments.as_ref().unwrap().jvm;
+    assert_eq!(jvm.len(), 4);
+    assert!(matches!(&jvm[3], Argument::String(value) if value.starts_with("-DFabricMcEmu")));
+}
+
+#[test]
+fn neoforge_profile_merges_onto_vanilla() {
+    let merged = inheritance_chain(
+        r#"{
+            "id": "neoforge-21.1.77",
+            "inheritsFrom": "1.21.1",
+            "type": "release",
+            "mainClass": "cpw.mods.bootstraplauncher.BootstrapLauncher",
+            "arguments": {
+                "game": ["--fml.neoForgeVersion", "21.1.77", "--launchTarget", "forgeclient"],
+                "jvm": ["-DlibraryDirectory=${library_directory}"]
+            },
+            "libraries": [
+                {"name": "net.neoforged.fancymodloader:loader:4.0.31"},
+                {"name": "cpw.mods:bootstraplauncher:2.0.2"},
+                {"name": "org.ow2.asm:asm:9.7"}
+            ]
+        }"#,
+    );
+
+    assert_eq!(
+        merged.main_class,
+        "cpw.mods.bootstraplauncher.BootstrapLauncher"
+    );
+    let names = library_names(&merged);
+    assert_eq!(names.len(), 6);
+    assert!(names.contains(&"org.ow2.asm:asm:9.7"));
+    assert!(!names.contains(&"org.ow2.asm:asm:9.6"));
+    let game = &merged.arguments.as_ref().unwrap().game;
+    assert_eq!(game.len(), 6);
+    assert!(matches!(&game[0], Argument::String(value) if value == "--username"));
+    assert!(matches!(&game[2], Argument::String(value) if value == "--fml.neoForgeVersion"));
+    assert_eq!(merged.java_version.as_ref().unwrap().major_version, 21);
+}
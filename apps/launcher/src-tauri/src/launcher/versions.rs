@@ -4,7 +4,9 @@ use crate::models::{LaunchOptions, ModLoaderKind};
 use crate::net::http::fetch_json;
 use manifest::{VersionData, VersionManifest};
 use reqwest::Client;
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 use tauri::Window;
 
 pub async fn resolve_version_data(
@@ -74,7 +76,9 @@ pub async fn resolve_version_data(
         }
     };
 
-    version_data = resolve_inherited_version_data(client, manifest, version_data).await?;
+    version_data =
+        resolve_inherited_version_data(client, manifest, version_data, &game_dir.join("versions"))
+            .await?;
     Ok(version_data)
 }
 
@@ -82,47 +86,85 @@ async fn resolve_inherited_version_data(
     client: &Client,
     manifest: &VersionManifest,
     version_data: VersionData,
+    versions_dir: &Path,
 ) -> Result<VersionData, LauncherError> {
-    let mut visited = std::collections::HashSet::new();
+    let mut visited = HashSet::from([version_data.id.clone()]);
+    let mut next_parent = version_data.inherits_from.clone();
     let mut chain = vec![version_data];
-    let mut next_parent = chain
-        .last()
-        .and_then(|version| version.inherits_from.clone());
 
     while let Some(parent_id) = next_parent {
         if !visited.insert(parent_id.clone()) {
             return Err(format!("Version inheritance loop detected at {parent_id}").into());
         }
 
-        let parent_ref = manifest
-            .versions
-            .iter()
-            .find(|version| version.id == parent_id)
-            .ok_or_else(|| format!("Parent version {parent_id} not found in manifest"))?;
-        let parent_data: VersionData = fetch_json(client, &parent_ref.url).await?;
+        let parent_data = load_parent_version(client, manifest, versions_dir, &parent_id).await?;
         next_parent = parent_data.inherits_from.clone();
         chain.push(parent_data);
     }
 
-    let mut merged = chain
-        .pop()
-        .ok_or_else(|| "Failed to resolve version data.".to_string())?;
+    merge_inheritance_chain(chain)
+        .ok_or_else(|| "Failed to resolve version data.".to_string().into())
+}
+
+// Mojang versions always come from the manifest; anything else must already be
+// installed, as custom profiles are.
+async fn load_parent_version(
+    client: &Client,
+    manifest: &VersionManifest,
+    versions_dir: &Path,
+    parent_id: &str,
+) -> Result<VersionData, LauncherError> {
+    if let Some(parent_ref) = manifest
+        .versions
+        .iter()
+        .find(|version| version.id == parent_id)
+    {
+        return Ok(fetch_json(client, &parent_ref.url).await?);
+    }
+
+    let local_path = versions_dir
+        .join(parent_id)
+        .join(format!("{parent_id}.json"));
+    let bytes = fs::read(&local_path).map_err(|_| {
+        format!("Parent version {parent_id} not found in manifest or installed versions")
+    })?;
+    serde_json::from_slice(&bytes)
+        .map_err(|err| format!("Failed to parse {}: {err}", local_path.display()).into())
+}
+
+/// Folds an inheritance chain, ordered child first, into one version. Children
+/// override scalar fields and libraries with the same coordinates (ignoring the
+/// version); arguments are concatenated parent first.
+pub(crate) fn merge_inheritance_chain(mut chain: Vec<VersionData>) -> Option<VersionData> {
+    let mut merged = chain.pop()?;
     while let Some(overlay) = chain.pop() {
         merged = merge_versions(merged, overlay);
     }
-
-    Ok(merged)
+    Some(merged)
 }
 
 fn merge_versions(base: VersionData, overlay: VersionData) -> VersionData {
-    let mut libraries = base.libraries;
-    libraries.extend(overlay.libraries.clone());
+    let overridden = overlay
+        .libraries
+        .iter()
+        .map(|library| library_key(&library.name))
+        .collect::<HashSet<_>>();
+    let mut libraries = overlay.libraries;
+    libraries.extend(
+        base.libraries
+            .into_iter()
+            .filter(|library| !overridden.contains(&library_key(&library.name))),
+    );
 
-    let arguments = merge_arguments(base.arguments, overlay.arguments.clone());
+    let arguments = merge_arguments(base.arguments, overlay.arguments);
 
     VersionData {
         id: overlay.id,
-        kind: overlay.kind,
+        kind: if overlay.kind.trim().is_empty() {
+            base.kind
+        } else {
+            overlay.kind
+        },
         main_class: if overlay.main_class.trim().is_empty() {
             base.main_class
         } else {
@@ -138,6 +180,19 @@ fn merge_versions(base: VersionData, overlay: VersionData) -> VersionData {
     }
 }
 
+// `group:artifact[:classifier]`, so a loader's newer ASM replaces vanilla's while
+// per-platform native artifacts stay distinct.
+fn library_key(name: &str) -> String {
+    let mut parts = name.split(':');
+    let group = parts.next().unwrap_or_default();
+    let artifact = parts.next().unwrap_or_default();
+    // Skips the version.
+    match parts.nth(1) {
+        Some(classifier) => format!("{group}:{artifact}:{classifier}"),
+        None => format!("{group}:{artifact}"),
+    }
+}
+
 fn merge_arguments(
     base: Option<manifest::Arguments>,
     overlay: Option<manifest::Arguments>,
@@ -153,3 +208,60 @@ fn merge_arguments(
         (None, None) => None,
     }
 }
+
+/// Problems with an installed `versions/<id>/<id>.json`: an unreadable file, an
+/// `id` that does not match its folder, or an `inheritsFrom` chain that neither
+/// the install nor the Mojang manifest (`known_remote`) can resolve.
+pub(crate) fn check_installed_version(
+    versions_dir: &Path,
+    id: &str,
+    known_remote: &HashSet<String>,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut visited = HashSet::new();
+    let mut current = id.to_string();
+
+    loop {
+        if !visited.insert(current.clone()) {
+            problems.push(format!("Version inheritance loop at {current}"));
+            break;
+        }
+
+        let json_path = versions_dir.join(&current).join(format!("{current}.json"));
+        if !json_path.is_file() {
+            // A vanilla parent is fetched from the manifest at launch.
+            if current != id && known_remote.contains(&current) {
+                break;
+            }
+            problems.push(format!("{current}/{current}.json is missing"));
+            break;
+        }
+
+        let value = match fs::read(&json_path)
+            .map_err(|err| err.to_string())
+            .and_then(|bytes| {
+                serde_json::from_slice::<serde_json::Value>(&bytes).map_err(|err| err.to_string())
+            }) {
+            Ok(value) => value,
+            Err(err) => {
+                problems.push(format!("{current}/{current}.json is unreadable: {err}"));
+                break;
+            }
+        };
+
+        match value.get("id").and_then(|value| value.as_str()) {
+            Some(json_id) if json_id == current => {}
+            Some(json_id) => problems.push(format!(
+                "{current}/{current}.json declares id {json_id}, not {current}"
+            )),
+            None => problems.push(format!("{current}/{current}.json has no id")),
+        }
+
+        match value.get("inheritsFrom").and_then(|value| value.as_str()) {
+            Some(parent) => current = parent.to_string(),
+            None => break,
+        }
+    }
+
+    problems
+}
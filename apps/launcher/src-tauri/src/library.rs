@@ -1,5 +1,7 @@
 mod atlas_sync;
 mod error;
+mod screenshots;
+mod worlds;
 
 use crate::launcher::manifest::VersionManifest;
 use crate::models::{
@@ -12,10 +14,13 @@ use atlas_client::hub::HubClient;
 use error::LibraryError;
 use std::collections::HashSet;
 use std::fs;
-use std::path::Component;
+use std::path::{Component, Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::Window;
 
+pub use screenshots::{delete_screenshot, list_instance_screenshots, open_screenshot};
+pub use worlds::{backup_world, list_world_backups, list_worlds, restore_world};
+
 pub async fn fetch_version_manifest_summary() -> Result<VersionManifestSummary, LibraryError> {
     let manifest: VersionManifest =
         fetch_json_shared(crate::launcher::manifest::VERSION_MANIFEST_URL).await?;
@@ -52,10 +57,13 @@ pub async fn fetch_atlas_remote_packs(
     let mut hub =
         HubClient::new(atlas_hub_url).map_err(|err| LibraryError::Message(err.to_string()))?;
     hub.set_token(access_token.to_string());
-    let packs = hub
-        .list_launcher_packs()
-        .await
-        .map_err(|err| LibraryError::Message(err.to_string()))?;
+    let packs = hub.list_launcher_packs().await.map_err(|err| {
+        if err.is_unauthorized() {
+            LibraryError::Message("Atlas session expired; please sign in again.".to_string())
+        } else {
+            LibraryError::Message(err.to_string())
+        }
+    })?;
     let mut seen_pack_ids = HashSet::new();
     let mut remote_packs = Vec::new();
     for pack in packs {
@@ -179,8 +187,8 @@ pub fn set_mod_enabled(game_dir: &str, file_name: &str, enabled: bool) -> Result
     let mods_dir = minecraft_dir_for_instance(&base_dir).join("mods");
     paths::ensure_dir(&mods_dir)?;
 
-    let safe_name = sanitize_mod_filename(file_name)?;
-    let current_path = mods_dir.join(&safe_name);
+    let current_path = paths::safe_join(&mods_dir, file_name)?;
+    let safe_name = file_name.to_string();
     if !current_path.exists() {
         return Err(format!("Mod {safe_name} not found.").into());
     }
@@ -211,8 +219,7 @@ pub fn delete_mod(game_dir: &str, file_name: &str) -> Result<(), LibraryError> {
     let mods_dir = minecraft_dir_for_instance(&base_dir).join("mods");
     paths::ensure_dir(&mods_dir)?;
 
-    let safe_name = sanitize_mod_filename(file_name)?;
-    let path = mods_dir.join(&safe_name);
+    let path = paths::safe_join(&mods_dir, file_name)?;
     if !path.exists() {
         return Ok(());
     }
@@ -258,35 +265,39 @@ pub fn uninstall_instance_data(game_dir: &str, preserve_saves: bool) -> Result<(
     }
 
     let minecraft_dir = minecraft_dir_for_instance(&base_dir);
-    let saves_path = minecraft_dir.join("saves");
-    let mut preserved_saves_path = None;
-    if saves_path.exists() {
-        let parent = base_dir
-            .parent()
-            .ok_or_else(|| "Instance directory has no parent.".to_string())?;
-        let stamp = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|err| format!("Failed to read system clock: {err}"))?
-            .as_millis();
-        let mut candidate = parent.join(format!(".atlas-preserve-saves-{stamp}"));
+    let parent = base_dir
+        .parent()
+        .ok_or_else(|| "Instance directory has no parent.".to_string())?;
+    let stamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| format!("Failed to read system clock: {err}"))?
+        .as_millis();
+    // World backups are kept with the saves they were made from.
+    let mut preserved = Vec::new();
+    for name in PRESERVED_DIRS {
+        let source = minecraft_dir.join(name);
+        if !source.exists() {
+            continue;
+        }
+        let mut candidate = parent.join(format!(".atlas-preserve-{name}-{stamp}"));
         let mut suffix = 0usize;
         while candidate.exists() {
             suffix += 1;
-            candidate = parent.join(format!(".atlas-preserve-saves-{stamp}-{suffix}"));
+            candidate = parent.join(format!(".atlas-preserve-{name}-{stamp}-{suffix}"));
         }
-        fs::rename(&saves_path, &candidate).map_err(|err| {
-            format!(
-                "Failed to preserve saves directory {}: {err}",
-                saves_path.display()
+        if let Err(err) = fs::rename(&source, &candidate) {
+            restore_preserved_dirs(&minecraft_dir, preserved);
+            return Err(format!(
+                "Failed to preserve {name} directory {}: {err}",
+                source.display()
             )
-        })?;
-        preserved_saves_path = Some(candidate);
+            .into());
+        }
+        preserved.push((name, candidate));
     }
 
     if let Err(err) = fs::remove_dir_all(&base_dir) {
-        if let Some(path) = preserved_saves_path {
-            let _ = fs::rename(path, saves_path);
-        }
+        restore_preserved_dirs(&minecraft_dir, preserved);
         return Err(format!("Failed to remove instance data: {err}").into());
     }
 
@@ -299,12 +310,12 @@ pub fn uninstall_instance_data(game_dir: &str, preserve_saves: bool) -> Result<(
         )
     })?;
 
-    if let Some(path) = preserved_saves_path {
-        let restored_saves_path = minecraft_dir.join("saves");
-        fs::rename(&path, &restored_saves_path).map_err(|err| {
+    for (name, path) in preserved {
+        let restored_path = minecraft_dir.join(name);
+        fs::rename(&path, &restored_path).map_err(|err| {
             format!(
-                "Failed to restore saves directory to {}: {err}",
-                restored_saves_path.display()
+                "Failed to restore {name} directory to {}: {err}",
+                restored_path.display()
             )
         })?;
     }
@@ -312,6 +323,16 @@ pub fn uninstall_instance_data(game_dir: &str, preserve_saves: bool) -> Result<(
     Ok(())
 }
 
+const PRESERVED_DIRS: [&str; 2] = ["saves", worlds::WORLD_BACKUPS_DIR];
+
+// Best effort: puts directories moved aside back after a failed uninstall.
+fn restore_preserved_dirs(minecraft_dir: &Path, preserved: Vec<(&str, PathBuf)>) {
+    for (name, path) in preserved {
+        let _ = fs::create_dir_all(minecraft_dir);
+        let _ = fs::rename(path, minecraft_dir.join(name));
+    }
+}
+
 fn is_mod_filename(name: &str) -> bool {
     let lower = name.to_lowercase();
     lower.ends_with(".jar")
@@ -326,20 +347,6 @@ fn format_mod_display_name(name: &str) -> String {
     trimmed.to_string()
 }
 
-fn sanitize_mod_filename(file_name: &str) -> Result<String, LibraryError> {
-    if file_name.trim().is_empty() {
-        return Err("Mod filename is required.".to_string().into());
-    }
-    let path = std::path::Path::new(file_name);
-    if path
-        .components()
-        .any(|component| !matches!(component, Component::Normal(_)))
-    {
-        return Err("Invalid mod filename.".to_string().into());
-    }
-    Ok(file_name.to_string())
-}
-
 fn minecraft_dir_for_instance(base_dir: &std::path::Path) -> std::path::PathBuf {
     let modern_dir = base_dir.join(".minecraft");
     if modern_dir.exists() {
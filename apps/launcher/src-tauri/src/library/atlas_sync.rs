@@ -2,9 +2,9 @@ use crate::launcher::download::download_raw;
 use crate::library::error::LibraryError;
 use crate::models::{AtlasPackSyncResult, LaunchEvent};
 use crate::net::http::shared_client;
-use crate::paths::{ensure_dir, normalize_path};
+use crate::paths::{ensure_dir, normalize_path, safe_join, safe_relative_path};
 use crate::telemetry;
-use atlas_client::hub::HubClient;
+use atlas_client::hub::{HubClient, ARTIFACT_DOWNLOAD_TIMEOUT};
 use mod_resolver::pointer::{
     destination_relative_path as resolve_destination_relative_path, is_pointer_path,
     resolve_pointer_path, PointerKind,
@@ -224,7 +224,7 @@ pub async fn sync_atlas_pack(
         {
             expected_mod_paths.insert(safe_relative.clone());
         }
-        let asset_path = minecraft_dir.join(&safe_relative);
+        let asset_path = safe_join(&minecraft_dir, &relative_asset_path)?;
         if asset_path.exists() {
             let can_reuse = match verify_dependency_hash(&asset_path, dep) {
                 Ok(()) => true,
@@ -380,7 +380,13 @@ async fn request_artifact_download(
     let response = hub
         .get_launcher_artifact(pack_id, channel, current_build_id)
         .await
-        .map_err(|err| format!("Failed to request artifact metadata: {err}"))?;
+        .map_err(|err| {
+            if err.is_unauthorized() {
+                "Atlas session expired; please sign in again.".to_string()
+            } else {
+                format!("Failed to request artifact metadata: {err}")
+            }
+        })?;
 
     Ok(ArtifactResponse {
         pack_id: response.pack_id.unwrap_or_else(|| pack_id.to_string()),
@@ -414,15 +420,18 @@ async fn download_blob_bytes(
     atlas_hub_url: &str,
     download_url: &str,
 ) -> Result<Vec<u8>, LibraryError> {
-    let hub = HubClient::new(atlas_hub_url).map_err(|err| format!("Invalid hub URL: {err}"))?;
+    let hub = HubClient::builder(atlas_hub_url)
+        .timeout(ARTIFACT_DOWNLOAD_TIMEOUT)
+        .user_agent(concat!("atlas-launcher/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .map_err(|err| format!("Invalid hub URL: {err}"))?;
     hub.download_blob(download_url)
         .await
         .map_err(|err| format!("Failed to download pack blob: {err}").into())
 }
 
 fn write_blob_file(game_dir: &Path, relative_path: &str, bytes: &[u8]) -> Result<(), LibraryError> {
-    let safe_relative = sanitize_relative_path(relative_path)?;
-    let target_path = game_dir.join(safe_relative);
+    let target_path = safe_join(game_dir, relative_path)?;
     if let Some(parent) = target_path.parent() {
         ensure_dir(parent)?;
     }
@@ -449,8 +458,7 @@ fn write_blob_file(game_dir: &Path, relative_path: &str, bytes: &[u8]) -> Result
 }
 
 fn remove_blob_file_if_exists(game_dir: &Path, relative_path: &str) -> Result<(), LibraryError> {
-    let safe_relative = sanitize_relative_path(relative_path)?;
-    let target_path = game_dir.join(safe_relative);
+    let target_path = safe_join(game_dir, relative_path)?;
     if !target_path.exists() {
         return Ok(());
     }
@@ -465,26 +473,8 @@ fn remove_blob_file_if_exists(game_dir: &Path, relative_path: &str) -> Result<()
 }
 
 fn sanitize_relative_path(value: &str) -> Result<PathBuf, LibraryError> {
-    let normalized = value.replace('\\', "/");
-    if normalized.trim().is_empty() {
-        return Err("Invalid empty path in pack blob".to_string().into());
-    }
-
-    let mut out = PathBuf::new();
-    for component in Path::new(&normalized).components() {
-        match component {
-            Component::Normal(part) => out.push(part),
-            _ => {
-                return Err(format!("Invalid relative path in pack blob: {}", normalized).into());
-            }
-        }
-    }
-
-    if out.as_os_str().is_empty() {
-        return Err(format!("Invalid relative path in pack blob: {}", normalized).into());
-    }
-
-    Ok(out)
+    safe_relative_path(value)
+        .map_err(|_| format!("Invalid relative path in pack blob: {value}").into())
 }
 
 fn dependency_kind_to_pointer_kind(kind: DependencyKind) -> PointerKind {
@@ -597,19 +587,7 @@ fn applies_to_this_client(dep: &protocol::Dependency) -> bool {
         DependencySide::Client | DependencySide::Both => {}
     }
 
-    match current_platform() {
-        Some(platform) => dep.platform.allows(platform),
-        None => true,
-    }
-}
-
-fn current_platform() -> Option<Platform> {
-    match std::env::consts::OS {
-        "macos" => Some(Platform::Macos),
-        "windows" => Some(Platform::Windows),
-        "linux" => Some(Platform::Linux),
-        _ => None,
-    }
+    dep.platform.allows(Platform::from_current())
 }
 
 fn verify_dependency_hash(path: &Path, dep: &protocol::Dependency) -> Result<(), LibraryError> {
@@ -0,0 +1,194 @@
+use crate::models::ScreenshotEntry;
+use crate::paths;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::fs;
+use std::path::PathBuf;
+use std::time::UNIX_EPOCH;
+
+use super::error::LibraryError;
+use super::minecraft_dir_for_instance;
+
+const DEFAULT_LIST_LIMIT: usize = 200;
+const MAX_LIST_LIMIT: usize = 1000;
+// Previews are inlined as data URLs, so only the newest few small files get one.
+const MAX_PREVIEWS: usize = 24;
+const MAX_PREVIEW_BYTES: u64 = 2 * 1024 * 1024;
+
+fn screenshots_dir(game_dir: &str) -> PathBuf {
+    let base_dir = paths::normalize_path(game_dir);
+    minecraft_dir_for_instance(&base_dir).join("screenshots")
+}
+
+/// `.png` files in the instance screenshots dir, newest first.
+pub fn list_instance_screenshots(
+    game_dir: &str,
+    limit: Option<usize>,
+    include_previews: bool,
+) -> Result<Vec<ScreenshotEntry>, LibraryError> {
+    let dir = screenshots_dir(game_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut screenshots = Vec::new();
+    let entries =
+        fs::read_dir(&dir).map_err(|err| format!("Failed to read screenshots dir: {err}"))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| format!("Failed to read screenshots dir entry: {err}"))?;
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !is_png(&name) {
+            continue;
+        }
+        let metadata = fs::metadata(&path)
+            .map_err(|err| format!("Failed to read screenshot metadata: {err}"))?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        screenshots.push(ScreenshotEntry {
+            file_name: name,
+            path: path.to_string_lossy().to_string(),
+            size: metadata.len(),
+            modified,
+            preview: None,
+        });
+    }
+
+    screenshots.sort_by(|a, b| {
+        b.modified
+            .cmp(&a.modified)
+            .then_with(|| b.file_name.cmp(&a.file_name))
+    });
+    screenshots.truncate(limit.unwrap_or(DEFAULT_LIST_LIMIT).clamp(1, MAX_LIST_LIMIT));
+
+    if include_previews {
+        for screenshot in screenshots
+            .iter_mut()
+            .take(MAX_PREVIEWS)
+            .filter(|screenshot| screenshot.size <= MAX_PREVIEW_BYTES)
+        {
+            if let Ok(bytes) = fs::read(&screenshot.path) {
+                screenshot.preview =
+                    Some(format!("data:image/png;base64,{}", STANDARD.encode(bytes)));
+            }
+        }
+    }
+    Ok(screenshots)
+}
+
+pub fn open_screenshot(game_dir: &str, file_name: &str) -> Result<(), LibraryError> {
+    let path = screenshot_path(game_dir, file_name)?;
+    if !path.is_file() {
+        return Err(format!("Screenshot {file_name} not found.").into());
+    }
+    tauri_plugin_opener::open_path(&path, None::<&str>)
+        .map_err(|err| format!("Failed to open screenshot: {err}"))?;
+    Ok(())
+}
+
+pub fn delete_screenshot(game_dir: &str, file_name: &str) -> Result<(), LibraryError> {
+    let path = screenshot_path(game_dir, file_name)?;
+    if !path.exists() {
+        return Ok(());
+    }
+    fs::remove_file(&path).map_err(|err| format!("Failed to delete screenshot: {err}"))?;
+    Ok(())
+}
+
+// Only the `.png` files the listing shows, directly inside the screenshots
+// dir; anything else would reach the OS default handler through `open_path`.
+fn screenshot_path(game_dir: &str, file_name: &str) -> Result<PathBuf, LibraryError> {
+    if file_name.contains(['/', '\\']) || !is_png(file_name) {
+        return Err(format!("Not a screenshot: {file_name}").into());
+    }
+    Ok(paths::safe_join(&screenshots_dir(game_dir), file_name)?)
+}
+
+fn is_png(name: &str) -> bool {
+    name.to_ascii_lowercase().ends_with(".png")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn unique_temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        std::env::temp_dir().join(format!("atlas-screenshots-{prefix}-{nanos}"))
+    }
+
+    #[test]
+    fn lists_png_files_newest_first() {
+        let instance = unique_temp_dir("list");
+        let dir = instance.join(".minecraft/screenshots");
+        fs::create_dir_all(&dir).expect("create screenshots dir");
+        for (name, age) in [("old.png", 60), ("new.PNG", 0), ("notes.txt", 0)] {
+            let path = dir.join(name);
+            fs::write(&path, b"png").expect("write file");
+            let modified = SystemTime::now() - Duration::from_secs(age);
+            fs::File::options()
+                .write(true)
+                .open(&path)
+                .and_then(|file| file.set_modified(modified))
+                .expect("set mtime");
+        }
+        let game_dir = instance.to_string_lossy().to_string();
+
+        let screenshots = list_instance_screenshots(&game_dir, None, true).expect("list");
+        let names: Vec<_> = screenshots.iter().map(|s| s.file_name.as_str()).collect();
+        assert_eq!(names, ["new.PNG", "old.png"]);
+        assert!(screenshots[0]
+            .preview
+            .as_deref()
+            .is_some_and(|preview| preview.starts_with("data:image/png;base64,")));
+        assert_eq!(
+            list_instance_screenshots(&game_dir, Some(1), false)
+                .expect("list")
+                .len(),
+            1
+        );
+
+        let _ = fs::remove_dir_all(instance);
+    }
+
+    #[test]
+    fn rejects_non_png_and_escaping_names() {
+        let instance = unique_temp_dir("reject");
+        let dir = instance.join(".minecraft/screenshots");
+        fs::create_dir_all(dir.join("nested")).expect("create screenshots dir");
+        fs::write(dir.join("run.sh"), b"#!/bin/sh").expect("write script");
+        fs::write(dir.join("nested/inner.png"), b"png").expect("write nested");
+        fs::write(instance.join(".minecraft/outside.png"), b"png").expect("write outside");
+        let game_dir = instance.to_string_lossy().to_string();
+
+        for name in [
+            "run.sh",
+            "../outside.png",
+            "nested/inner.png",
+            "/etc/passwd.png",
+        ] {
+            assert!(open_screenshot(&game_dir, name).is_err(), "{name}");
+            assert!(delete_screenshot(&game_dir, name).is_err(), "{name}");
+        }
+        assert!(dir.join("run.sh").exists());
+        assert!(dir.join("nested/inner.png").exists());
+        assert!(instance.join(".minecraft/outside.png").exists());
+
+        fs::write(dir.join("shot.png"), b"png").expect("write screenshot");
+        delete_screenshot(&game_dir, "shot.png").expect("delete screenshot");
+        assert!(!dir.join("shot.png").exists());
+
+        let _ = fs::remove_dir_all(instance);
+    }
+}
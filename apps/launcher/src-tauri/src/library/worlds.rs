@@ -0,0 +1,617 @@
+use crate::models::{WorldBackup, WorldEntry};
+use crate::paths::{self, ensure_dir};
+use flate2::read::GzDecoder;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use super::error::LibraryError;
+use super::minecraft_dir_for_instance;
+
+/// Lives next to `saves` inside the Minecraft dir, so uninstall and repair keep
+/// it whenever they keep saves.
+pub(crate) const WORLD_BACKUPS_DIR: &str = "world-backups";
+
+fn saves_dir(game_dir: &str) -> PathBuf {
+    minecraft_dir_for_instance(&paths::normalize_path(game_dir)).join("saves")
+}
+
+fn backups_dir(game_dir: &str) -> PathBuf {
+    minecraft_dir_for_instance(&paths::normalize_path(game_dir)).join(WORLD_BACKUPS_DIR)
+}
+
+/// Folders under `saves` with a `level.dat`, most recently played first.
+pub fn list_worlds(game_dir: &str) -> Result<Vec<WorldEntry>, LibraryError> {
+    let dir = saves_dir(game_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut worlds = Vec::new();
+    let entries = fs::read_dir(&dir).map_err(|err| format!("Failed to read saves dir: {err}"))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| format!("Failed to read saves dir entry: {err}"))?;
+        let path = entry.path();
+        let level_path = path.join("level.dat");
+        if !path.is_dir() || !level_path.is_file() {
+            continue;
+        }
+        let folder_name = entry.file_name().to_string_lossy().to_string();
+        let level = read_level_dat(&level_path).unwrap_or_else(|err| {
+            tracing::warn!("unreadable {}: {err}", level_path.display());
+            LevelInfo::default()
+        });
+        let last_played = level
+            .last_played_ms
+            .and_then(|value| u64::try_from(value).ok())
+            .unwrap_or_else(|| modified_secs(&level_path) * 1000);
+        worlds.push(WorldEntry {
+            name: level.name.unwrap_or_else(|| folder_name.clone()),
+            folder_name,
+            last_played,
+            game_version: level.version,
+            size: dir_size(&path),
+        });
+    }
+    worlds.sort_by_key(|world| std::cmp::Reverse(world.last_played));
+    Ok(worlds)
+}
+
+pub fn list_world_backups(game_dir: &str) -> Result<Vec<WorldBackup>, LibraryError> {
+    let dir = backups_dir(game_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut backups = Vec::new();
+    let entries =
+        fs::read_dir(&dir).map_err(|err| format!("Failed to read world backups dir: {err}"))?;
+    for entry in entries {
+        let entry = entry.map_err(|err| format!("Failed to read world backups entry: {err}"))?;
+        let path = entry.path();
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_file() || !file_name.to_ascii_lowercase().ends_with(".zip") {
+            continue;
+        }
+        backups.push(world_backup_entry(&path, file_name)?);
+    }
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created));
+    Ok(backups)
+}
+
+/// Zips `saves/<world>` into the backups dir as `<world>-<unix ms>.zip`, with
+/// every entry under the world's folder name.
+pub fn backup_world(game_dir: &str, world: &str) -> Result<WorldBackup, LibraryError> {
+    let world_dir = paths::safe_join(&saves_dir(game_dir), world)?;
+    if !world_dir.join("level.dat").is_file() {
+        return Err(format!("World {world} not found.").into());
+    }
+    let folder_name = world_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid world name: {world}"))?;
+
+    let dir = backups_dir(game_dir);
+    ensure_dir(&dir)?;
+    let file_name = format!("{folder_name}-{}.zip", unix_millis()?);
+    let path = dir.join(&file_name);
+    let partial = dir.join(format!("{file_name}.partial"));
+
+    let result = write_world_zip(&world_dir, &folder_name, &partial);
+    if let Err(err) = result {
+        let _ = fs::remove_file(&partial);
+        return Err(err);
+    }
+    fs::rename(&partial, &path).map_err(|err| format!("Failed to save world backup: {err}"))?;
+    world_backup_entry(&path, file_name)
+}
+
+/// Extracts a backup from the backups dir into `saves`. An existing world with
+/// the same folder name is left alone; the backup lands in a new folder.
+/// Returns the folder the world was restored to.
+pub fn restore_world(game_dir: &str, backup: &str) -> Result<String, LibraryError> {
+    let zip_path = paths::safe_join(&backups_dir(game_dir), backup)?;
+    if !zip_path.is_file() {
+        return Err(format!("World backup {backup} not found.").into());
+    }
+    let file = File::open(&zip_path).map_err(|err| format!("Failed to open backup: {err}"))?;
+    let mut archive =
+        ZipArchive::new(file).map_err(|err| format!("Failed to read backup: {err}"))?;
+
+    let folder_name = backup_folder_name(&mut archive)?;
+    let saves = saves_dir(game_dir);
+    ensure_dir(&saves)?;
+    let mut target_name = folder_name.clone();
+    if saves.join(&target_name).exists() {
+        target_name = format!("{folder_name}-restored-{}", unix_millis()?);
+    }
+    let target = paths::safe_join(&saves, &target_name)?;
+    let staging = paths::safe_join(&saves, &format!(".{target_name}.restoring"))?;
+    if staging.exists() {
+        fs::remove_dir_all(&staging)
+            .map_err(|err| format!("Failed to clear {}: {err}", staging.display()))?;
+    }
+
+    if let Err(err) = extract_world(&mut archive, &folder_name, &staging) {
+        let _ = fs::remove_dir_all(&staging);
+        return Err(err);
+    }
+    fs::rename(&staging, &target).map_err(|err| format!("Failed to restore world: {err}"))?;
+    Ok(target_name)
+}
+
+fn write_world_zip(world_dir: &Path, folder_name: &str, path: &Path) -> Result<(), LibraryError> {
+    let file = File::create(path).map_err(|err| format!("Failed to create backup: {err}"))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut pending = vec![(world_dir.to_path_buf(), folder_name.to_string())];
+    while let Some((dir, prefix)) = pending.pop() {
+        zip.add_directory(format!("{prefix}/"), options)
+            .map_err(|err| format!("Failed to write backup: {err}"))?;
+        let entries = fs::read_dir(&dir).map_err(|err| format!("Failed to read world: {err}"))?;
+        for entry in entries {
+            let entry = entry.map_err(|err| format!("Failed to read world entry: {err}"))?;
+            let kind = entry
+                .file_type()
+                .map_err(|err| format!("Failed to read world entry: {err}"))?;
+            let name = format!("{prefix}/{}", entry.file_name().to_string_lossy());
+            if kind.is_dir() {
+                pending.push((entry.path(), name));
+            } else if kind.is_file() {
+                // The game holds session.lock open while the world is loaded.
+                if entry.file_name() == "session.lock" {
+                    continue;
+                }
+                zip.start_file(&name, options)
+                    .map_err(|err| format!("Failed to write backup: {err}"))?;
+                let mut source = File::open(entry.path())
+                    .map_err(|err| format!("Failed to read {name}: {err}"))?;
+                io::copy(&mut source, &mut zip)
+                    .map_err(|err| format!("Failed to write backup: {err}"))?;
+            }
+        }
+    }
+    let mut file = zip
+        .finish()
+        .map_err(|err| format!("Failed to write backup: {err}"))?;
+    file.flush()?;
+    Ok(())
+}
+
+// Backups hold one top-level folder; anything else is not a world backup.
+fn backup_folder_name(archive: &mut ZipArchive<File>) -> Result<String, LibraryError> {
+    let mut folder = None;
+    for index in 0..archive.len() {
+        let entry = archive
+            .by_index(index)
+            .map_err(|err| format!("Failed to read backup: {err}"))?;
+        let path = entry_path(entry.name())?;
+        let Some(first) = path.components().next() else {
+            continue;
+        };
+        let first = first.as_os_str().to_string_lossy().to_string();
+        match &folder {
+            None => folder = Some(first),
+            Some(existing) if *existing == first => {}
+            Some(_) => {
+                return Err("Backup does not contain a single world folder."
+                    .to_string()
+                    .into())
+            }
+        }
+    }
+    folder.ok_or_else(|| "Backup is empty.".to_string().into())
+}
+
+fn extract_world(
+    archive: &mut ZipArchive<File>,
+    folder_name: &str,
+    destination: &Path,
+) -> Result<(), LibraryError> {
+    ensure_dir(destination)?;
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|err| format!("Failed to read backup: {err}"))?;
+        let path = entry_path(entry.name())?;
+        let Ok(relative) = path.strip_prefix(folder_name) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        let out_path = paths::safe_join(destination, &relative.to_string_lossy())?;
+        if entry.is_dir() {
+            ensure_dir(&out_path)?;
+            continue;
+        }
+        if let Some(parent) = out_path.parent() {
+            ensure_dir(parent)?;
+        }
+        let mut out =
+            File::create(&out_path).map_err(|err| format!("Failed to restore world: {err}"))?;
+        io::copy(&mut entry, &mut out).map_err(|err| format!("Failed to restore world: {err}"))?;
+    }
+    Ok(())
+}
+
+// The zip crate quietly drops leading `/` and resolves `..` in entry names;
+// backups with such entries are rejected instead of being reinterpreted.
+fn entry_path(name: &str) -> Result<PathBuf, LibraryError> {
+    let escapes = name.starts_with(['/', '\\'])
+        || name.contains(':')
+        || name.split(['/', '\\']).any(|part| part == "..");
+    if escapes {
+        return Err(format!("Backup entry escapes its folder: {name}").into());
+    }
+    Ok(name
+        .split(['/', '\\'])
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect())
+}
+
+fn world_backup_entry(path: &Path, file_name: String) -> Result<WorldBackup, LibraryError> {
+    let metadata =
+        fs::metadata(path).map_err(|err| format!("Failed to read backup metadata: {err}"))?;
+    Ok(WorldBackup {
+        file_name,
+        size: metadata.len(),
+        created: modified_secs(path),
+    })
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => dir_size(&entry.path()),
+            Ok(kind) if kind.is_file() => entry.metadata().map(|meta| meta.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+fn modified_secs(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+fn unix_millis() -> Result<u128, LibraryError> {
+    Ok(SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| format!("Failed to read system clock: {err}"))?
+        .as_millis())
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct LevelInfo {
+    name: Option<String>,
+    last_played_ms: Option<i64>,
+    version: Option<String>,
+}
+
+fn read_level_dat(path: &Path) -> Result<LevelInfo, String> {
+    let file = File::open(path).map_err(|err| err.to_string())?;
+    let mut bytes = Vec::new();
+    GzDecoder::new(file)
+        .read_to_end(&mut bytes)
+        .map_err(|err| err.to_string())?;
+    parse_level_nbt(&bytes)
+}
+
+const TAG_END: u8 = 0;
+const TAG_LONG: u8 = 4;
+const TAG_STRING: u8 = 8;
+const TAG_COMPOUND: u8 = 10;
+
+/// Reads `Data.LevelName`, `Data.LastPlayed` and `Data.Version.Name` from an
+/// uncompressed `level.dat` NBT tree, skipping every other tag.
+fn parse_level_nbt(bytes: &[u8]) -> Result<LevelInfo, String> {
+    let mut reader = NbtReader { bytes, pos: 0 };
+    if reader.u8()? != TAG_COMPOUND {
+        return Err("level.dat does not start with a compound".to_string());
+    }
+    reader.string()?;
+
+    let mut info = LevelInfo::default();
+    loop {
+        let tag = reader.u8()?;
+        if tag == TAG_END {
+            break;
+        }
+        let name = reader.string()?;
+        if tag != TAG_COMPOUND || name != "Data" {
+            reader.skip(tag)?;
+            continue;
+        }
+        loop {
+            let tag = reader.u8()?;
+            if tag == TAG_END {
+                break;
+            }
+            match (tag, reader.string()?.as_str()) {
+                (TAG_STRING, "LevelName") => info.name = Some(reader.string()?),
+                (TAG_LONG, "LastPlayed") => info.last_played_ms = Some(reader.i64()?),
+                (TAG_COMPOUND, "Version") => loop {
+                    let tag = reader.u8()?;
+                    if tag == TAG_END {
+                        break;
+                    }
+                    match (tag, reader.string()?.as_str()) {
+                        (TAG_STRING, "Name") => info.version = Some(reader.string()?),
+                        _ => reader.skip(tag)?,
+                    }
+                },
+                _ => reader.skip(tag)?,
+            }
+        }
+    }
+    Ok(info)
+}
+
+struct NbtReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl NbtReader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| "level.dat is truncated".to_string())?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i32(&mut self) -> Result<i32, String> {
+        let bytes = self.take(4)?;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn i64(&mut self) -> Result<i64, String> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(i64::from_be_bytes(buf))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let len = self.take(2)?;
+        let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+        Ok(String::from_utf8_lossy(self.take(len)?).to_string())
+    }
+
+    fn length(&mut self) -> Result<usize, String> {
+        usize::try_from(self.i32()?).map_err(|_| "negative NBT length".to_string())
+    }
+
+    fn skip(&mut self, tag: u8) -> Result<(), String> {
+        match tag {
+            1 => self.take(1).map(drop),
+            2 => self.take(2).map(drop),
+            3 | 5 => self.take(4).map(drop),
+            4 | 6 => self.take(8).map(drop),
+            7 => {
+                let len = self.length()?;
+                self.take(len).map(drop)
+            }
+            8 => self.string().map(drop),
+            9 => {
+                let item = self.u8()?;
+                for _ in 0..self.length()? {
+                    self.skip(item)?;
+                }
+                Ok(())
+            }
+            10 => loop {
+                let tag = self.u8()?;
+                if tag == TAG_END {
+                    return Ok(());
+                }
+                self.string()?;
+                self.skip(tag)?;
+            },
+            11 => {
+                let len = self.length()?;
+                self.take(len.saturating_mul(4)).map(drop)
+            }
+            12 => {
+                let len = self.length()?;
+                self.take(len.saturating_mul(8)).map(drop)
+            }
+            other => Err(format!("unknown NBT tag {other}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unique_temp_dir(prefix: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        std::env::temp_dir().join(format!("atlas-worlds-{prefix}-{nanos}"))
+    }
+
+    fn write_zip(path: &Path, entries: &[(&str, &[u8])]) -> ZipArchive<File> {
+        let mut zip = ZipWriter::new(File::create(path).expect("create zip"));
+        for (name, contents) in entries {
+            zip.start_file(*name, SimpleFileOptions::default())
+                .expect("start zip entry");
+            zip.write_all(contents).expect("write zip entry");
+        }
+        zip.finish().expect("finish zip");
+        ZipArchive::new(File::open(path).expect("open zip")).expect("read zip")
+    }
+
+    fn named(tag: u8, name: &str) -> Vec<u8> {
+        let mut out = vec![tag];
+        out.extend((name.len() as u16).to_be_bytes());
+        out.extend(name.as_bytes());
+        out
+    }
+
+    fn string(value: &str) -> Vec<u8> {
+        let mut out = (value.len() as u16).to_be_bytes().to_vec();
+        out.extend(value.as_bytes());
+        out
+    }
+
+    #[test]
+    fn reads_level_name_last_played_and_version() {
+        let mut nbt = named(TAG_COMPOUND, "");
+        nbt.extend(named(TAG_COMPOUND, "Data"));
+        nbt.extend(named(9, "ServerBrands"));
+        nbt.push(TAG_STRING);
+        nbt.extend(1i32.to_be_bytes());
+        nbt.extend(string("fabric"));
+        nbt.extend(named(TAG_LONG, "LastPlayed"));
+        nbt.extend(1_700_000_000_000i64.to_be_bytes());
+        nbt.extend(named(TAG_COMPOUND, "Version"));
+        nbt.extend(named(3, "Id"));
+        nbt.extend(3465i32.to_be_bytes());
+        nbt.extend(named(TAG_STRING, "Name"));
+        nbt.extend(string("1.20.1"));
+        nbt.push(TAG_END);
+        nbt.extend(named(TAG_STRING, "LevelName"));
+        nbt.extend(string("My World"));
+        nbt.push(TAG_END);
+        nbt.push(TAG_END);
+
+        assert_eq!(
+            parse_level_nbt(&nbt).expect("parse level.dat"),
+            LevelInfo {
+                name: Some("My World".to_string()),
+                last_played_ms: Some(1_700_000_000_000),
+                version: Some("1.20.1".to_string()),
+            }
+        );
+        assert!(parse_level_nbt(&nbt[..nbt.len() - 4]).is_err());
+    }
+
+    #[test]
+    fn backup_and_restore_round_trip() {
+        let instance = unique_temp_dir("round-trip");
+        let world = instance.join(".minecraft/saves/World1");
+        fs::create_dir_all(world.join("region")).expect("create world");
+        fs::write(world.join("level.dat"), b"level").expect("write level.dat");
+        fs::write(world.join("region/r.0.0.mca"), vec![7u8; 4096]).expect("write region");
+        fs::write(world.join("session.lock"), b"lock").expect("write session.lock");
+        let game_dir = instance.to_string_lossy().to_string();
+
+        let backup = backup_world(&game_dir, "World1").expect("backup world");
+        assert!(backup.file_name.starts_with("World1-"));
+        assert_eq!(
+            list_world_backups(&game_dir).expect("list backups")[0].file_name,
+            backup.file_name
+        );
+
+        let restored = restore_world(&game_dir, &backup.file_name).expect("restore world");
+        assert!(restored.starts_with("World1-restored-"));
+        let restored_dir = instance.join(".minecraft/saves").join(&restored);
+        assert_eq!(fs::read(restored_dir.join("level.dat")).unwrap(), b"level");
+        assert_eq!(
+            fs::read(restored_dir.join("region/r.0.0.mca")).unwrap(),
+            vec![7u8; 4096]
+        );
+        assert!(!restored_dir.join("session.lock").exists());
+        assert!(backup_world(&game_dir, "../World1").is_err());
+        assert!(restore_world(&game_dir, "../../backup.zip").is_err());
+
+        let _ = fs::remove_dir_all(instance);
+    }
+
+    #[test]
+    fn extract_rejects_entries_outside_the_target() {
+        let temp = unique_temp_dir("extract");
+        fs::create_dir_all(&temp).expect("create temp dir");
+        let destination = temp.join("restored");
+
+        for name in ["World/../../evil.txt", "/evil.txt"] {
+            let mut archive = write_zip(
+                &temp.join("escape.zip"),
+                &[("World/level.dat", b"level"), (name, b"evil")],
+            );
+            assert!(extract_world(&mut archive, "World", &destination).is_err());
+            assert!(!temp.join("evil.txt").exists());
+        }
+
+        let _ = fs::remove_dir_all(temp);
+    }
+
+    #[test]
+    fn backups_must_hold_one_world_folder() {
+        let temp = unique_temp_dir("folder-name");
+        fs::create_dir_all(&temp).expect("create temp dir");
+        let zip_path = temp.join("backup.zip");
+
+        let mut archive = write_zip(
+            &zip_path,
+            &[("World/level.dat", b"level"), ("World/data/raids.dat", b"")],
+        );
+        assert_eq!(backup_folder_name(&mut archive).unwrap(), "World");
+
+        let mut archive = write_zip(
+            &zip_path,
+            &[("World/level.dat", b"level"), ("Other/level.dat", b"level")],
+        );
+        assert!(backup_folder_name(&mut archive).is_err());
+
+        let mut archive = write_zip(&zip_path, &[("../World/level.dat", b"level")]);
+        assert!(backup_folder_name(&mut archive).is_err());
+
+        let mut archive = write_zip(&zip_path, &[]);
+        assert!(backup_folder_name(&mut archive).is_err());
+
+        let _ = fs::remove_dir_all(temp);
+    }
+
+    #[test]
+    fn uninstall_keeping_saves_keeps_world_backups() {
+        let root = unique_temp_dir("uninstall");
+        let instance = root.join("instances/pack");
+        let minecraft = instance.join(".minecraft");
+        fs::create_dir_all(minecraft.join("saves/World1")).expect("create saves");
+        fs::create_dir_all(minecraft.join(WORLD_BACKUPS_DIR)).expect("create backups");
+        fs::create_dir_all(minecraft.join("mods")).expect("create mods");
+        fs::write(minecraft.join("saves/World1/level.dat"), b"level").expect("write world");
+        fs::write(
+            minecraft.join(WORLD_BACKUPS_DIR).join("World1-1.zip"),
+            b"zip",
+        )
+        .expect("write backup");
+        fs::write(minecraft.join("mods/a.jar"), b"jar").expect("write mod");
+
+        super::super::uninstall_instance_data(&instance.to_string_lossy(), true)
+            .expect("uninstall");
+
+        assert!(minecraft.join("saves/World1/level.dat").is_file());
+        assert!(minecraft
+            .join(WORLD_BACKUPS_DIR)
+            .join("World1-1.zip")
+            .is_file());
+        assert!(!minecraft.join("mods").exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -19,6 +19,11 @@ mod telemetry;
 use crate::state::AppState;
 
 fn main() {
+    let settings = settings::load_settings().unwrap_or_default();
+    telemetry::init_tracing(settings.launcher_log_level.as_deref());
+    net::http::configure_timeouts(config::resolve_http_timeouts(&settings));
+    paths::set_game_dir_override(settings.game_dir_override.as_deref());
+
     tauri::Builder::default()
         .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
             use tauri::Manager;
@@ -67,7 +72,10 @@ fn main() {
         })
         .invoke_handler(tauri::generate_handler![
             commands::settings::get_default_game_dir,
+            commands::settings::get_storage_error,
             commands::settings::get_system_memory_mb,
+            commands::settings::probe_java,
+            commands::settings::discover_java_runtimes,
             commands::library::get_version_manifest_summary,
             commands::library::get_fabric_loader_versions,
             commands::library::get_neoforge_loader_versions,
@@ -75,6 +83,13 @@ fn main() {
             commands::library::list_mods,
             commands::library::set_mod_enabled,
             commands::library::delete_mod,
+            commands::library::list_instance_screenshots,
+            commands::library::open_screenshot,
+            commands::library::delete_screenshot,
+            commands::library::list_worlds,
+            commands::library::list_world_backups,
+            commands::library::backup_world,
+            commands::library::restore_world,
             commands::library::uninstall_instance_data,
             commands::library::resolve_pack_mod,
             commands::library::list_atlas_remote_packs,
@@ -92,6 +107,8 @@ fn main() {
             commands::auth::complete_atlas_device_code,
             commands::launcher::launch_minecraft,
             commands::launcher::download_minecraft_files,
+            commands::launcher::get_launch_history,
+            commands::launcher::get_instance_stats,
             commands::auth::restore_session,
             commands::auth::restore_atlas_session,
             commands::auth::sign_out,
@@ -104,6 +121,7 @@ fn main() {
             commands::diagnostics::run_troubleshooter,
             commands::diagnostics::apply_fix,
             commands::diagnostics::repair_installation,
+            commands::diagnostics::validate_installed_version,
             commands::diagnostics::create_support_bundle,
             commands::restart::restart_app
         ])
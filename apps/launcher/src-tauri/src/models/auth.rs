@@ -31,6 +31,9 @@ impl From<atlas_client::device_code::DeviceCodeResponse> for DeviceCodeResponse
 pub struct Profile {
     pub id: String,
     pub name: String,
+    /// The account does not own Java Edition and can only launch the demo.
+    #[serde(default)]
+    pub demo: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
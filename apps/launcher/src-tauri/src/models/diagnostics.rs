@@ -29,6 +29,7 @@ pub enum FixAction {
     SetSafeMemory,
     ResyncPack,
     RepairRuntime,
+    RepairVersion,
     FullRepair,
 }
 
@@ -57,6 +58,14 @@ pub struct FixResult {
     pub message: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionValidation {
+    pub id: String,
+    pub problems: Vec<String>,
+    pub repaired: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct RepairResult {
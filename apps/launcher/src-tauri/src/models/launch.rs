@@ -5,6 +5,8 @@ use super::settings::{default_memory_mb, ModLoaderConfig};
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct LaunchOptions {
+    #[serde(default)]
+    pub instance_id: Option<String>,
     #[serde(default)]
     pub game_dir: String,
     #[serde(default)]
@@ -17,6 +19,33 @@ pub struct LaunchOptions {
     pub version: Option<String>,
     #[serde(default)]
     pub loader: ModLoaderConfig,
+    #[serde(default)]
+    pub width: Option<u32>,
+    #[serde(default)]
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub fullscreen: bool,
+    #[serde(default)]
+    pub demo: bool,
+    #[serde(default)]
+    pub quick_play: Option<QuickPlay>,
+    #[serde(default)]
+    pub shared_storage: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum QuickPlayKind {
+    Singleplayer,
+    Multiplayer,
+}
+
+/// Joins a world (folder name under `saves`) or a server (`host[:port]`) on start.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickPlay {
+    pub kind: QuickPlayKind,
+    pub target: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -30,3 +59,46 @@ pub struct LaunchEvent {
     #[serde(default)]
     pub percent: Option<u64>,
 }
+
+/// Play activity for one instance. Only time with the game window on-screen
+/// counts as playtime.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceStats {
+    #[serde(default)]
+    pub last_played_unix: Option<u64>,
+    #[serde(default)]
+    pub total_playtime_seconds: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LaunchOutcome {
+    WindowDetected,
+    Crashed,
+    TimedOut,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchRecord {
+    #[serde(default)]
+    pub instance_id: Option<String>,
+    pub version: String,
+    pub started_at_ms: u64,
+    pub outcome: LaunchOutcome,
+    #[serde(default)]
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JavaInfo {
+    pub path: String,
+    pub version: String,
+    pub major: u32,
+    pub vendor: Option<String>,
+    pub arch: Option<String>,
+    pub is_64bit: bool,
+}
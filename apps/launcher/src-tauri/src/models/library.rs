@@ -31,6 +31,38 @@ pub struct ModEntry {
     pub modified: u64,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldEntry {
+    pub folder_name: String,
+    pub name: String,
+    /// Unix milliseconds, from `level.dat` or its mtime.
+    pub last_played: u64,
+    #[serde(default)]
+    pub game_version: Option<String>,
+    pub size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WorldBackup {
+    pub file_name: String,
+    pub size: u64,
+    pub created: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotEntry {
+    pub file_name: String,
+    pub path: String,
+    pub size: u64,
+    pub modified: u64,
+    /// `data:image/png;base64,...` when previews were requested and the file is small.
+    #[serde(default)]
+    pub preview: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AtlasRemotePack {
@@ -10,11 +10,16 @@ pub use auth::{
 };
 pub use diagnostics::{
     FixAction, FixResult, LaunchReadinessReport, ReadinessItem, RepairResult, SupportBundleResult,
-    TroubleshooterFinding, TroubleshooterReport,
+    TroubleshooterFinding, TroubleshooterReport, VersionValidation,
+};
+pub use launch::{
+    InstanceStats, JavaInfo, LaunchEvent, LaunchOptions, LaunchOutcome, LaunchRecord, QuickPlay,
+    QuickPlayKind,
 };
-pub use launch::{LaunchEvent, LaunchOptions};
 pub use library::{
-    AtlasPackSyncResult, AtlasRemotePack, FabricLoaderVersion, ModEntry, VersionManifestSummary,
-    VersionSummary,
+    AtlasPackSyncResult, AtlasRemotePack, FabricLoaderVersion, ModEntry, ScreenshotEntry,
+    VersionManifestSummary, VersionSummary, WorldBackup, WorldEntry,
+};
+pub use settings::{
+    AppSettings, InstanceSource, ModLoaderConfig, ModLoaderKind, PostLaunchBehavior,
 };
-pub use settings::{AppSettings, InstanceSource, ModLoaderConfig, ModLoaderKind};
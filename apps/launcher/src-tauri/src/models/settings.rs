@@ -27,6 +27,18 @@ pub struct AppSettings {
     pub first_launch_notice_dismissed_at: Option<String>,
     #[serde(default)]
     pub default_memory_profile_v1_applied: bool,
+    #[serde(default)]
+    pub launcher_log_level: Option<String>,
+    #[serde(default)]
+    pub post_launch_behavior: PostLaunchBehavior,
+    #[serde(default)]
+    pub shared_game_storage: bool,
+    #[serde(default)]
+    pub network_connect_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub network_read_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub game_dir_override: Option<String>,
 }
 
 impl Default for AppSettings {
@@ -44,10 +56,32 @@ impl Default for AppSettings {
             first_launch_completed_at: None,
             first_launch_notice_dismissed_at: None,
             default_memory_profile_v1_applied: false,
+            launcher_log_level: None,
+            post_launch_behavior: PostLaunchBehavior::default(),
+            shared_game_storage: false,
+            network_connect_timeout_secs: None,
+            network_read_timeout_secs: None,
+            game_dir_override: None,
         }
     }
 }
 
+/// What happens to the launcher window once the Minecraft window appears.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PostLaunchBehavior {
+    KeepOpen,
+    Minimize,
+    Hide,
+    Close,
+}
+
+impl Default for PostLaunchBehavior {
+    fn default() -> Self {
+        PostLaunchBehavior::KeepOpen
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OnboardingIntent {
@@ -110,6 +144,10 @@ pub struct InstanceConfig {
     pub source: InstanceSource,
     #[serde(default)]
     pub atlas_pack: Option<AtlasPackLink>,
+    #[serde(default)]
+    pub last_played_unix: Option<u64>,
+    #[serde(default)]
+    pub total_playtime_seconds: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -137,6 +175,29 @@ pub struct AtlasPackLink {
     pub build_version: Option<String>,
     #[serde(default)]
     pub artifact_key: Option<String>,
+    #[serde(default)]
+    pub metadata: Option<AtlasPackMetadata>,
+}
+
+/// Pack details from the last successful hub listing or sync, so instance
+/// cards can render without a network round trip.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AtlasPackMetadata {
+    #[serde(default)]
+    pub pack_name: Option<String>,
+    #[serde(default)]
+    pub channel: Option<String>,
+    #[serde(default)]
+    pub build_version: Option<String>,
+    #[serde(default)]
+    pub minecraft_version: Option<String>,
+    #[serde(default)]
+    pub modloader: Option<String>,
+    #[serde(default)]
+    pub modloader_version: Option<String>,
+    #[serde(default)]
+    pub updated_at: Option<String>,
 }
 
 pub fn default_memory_mb() -> u32 {
@@ -3,13 +3,48 @@ use reqwest::Client;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::sync::OnceLock;
+use std::time::Duration;
 
 use super::errors::HttpError;
 
 static CLIENT: OnceLock<Client> = OnceLock::new();
+static TIMEOUTS: OnceLock<HttpTimeouts> = OnceLock::new();
+
+/// Connect and per-read timeouts for the shared client. The read timeout resets
+/// on every chunk, so slow but steady downloads are unaffected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpTimeouts {
+    pub connect: Duration,
+    pub read: Duration,
+}
+
+impl Default for HttpTimeouts {
+    fn default() -> Self {
+        Self {
+            connect: Duration::from_secs(10),
+            read: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Must run before the first `shared_client()` call; later calls are ignored.
+pub fn configure_timeouts(timeouts: HttpTimeouts) {
+    let _ = TIMEOUTS.set(timeouts);
+}
+
+fn configured_timeouts() -> HttpTimeouts {
+    TIMEOUTS.get().copied().unwrap_or_default()
+}
 
 pub fn shared_client() -> &'static Client {
-    CLIENT.get_or_init(|| Client::new())
+    CLIENT.get_or_init(|| {
+        let timeouts = configured_timeouts();
+        Client::builder()
+            .connect_timeout(timeouts.connect)
+            .read_timeout(timeouts.read)
+            .build()
+            .unwrap_or_default()
+    })
 }
 
 #[async_trait]
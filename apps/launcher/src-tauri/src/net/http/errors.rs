@@ -3,6 +3,12 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum HttpError {
+    #[error("Request to {url} timed out after {attempts} attempt(s): {source}")]
+    Timeout {
+        url: String,
+        attempts: usize,
+        source: reqwest::Error,
+    },
     #[error("Request failed: {0}")]
     Request(#[from] reqwest::Error),
     #[error("Request failed ({status}): {body}")]
@@ -3,12 +3,10 @@ use serde::de::DeserializeOwned;
 
 use super::client::shared_client;
 use super::errors::HttpError;
-use super::retry::get_with_retries;
+use super::retry::get_text_with_retries;
 
 pub async fn fetch_json<T: DeserializeOwned>(client: &Client, url: &str) -> Result<T, HttpError> {
-    let response = get_with_retries(client, url).await?;
-
-    let body = response.text().await.map_err(HttpError::Request)?;
+    let body = get_text_with_retries(client, url).await?;
     serde_json::from_str::<T>(&body).map_err(|err| HttpError::Parse { source: err, body })
 }
 
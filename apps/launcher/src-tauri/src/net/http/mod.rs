@@ -4,7 +4,7 @@ pub mod json;
 pub mod retry;
 pub mod text;
 
-pub use client::{shared_client, HttpClient, ReqwestHttpClient};
+pub use client::{configure_timeouts, shared_client, HttpClient, HttpTimeouts, ReqwestHttpClient};
 pub use errors::HttpError;
 pub use json::{fetch_json, fetch_json_shared};
 pub use text::fetch_text;
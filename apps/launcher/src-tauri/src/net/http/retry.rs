@@ -29,7 +29,7 @@ pub async fn get_with_retries(client: &Client, url: &str) -> Result<reqwest::Res
                     backoff = (backoff * 2).min(Duration::from_secs(3));
                     continue;
                 }
-                return Err(HttpError::Request(err));
+                return Err(request_error(url, attempt + 1, err));
             }
         }
     }
@@ -40,6 +40,43 @@ pub async fn get_with_retries(client: &Client, url: &str) -> Result<reqwest::Res
     })
 }
 
+/// Like `get_with_retries`, but also retries when the body stalls or drops
+/// mid-read, which the send alone does not cover.
+pub async fn get_text_with_retries(client: &Client, url: &str) -> Result<String, HttpError> {
+    let mut backoff = Duration::from_millis(250);
+    for attempt in 0..=HTTP_MAX_RETRIES {
+        let response = get_with_retries(client, url).await?;
+        match response.text().await {
+            Ok(body) => return Ok(body),
+            Err(err) => {
+                if retryable_body_error(&err) && attempt < HTTP_MAX_RETRIES {
+                    sleep(with_jitter(backoff)).await;
+                    backoff = (backoff * 2).min(Duration::from_secs(3));
+                    continue;
+                }
+                return Err(request_error(url, attempt + 1, err));
+            }
+        }
+    }
+
+    Err(HttpError::Status {
+        status: StatusCode::REQUEST_TIMEOUT,
+        body: "Request failed after retries.".to_string(),
+    })
+}
+
+fn request_error(url: &str, attempts: usize, err: reqwest::Error) -> HttpError {
+    if err.is_timeout() {
+        HttpError::Timeout {
+            url: url.to_string(),
+            attempts,
+            source: err,
+        }
+    } else {
+        HttpError::Request(err)
+    }
+}
+
 fn retryable_status(status: StatusCode) -> bool {
     status == StatusCode::REQUEST_TIMEOUT
         || status == StatusCode::TOO_MANY_REQUESTS
@@ -50,6 +87,10 @@ fn retryable_error(err: &reqwest::Error) -> bool {
     err.is_timeout() || err.is_connect() || err.is_request()
 }
 
+fn retryable_body_error(err: &reqwest::Error) -> bool {
+    err.is_timeout() || err.is_body() || err.is_decode()
+}
+
 fn with_jitter(base: Duration) -> Duration {
     let nanos = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -1,15 +1,100 @@
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::RwLock;
 
-pub fn default_game_dir() -> PathBuf {
-    if let Some(data) = dirs::data_dir() {
-        return data.join("atlas").join("game");
+const DATA_DIR_ENV: &str = "ATLAS_DATA_DIR";
+const GAME_DIR_ENV: &str = "ATLAS_GAME_DIR";
+
+static GAME_DIR_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+#[derive(Debug, Clone)]
+pub struct BaseDirs {
+    pub data_dir: PathBuf,
+    pub game_dir: PathBuf,
+}
+
+/// Where the launcher keeps its own files (settings, auth, logs) and games.
+/// `ATLAS_DATA_DIR`/`ATLAS_GAME_DIR` win over the settings game dir override,
+/// which wins over the platform data directory. Relative values resolve against
+/// the launcher executable so a portable install can carry its data along.
+pub fn resolve_base_dirs() -> Result<BaseDirs, String> {
+    let data_dir = env_dir(DATA_DIR_ENV)
+        .or_else(platform_data_dir)
+        .ok_or_else(|| "Unable to resolve a writable data directory".to_string())?;
+    let game_dir = env_dir(GAME_DIR_ENV)
+        .or_else(|| {
+            GAME_DIR_OVERRIDE
+                .read()
+                .ok()
+                .and_then(|value| value.clone())
+        })
+        .unwrap_or_else(|| data_dir.join("game"));
+    Ok(BaseDirs { data_dir, game_dir })
+}
+
+pub fn set_game_dir_override(value: Option<&str>) {
+    let value = value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(absolute_from_exe);
+    if let Ok(mut guard) = GAME_DIR_OVERRIDE.write() {
+        *guard = value;
     }
+}
 
-    if let Some(home) = dirs::home_dir() {
-        return home.join(".atlas").join("game");
+/// Creates both base directories and checks that a file can be written to each.
+pub fn validate_base_dirs() -> Result<BaseDirs, String> {
+    let dirs = resolve_base_dirs()?;
+    for (label, path, env) in [
+        ("Data directory", &dirs.data_dir, DATA_DIR_ENV),
+        ("Game directory", &dirs.game_dir, GAME_DIR_ENV),
+    ] {
+        let probe = path.join(".atlas-write-test");
+        let result = ensure_dir(path).and_then(|_| {
+            std::fs::write(&probe, b"ok").map_err(|err| err.to_string())?;
+            std::fs::remove_file(&probe).map_err(|err| err.to_string())
+        });
+        if let Err(err) = result {
+            return Err(format!(
+                "{label} {} is not writable ({err}). Set {env} to a writable folder.",
+                path.display()
+            ));
+        }
     }
+    Ok(dirs)
+}
 
-    PathBuf::from("atlas-game")
+fn env_dir(name: &str) -> Option<PathBuf> {
+    let value = std::env::var(name).ok()?;
+    let value = value.trim();
+    (!value.is_empty()).then(|| absolute_from_exe(value))
+}
+
+fn absolute_from_exe(value: &str) -> PathBuf {
+    let path = PathBuf::from(value);
+    if path.is_absolute() {
+        return path;
+    }
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(&path)))
+        .unwrap_or(path)
+}
+
+fn platform_data_dir() -> Option<PathBuf> {
+    if let Some(data) = dirs::data_dir() {
+        return Some(data.join("atlas"));
+    }
+    dirs::home_dir().map(|home| home.join(".atlas"))
+}
+
+pub fn default_game_dir() -> PathBuf {
+    resolve_base_dirs()
+        .map(|dirs| dirs.game_dir)
+        .unwrap_or_else(|_| PathBuf::from("atlas-game"))
+}
+
+pub fn shared_game_storage_dir() -> PathBuf {
+    default_game_dir().join("shared")
 }
 
 pub fn ensure_dir(path: &Path) -> Result<(), String> {
@@ -29,13 +114,7 @@ pub fn normalize_path(path: &str) -> PathBuf {
 }
 
 pub fn auth_store_dir() -> Result<PathBuf, String> {
-    if let Some(base) = dirs::data_dir() {
-        return Ok(base.join("atlas"));
-    }
-    if let Some(home) = dirs::home_dir() {
-        return Ok(home.join(".atlas"));
-    }
-    Err("Unable to resolve a writable data directory".to_string())
+    resolve_base_dirs().map(|dirs| dirs.data_dir)
 }
 
 pub fn auth_store_path() -> Result<PathBuf, String> {
@@ -49,3 +128,89 @@ pub fn atlas_auth_store_path() -> Result<PathBuf, String> {
 pub fn settings_store_path() -> Result<PathBuf, String> {
     Ok(auth_store_dir()?.join("settings.json"))
 }
+
+/// Checks a user- or pack-provided relative path lexically: only plain
+/// components, no `..`, roots, drive letters (`C:`) or `:` streams.
+pub fn safe_relative_path(rel: &str) -> Result<PathBuf, String> {
+    let normalized = rel.trim().replace('\\', "/");
+    let mut out = PathBuf::new();
+    for component in Path::new(&normalized).components() {
+        match component {
+            Component::Normal(part) if !part.to_string_lossy().contains(':') => out.push(part),
+            Component::CurDir => {}
+            _ => return Err(format!("Path escapes its directory: {rel}")),
+        }
+    }
+    if out.as_os_str().is_empty() {
+        return Err(format!("Path is empty: {rel}"));
+    }
+    Ok(out)
+}
+
+/// Joins `rel` under `base`, rejecting anything `safe_relative_path` rejects
+/// and existing symlinks along the way that resolve outside `base`.
+pub fn safe_join(base: &Path, rel: &str) -> Result<PathBuf, String> {
+    let relative = safe_relative_path(rel)?;
+    let joined = base.join(&relative);
+    let Ok(canonical_base) = std::fs::canonicalize(base) else {
+        return Ok(joined);
+    };
+
+    let mut current = base.to_path_buf();
+    for component in relative.components() {
+        current.push(component);
+        if std::fs::symlink_metadata(&current).is_err() {
+            break;
+        }
+        let resolved = std::fs::canonicalize(&current)
+            .map_err(|err| format!("Failed to resolve {}: {err}", current.display()))?;
+        if !resolved.starts_with(&canonical_base) {
+            return Err(format!("Path escapes its directory: {rel}"));
+        }
+    }
+    Ok(joined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_join_rejects_parent_and_absolute_components() {
+        let base = Path::new("/games/instance");
+        assert!(safe_join(base, "../other/mods/a.jar").is_err());
+        assert!(safe_join(base, "mods/../../a.jar").is_err());
+        assert!(safe_join(base, "/etc/passwd").is_err());
+        assert!(safe_join(base, "\\\\server\\share\\a.jar").is_err());
+        assert!(safe_join(base, "").is_err());
+        assert_eq!(
+            safe_join(base, "./config\\sodium.json").unwrap(),
+            base.join("config").join("sodium.json")
+        );
+    }
+
+    #[test]
+    fn safe_join_rejects_windows_drive_letters_and_streams() {
+        let base = Path::new("/games/instance");
+        assert!(safe_join(base, "C:\\Windows\\system32").is_err());
+        assert!(safe_join(base, "C:evil.jar").is_err());
+        assert!(safe_join(base, "mods/a.jar:stream").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn safe_join_rejects_symlinks_that_leave_the_base() {
+        let root = std::env::temp_dir().join(format!("atlas-safe-join-{}", std::process::id()));
+        let base = root.join("instance");
+        let outside = root.join("outside");
+        std::fs::create_dir_all(base.join("mods")).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, base.join("config")).unwrap();
+        std::os::unix::fs::symlink(base.join("mods"), base.join("mods-link")).unwrap();
+
+        assert!(safe_join(&base, "config/options.txt").is_err());
+        assert!(safe_join(&base, "mods-link/a.jar").is_ok());
+        assert!(safe_join(&base, "mods/new/a.jar").is_ok());
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
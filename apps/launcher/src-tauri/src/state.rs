@@ -2,6 +2,7 @@ use std::sync::Mutex;
 
 use crate::auth::{AtlasPendingAuth, PendingAuth};
 use crate::models::{AppSettings, AtlasSession, AuthSession};
+use crate::paths;
 use crate::settings;
 
 pub struct AppState {
@@ -10,6 +11,7 @@ pub struct AppState {
     pub atlas_auth: Mutex<Option<AtlasSession>>,
     pub pending_atlas_auth: Mutex<Option<AtlasPendingAuth>>,
     pub settings: Mutex<AppSettings>,
+    pub storage_error: Option<String>,
 }
 
 impl Default for AppState {
@@ -21,6 +23,7 @@ impl Default for AppState {
             atlas_auth: Mutex::new(None),
             pending_atlas_auth: Mutex::new(None),
             settings: Mutex::new(settings),
+            storage_error: paths::validate_base_dirs().err(),
         }
     }
 }
@@ -1,10 +1,17 @@
 use crate::paths::{auth_store_dir, ensure_dir};
 use std::fs::OpenOptions;
-use std::io::Write;
+use std::io::{self, Write};
 use std::sync::{Mutex, OnceLock};
 use std::time::{SystemTime, UNIX_EPOCH};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, Registry};
+
+const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::INFO;
 
 static LOG_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
+static LEVEL_HANDLE: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
 
 fn log_mutex() -> &'static Mutex<()> {
     LOG_LOCK.get_or_init(|| Mutex::new(()))
@@ -21,31 +28,73 @@ fn log_path() -> Result<std::path::PathBuf, String> {
     Ok(auth_store_dir()?.join("launcher.log"))
 }
 
-fn append_line(level: &str, message: &str) {
-    let Ok(_guard) = log_mutex().lock() else {
-        return;
-    };
+fn append_bytes(bytes: &[u8]) -> io::Result<()> {
+    let _guard = log_mutex()
+        .lock()
+        .map_err(|_| io::Error::other("launcher log lock poisoned"))?;
 
-    let Ok(path) = log_path() else {
-        return;
-    };
+    let path = log_path().map_err(io::Error::other)?;
     if let Some(parent) = path.parent() {
-        if ensure_dir(parent).is_err() {
-            return;
-        }
+        ensure_dir(parent).map_err(io::Error::other)?;
     }
 
-    let Ok(mut file) = OpenOptions::new().create(true).append(true).open(&path) else {
-        return;
-    };
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?
+        .write_all(bytes)
+}
 
-    let _ = writeln!(
-        file,
-        "[{}] [{}] {}",
+fn append_line(level: &str, message: &str) {
+    let line = format!(
+        "[{}] [{}] {}\n",
         timestamp_seconds(),
         level.to_uppercase(),
         message
     );
+    let _ = append_bytes(line.as_bytes());
+}
+
+struct LogFileWriter;
+
+impl Write for LogFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        append_bytes(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Installs the structured `tracing` sink. Spans and events are appended to
+/// `launcher.log` alongside the plain lines written by `info`/`warn`/`error`.
+pub fn init_tracing(level: Option<&str>) {
+    let (filter, handle) = reload::Layer::new(parse_level(level));
+    let file_layer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_writer(|| LogFileWriter);
+    if tracing_subscriber::registry()
+        .with(filter)
+        .with(file_layer)
+        .try_init()
+        .is_ok()
+    {
+        let _ = LEVEL_HANDLE.set(handle);
+    }
+}
+
+pub fn set_log_level(level: Option<&str>) {
+    if let Some(handle) = LEVEL_HANDLE.get() {
+        let _ = handle.modify(|filter| *filter = parse_level(level));
+    }
+}
+
+fn parse_level(level: Option<&str>) -> LevelFilter {
+    level
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_LOG_LEVEL)
 }
 
 pub fn info(message: impl AsRef<str>) {
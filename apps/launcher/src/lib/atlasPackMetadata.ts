@@ -0,0 +1,73 @@
+import type { AtlasPackMetadata } from "@/types/settings";
+import type { AtlasPackSyncResult, AtlasRemotePack } from "@/types/library";
+import { formatLoaderKind } from "@/lib/utils";
+
+function clean(value: string | null | undefined): string | null {
+  return value?.trim() || null;
+}
+
+export function metadataFromRemotePack(remote: AtlasRemotePack): AtlasPackMetadata {
+  return {
+    packName: clean(remote.packName),
+    channel: clean(remote.channel),
+    buildVersion: clean(remote.buildVersion),
+    minecraftVersion: clean(remote.minecraftVersion),
+    modloader: clean(remote.modloader),
+    modloaderVersion: clean(remote.modloaderVersion),
+    updatedAt: new Date().toISOString()
+  };
+}
+
+// A sync result has no pack name, so the previous one is kept.
+export function metadataFromSyncResult(
+  result: AtlasPackSyncResult,
+  previous: AtlasPackMetadata | null | undefined
+): AtlasPackMetadata {
+  return {
+    packName: previous?.packName ?? null,
+    channel: clean(result.channel),
+    buildVersion: clean(result.buildVersion),
+    minecraftVersion: clean(result.minecraftVersion) ?? previous?.minecraftVersion ?? null,
+    modloader: clean(result.modloader) ?? previous?.modloader ?? null,
+    modloaderVersion: clean(result.modloaderVersion) ?? previous?.modloaderVersion ?? null,
+    updatedAt: new Date().toISOString()
+  };
+}
+
+export function normalizePackMetadata(
+  metadata: AtlasPackMetadata | null | undefined
+): AtlasPackMetadata | null {
+  if (!metadata) {
+    return null;
+  }
+  return {
+    packName: clean(metadata.packName),
+    channel: clean(metadata.channel),
+    buildVersion: clean(metadata.buildVersion),
+    minecraftVersion: clean(metadata.minecraftVersion),
+    modloader: clean(metadata.modloader),
+    modloaderVersion: clean(metadata.modloaderVersion),
+    updatedAt: clean(metadata.updatedAt)
+  };
+}
+
+// e.g. "Fabric 1.20.1 · Build 1.4.0"
+export function formatPackMetadata(metadata: AtlasPackMetadata | null | undefined): string | null {
+  if (!metadata) {
+    return null;
+  }
+  const parts: string[] = [];
+  const runtime = [
+    metadata.modloader ? formatLoaderKind(metadata.modloader) : null,
+    metadata.minecraftVersion
+  ]
+    .filter(Boolean)
+    .join(" ");
+  if (runtime) {
+    parts.push(runtime);
+  }
+  if (metadata.buildVersion) {
+    parts.push(`Build ${metadata.buildVersion}`);
+  }
+  return parts.length > 0 ? parts.join(" · ") : null;
+}
@@ -57,6 +57,7 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
   const profile = ref<Profile | null>(null);
   const atlasProfile = ref<AtlasProfile | null>(null);
   const launcherLinkSession = ref<LauncherLinkSession | null>(null);
+  const launcherLinkReconnecting = ref(false);
   const authInFlight = ref(false);
   const atlasAuthInFlight = ref(false);
   let microsoftPkceLoginAttempt = 0;
@@ -66,6 +67,7 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
   let launcherLinkPollTimer: number | undefined;
   const LAUNCHER_LINK_STORAGE_KEY = "atlas.launcherLinkSession";
   const LAUNCHER_LINK_POLL_INTERVAL_MS = 3000;
+  const LAUNCHER_LINK_MAX_BACKOFF_MS = 30000;
 
   const isSigningIn = computed(
     () =>
@@ -136,6 +138,7 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
       window.clearTimeout(launcherLinkPollTimer);
       launcherLinkPollTimer = undefined;
     }
+    launcherLinkReconnecting.value = false;
   }
 
   function isLauncherLinkExpired(session: LauncherLinkSession) {
@@ -148,6 +151,7 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
       return {
         success: false,
         retryable: false,
+        transient: false,
         message: "Missing Minecraft profile."
       };
     }
@@ -171,6 +175,7 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
         return {
           success: true,
           retryable: false,
+          transient: false,
           message: ""
         };
       }
@@ -178,14 +183,17 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
       return {
         success: false,
         retryable: false,
+        transient: false,
         message: "Launcher link failed."
       };
     } catch (err) {
       const message = String(err);
-      const retryable = /link session not claimed/i.test(message);
+      const transient = /atlas hub unreachable/i.test(message);
+      const retryable = transient || /link session not claimed/i.test(message);
       return {
         success: false,
         retryable,
+        transient,
         message
       };
     }
@@ -203,6 +211,7 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
     }
     const attempt = ++launcherLinkPollAttempt;
     clearLauncherLinkPoll();
+    let delay = LAUNCHER_LINK_POLL_INTERVAL_MS;
 
     const poll = async () => {
       if (attempt !== launcherLinkPollAttempt) {
@@ -210,11 +219,15 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
       }
       if (isLauncherLinkExpired(session)) {
         setStatus("Launcher link expired. Request a new link code.");
+        clearLauncherLinkPoll();
         launcherLinkSession.value = null;
         writeStoredLauncherLinkSession(null);
         return;
       }
       const outcome = await tryCompleteLauncherLink(session);
+      if (attempt !== launcherLinkPollAttempt) {
+        return;
+      }
       if (outcome.success) {
         clearLauncherLinkPoll();
         return;
@@ -224,16 +237,45 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
         clearLauncherLinkPoll();
         return;
       }
-      launcherLinkPollTimer = window.setTimeout(poll, LAUNCHER_LINK_POLL_INTERVAL_MS);
+      if (outcome.transient) {
+        // Keep the link code; the session stays valid on the hub until it expires.
+        if (!launcherLinkReconnecting.value) {
+          pushLog(`Launcher link lost contact with Atlas Hub: ${outcome.message}`);
+        }
+        launcherLinkReconnecting.value = true;
+        setStatus("Reconnecting to Atlas Hub to finish linking...");
+        delay = Math.min(delay * 2, LAUNCHER_LINK_MAX_BACKOFF_MS);
+      } else {
+        if (launcherLinkReconnecting.value) {
+          pushLog("Launcher link reconnected to Atlas Hub.");
+          setStatus("Waiting for the link code to be claimed.");
+        }
+        launcherLinkReconnecting.value = false;
+        delay = LAUNCHER_LINK_POLL_INTERVAL_MS;
+      }
+      launcherLinkPollTimer = window.setTimeout(poll, delay);
     };
 
-    launcherLinkPollTimer = window.setTimeout(poll, LAUNCHER_LINK_POLL_INTERVAL_MS);
+    launcherLinkPollTimer = window.setTimeout(poll, delay);
+  }
+
+  function cancelLauncherLink() {
+    launcherLinkPollAttempt += 1;
+    clearLauncherLinkPoll();
+    launcherLinkSession.value = null;
+    writeStoredLauncherLinkSession(null);
+    setStatus("Launcher link cancelled.");
   }
 
-  async function waitForMicrosoftDeviceApproval(deviceCodeValue: string, attempt: number) {
+  async function waitForMicrosoftDeviceApproval(
+    deviceCodeValue: string,
+    intervalSeconds: number,
+    attempt: number
+  ) {
     try {
       const result = await invoke<Profile>("complete_device_code", {
-        deviceCode: deviceCodeValue
+        deviceCode: deviceCodeValue,
+        intervalSeconds
       });
       if (attempt !== microsoftDeviceLoginAttempt) {
         return;
@@ -467,7 +509,7 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
     if (!response) {
       return;
     }
-    void waitForMicrosoftDeviceApproval(response.device_code, attempt);
+    void waitForMicrosoftDeviceApproval(response.device_code, response.interval ?? 5, attempt);
   }
 
   async function startAtlasLogin() {
@@ -594,6 +636,7 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
     profile,
     atlasProfile,
     launcherLinkSession,
+    launcherLinkReconnecting,
     restoreSession,
     restoreAtlasSession,
     restoreSessions,
@@ -604,6 +647,7 @@ export function useAuth({ setStatus, pushLog, run, onUnhandledDeepLink }: AuthDe
     signOutAtlas,
     createLauncherLink,
     completeLauncherLink,
+    cancelLauncherLink,
     startLauncherLinkCompletionPoll
   };
 }
@@ -7,13 +7,15 @@ interface LaunchEventsDeps {
   progress: Ref<number>;
   pushLog: (entry: string) => void;
   upsertTaskFromEvent?: (event: LaunchEvent) => void;
+  onLaunchExited?: () => void;
 }
 
 export async function initLaunchEvents({
   status,
   progress,
   pushLog,
-  upsertTaskFromEvent
+  upsertTaskFromEvent,
+  onLaunchExited
 }: LaunchEventsDeps) {
   await listen<LaunchEvent>("launch://status", (event) => {
     const payload = event.payload;
@@ -32,4 +34,8 @@ export async function initLaunchEvents({
     const prefix = payload.stream?.trim() ? `[mc:${payload.stream}] ` : "[mc] ";
     pushLog(`${prefix}${payload.message}`);
   });
+
+  await listen("launch://exited", () => {
+    onLaunchExited?.();
+  });
 }
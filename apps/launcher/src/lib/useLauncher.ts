@@ -2,7 +2,7 @@ import { invoke } from "@tauri-apps/api/core";
 import type { Ref } from "vue";
 import type { Profile } from "@/types/auth";
 import type { AppSettings, InstanceConfig } from "@/types/settings";
-import type { LaunchOptions } from "@/types/launch";
+import type { LaunchOptions, QuickPlay } from "@/types/launch";
 
 interface LauncherDeps {
   profile: Ref<Profile | null>;
@@ -51,16 +51,18 @@ export function useLauncher({
     const jvmArgs = (active.jvmArgs ?? "").trim() || (settings.value.defaultJvmArgs ?? "");
 
     return {
+      instanceId: active.id,
       gameDir,
       javaPath: active.javaPath ?? "",
       memoryMb,
       jvmArgs,
       version: active.version ?? null,
-      loader
+      loader,
+      sharedStorage: settings.value.sharedGameStorage === true
     };
   }
 
-  async function launchMinecraft() {
+  async function launchMinecraft(quickPlay?: QuickPlay) {
     if (!profile.value) {
       setStatus("Sign in with Microsoft before launching.");
       return;
@@ -69,8 +71,13 @@ export function useLauncher({
     if (!options) {
       return;
     }
+    options.quickPlay = quickPlay ?? null;
     setProgress(0);
-    setStatus("Starting Minecraft...");
+    setStatus(
+      profile.value.demo
+        ? "Starting the Minecraft demo (this account does not own Java Edition)..."
+        : "Starting Minecraft..."
+    );
     await run(async () => {
       try {
         await invoke("launch_minecraft", { options });
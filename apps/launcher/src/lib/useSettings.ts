@@ -7,9 +7,11 @@ import type {
   OnboardingIntent,
   OnboardingIntentSource,
   LaunchReadinessWizardState,
-  ModLoaderConfig
+  ModLoaderConfig,
+  PostLaunchBehavior
 } from "@/types/settings";
 import type { AtlasRemotePack } from "@/types/library";
+import { metadataFromRemotePack, normalizePackMetadata } from "@/lib/atlasPackMetadata";
 
 interface SettingsDeps {
   setStatus: (message: string) => void;
@@ -44,7 +46,13 @@ export function useSettings({ setStatus, pushLog, run }: SettingsDeps) {
     pendingIntent: null,
     firstLaunchCompletedAt: null,
     firstLaunchNoticeDismissedAt: null,
-    defaultMemoryProfileV1Applied: false
+    defaultMemoryProfileV1Applied: false,
+    launcherLogLevel: null,
+    postLaunchBehavior: "keep_open",
+    sharedGameStorage: false,
+    networkConnectTimeoutSecs: null,
+    networkReadTimeoutSecs: null,
+    gameDirOverride: null
   });
   const defaultGameDir = ref("");
   const systemMemoryMb = ref<number | null>(null);
@@ -74,6 +82,22 @@ export function useSettings({ setStatus, pushLog, run }: SettingsDeps) {
     }
   });
 
+  const settingsPostLaunchBehavior = computed({
+    get: () => settings.value.postLaunchBehavior ?? "keep_open",
+    set: (value: PostLaunchBehavior) => {
+      settings.value.postLaunchBehavior = value;
+      queueSave();
+    }
+  });
+
+  const settingsSharedGameStorage = computed({
+    get: () => settings.value.sharedGameStorage === true,
+    set: (value: boolean) => {
+      settings.value.sharedGameStorage = value;
+      queueSave();
+    }
+  });
+
   const settingsMemoryMaxMb = computed(() => resolveMemoryEntryCapMb(systemMemoryMb.value));
   const settingsRecommendedMemoryMb = computed(() => {
     if (systemMemoryMb.value == null) {
@@ -215,7 +239,9 @@ export function useSettings({ setStatus, pushLog, run }: SettingsDeps) {
       memoryMb: null,
       jvmArgs: null,
       source: "local",
-      atlasPack: null
+      atlasPack: null,
+      lastPlayedUnix: null,
+      totalPlaytimeSeconds: null
     };
   }
 
@@ -233,7 +259,8 @@ export function useSettings({ setStatus, pushLog, run }: SettingsDeps) {
           channel: normalizeAtlasChannel(instance.atlasPack.channel),
           buildId: instance.atlasPack.buildId ?? null,
           buildVersion: instance.atlasPack.buildVersion ?? null,
-          artifactKey: instance.atlasPack.artifactKey ?? null
+          artifactKey: instance.atlasPack.artifactKey ?? null,
+          metadata: normalizePackMetadata(instance.atlasPack.metadata)
         }
         : null;
     return {
@@ -249,7 +276,10 @@ export function useSettings({ setStatus, pushLog, run }: SettingsDeps) {
       memoryMb: typeof instance.memoryMb === "number" ? instance.memoryMb : null,
       jvmArgs: (instance.jvmArgs ?? "").trim() || null,
       source,
-      atlasPack
+      atlasPack,
+      lastPlayedUnix: typeof instance.lastPlayedUnix === "number" ? instance.lastPlayedUnix : null,
+      totalPlaytimeSeconds:
+        typeof instance.totalPlaytimeSeconds === "number" ? instance.totalPlaytimeSeconds : null
     };
   }
 
@@ -304,7 +334,13 @@ export function useSettings({ setStatus, pushLog, run }: SettingsDeps) {
         pendingIntent: normalizeOnboardingIntent(loaded.pendingIntent),
         firstLaunchCompletedAt: loaded.firstLaunchCompletedAt ?? null,
         firstLaunchNoticeDismissedAt: loaded.firstLaunchNoticeDismissedAt ?? null,
-        defaultMemoryProfileV1Applied: loaded.defaultMemoryProfileV1Applied === true
+        defaultMemoryProfileV1Applied: loaded.defaultMemoryProfileV1Applied === true,
+        launcherLogLevel: loaded.launcherLogLevel ?? null,
+        postLaunchBehavior: loaded.postLaunchBehavior ?? "keep_open",
+        sharedGameStorage: loaded.sharedGameStorage === true,
+        networkConnectTimeoutSecs: loaded.networkConnectTimeoutSecs ?? null,
+        networkReadTimeoutSecs: loaded.networkReadTimeoutSecs ?? null,
+        gameDirOverride: loaded.gameDirOverride ?? null
       };
       const defaultsChanged = ensureDefaults();
       const memoryProfileChanged = await applyDefaultMemoryProfileIfNeeded();
@@ -320,6 +356,11 @@ export function useSettings({ setStatus, pushLog, run }: SettingsDeps) {
   async function loadDefaultGameDir() {
     try {
       defaultGameDir.value = await invoke<string>("get_default_game_dir");
+      const storageError = await invoke<string | null>("get_storage_error");
+      if (storageError) {
+        setStatus(storageError);
+        pushLog(storageError);
+      }
     } catch (err) {
       pushLog(`Failed to resolve default game dir: ${String(err)}`);
     }
@@ -533,7 +574,9 @@ export function useSettings({ setStatus, pushLog, run }: SettingsDeps) {
       ...source,
       id: newId,
       name: `${source.name} Copy`,
-      gameDir: deriveInstanceDir(defaultGameDir.value, newId)
+      gameDir: deriveInstanceDir(defaultGameDir.value, newId),
+      lastPlayedUnix: null,
+      totalPlaytimeSeconds: null
     };
     settings.value.instances = [...instances.value, copy];
     settings.value.selectedInstanceId = copy.id;
@@ -638,8 +681,13 @@ export function useSettings({ setStatus, pushLog, run }: SettingsDeps) {
         channel: selectedChannel,
         buildId: remote.buildId ?? null,
         buildVersion: remote.buildVersion ?? null,
-        artifactKey: remote.artifactKey ?? null
-      }
+        artifactKey: remote.artifactKey ?? null,
+        metadata: canApplyRemoteRuntime
+          ? metadataFromRemotePack(remote)
+          : existing?.atlasPack?.metadata ?? null
+      },
+      lastPlayedUnix: existing?.lastPlayedUnix ?? null,
+      totalPlaytimeSeconds: existing?.totalPlaytimeSeconds ?? null
     };
   }
 
@@ -733,7 +781,9 @@ export function useSettings({ setStatus, pushLog, run }: SettingsDeps) {
     updateInstance,
     removeInstance,
     syncAtlasRemotePacks,
-    settingsThemeMode
+    settingsThemeMode,
+    settingsPostLaunchBehavior,
+    settingsSharedGameStorage
   };
 }
 
@@ -46,6 +46,16 @@ export function pruneHomePath(path?: string | null): string {
   return original
 }
 
+// e.g. "45m", "3h 12m"
+export function formatPlaytime(seconds?: number | null): string | null {
+  if (!seconds || seconds < 60) {
+    return null
+  }
+  const minutes = Math.floor(seconds / 60)
+  const hours = Math.floor(minutes / 60)
+  return hours > 0 ? `${hours}h ${minutes % 60}m` : `${minutes}m`
+}
+
 export function formatLoaderKind(loader?: string | null): string {
   const normalized = (loader ?? "").trim().toLowerCase()
   if (normalized === "fabric") {
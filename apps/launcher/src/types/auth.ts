@@ -1,6 +1,7 @@
 export interface Profile {
   id: string;
   name: string;
+  demo?: boolean;
 }
 
 export interface AtlasProfile {
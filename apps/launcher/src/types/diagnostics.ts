@@ -20,6 +20,7 @@ export type FixAction =
   | "setSafeMemory"
   | "resyncPack"
   | "repairRuntime"
+  | "repairVersion"
   | "fullRepair";
 
 export interface TroubleshooterFinding {
@@ -35,6 +36,12 @@ export interface TroubleshooterReport {
   findings: TroubleshooterFinding[];
 }
 
+export interface VersionValidation {
+  id: string;
+  problems: string[];
+  repaired: boolean;
+}
+
 export interface FixResult {
   action: FixAction;
   applied: boolean;
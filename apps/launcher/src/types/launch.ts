@@ -13,11 +13,50 @@ export interface LaunchLogEvent {
   message: string;
 }
 
+export type QuickPlayKind = "singleplayer" | "multiplayer";
+
+export interface QuickPlay {
+  kind: QuickPlayKind;
+  target: string;
+}
+
 export interface LaunchOptions {
+  instanceId?: string | null;
   gameDir: string;
   javaPath?: string;
   memoryMb?: number;
   jvmArgs?: string;
   version?: string | null;
   loader?: ModLoaderConfig;
+  width?: number | null;
+  height?: number | null;
+  fullscreen?: boolean;
+  demo?: boolean;
+  quickPlay?: QuickPlay | null;
+  sharedStorage?: boolean;
+}
+
+export type LaunchOutcome = "windowDetected" | "crashed" | "timedOut";
+
+export interface LaunchRecord {
+  instanceId?: string | null;
+  version: string;
+  startedAtMs: number;
+  outcome: LaunchOutcome;
+  exitCode?: number | null;
+  durationMs: number;
+}
+
+export interface InstanceStats {
+  lastPlayedUnix?: number | null;
+  totalPlaytimeSeconds: number;
+}
+
+export interface JavaInfo {
+  path: string;
+  version: string;
+  major: number;
+  vendor?: string | null;
+  arch?: string | null;
+  is64bit: boolean;
 }
@@ -23,6 +23,28 @@ export interface ModEntry {
   modified: number;
 }
 
+export interface ScreenshotEntry {
+  fileName: string;
+  path: string;
+  size: number;
+  modified: number;
+  preview?: string | null;
+}
+
+export interface WorldEntry {
+  folderName: string;
+  name: string;
+  lastPlayed: number;
+  gameVersion?: string | null;
+  size: number;
+}
+
+export interface WorldBackup {
+  fileName: string;
+  size: number;
+  created: number;
+}
+
 export type AtlasChannel = "dev" | "beta" | "production";
 
 export interface AtlasRemotePack {
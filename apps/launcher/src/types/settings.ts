@@ -27,6 +27,17 @@ export interface AtlasPackLink {
   buildId?: string | null;
   buildVersion?: string | null;
   artifactKey?: string | null;
+  metadata?: AtlasPackMetadata | null;
+}
+
+export interface AtlasPackMetadata {
+  packName?: string | null;
+  channel?: string | null;
+  buildVersion?: string | null;
+  minecraftVersion?: string | null;
+  modloader?: string | null;
+  modloaderVersion?: string | null;
+  updatedAt?: string | null;
 }
 
 export interface InstanceConfig {
@@ -40,8 +51,12 @@ export interface InstanceConfig {
   jvmArgs?: string | null;
   source?: InstanceSource;
   atlasPack?: AtlasPackLink | null;
+  lastPlayedUnix?: number | null;
+  totalPlaytimeSeconds?: number | null;
 }
 
+export type PostLaunchBehavior = "keep_open" | "minimize" | "hide" | "close";
+
 export interface AppSettings {
   msClientId?: string | null;
   atlasHubUrl?: string | null;
@@ -55,4 +70,10 @@ export interface AppSettings {
   firstLaunchCompletedAt?: string | null;
   firstLaunchNoticeDismissedAt?: string | null;
   defaultMemoryProfileV1Applied?: boolean;
+  launcherLogLevel?: string | null;
+  postLaunchBehavior?: PostLaunchBehavior;
+  sharedGameStorage?: boolean;
+  networkConnectTimeoutSecs?: number | null;
+  networkReadTimeoutSecs?: number | null;
+  gameDirOverride?: string | null;
 }
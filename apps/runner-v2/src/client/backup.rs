@@ -1,29 +1,20 @@
 use anyhow::Result;
-use runner_core_v2::proto::{Envelope, Outbound, Request, Response};
+use runner_core_v2::proto::{Envelope, Request, Response};
+use runner_v2_utils::RuntimePaths;
 
-use crate::client::connect_or_start;
+use crate::client::{connect_or_start, send_and_wait};
 
-pub async fn backup_now() -> Result<String> {
-    let mut framed = connect_or_start().await?;
+pub async fn backup_now(paths: &RuntimePaths) -> Result<String> {
+    let mut framed = connect_or_start(paths).await?;
     let req = Envelope {
         id: 1,
+        profile: crate::client::target_profile(),
         payload: Request::Backup {},
     };
-    runner_ipc_v2::framing::send_request(&mut framed, &req).await?;
 
-    loop {
-        let outbound = runner_ipc_v2::framing::read_outbound(&mut framed)
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("runnerd closed the connection"))?;
-        match outbound {
-            Outbound::Response(env) => match env.payload {
-                Response::BackupCreated { path } => return Ok(path),
-                Response::Error(err) => {
-                    return Err(anyhow::anyhow!("backup failed: {}", err.message))
-                }
-                other => return Err(anyhow::anyhow!("unexpected response: {:?}", other)),
-            },
-            Outbound::Event(_) => continue,
-        }
+    match send_and_wait(&mut framed, req).await? {
+        Response::BackupCreated { path } => Ok(path),
+        Response::Error(err) => Err(anyhow::anyhow!("backup failed: {}", err.message)),
+        other => Err(anyhow::anyhow!("unexpected response: {:?}", other)),
     }
 }
@@ -1,9 +1,11 @@
 use anyhow::{bail, Context, Result};
-use atlas_client::device_code::{normalize_hub_url, DEFAULT_ATLAS_HUB_URL};
-use atlas_client::hub::{HubClient, LauncherPack};
+use atlas_client::device_code::DEFAULT_ATLAS_HUB_URL;
+use atlas_client::hub::{HubClient, LauncherPack, RunnerServiceToken};
+use atlas_client::hub_url::normalize_hub_url_string;
 use dialoguer::{theme::ColorfulTheme, FuzzySelect, Input};
 use serde_json::Value;
 use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 pub async fn exec(
@@ -15,29 +17,7 @@ pub async fn exec(
     let hub_url = resolve_hub_url(hub_url)?;
 
     let mut hub = HubClient::new(&hub_url)?;
-    let device_code = hub.login().await?;
-    println!(
-        "To authorize, please visit: {}",
-        device_code
-            .verification_uri_complete
-            .as_ref()
-            .unwrap_or(&device_code.verification_uri)
-    );
-    println!("User code: {}", device_code.user_code);
-
-    let mut interval = Duration::from_secs(device_code.interval);
-    if interval.as_secs() == 0 {
-        interval = Duration::from_secs(5);
-    }
-
-    loop {
-        tokio::time::sleep(interval).await;
-        match hub.poll_token(&device_code.device_code).await {
-            Ok(Some(_)) => break,
-            Ok(None) => continue,
-            Err(err) => bail!("Authentication failed: {err}"),
-        }
-    }
+    device_login(&mut hub).await?;
 
     let resolved_pack_id = resolve_pack_id(&hub, pack_id).await?;
     let label = resolve_token_name(token_name)?;
@@ -53,6 +33,7 @@ pub async fn exec(
         &channel,
         &created.token,
         created.prefix.as_str(),
+        created.id.as_deref(),
     )
     .await?;
 
@@ -62,26 +43,178 @@ pub async fn exec(
     ))
 }
 
+/// Replaces the deploy key in `deploy.json` with a newly issued service token.
+/// The old token is only revoked once the new one has been validated and saved.
+pub async fn rotate(token_name: Option<String>) -> Result<String> {
+    let path = deploy_config_path()?;
+    let content = std::fs::read_to_string(&path).with_context(|| {
+        format!(
+            "Failed to read {}. Run `atlas-runner auth login` first.",
+            path.display()
+        )
+    })?;
+    let mut config: Value =
+        serde_json::from_str(&content).context("Failed to parse runner deploy config")?;
+    let field = |name: &str| {
+        config
+            .get(name)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToString::to_string)
+    };
+    let hub_url =
+        normalize_hub_url_string(&field("hub_url").context("Deploy config is missing hub_url")?)?;
+    let pack_id = field("pack_id").context("Deploy config is missing pack_id")?;
+    let old_prefix = field("prefix");
+    let old_id = field("token_id");
+    println!(
+        "Current token prefix: {}",
+        old_prefix.as_deref().unwrap_or("unknown")
+    );
+
+    let mut hub = HubClient::new(&hub_url)?;
+    device_login(&mut hub).await?;
+
+    let label = resolve_token_name(token_name)?;
+    let created = hub
+        .create_runner_service_token(&pack_id, Some(label))
+        .await
+        .context("Failed to create a new service token; the old token is still in use")?;
+    println!("New token prefix: {}", created.prefix);
+
+    let mut check = HubClient::new(&hub_url)?;
+    check.set_service_token(created.token.clone());
+    if let Err(err) = check.validate_service_token().await {
+        if let Some(id) = created.id.as_deref() {
+            let _ = hub.revoke_runner_service_token(&pack_id, id).await;
+        }
+        bail!("New service token failed validation ({err}); kept the old token.");
+    }
+
+    config["deploy_key"] = Value::String(created.token.clone());
+    config["prefix"] = Value::String(created.prefix.clone());
+    config["token_id"] = created.id.clone().map_or(Value::Null, Value::String);
+    write_deploy_config_atomically(&path, &config)?;
+
+    let old_label = old_prefix.as_deref().unwrap_or("unknown");
+    let old_id = match (old_id, old_prefix.as_deref()) {
+        (Some(id), _) => Some(id),
+        (None, Some(prefix)) => {
+            match find_old_token_id(&hub, &pack_id, prefix, created.id.as_deref()).await {
+                Ok(Ok(id)) => Some(id),
+                Ok(Err(matches)) => {
+                    return Ok(format!(
+                        "Rotated deploy key ({old_label} -> {}). {matches} active tokens match prefix {prefix}, so none was revoked; revoke the old one on the hub.",
+                        created.prefix
+                    ));
+                }
+                Err(err) => {
+                    return Ok(format!(
+                        "Rotated deploy key ({old_label} -> {}), but listing tokens failed: {err}. Revoke the old token on the hub.",
+                        created.prefix
+                    ));
+                }
+            }
+        }
+        (None, None) => None,
+    };
+    let Some(old_id) = old_id.filter(|id| Some(id.as_str()) != created.id.as_deref()) else {
+        return Ok(format!(
+            "Rotated deploy key (new prefix {}). The old token was not recorded; revoke it on the hub.",
+            created.prefix
+        ));
+    };
+    match hub.revoke_runner_service_token(&pack_id, &old_id).await {
+        Ok(()) => Ok(format!(
+            "Rotated deploy key ({old_label} -> {}) and revoked the old token.",
+            created.prefix
+        )),
+        Err(err) => Ok(format!(
+            "Rotated deploy key ({old_label} -> {}), but revoking the old token failed: {err}. Revoke it on the hub.",
+            created.prefix
+        )),
+    }
+}
+
+// Deploy configs written before the token id was recorded only know the
+// prefix, which is short enough to collide; it is only trusted when exactly
+// one active token other than the new one matches.
+async fn find_old_token_id(
+    hub: &HubClient,
+    pack_id: &str,
+    prefix: &str,
+    new_id: Option<&str>,
+) -> Result<std::result::Result<String, usize>> {
+    let tokens = hub.list_runner_service_tokens(pack_id).await?;
+    Ok(single_prefix_match(&tokens, prefix, new_id))
+}
+
+fn single_prefix_match(
+    tokens: &[RunnerServiceToken],
+    prefix: &str,
+    new_id: Option<&str>,
+) -> std::result::Result<String, usize> {
+    let matches = tokens
+        .iter()
+        .filter(|token| {
+            token.token_prefix == prefix
+                && token.revoked_at.is_none()
+                && Some(token.id.as_str()) != new_id
+        })
+        .collect::<Vec<_>>();
+    match matches.as_slice() {
+        [token] => Ok(token.id.clone()),
+        _ => Err(matches.len()),
+    }
+}
+
+async fn device_login(hub: &mut HubClient) -> Result<()> {
+    let device_code = hub.login().await?;
+    println!(
+        "To authorize, please visit: {}",
+        device_code
+            .verification_uri_complete
+            .as_ref()
+            .unwrap_or(&device_code.verification_uri)
+    );
+    println!("User code: {}", device_code.user_code);
+
+    let mut interval = Duration::from_secs(device_code.interval);
+    if interval.as_secs() == 0 {
+        interval = Duration::from_secs(5);
+    }
+
+    loop {
+        tokio::time::sleep(interval).await;
+        match hub.poll_token(&device_code.device_code).await {
+            Ok(Some(_)) => return Ok(()),
+            Ok(None) => continue,
+            Err(err) => bail!("Authentication failed: {err}"),
+        }
+    }
+}
+
 fn resolve_hub_url(cli_hub_url: Option<String>) -> Result<String> {
     if let Some(value) = cli_hub_url {
         let trimmed = value.trim();
         if !trimmed.is_empty() {
-            return Ok(normalize_hub_url(trimmed));
+            return Ok(normalize_hub_url_string(trimmed)?);
         }
     }
 
     if let Ok(value) = std::env::var("ATLAS_HUB_URL") {
         let trimmed = value.trim();
         if !trimmed.is_empty() {
-            return Ok(normalize_hub_url(trimmed));
+            return Ok(normalize_hub_url_string(trimmed)?);
         }
     }
 
     if let Some(value) = read_hub_url_from_deploy_config()? {
-        return Ok(normalize_hub_url(&value));
+        return Ok(normalize_hub_url_string(&value)?);
     }
 
-    Ok(normalize_hub_url(DEFAULT_ATLAS_HUB_URL))
+    Ok(normalize_hub_url_string(DEFAULT_ATLAS_HUB_URL)?)
 }
 
 fn read_hub_url_from_deploy_config() -> Result<Option<String>> {
@@ -108,8 +241,9 @@ async fn save_deploy_key(
     channel: &str,
     deploy_key: &str,
     prefix: &str,
+    token_id: Option<&str>,
 ) -> Result<()> {
-    write_deploy_key_file(hub_url, pack_id, channel, deploy_key, prefix)?;
+    write_deploy_key_file(hub_url, pack_id, channel, deploy_key, prefix, token_id)?;
     Ok(())
 }
 
@@ -120,20 +254,39 @@ fn normalize_channel(channel: Option<String>) -> String {
         .unwrap_or_else(|| "production".to_string())
 }
 
+fn deploy_config_path() -> Result<PathBuf> {
+    let base = dirs::data_dir()
+        .or_else(dirs::home_dir)
+        .ok_or_else(|| anyhow::anyhow!("Unable to resolve a writable data directory"))?;
+    Ok(base.join("atlas").join("runnerd").join("deploy.json"))
+}
+
+/// Writes to a sibling temp file and renames it over `path`, so a crash never
+/// leaves a half-written deploy key behind.
+fn write_deploy_config_atomically(path: &Path, config: &Value) -> Result<()> {
+    let content = serde_json::to_string_pretty(config)
+        .map_err(|err| anyhow::anyhow!("Failed to serialize deploy key config: {err}"))?;
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, content)
+        .map_err(|err| anyhow::anyhow!("Failed to write deploy key config: {err}"))?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|err| anyhow::anyhow!("Failed to replace deploy key config: {err}"))?;
+    Ok(())
+}
+
 fn write_deploy_key_file(
     hub_url: &str,
     pack_id: &str,
     channel: &str,
     deploy_key: &str,
     prefix: &str,
+    token_id: Option<&str>,
 ) -> Result<()> {
-    let base = dirs::data_dir()
-        .or_else(dirs::home_dir)
-        .ok_or_else(|| anyhow::anyhow!("Unable to resolve a writable data directory"))?;
-    let dir = base.join("atlas").join("runnerd");
-    std::fs::create_dir_all(&dir)
-        .map_err(|err| anyhow::anyhow!("Failed to create runnerd config dir: {err}"))?;
-    let path = dir.join("deploy.json");
+    let path = deploy_config_path()?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|err| anyhow::anyhow!("Failed to create runnerd config dir: {err}"))?;
+    }
 
     let payload = serde_json::json!({
         "hub_url": hub_url,
@@ -141,6 +294,7 @@ fn write_deploy_key_file(
         "channel": channel,
         "deploy_key": deploy_key,
         "prefix": prefix,
+        "token_id": token_id,
     });
     let content = serde_json::to_string_pretty(&payload)
         .map_err(|err| anyhow::anyhow!("Failed to serialize deploy key config: {err}"))?;
@@ -205,3 +359,41 @@ fn resolve_token_name(token_name: Option<String>) -> Result<String> {
         .interact_text()
         .context("Failed to read token name")
 }
+
+#[cfg(test)]
+mod tests {
+    use atlas_client::hub::RunnerServiceToken;
+
+    use super::single_prefix_match;
+
+    fn token(id: &str, prefix: &str, revoked: bool) -> RunnerServiceToken {
+        RunnerServiceToken {
+            id: id.to_string(),
+            name: None,
+            token_prefix: prefix.to_string(),
+            revoked_at: revoked.then(|| "2026-01-01T00:00:00Z".to_string()),
+        }
+    }
+
+    #[test]
+    fn prefix_only_revokes_a_unique_match() {
+        let tokens = vec![
+            token("old", "atlas_runner_abc", false),
+            token("new", "atlas_runner_abc", false),
+            token("gone", "atlas_runner_abc", true),
+            token("other", "atlas_runner_xyz", false),
+        ];
+        assert_eq!(
+            single_prefix_match(&tokens, "atlas_runner_abc", Some("new")),
+            Ok("old".to_string())
+        );
+        assert_eq!(
+            single_prefix_match(&tokens, "atlas_runner_abc", None),
+            Err(2)
+        );
+        assert_eq!(
+            single_prefix_match(&tokens, "atlas_runner_zzz", Some("new")),
+            Err(0)
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use anyhow::Result;
+use runner_core_v2::proto::{Envelope, Request, Response};
+use runner_v2_utils::RuntimePaths;
+use std::collections::BTreeMap;
+
+use crate::client::{connect_or_start, send_and_wait};
+
+pub struct PropertiesUpdate {
+    pub changed: Vec<String>,
+    pub restart_required: bool,
+}
+
+pub async fn get_server_properties(paths: &RuntimePaths) -> Result<BTreeMap<String, String>> {
+    let mut framed = connect_or_start(paths).await?;
+    let req = Envelope {
+        id: 1,
+        profile: crate::client::target_profile(),
+        payload: Request::GetServerProperties {},
+    };
+
+    match send_and_wait(&mut framed, req).await? {
+        Response::ServerProperties { properties } => Ok(properties),
+        Response::Error(err) => Err(anyhow::anyhow!("config get failed: {}", err.message)),
+        other => Err(anyhow::anyhow!("unexpected response: {other:?}")),
+    }
+}
+
+pub async fn set_server_properties(
+    paths: &RuntimePaths,
+    changes: BTreeMap<String, String>,
+) -> Result<PropertiesUpdate> {
+    let mut framed = connect_or_start(paths).await?;
+    let req = Envelope {
+        id: 1,
+        profile: crate::client::target_profile(),
+        payload: Request::SetServerProperties { changes },
+    };
+
+    match send_and_wait(&mut framed, req).await? {
+        Response::ServerPropertiesUpdated {
+            changed,
+            restart_required,
+        } => Ok(PropertiesUpdate {
+            changed,
+            restart_required,
+        }),
+        Response::Error(err) => Err(anyhow::anyhow!("config set failed: {}", err.message)),
+        other => Err(anyhow::anyhow!("unexpected response: {other:?}")),
+    }
+}
+
+pub async fn set_pinned_version(paths: &RuntimePaths, version: Option<String>) -> Result<String> {
+    let mut framed = connect_or_start(paths).await?;
+    let req = Envelope {
+        id: 1,
+        profile: crate::client::target_profile(),
+        payload: Request::SetPinnedVersion { version },
+    };
+
+    match send_and_wait(&mut framed, req).await? {
+        Response::PinnedVersionSet {
+            version: Some(version),
+        } => Ok(format!(
+            "pinned to {version}; automatic updates are off. Restart the server to deploy it"
+        )),
+        Response::PinnedVersionSet { version: None } => {
+            Ok("unpinned; following the channel's latest build again".to_string())
+        }
+        Response::Error(err) => Err(anyhow::anyhow!("pin failed: {}", err.message)),
+        other => Err(anyhow::anyhow!("unexpected response: {other:?}")),
+    }
+}
@@ -1,28 +1,31 @@
-use crate::client::connect_or_start;
-use atlas_client::hub::HubClient;
+use crate::client::{
+    connect_only, connect_or_start, connect_or_start_unchecked, send_and_wait,
+    send_and_wait_with_events, version_mismatch_error,
+};
+use atlas_client::hub::{HubClient, ARTIFACT_DOWNLOAD_TIMEOUT};
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
-use runner_core_v2::proto::{Envelope, Outbound, Request, Response};
+use runner_core_v2::proto::{Envelope, ErrorCode, Event, Request, Response, ServerSummary};
 use runner_core_v2::PROTOCOL_VERSION;
-use runner_v2_utils::{ensure_dir, runtime_paths_v2};
+use runner_v2_utils::{ensure_dir, RuntimePaths};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::io::{self, IsTerminal};
 use std::path::PathBuf;
 use sysinfo::System;
 
-pub async fn ping() -> anyhow::Result<String> {
-    let mut framed = connect_or_start().await?;
+pub async fn ping(paths: &RuntimePaths) -> anyhow::Result<String> {
+    let mut framed = connect_or_start_unchecked(paths).await?;
 
     let req = Envelope {
         id: 1,
+        profile: crate::client::target_profile(),
         payload: Request::Ping {
             client_version: env!("ATLAS_BUILD_VERSION").to_string(),
             protocol_version: PROTOCOL_VERSION,
         },
     };
 
-    runner_ipc_v2::framing::send_request(&mut framed, &req).await?;
-    let resp = read_response_payload(&mut framed).await?;
+    let resp = send_and_wait(&mut framed, req).await?;
 
     match resp {
         Response::Pong {
@@ -31,20 +34,23 @@ pub async fn ping() -> anyhow::Result<String> {
         } => Ok(format!(
             "pong: daemon={daemon_version} protocol={protocol_version}"
         )),
+        Response::Error(err) if matches!(err.code, ErrorCode::VersionMismatch) => {
+            Err(version_mismatch_error(&err))
+        }
         other => Ok(format!("unexpected: {other:?}")),
     }
 }
 
-pub async fn shutdown() -> anyhow::Result<String> {
-    let mut framed = connect_or_start().await?;
+pub async fn shutdown(paths: &RuntimePaths) -> anyhow::Result<String> {
+    let mut framed = connect_or_start_unchecked(paths).await?;
 
     let req = Envelope {
         id: 1,
+        profile: crate::client::target_profile(),
         payload: Request::Shutdown {},
     };
 
-    runner_ipc_v2::framing::send_request(&mut framed, &req).await?;
-    let resp = read_response_payload(&mut framed).await?;
+    let resp = send_and_wait(&mut framed, req).await?;
 
     match resp {
         Response::ShutdownAck {} => Ok(format!("Daemon acknowledged shutdown request.")),
@@ -53,13 +59,14 @@ pub async fn shutdown() -> anyhow::Result<String> {
 }
 
 pub async fn up(
+    paths: &RuntimePaths,
     profile: String,
     pack_blob: Option<PathBuf>,
     server_root: Option<PathBuf>,
     max_ram: Option<u32>,
     accept_eula: bool,
 ) -> anyhow::Result<String> {
-    let mut framed = connect_or_start().await?;
+    let mut framed = connect_or_start(paths).await?;
 
     let pack_blob_path = if let Some(path) = pack_blob {
         if !path.exists() {
@@ -68,10 +75,31 @@ pub async fn up(
         path
     } else {
         let config = load_deploy_key()?;
-        let mut hub = HubClient::new(&config.hub_url)?;
+        let mut hub = HubClient::builder(&config.hub_url)
+            .timeout(ARTIFACT_DOWNLOAD_TIMEOUT)
+            .user_agent(concat!("atlas-runner/", env!("ATLAS_BUILD_VERSION")))
+            .build()?;
         hub.set_service_token(config.deploy_key.clone());
-        let build = hub.get_build_blob(&config.pack_id, &config.channel).await?;
-        let paths = runtime_paths_v2();
+        let progress = atlas_client::progress::throttled(|progress| {
+            eprintln!("[download] Downloading pack build: {progress}");
+        });
+        let build = hub
+            .get_build_blob_with_progress(
+                &config.pack_id,
+                &config.channel,
+                config.pinned_version.as_deref(),
+                &progress,
+            )
+            .await
+            .map_err(|err| {
+                if err.is_unauthorized() {
+                    anyhow::anyhow!(
+                        "{err}\nThe deploy key was rejected; run `atlas-runner auth login` again."
+                    )
+                } else {
+                    err.into()
+                }
+            })?;
         ensure_dir(&paths.runtime_dir)?;
         let blob_path = paths
             .runtime_dir
@@ -85,13 +113,14 @@ pub async fn up(
         "ATLAS_PACK_BLOB".to_string(),
         pack_blob_path.to_string_lossy().to_string(),
     );
-    if let Some(root) = server_root {
+    if let Some(root) = &server_root {
         env.insert(
             "ATLAS_SERVER_ROOT".to_string(),
             root.to_string_lossy().to_string(),
         );
     }
 
+    let mut eula_confirmed = accept_eula;
     if let Ok(mut config) = load_deploy_key() {
         if config.eula_accepted != Some(true) {
             if accept_eula {
@@ -112,6 +141,7 @@ pub async fn up(
                     anyhow::bail!("EULA not accepted. Re-run with --accept-eula to proceed.");
                 }
                 config.eula_accepted = Some(true);
+                eula_confirmed = true;
             }
         }
         if config.max_ram.is_none() {
@@ -169,13 +199,17 @@ pub async fn up(
         let _ = save_deploy_key(&config);
     }
 
+    if eula_confirmed {
+        request_accept_eula(&mut framed, server_root).await?;
+    }
+
     let req = Envelope {
         id: 1,
+        profile: crate::client::target_profile(),
         payload: Request::Start { profile, env },
     };
 
-    runner_ipc_v2::framing::send_request(&mut framed, &req).await?;
-    let resp = read_response_payload(&mut framed).await?;
+    let resp = send_and_wait_with_events(&mut framed, req, print_start_progress).await?;
 
     match resp {
         Response::Started { pid, .. } => Ok(format!("started server pid={pid}")),
@@ -184,6 +218,127 @@ pub async fn up(
     }
 }
 
+pub async fn clear_crash_loop(paths: &RuntimePaths) -> anyhow::Result<String> {
+    let mut framed = connect_or_start(paths).await?;
+    let req = Envelope {
+        id: 1,
+        profile: crate::client::target_profile(),
+        payload: Request::ClearCrashLoop {},
+    };
+
+    match send_and_wait(&mut framed, req).await? {
+        Response::CrashLoopCleared {} => {
+            Ok("crash loop cleared; automatic restarts are enabled again".to_string())
+        }
+        Response::Error(err) => Err(anyhow::anyhow!("clear failed: {}", err.message)),
+        other => Ok(format!("unexpected: {other:?}")),
+    }
+}
+
+pub async fn list_servers(paths: &RuntimePaths) -> anyhow::Result<Vec<ServerSummary>> {
+    let mut framed = connect_or_start(paths).await?;
+    let req = Envelope {
+        id: 1,
+        profile: None,
+        payload: Request::ListServers {},
+    };
+
+    match send_and_wait(&mut framed, req).await? {
+        Response::ServerList { servers } => Ok(servers),
+        Response::Error(err) => Err(anyhow::anyhow!("list failed: {}", err.message)),
+        other => Err(anyhow::anyhow!("unexpected response: {other:?}")),
+    }
+}
+
+pub async fn set_maintenance(paths: &RuntimePaths, on: bool) -> anyhow::Result<String> {
+    let mut framed = connect_or_start(paths).await?;
+    let req = Envelope {
+        id: 1,
+        profile: crate::client::target_profile(),
+        payload: Request::SetMaintenance { on },
+    };
+
+    match send_and_wait(&mut framed, req).await? {
+        Response::MaintenanceSet { on: true, .. } => {
+            Ok("maintenance mode on; the server will not be started automatically".to_string())
+        }
+        Response::MaintenanceSet { starting: true, .. } => {
+            Ok("maintenance mode off; starting server".to_string())
+        }
+        Response::MaintenanceSet { .. } => Ok("maintenance mode off".to_string()),
+        Response::Error(err) => Err(anyhow::anyhow!("maintenance failed: {}", err.message)),
+        other => Ok(format!("unexpected: {other:?}")),
+    }
+}
+
+pub async fn set_channel(paths: &RuntimePaths, channel: String) -> anyhow::Result<String> {
+    if let Ok(mut framed) = connect_only(paths).await {
+        let req = Envelope {
+            id: 1,
+            profile: crate::client::target_profile(),
+            payload: Request::SetChannel { channel },
+        };
+        return match send_and_wait(&mut framed, req).await? {
+            Response::ChannelSet {
+                channel,
+                reconciling: true,
+            } => Ok(format!(
+                "switched to channel {channel}; updating the server to its latest build"
+            )),
+            Response::ChannelSet { channel, .. } => Ok(format!(
+                "switched to channel {channel}; the next start deploys its latest build"
+            )),
+            Response::Error(err) => Err(anyhow::anyhow!("channel switch failed: {}", err.message)),
+            other => Ok(format!("unexpected: {other:?}")),
+        };
+    }
+
+    // runnerd is not running, so nothing needs reconciling until the next start.
+    let mut config = load_deploy_key()?;
+    let mut hub = HubClient::new(&config.hub_url)?;
+    hub.set_service_token(config.deploy_key.clone());
+    if !hub.has_channel_access(&config.pack_id, &channel).await? {
+        anyhow::bail!(
+            "channel switch failed: the deploy key for pack {} cannot download builds from the {} channel",
+            config.pack_id,
+            channel
+        );
+    }
+    config.channel = channel.clone();
+    save_deploy_key(&config)?;
+    Ok(format!(
+        "switched to channel {channel}; the next start deploys its latest build"
+    ))
+}
+
+pub async fn accept_eula(
+    paths: &RuntimePaths,
+    server_root: Option<PathBuf>,
+) -> anyhow::Result<String> {
+    let mut framed = connect_or_start(paths).await?;
+    let path = request_accept_eula(&mut framed, server_root).await?;
+    Ok(format!("EULA accepted: {path}"))
+}
+
+async fn request_accept_eula(
+    framed: &mut runner_ipc_v2::framing::FramedStream,
+    server_root: Option<PathBuf>,
+) -> anyhow::Result<String> {
+    let req = Envelope {
+        id: 2,
+        profile: crate::client::target_profile(),
+        payload: Request::AcceptEula {
+            server_root: server_root.map(|root| root.to_string_lossy().to_string()),
+        },
+    };
+
+    match send_and_wait(framed, req).await? {
+        Response::EulaAccepted { path, .. } => Ok(path),
+        Response::Error(err) => Err(anyhow::anyhow!("accepting EULA failed: {}", err.message)),
+        other => Err(anyhow::anyhow!("unexpected response: {other:?}")),
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct DeployKeyConfig {
     hub_url: String,
@@ -193,13 +348,37 @@ struct DeployKeyConfig {
     deploy_key: String,
     #[serde(default)]
     prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token_id: Option<String>,
     #[serde(default)]
     max_ram: Option<u32>,
     #[serde(default)]
+    jvm_args: Option<Vec<String>>,
+    #[serde(default)]
     should_autostart: Option<bool>,
     #[serde(default)]
     eula_accepted: Option<bool>,
     #[serde(default)]
+    sync_operators: Option<bool>,
+    #[serde(default)]
+    keep_local_operators: Option<bool>,
+    #[serde(default)]
+    crash_loop_max_restarts: Option<u32>,
+    #[serde(default)]
+    crash_loop_window_secs: Option<u64>,
+    #[serde(default)]
+    stop_grace_secs: Option<u64>,
+    #[serde(default)]
+    log_buffer_lines: Option<usize>,
+    #[serde(default)]
+    daemon_log_buffer_lines: Option<usize>,
+    #[serde(default)]
+    maintenance: Option<bool>,
+    #[serde(default)]
+    restart_schedule: Option<serde_json::Value>,
+    #[serde(default)]
+    pinned_version: Option<String>,
+    #[serde(default)]
     first_run: Option<bool>,
 }
 
@@ -308,20 +487,23 @@ fn default_channel() -> String {
     "production".to_string()
 }
 
-fn is_interactive_terminal() -> bool {
-    io::stdin().is_terminal() && io::stdout().is_terminal()
+fn print_start_progress(event: &Event) {
+    let Event::StartProgress {
+        phase,
+        message,
+        current,
+        total,
+    } = event
+    else {
+        return;
+    };
+    if *total > 1 {
+        eprintln!("[{phase} {current}/{total}] {message}");
+    } else {
+        eprintln!("[{phase}] {message}");
+    }
 }
 
-async fn read_response_payload(
-    framed: &mut runner_ipc_v2::framing::FramedStream,
-) -> anyhow::Result<Response> {
-    loop {
-        let outbound = runner_ipc_v2::framing::read_outbound(framed)
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("runnerd closed the connection"))?;
-        match outbound {
-            Outbound::Response(env) => return Ok(env.payload),
-            Outbound::Event(_) => continue,
-        }
-    }
+fn is_interactive_terminal() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
 }
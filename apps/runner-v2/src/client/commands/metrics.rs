@@ -0,0 +1,20 @@
+use anyhow::Result;
+use runner_core_v2::proto::{Envelope, PerformanceSample, PerformanceSummary, Request, Response};
+use runner_v2_utils::RuntimePaths;
+
+use crate::client::{connect_or_start, send_and_wait};
+
+pub async fn metrics(paths: &RuntimePaths) -> Result<(PerformanceSummary, Vec<PerformanceSample>)> {
+    let mut framed = connect_or_start(paths).await?;
+    let req = Envelope {
+        id: 1,
+        profile: crate::client::target_profile(),
+        payload: Request::Metrics {},
+    };
+
+    match send_and_wait(&mut framed, req).await? {
+        Response::Metrics { summary, history } => Ok((summary, history)),
+        Response::Error(err) => Err(anyhow::anyhow!("metrics failed: {}", err.message)),
+        other => Err(anyhow::anyhow!("unexpected response: {other:?}")),
+    }
+}
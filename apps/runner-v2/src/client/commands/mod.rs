@@ -1,4 +1,9 @@
 pub mod auth;
+pub mod config;
 pub mod core;
+pub mod metrics;
 pub mod rcon;
+pub mod ready;
+pub mod status;
 pub mod supervisor;
+pub mod whitelist;
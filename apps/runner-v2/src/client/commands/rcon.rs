@@ -3,37 +3,80 @@ use runner_core_v2::proto::*;
 use runner_ipc_v2::framing;
 use tokio::io::{self, AsyncBufReadExt, BufReader};
 
-pub async fn rcon_exec(mut framed: framing::FramedStream, command: String) -> anyhow::Result<()> {
+// Responses the server sends back, over a successful RCON exchange, when the
+// command itself was rejected.
+const ERROR_MARKERS: &[&str] = &[
+    "unknown command",
+    "unknown or incomplete command",
+    "incorrect argument",
+    "<--[here]",
+    "no player was found",
+    "no entity was found",
+    "you do not have permission",
+];
+
+// Commands that report what they changed, so an empty reply means they did nothing.
+const MUTATING_COMMANDS: &[&str] = &[
+    "op",
+    "deop",
+    "kick",
+    "ban",
+    "ban-ip",
+    "pardon",
+    "pardon-ip",
+    "whitelist",
+    "give",
+    "clear",
+    "gamemode",
+    "gamerule",
+    "difficulty",
+    "time",
+    "weather",
+    "tp",
+    "teleport",
+    "kill",
+    "effect",
+    "xp",
+    "experience",
+    "setblock",
+    "fill",
+    "summon",
+    "scoreboard",
+    "team",
+];
+
+pub async fn rcon_exec(
+    mut framed: framing::FramedStream,
+    command: String,
+    strict: bool,
+    quiet: bool,
+) -> anyhow::Result<()> {
     let req = Envelope {
         id: 1,
-        payload: Request::RconExec { command },
+        profile: crate::client::target_profile(),
+        payload: Request::RconExec {
+            command: command.clone(),
+        },
     };
 
-    // send request as a Response outbound? no—client sends Request envelope directly
-    // framing currently only has send_outbound; add a tiny helper or just serialize here:
-    send_request(&mut framed, &req).await?;
-
-    // read daemon outbound until we get the matching response
-    while let Some(msg) = framing::read_outbound(&mut framed).await? {
-        match msg {
-            Outbound::Response(env) if env.id == 1 => match env.payload {
-                Response::RconResult { text } => {
-                    print!("{text}");
-                    if !text.ends_with('\n') {
-                        println!();
-                    }
-                    return Ok(());
+    match crate::client::send_and_wait(&mut framed, req).await? {
+        Response::RconResult { text } => {
+            if !quiet {
+                print!("{text}");
+                if !text.ends_with('\n') {
+                    println!();
+                }
+            }
+            if strict {
+                if let Some(reason) = response_failure(&command, &text) {
+                    anyhow::bail!("command failed: {reason}");
                 }
-                Response::Error(e) => anyhow::bail!("rcon error: {} ({:?})", e.message, e.code),
-                other => anyhow::bail!("unexpected response: {other:?}"),
-            },
-            _ => {
-                // ignore unrelated events/responses (shouldn't happen for simple exec)
             }
+            Ok(())
         }
+        Response::Error(e) => anyhow::bail!("rcon error: {} ({:?})", e.message, e.code),
+        other => anyhow::bail!("unexpected response: {other:?}"),
     }
-
-    anyhow::bail!("daemon closed connection");
 }
 
 pub async fn rcon_interactive(mut framed: framing::FramedStream) -> anyhow::Result<()> {
@@ -41,6 +84,7 @@ pub async fn rcon_interactive(mut framed: framing::FramedStream) -> anyhow::Resu
     let open_id = 1;
     let open_req = Envelope {
         id: open_id,
+        profile: crate::client::target_profile(),
         payload: Request::RconOpen {},
     };
     send_request(&mut framed, &open_req).await?;
@@ -78,7 +122,7 @@ pub async fn rcon_interactive(mut framed: framing::FramedStream) -> anyhow::Resu
                 match line {
                     None => {
                         // EOF (Ctrl+D): close session
-                        let close_req = Envelope { id: 9999, payload: Request::RconClose { session } };
+                        let close_req = Envelope { id: 9999, profile: crate::client::target_profile(), payload: Request::RconClose { session } };
                         let _ = send_request(&mut framed, &close_req).await;
                         return Ok(());
                     }
@@ -88,12 +132,12 @@ pub async fn rcon_interactive(mut framed: framing::FramedStream) -> anyhow::Resu
                             continue;
                         }
                         if s == "exit" || s == "quit" {
-                            let close_req = Envelope { id: 9999, payload: Request::RconClose { session } };
+                            let close_req = Envelope { id: 9999, profile: crate::client::target_profile(), payload: Request::RconClose { session } };
                             send_request(&mut framed, &close_req).await?;
                             return Ok(());
                         }
 
-                        let send_req = Envelope { id: 2, payload: Request::RconSend { session, command: s } };
+                        let send_req = Envelope { id: 2, profile: crate::client::target_profile(), payload: Request::RconSend { session, command: s } };
                         send_request(&mut framed, &send_req).await?;
                     }
                 }
@@ -118,6 +162,30 @@ pub async fn rcon_interactive(mut framed: framing::FramedStream) -> anyhow::Resu
     }
 }
 
+/// Guesses whether an RCON reply reports a failed command, since the protocol
+/// itself always succeeds.
+fn response_failure(command: &str, text: &str) -> Option<String> {
+    let lower = text.to_ascii_lowercase();
+    if let Some(marker) = ERROR_MARKERS.iter().find(|marker| lower.contains(*marker)) {
+        let line = text
+            .lines()
+            .find(|line| line.to_ascii_lowercase().contains(marker))
+            .unwrap_or(text);
+        return Some(line.trim().to_string());
+    }
+    let name = command
+        .trim()
+        .trim_start_matches('/')
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if text.trim().is_empty() && MUTATING_COMMANDS.contains(&name.as_str()) {
+        return Some(format!("`{name}` returned no output"));
+    }
+    None
+}
+
 /// Client helper: send a Request envelope (client -> daemon).
 async fn send_request(
     framed: &mut framing::FramedStream,
@@ -128,3 +196,29 @@ async fn send_request(
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
     framed.send(bytes.into()).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::response_failure;
+
+    #[test]
+    fn detects_rejected_commands() {
+        assert!(
+            response_failure("foo", "Unknown or incomplete command, see below for error").is_some()
+        );
+        assert!(response_failure(
+            "give Steve dirt x",
+            "Incorrect argument for command\n...dirt x<--[HERE]"
+        )
+        .is_some());
+        assert_eq!(
+            response_failure("/op Steve", "").as_deref(),
+            Some("`op` returned no output")
+        );
+        assert_eq!(response_failure("say hi", ""), None);
+        assert_eq!(
+            response_failure("list", "There are 0 of a max of 20 players online:"),
+            None
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use anyhow::Result;
+use runner_core_v2::proto::{Envelope, LogFilter, LogLine, Request, Response, ServerStatus};
+use runner_v2_utils::RuntimePaths;
+use std::time::{Duration, Instant};
+
+use crate::client::commands::{status::status, supervisor::logs_tail};
+use crate::client::{connect_or_start, send_and_wait};
+
+const READY_LINE_PATTERN: &str = r"Done \([^)]*\)! For help, type";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Polls until the server logs its "Done (12.3s)! For help, type" line after
+/// `since_ms`, or until RCON answers `list`. Fails when the server stops or
+/// `timeout` passes first.
+pub async fn wait_ready(paths: &RuntimePaths, since_ms: u64, timeout: Duration) -> Result<String> {
+    let started = Instant::now();
+    let mut last_progress = Instant::now();
+    let filter = LogFilter {
+        regex: Some(READY_LINE_PATTERN.to_string()),
+        ..LogFilter::default()
+    };
+
+    loop {
+        let ready = logs_tail(paths, 1, Some(since_ms), None, Some(filter.clone())).await?;
+        if let Some(line) = ready_line(&ready.lines) {
+            return Ok(line.to_string());
+        }
+
+        let info = status(paths).await?;
+        let last_exit_code = info.last_exit.and_then(|last_exit| last_exit.exit.code);
+        if let Some(code) = stopped_exit_code(&info.server, last_exit_code) {
+            anyhow::bail!("server stopped before it became ready (exit code: {code:?})");
+        }
+        if matches!(info.server, ServerStatus::Running { .. }) && rcon_answers(paths).await {
+            return Ok("RCON answered `list`".to_string());
+        }
+
+        if started.elapsed() >= timeout {
+            anyhow::bail!("server was not ready after {}s", timeout.as_secs());
+        }
+        if last_progress.elapsed() >= PROGRESS_INTERVAL {
+            last_progress = Instant::now();
+            eprintln!(
+                "[ready] waiting for the server to finish starting ({}s elapsed)",
+                started.elapsed().as_secs()
+            );
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+// The daemon filters with the regex, but it can also return its own
+// "earlier lines evicted" marker, so each line is checked again here.
+fn ready_line(lines: &[LogLine]) -> Option<&str> {
+    lines
+        .iter()
+        .rev()
+        .map(|line| line.line.trim())
+        .find(|line| is_ready_line(line))
+}
+
+fn is_ready_line(line: &str) -> bool {
+    line.split_once("Done (")
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .is_some_and(|(elapsed, rest)| !elapsed.is_empty() && rest.starts_with("! For help, type"))
+}
+
+/// `Some(exit code)` once the server is no longer starting or running.
+fn stopped_exit_code(server: &ServerStatus, last_exit_code: Option<i32>) -> Option<Option<i32>> {
+    match server {
+        ServerStatus::Idle {} => Some(last_exit_code),
+        ServerStatus::Exited { exit, .. } | ServerStatus::Crashed { exit, .. } => Some(exit.code),
+        _ => None,
+    }
+}
+
+async fn rcon_answers(paths: &RuntimePaths) -> bool {
+    let Ok(mut framed) = connect_or_start(paths).await else {
+        return false;
+    };
+    let req = Envelope {
+        id: 1,
+        profile: crate::client::target_profile(),
+        payload: Request::RconExec {
+            command: "list".to_string(),
+        },
+    };
+    matches!(
+        send_and_wait(&mut framed, req).await,
+        Ok(Response::RconResult { .. })
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use runner_core_v2::proto::{ExitInfo, LogLine, LogStream, ServerStatus};
+
+    use super::{ready_line, stopped_exit_code};
+
+    fn line(text: &str) -> LogLine {
+        LogLine {
+            at_ms: 0,
+            stream: LogStream::Stdout,
+            line: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn eviction_marker_is_not_a_ready_line() {
+        let marker = line("[runnerd] earlier lines evicted; the buffer keeps the last 5000 lines");
+        assert_eq!(ready_line(std::slice::from_ref(&marker)), None);
+        assert_eq!(
+            ready_line(&[
+                marker,
+                line("[12:00:00] [Server thread/INFO]: Done (12.3s)! For help, type \"help\"")
+            ]),
+            Some("[12:00:00] [Server thread/INFO]: Done (12.3s)! For help, type \"help\"")
+        );
+    }
+
+    #[test]
+    fn exited_and_crashed_servers_stop_the_wait() {
+        let exit = ExitInfo {
+            code: Some(1),
+            signal: None,
+        };
+        let exited = ServerStatus::Exited {
+            profile: "default".into(),
+            exit: exit.clone(),
+            at_ms: 0,
+        };
+        let crashed = ServerStatus::Crashed {
+            profile: "default".into(),
+            exit,
+            at_ms: 0,
+            last_logs: Vec::new(),
+        };
+        let starting = ServerStatus::Starting {
+            profile: "default".into(),
+            since_ms: 0,
+        };
+        assert_eq!(stopped_exit_code(&exited, None), Some(Some(1)));
+        assert_eq!(stopped_exit_code(&crashed, None), Some(Some(1)));
+        assert_eq!(
+            stopped_exit_code(&ServerStatus::Idle {}, Some(2)),
+            Some(Some(2))
+        );
+        assert_eq!(stopped_exit_code(&starting, None), None);
+    }
+}
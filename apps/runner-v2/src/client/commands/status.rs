@@ -0,0 +1,40 @@
+use anyhow::Result;
+use runner_core_v2::proto::{
+    Envelope, LastExit, ProvisionSummary, Request, Response, ServerStatus,
+};
+use runner_v2_utils::RuntimePaths;
+
+use crate::client::{connect_or_start, send_and_wait};
+
+pub struct StatusInfo {
+    pub server: ServerStatus,
+    pub crash_looping: bool,
+    pub last_exit: Option<LastExit>,
+    pub last_provision: Option<ProvisionSummary>,
+}
+
+pub async fn status(paths: &RuntimePaths) -> Result<StatusInfo> {
+    let mut framed = connect_or_start(paths).await?;
+    let req = Envelope {
+        id: 1,
+        profile: crate::client::target_profile(),
+        payload: Request::Status {},
+    };
+
+    match send_and_wait(&mut framed, req).await? {
+        Response::Status {
+            server,
+            crash_looping,
+            last_exit,
+            last_provision,
+            ..
+        } => Ok(StatusInfo {
+            server,
+            crash_looping,
+            last_exit: last_exit.map(|last_exit| *last_exit),
+            last_provision: last_provision.map(|provision| *provision),
+        }),
+        Response::Error(err) => Err(anyhow::anyhow!("status failed: {}", err.message)),
+        other => Err(anyhow::anyhow!("unexpected response: {other:?}")),
+    }
+}
@@ -1,13 +1,15 @@
 use anyhow::Result;
-use runner_core_v2::proto::{Envelope, ExitInfo, LogLine, Outbound, Request, Response};
+use runner_core_v2::proto::{Envelope, ExitInfo, LogFilter, LogLine, Request, Response};
+use runner_v2_utils::RuntimePaths;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
-use crate::client::connect_or_start;
+use crate::client::{connect_or_start, send_and_wait};
 
 pub struct StopInfo {
     pub exit: Option<ExitInfo>,
     pub stopped_at_ms: u64,
+    pub forced: bool,
 }
 
 pub struct LogsTailInfo {
@@ -15,23 +17,24 @@ pub struct LogsTailInfo {
     pub truncated: bool,
 }
 
-pub async fn stop(force: bool) -> Result<StopInfo> {
-    let mut framed = connect_or_start().await?;
+pub async fn stop(paths: &RuntimePaths, force: bool) -> Result<StopInfo> {
+    let mut framed = connect_or_start(paths).await?;
     let req = Envelope {
         id: 1,
+        profile: crate::client::target_profile(),
         payload: Request::Stop {
             force,
             grace_ms: None,
         },
     };
 
-    runner_ipc_v2::framing::send_request(&mut framed, &req).await?;
-    let resp = read_response_payload(&mut framed).await?;
+    let resp = send_and_wait(&mut framed, req).await?;
 
     match resp {
         Response::Stopped {
             exit,
             stopped_at_ms,
+            forced,
         } => {
             if let Ok(mut config) = load_deploy_key() {
                 config.should_autostart = Some(false);
@@ -40,6 +43,7 @@ pub async fn stop(force: bool) -> Result<StopInfo> {
             Ok(StopInfo {
                 exit,
                 stopped_at_ms,
+                forced,
             })
         }
         Response::Error(err) => Err(anyhow::anyhow!("stop failed: {}", err.message)),
@@ -47,15 +51,26 @@ pub async fn stop(force: bool) -> Result<StopInfo> {
     }
 }
 
-pub async fn logs_tail(lines: usize) -> Result<LogsTailInfo> {
-    let mut framed = connect_or_start().await?;
+pub async fn logs_tail(
+    paths: &RuntimePaths,
+    lines: usize,
+    since_ms: Option<u64>,
+    until_ms: Option<u64>,
+    filter: Option<LogFilter>,
+) -> Result<LogsTailInfo> {
+    let mut framed = connect_or_start(paths).await?;
     let req = Envelope {
         id: 1,
-        payload: Request::LogsTail { lines },
+        profile: crate::client::target_profile(),
+        payload: Request::LogsTail {
+            lines,
+            since_ms,
+            until_ms,
+            filter,
+        },
     };
 
-    runner_ipc_v2::framing::send_request(&mut framed, &req).await?;
-    let resp = read_response_payload(&mut framed).await?;
+    let resp = send_and_wait(&mut framed, req).await?;
 
     match resp {
         Response::LogsTail { lines, truncated } => Ok(LogsTailInfo { lines, truncated }),
@@ -64,15 +79,26 @@ pub async fn logs_tail(lines: usize) -> Result<LogsTailInfo> {
     }
 }
 
-pub async fn daemon_logs_tail(lines: usize) -> Result<LogsTailInfo> {
-    let mut framed = connect_or_start().await?;
+pub async fn daemon_logs_tail(
+    paths: &RuntimePaths,
+    lines: usize,
+    since_ms: Option<u64>,
+    until_ms: Option<u64>,
+    filter: Option<LogFilter>,
+) -> Result<LogsTailInfo> {
+    let mut framed = connect_or_start(paths).await?;
     let req = Envelope {
         id: 1,
-        payload: Request::DaemonLogsTail { lines },
+        profile: crate::client::target_profile(),
+        payload: Request::DaemonLogsTail {
+            lines,
+            since_ms,
+            until_ms,
+            filter,
+        },
     };
 
-    runner_ipc_v2::framing::send_request(&mut framed, &req).await?;
-    let resp = read_response_payload(&mut framed).await?;
+    let resp = send_and_wait(&mut framed, req).await?;
 
     match resp {
         Response::LogsTail { lines, truncated } => Ok(LogsTailInfo { lines, truncated }),
@@ -81,15 +107,26 @@ pub async fn daemon_logs_tail(lines: usize) -> Result<LogsTailInfo> {
     }
 }
 
-pub async fn logs_tail_follow(lines: usize) -> Result<LogsTailInfo> {
-    let mut framed = crate::client::connect_only().await?;
+pub async fn logs_tail_follow(
+    paths: &RuntimePaths,
+    lines: usize,
+    since_ms: Option<u64>,
+    until_ms: Option<u64>,
+    filter: Option<LogFilter>,
+) -> Result<LogsTailInfo> {
+    let mut framed = crate::client::connect_only(paths).await?;
     let req = Envelope {
         id: 1,
-        payload: Request::LogsTail { lines },
+        profile: crate::client::target_profile(),
+        payload: Request::LogsTail {
+            lines,
+            since_ms,
+            until_ms,
+            filter,
+        },
     };
 
-    runner_ipc_v2::framing::send_request(&mut framed, &req).await?;
-    let resp = read_response_payload(&mut framed).await?;
+    let resp = send_and_wait(&mut framed, req).await?;
 
     match resp {
         Response::LogsTail { lines, truncated } => Ok(LogsTailInfo { lines, truncated }),
@@ -98,15 +135,26 @@ pub async fn logs_tail_follow(lines: usize) -> Result<LogsTailInfo> {
     }
 }
 
-pub async fn daemon_logs_tail_follow(lines: usize) -> Result<LogsTailInfo> {
-    let mut framed = crate::client::connect_only().await?;
+pub async fn daemon_logs_tail_follow(
+    paths: &RuntimePaths,
+    lines: usize,
+    since_ms: Option<u64>,
+    until_ms: Option<u64>,
+    filter: Option<LogFilter>,
+) -> Result<LogsTailInfo> {
+    let mut framed = crate::client::connect_only(paths).await?;
     let req = Envelope {
         id: 1,
-        payload: Request::DaemonLogsTail { lines },
+        profile: crate::client::target_profile(),
+        payload: Request::DaemonLogsTail {
+            lines,
+            since_ms,
+            until_ms,
+            filter,
+        },
     };
 
-    runner_ipc_v2::framing::send_request(&mut framed, &req).await?;
-    let resp = read_response_payload(&mut framed).await?;
+    let resp = send_and_wait(&mut framed, req).await?;
 
     match resp {
         Response::LogsTail { lines, truncated } => Ok(LogsTailInfo { lines, truncated }),
@@ -115,20 +163,6 @@ pub async fn daemon_logs_tail_follow(lines: usize) -> Result<LogsTailInfo> {
     }
 }
 
-async fn read_response_payload(
-    framed: &mut runner_ipc_v2::framing::FramedStream,
-) -> Result<Response> {
-    loop {
-        let outbound = runner_ipc_v2::framing::read_outbound(framed)
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("runnerd closed the connection"))?;
-        match outbound {
-            Outbound::Response(env) => return Ok(env.payload),
-            Outbound::Event(_) => continue,
-        }
-    }
-}
-
 #[derive(Debug, Clone, Deserialize, Serialize)]
 struct DeployKeyConfig {
     hub_url: String,
@@ -138,12 +172,36 @@ struct DeployKeyConfig {
     deploy_key: String,
     #[serde(default)]
     prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    token_id: Option<String>,
     #[serde(default)]
     max_ram: Option<u32>,
     #[serde(default)]
+    jvm_args: Option<Vec<String>>,
+    #[serde(default)]
     should_autostart: Option<bool>,
     #[serde(default)]
     eula_accepted: Option<bool>,
+    #[serde(default)]
+    sync_operators: Option<bool>,
+    #[serde(default)]
+    keep_local_operators: Option<bool>,
+    #[serde(default)]
+    crash_loop_max_restarts: Option<u32>,
+    #[serde(default)]
+    crash_loop_window_secs: Option<u64>,
+    #[serde(default)]
+    stop_grace_secs: Option<u64>,
+    #[serde(default)]
+    log_buffer_lines: Option<usize>,
+    #[serde(default)]
+    daemon_log_buffer_lines: Option<usize>,
+    #[serde(default)]
+    maintenance: Option<bool>,
+    #[serde(default)]
+    restart_schedule: Option<serde_json::Value>,
+    #[serde(default)]
+    pinned_version: Option<String>,
 }
 
 fn save_deploy_key(config: &DeployKeyConfig) -> Result<()> {
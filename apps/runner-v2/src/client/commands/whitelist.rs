@@ -0,0 +1,40 @@
+use anyhow::Result;
+use runner_core_v2::proto::{Envelope, Request, Response};
+use runner_v2_utils::RuntimePaths;
+
+use crate::client::{connect_or_start, send_and_wait};
+
+pub struct WhitelistSync {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub written_directly: Vec<String>,
+    pub operators_added: Vec<String>,
+    pub operators_removed: Vec<String>,
+}
+
+pub async fn sync_whitelist(paths: &RuntimePaths) -> Result<WhitelistSync> {
+    let mut framed = connect_or_start(paths).await?;
+    let req = Envelope {
+        id: 1,
+        profile: crate::client::target_profile(),
+        payload: Request::SyncWhitelist {},
+    };
+
+    match send_and_wait(&mut framed, req).await? {
+        Response::WhitelistSynced {
+            added,
+            removed,
+            written_directly,
+            operators_added,
+            operators_removed,
+        } => Ok(WhitelistSync {
+            added,
+            removed,
+            written_directly,
+            operators_added,
+            operators_removed,
+        }),
+        Response::Error(err) => Err(anyhow::anyhow!("whitelist sync failed: {}", err.message)),
+        other => Err(anyhow::anyhow!("unexpected response: {other:?}")),
+    }
+}
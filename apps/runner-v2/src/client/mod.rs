@@ -1,30 +1,69 @@
 pub mod backup;
 mod commands;
+mod request;
 
-use runner_v2_utils::{ensure_dir, runtime_paths_v2};
+use runner_core_v2::proto::{Envelope, ErrorCode, ProfileId, Request, Response, RpcError};
+use runner_core_v2::PROTOCOL_VERSION;
+use runner_v2_utils::{ensure_dir, RuntimePaths, SOCKET_ENV};
+use std::sync::OnceLock;
 use std::time::Duration;
 use tokio::process::Command;
 
-pub use commands::auth::exec as auth;
-pub use commands::core::{ping, shutdown, up};
+pub use commands::auth::{exec as auth, rotate as rotate_token};
+pub use commands::config::{get_server_properties, set_pinned_version, set_server_properties};
+pub use commands::core::{
+    accept_eula, clear_crash_loop, list_servers, ping, set_channel, set_maintenance, shutdown, up,
+};
+pub use commands::metrics::metrics;
 pub use commands::rcon::{rcon_exec, rcon_interactive};
+pub use commands::ready::wait_ready;
+pub use commands::status::{status, StatusInfo};
 pub use commands::supervisor::{
     daemon_logs_tail, daemon_logs_tail_follow, logs_tail, logs_tail_follow, stop,
 };
+pub use commands::whitelist::sync_whitelist;
+pub(crate) use request::{send_and_wait, send_and_wait_with_events};
+
+static PROFILE: OnceLock<ProfileId> = OnceLock::new();
+
+/// Selects the server profile every request targets; unset means the daemon's
+/// default profile.
+pub fn set_profile(profile: Option<ProfileId>) {
+    if let Some(profile) = profile {
+        let _ = PROFILE.set(profile);
+    }
+}
+
+pub(crate) fn target_profile() -> Option<ProfileId> {
+    PROFILE.get().cloned()
+}
+
+pub(crate) async fn connect_or_start(
+    paths: &RuntimePaths,
+) -> anyhow::Result<runner_ipc_v2::framing::FramedStream> {
+    let mut framed = connect_or_start_unchecked(paths).await?;
+    negotiate_protocol(&mut framed).await?;
+    Ok(framed)
+}
 
-pub(crate) async fn connect_or_start() -> anyhow::Result<runner_ipc_v2::framing::FramedStream> {
-    let paths = runtime_paths_v2();
+// Skips the protocol handshake; used by commands that must keep working
+// against a mismatched daemon (ping reports it, shutdown stops it).
+pub(crate) async fn connect_or_start_unchecked(
+    paths: &RuntimePaths,
+) -> anyhow::Result<runner_ipc_v2::framing::FramedStream> {
     ensure_dir(&paths.runtime_dir)?;
 
-    if let Ok(stream) = runner_ipc_v2::socket::connect(&paths.socket_path).await {
-        return Ok(runner_ipc_v2::framing::framed(stream));
+    runner_ipc_v2::transport::endpoint(paths)?;
+
+    if let Ok(framed) = runner_ipc_v2::transport::connect(paths).await {
+        return Ok(framed);
     }
 
-    start_daemon_detached().await?;
+    start_daemon_detached(paths).await?;
 
     for _ in 0..30 {
-        if let Ok(stream) = runner_ipc_v2::socket::connect(&paths.socket_path).await {
-            return Ok(runner_ipc_v2::framing::framed(stream));
+        if let Ok(framed) = runner_ipc_v2::transport::connect(paths).await {
+            return Ok(framed);
         }
         tokio::time::sleep(Duration::from_millis(50)).await;
     }
@@ -32,15 +71,51 @@ pub(crate) async fn connect_or_start() -> anyhow::Result<runner_ipc_v2::framing:
     anyhow::bail!("failed to connect to runnerd2 after starting it");
 }
 
-pub(crate) async fn connect_only() -> anyhow::Result<runner_ipc_v2::framing::FramedStream> {
-    let paths = runtime_paths_v2();
-    runner_ipc_v2::socket::connect(&paths.socket_path)
-        .await
-        .map(runner_ipc_v2::framing::framed)
-        .map_err(Into::into)
+pub(crate) async fn connect_only(
+    paths: &RuntimePaths,
+) -> anyhow::Result<runner_ipc_v2::framing::FramedStream> {
+    let mut framed = runner_ipc_v2::transport::connect(paths).await?;
+    negotiate_protocol(&mut framed).await?;
+    Ok(framed)
+}
+
+async fn negotiate_protocol(
+    framed: &mut runner_ipc_v2::framing::FramedStream,
+) -> anyhow::Result<()> {
+    let req = Envelope {
+        id: 0,
+        profile: crate::client::target_profile(),
+        payload: Request::Ping {
+            client_version: env!("ATLAS_BUILD_VERSION").to_string(),
+            protocol_version: PROTOCOL_VERSION,
+        },
+    };
+    match send_and_wait(framed, req).await? {
+        Response::Error(err) if matches!(err.code, ErrorCode::VersionMismatch) => {
+            Err(version_mismatch_error(&err))
+        }
+        Response::Error(err) => Err(anyhow::anyhow!("handshake failed: {}", err.message)),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn version_mismatch_error(err: &RpcError) -> anyhow::Error {
+    let detail = |key: &str| {
+        err.details
+            .get(key)
+            .cloned()
+            .unwrap_or_else(|| "?".to_string())
+    };
+    anyhow::anyhow!(
+        "atlas-runner speaks protocol {} but the running atlas-runnerd speaks protocol {}. \
+         Update both to the same release, then restart the daemon with `atlas-runner daemon stop`.",
+        detail("client_protocol"),
+        detail("daemon_protocol")
+    )
 }
 
-async fn start_daemon_detached() -> anyhow::Result<()> {
+// The daemon inherits the socket path so it binds where this CLI connects.
+async fn start_daemon_detached(paths: &RuntimePaths) -> anyhow::Result<()> {
     // 1) Dev: run an arbitrary command via shell
     // Example:
     //   ATLAS_RUNNERD_CMD='cargo run -p runnerd-v2' cargo run -p runner-v2 -- ping
@@ -51,6 +126,7 @@ async fn start_daemon_detached() -> anyhow::Result<()> {
         let mut c = Command::new("sh");
 
         c.arg("-lc").arg(cmd);
+        c.env(SOCKET_ENV, &paths.socket_path);
         c.stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null())
@@ -63,6 +139,7 @@ async fn start_daemon_detached() -> anyhow::Result<()> {
     //   ATLAS_RUNNERD_PATH=target/debug/runnerd2 cargo run -p runner-v2 -- ping
     if let Ok(path) = std::env::var("ATLAS_RUNNERD_PATH") {
         Command::new(path)
+            .env(SOCKET_ENV, &paths.socket_path)
             .stdin(std::process::Stdio::null())
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null())
@@ -72,6 +149,7 @@ async fn start_daemon_detached() -> anyhow::Result<()> {
 
     // 3) Default: hope atlas-runnerd is on PATH
     Command::new("atlas-runnerd")
+        .env(SOCKET_ENV, &paths.socket_path)
         .stdin(std::process::Stdio::null())
         .stdout(std::process::Stdio::null())
         .stderr(std::process::Stdio::null())
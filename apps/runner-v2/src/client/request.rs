@@ -0,0 +1,151 @@
+use std::fmt;
+use std::time::Duration;
+
+use runner_core_v2::proto::{Envelope, Event, Outbound, Request, Response};
+use runner_core_v2::PROTOCOL_VERSION;
+use runner_ipc_v2::framing::{self, FramedStream};
+use tokio::time::{interval, sleep_until, Instant, MissedTickBehavior};
+
+const TIMEOUT_ENV: &str = "ATLAS_RUNNER_TIMEOUT_SECS";
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(10);
+const KEEPALIVE_REQUEST_ID: u64 = u64::MAX;
+
+/// Returned (inside `anyhow::Error`) when runnerd does not answer in time.
+#[derive(Debug)]
+pub struct RequestTimeout {
+    pub request: &'static str,
+    pub after: Duration,
+}
+
+impl fmt::Display for RequestTimeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "runnerd did not answer {} within {}s (set {} to wait longer)",
+            self.request,
+            self.after.as_secs(),
+            TIMEOUT_ENV
+        )
+    }
+}
+
+impl std::error::Error for RequestTimeout {}
+
+/// Sends `req` and waits for its response, skipping unrelated events.
+///
+/// `Start` waits with an idle timeout instead: the client pings the daemon
+/// periodically and any traffic (pongs, events) resets the deadline, so a slow
+/// first deploy keeps going while a wedged daemon still times out.
+pub async fn send_and_wait(
+    framed: &mut FramedStream,
+    req: Envelope<Request>,
+) -> anyhow::Result<Response> {
+    send_and_wait_with_events(framed, req, |_| {}).await
+}
+
+pub async fn send_and_wait_with_events(
+    framed: &mut FramedStream,
+    req: Envelope<Request>,
+    mut on_event: impl FnMut(&Event),
+) -> anyhow::Result<Response> {
+    let name = request_name(&req.payload);
+    let timeout = request_timeout(&req.payload);
+    let keepalive = matches!(req.payload, Request::Start { .. });
+    let id = req.id;
+
+    framing::send_request(framed, &req).await?;
+
+    let mut deadline = Instant::now() + timeout;
+    let mut pings = interval(KEEPALIVE_INTERVAL);
+    pings.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    pings.tick().await;
+
+    loop {
+        tokio::select! {
+            _ = pings.tick(), if keepalive => {
+                framing::send_request(framed, &keepalive_ping()).await?;
+            }
+            outbound = framing::read_outbound(framed) => {
+                let outbound = outbound?
+                    .ok_or_else(|| anyhow::anyhow!("runnerd closed the connection"))?;
+                match outbound {
+                    Outbound::Response(env) if env.id == id => return Ok(env.payload),
+                    Outbound::Response(_) => {}
+                    Outbound::Event(event) => on_event(&event),
+                }
+                if keepalive {
+                    deadline = Instant::now() + timeout;
+                }
+            }
+            _ = sleep_until(deadline) => {
+                return Err(RequestTimeout { request: name, after: timeout }.into());
+            }
+        }
+    }
+}
+
+fn request_timeout(req: &Request) -> Duration {
+    if let Some(secs) = std::env::var(TIMEOUT_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+    {
+        return Duration::from_secs(secs);
+    }
+
+    let secs = match req {
+        Request::Ping { .. }
+        | Request::Status {}
+        | Request::ListServers {}
+        | Request::Metrics {} => 5,
+        Request::GetServerProperties {} | Request::SetServerProperties { .. } => 10,
+        Request::LogsTail { .. } | Request::DaemonLogsTail { .. } => 10,
+        Request::RconExec { .. } | Request::RconOpen {} | Request::RconClose { .. } => 15,
+        Request::Start { .. } => 60,
+        Request::Stop { .. } | Request::Shutdown {} => 120,
+        Request::Backup {} => 600,
+        _ => 30,
+    };
+    Duration::from_secs(secs)
+}
+
+fn request_name(req: &Request) -> &'static str {
+    match req {
+        Request::Ping { .. } => "ping",
+        Request::Status {} => "status",
+        Request::ListServers {} => "list servers",
+        Request::Metrics {} => "metrics",
+        Request::ClearCrashLoop {} => "clear crash loop",
+        Request::SetMaintenance { .. } => "maintenance",
+        Request::SetPinnedVersion { .. } => "pin version",
+        Request::SetChannel { .. } => "set channel",
+        Request::Start { .. } => "start",
+        Request::Stop { .. } => "stop",
+        Request::Shutdown {} => "shutdown",
+        Request::LogsTail { .. } => "logs",
+        Request::DaemonLogsTail { .. } => "daemon logs",
+        Request::Backup {} => "backup",
+        Request::Subscribe { .. } | Request::Unsubscribe {} => "subscribe",
+        Request::RconExec { .. }
+        | Request::RconOpen {}
+        | Request::RconSend { .. }
+        | Request::RconClose { .. } => "rcon",
+        Request::GetServerProperties {} | Request::SetServerProperties { .. } => {
+            "server properties"
+        }
+        Request::AcceptEula { .. } => "accept eula",
+        Request::SyncWhitelist {} => "whitelist sync",
+        Request::SaveDeployKey { .. } => "save deploy key",
+    }
+}
+
+fn keepalive_ping() -> Envelope<Request> {
+    Envelope {
+        id: KEEPALIVE_REQUEST_ID,
+        profile: crate::client::target_profile(),
+        payload: Request::Ping {
+            client_version: env!("ATLAS_BUILD_VERSION").to_string(),
+            protocol_version: PROTOCOL_VERSION,
+        },
+    }
+}
@@ -0,0 +1,66 @@
+use anyhow::{bail, Context};
+
+/// Parses a `--since`/`--until` value: an RFC3339 timestamp
+/// (`2026-01-02T03:04:05Z`) or a duration before `now_ms` such as `30s`,
+/// `10m`, `2h` or `1d`.
+pub fn parse_log_time(value: &str, now_ms: u64) -> anyhow::Result<u64> {
+    let value = value.trim();
+    if let Some(ago_ms) = parse_relative_ms(value) {
+        return Ok(now_ms.saturating_sub(ago_ms));
+    }
+    let at = chrono::DateTime::parse_from_rfc3339(value).with_context(|| {
+        format!("invalid time '{value}': expected RFC3339 or a duration like 10m or 2h")
+    })?;
+    u64::try_from(at.timestamp_millis()).with_context(|| format!("time '{value}' is before 1970"))
+}
+
+/// Resolves both bounds against the same `now`, rejecting an inverted range.
+pub fn log_window(
+    since: Option<&str>,
+    until: Option<&str>,
+) -> anyhow::Result<(Option<u64>, Option<u64>)> {
+    let now_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+    let since_ms = since.map(|v| parse_log_time(v, now_ms)).transpose()?;
+    let until_ms = until.map(|v| parse_log_time(v, now_ms)).transpose()?;
+    if let (Some(since_ms), Some(until_ms)) = (since_ms, until_ms) {
+        if since_ms > until_ms {
+            bail!("--since must not be later than --until");
+        }
+    }
+    Ok((since_ms, until_ms))
+}
+
+fn parse_relative_ms(value: &str) -> Option<u64> {
+    let unit_ms = match value.chars().last()? {
+        's' => 1_000,
+        'm' => 60_000,
+        'h' => 3_600_000,
+        'd' => 86_400_000,
+        _ => return None,
+    };
+    let amount = value[..value.len() - 1].parse::<u64>().ok()?;
+    amount.checked_mul(unit_ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_relative_durations_and_rfc3339() {
+        let now = 10 * 3_600_000;
+        assert_eq!(parse_log_time("10m", now).unwrap(), now - 600_000);
+        assert_eq!(parse_log_time("2h", now).unwrap(), now - 7_200_000);
+        assert_eq!(
+            parse_log_time("1970-01-01T00:00:01.5+00:00", now).unwrap(),
+            1_500
+        );
+        assert!(parse_log_time("yesterday", now).is_err());
+    }
+
+    #[test]
+    fn rejects_inverted_ranges() {
+        assert!(log_window(Some("1h"), Some("2h")).is_err());
+        assert!(log_window(Some("2h"), Some("1h")).is_ok());
+    }
+}
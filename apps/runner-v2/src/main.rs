@@ -1,17 +1,29 @@
 use atlas_client::hub::{DistributionReleaseAsset, DistributionReleaseResponse, HubClient};
-use clap::{Args as ClapArgs, Parser, Subcommand};
-use runner_core_v2::proto::{LogLine, LogStream};
-use runner_v2_utils::runtime_paths_v2;
+use atlas_client::hub_url::normalize_hub_url_string;
+use clap::{Args as ClapArgs, Parser, Subcommand, ValueEnum};
+use runner_core_v2::proto::{
+    ExitReason, LastExit, LogFilter, LogLevel, LogLine, LogStream, ProvisionSummary, ServerStatus,
+    ServerSummary, DEFAULT_PROFILE,
+};
+use runner_v2_utils::{runtime_paths_for_socket, runtime_paths_v2, RuntimePaths};
 use semver::Version;
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use tokio::time::{sleep, Duration};
 
 mod client;
+mod log_window;
 
 #[derive(Parser)]
 #[command(version = env!("ATLAS_BUILD_VERSION"))]
 struct Args {
+    #[arg(long, global = true, value_name = "PATH")]
+    socket: Option<PathBuf>,
+
+    #[arg(long, global = true, value_name = "PROFILE")]
+    profile: Option<String>,
+
     #[command(subcommand)]
     cmd: RootCommand,
 }
@@ -30,10 +42,38 @@ enum RootCommand {
         #[command(subcommand)]
         command: DaemonCommand,
     },
+    #[command(args_conflicts_with_subcommands = true)]
+    Config {
+        #[arg(long, value_name = "VERSION", conflicts_with = "unpin")]
+        pin: Option<String>,
+
+        #[arg(long)]
+        unpin: bool,
+
+        #[command(subcommand)]
+        command: Option<ConfigCommand>,
+    },
     Host {
         #[command(subcommand)]
         command: HostCommand,
     },
+    Maintenance {
+        #[command(subcommand)]
+        command: MaintenanceCommand,
+    },
+    Channel {
+        #[arg(value_parser = ["dev", "beta", "production"])]
+        channel: String,
+    },
+    Token {
+        #[command(subcommand)]
+        command: TokenCommand,
+    },
+    #[command(visible_alias = "list")]
+    Ps {
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -41,6 +81,17 @@ enum AuthCommand {
     Login(AuthLoginArgs),
 }
 
+#[derive(Subcommand)]
+enum TokenCommand {
+    Rotate(TokenRotateArgs),
+}
+
+#[derive(ClapArgs)]
+struct TokenRotateArgs {
+    #[arg(long, value_name = "TOKEN_NAME")]
+    token_name: Option<String>,
+}
+
 #[derive(ClapArgs)]
 struct AuthLoginArgs {
     #[arg(long, value_name = "HUB_URL")]
@@ -64,13 +115,15 @@ enum ServerCommand {
     Command(ServerCommandArgs),
     Console,
     Backup,
+    AcceptEula(ServerAcceptEulaArgs),
+    SyncWhitelist,
+    Metrics,
+    Status,
+    ClearCrashLoop,
 }
 
 #[derive(ClapArgs)]
 struct ServerStartArgs {
-    #[arg(long, value_name = "PROFILE", default_value = "default")]
-    profile: String,
-
     #[arg(long, value_name = "PACK_BLOB")]
     pack_blob: Option<PathBuf>,
 
@@ -82,6 +135,20 @@ struct ServerStartArgs {
 
     #[arg(long, default_value_t = false)]
     accept_eula: bool,
+
+    #[arg(
+        long,
+        value_name = "SECS",
+        num_args = 0..=1,
+        default_missing_value = "300"
+    )]
+    wait_ready: Option<u64>,
+}
+
+#[derive(ClapArgs)]
+struct ServerAcceptEulaArgs {
+    #[arg(long, value_name = "SERVER_ROOT")]
+    server_root: Option<PathBuf>,
 }
 
 #[derive(ClapArgs)]
@@ -95,13 +162,61 @@ struct ServerLogsArgs {
     #[arg(short = 'n', long = "lines", default_value_t = 200)]
     lines: usize,
 
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    format: LogFormat,
+
+    #[arg(long)]
+    no_color: bool,
+
     #[arg(short = 'f', long = "follow")]
     follow: bool,
+
+    #[arg(long, value_name = "TIME")]
+    since: Option<String>,
+
+    #[arg(long, value_name = "TIME", conflicts_with = "follow")]
+    until: Option<String>,
+
+    #[arg(long, value_name = "TEXT")]
+    grep: Option<String>,
+
+    #[arg(long, value_name = "PATTERN")]
+    regex: Option<String>,
+
+    #[arg(short = 'C', long, value_name = "LINES", default_value_t = 0)]
+    context: usize,
 }
 
 #[derive(ClapArgs)]
 struct ServerCommandArgs {
     command: String,
+    #[arg(long)]
+    strict: bool,
+    #[arg(long)]
+    quiet: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum LogFormat {
+    Text,
+    Json,
+    Raw,
+}
+
+#[derive(Clone, Copy)]
+struct LogOutput {
+    format: LogFormat,
+    color: bool,
+}
+
+impl LogOutput {
+    fn new(format: LogFormat, no_color: bool) -> Self {
+        let color = !no_color
+            && !matches!(format, LogFormat::Json)
+            && std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+            && std::io::stdout().is_terminal();
+        Self { format, color }
+    }
 }
 
 #[derive(Subcommand)]
@@ -116,8 +231,52 @@ struct DaemonLogsArgs {
     #[arg(short = 'n', long = "lines", default_value_t = 200)]
     lines: usize,
 
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    format: LogFormat,
+
+    #[arg(long)]
+    no_color: bool,
+
     #[arg(short = 'f', long = "follow")]
     follow: bool,
+
+    #[arg(long, value_name = "TIME")]
+    since: Option<String>,
+
+    #[arg(long, value_name = "TIME", conflicts_with = "follow")]
+    until: Option<String>,
+
+    #[arg(long, value_name = "TEXT")]
+    grep: Option<String>,
+
+    #[arg(long, value_name = "PATTERN")]
+    regex: Option<String>,
+
+    #[arg(short = 'C', long, value_name = "LINES", default_value_t = 0)]
+    context: usize,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    Get(ConfigGetArgs),
+    Set(ConfigSetArgs),
+}
+
+#[derive(ClapArgs)]
+struct ConfigGetArgs {
+    key: Option<String>,
+}
+
+#[derive(ClapArgs)]
+struct ConfigSetArgs {
+    key: String,
+    value: String,
+}
+
+#[derive(Subcommand)]
+enum MaintenanceCommand {
+    On,
+    Off,
 }
 
 #[derive(Subcommand)]
@@ -144,6 +303,15 @@ struct HostPathArgs {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    let paths = match args.socket {
+        Some(socket) => runtime_paths_for_socket(std::path::absolute(socket)?),
+        None => runtime_paths_v2(),
+    };
+    let profile = args
+        .profile
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+    client::set_profile(args.profile);
     match args.cmd {
         RootCommand::Auth {
             command:
@@ -160,18 +328,17 @@ async fn main() -> anyhow::Result<()> {
         RootCommand::Server {
             command:
                 ServerCommand::Start(ServerStartArgs {
-                    profile,
                     pack_blob,
                     server_root,
                     max_ram,
                     accept_eula,
+                    wait_ready,
                 }),
         } => {
-            if profile != "default" {
-                eprintln!("Ignoring --profile; runner uses a single profile: default");
-            }
+            let started_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
             let resp = client::up(
-                "default".to_string(),
+                &paths,
+                profile.clone(),
                 pack_blob,
                 server_root,
                 max_ram,
@@ -179,11 +346,24 @@ async fn main() -> anyhow::Result<()> {
             )
             .await?;
             println!("{resp}");
+            if let Some(timeout_secs) = wait_ready {
+                eprintln!("[ready] waiting up to {timeout_secs}s for the server to accept players");
+                let ready = client::wait_ready(
+                    &paths,
+                    started_ms,
+                    std::time::Duration::from_secs(timeout_secs),
+                )
+                .await?;
+                println!("server is ready: {ready}");
+            }
         }
         RootCommand::Server {
             command: ServerCommand::Stop(ServerStopArgs { force }),
         } => {
-            let resp = client::stop(force).await?;
+            let resp = client::stop(&paths, force).await?;
+            if resp.forced && !force {
+                eprintln!("server did not exit within the grace period and was killed");
+            }
             if let Some(exit) = resp.exit {
                 println!(
                     "stopped at {} (exit code: {:?})",
@@ -194,14 +374,28 @@ async fn main() -> anyhow::Result<()> {
             }
         }
         RootCommand::Server {
-            command: ServerCommand::Logs(ServerLogsArgs { lines, follow }),
+            command:
+                ServerCommand::Logs(ServerLogsArgs {
+                    lines,
+                    format,
+                    no_color,
+                    follow,
+                    since,
+                    until,
+                    grep,
+                    regex,
+                    context,
+                }),
         } => {
+            let (since_ms, until_ms) = log_window::log_window(since.as_deref(), until.as_deref())?;
+            let output = LogOutput::new(format, no_color);
+            let filter = log_filter(grep, regex, context);
             if follow {
-                follow_logs(lines, false).await?;
+                follow_logs(&paths, lines, since_ms, filter, output, false).await?;
             } else {
-                let resp = client::logs_tail(lines).await?;
+                let resp = client::logs_tail(&paths, lines, since_ms, until_ms, filter).await?;
                 for line in resp.lines {
-                    print_log_line(&line);
+                    print_log_line(&line, output);
                 }
                 if resp.truncated {
                     eprintln!("log output was truncated; use --lines or --follow for more output");
@@ -209,15 +403,20 @@ async fn main() -> anyhow::Result<()> {
             }
         }
         RootCommand::Server {
-            command: ServerCommand::Command(ServerCommandArgs { command }),
+            command:
+                ServerCommand::Command(ServerCommandArgs {
+                    command,
+                    strict,
+                    quiet,
+                }),
         } => {
-            let framed = client::connect_or_start().await?;
-            client::rcon_exec(framed, command).await?;
+            let framed = client::connect_or_start(&paths).await?;
+            client::rcon_exec(framed, command, strict, quiet).await?;
         }
         RootCommand::Server {
             command: ServerCommand::Console,
         } => {
-            let framed = client::connect_or_start().await?;
+            let framed = client::connect_or_start(&paths).await?;
             client::rcon_interactive(framed)
                 .await
                 .map_err(|e| anyhow::anyhow!("interactive rcon failed: {e}"))?;
@@ -225,40 +424,188 @@ async fn main() -> anyhow::Result<()> {
         RootCommand::Server {
             command: ServerCommand::Backup,
         } => {
-            let path = client::backup::backup_now().await?;
+            let path = client::backup::backup_now(&paths).await?;
             println!("backup created: {}", path);
         }
+        RootCommand::Server {
+            command: ServerCommand::AcceptEula(ServerAcceptEulaArgs { server_root }),
+        } => {
+            let resp = client::accept_eula(&paths, server_root).await?;
+            println!("{resp}");
+        }
+        RootCommand::Server {
+            command: ServerCommand::ClearCrashLoop,
+        } => {
+            let resp = client::clear_crash_loop(&paths).await?;
+            println!("{resp}");
+        }
+        RootCommand::Server {
+            command: ServerCommand::Metrics,
+        } => {
+            let (summary, history) = client::metrics(&paths).await?;
+            match &summary.latest {
+                Some(latest) => {
+                    println!("tps: {:.2}", latest.tps);
+                    if let Some(mspt) = latest.mspt {
+                        println!("mspt: {:.2}", mspt);
+                    }
+                    if let Some(avg) = summary.avg_tps {
+                        println!("avg tps ({} samples): {:.2}", history.len(), avg);
+                    }
+                    if let Some(avg) = summary.avg_mspt {
+                        println!("avg mspt: {:.2}", avg);
+                    }
+                }
+                None if summary.unsupported => {
+                    println!("this server does not support a known TPS command")
+                }
+                None => println!("no samples yet"),
+            }
+        }
+        RootCommand::Server {
+            command: ServerCommand::Status,
+        } => {
+            let status = client::status(&paths).await?;
+            println!("{}", describe_server_status(&status));
+        }
+        RootCommand::Server {
+            command: ServerCommand::SyncWhitelist,
+        } => {
+            let sync = client::sync_whitelist(&paths).await?;
+            if sync.added.is_empty() && sync.removed.is_empty() {
+                println!("whitelist already up to date");
+            }
+            for name in &sync.operators_added {
+                println!("opped {name}");
+            }
+            for name in &sync.operators_removed {
+                println!("deopped {name}");
+            }
+            for name in &sync.added {
+                println!("added {name}");
+            }
+            for name in &sync.removed {
+                println!("removed {name}");
+            }
+            if !sync.written_directly.is_empty() {
+                println!(
+                    "written to whitelist.json by UUID: {}",
+                    sync.written_directly.join(", ")
+                );
+            }
+        }
         RootCommand::Daemon {
             command: DaemonCommand::Status,
         } => {
-            let resp = client::ping().await?;
+            let resp = client::ping(&paths).await?;
             println!("{resp}");
         }
         RootCommand::Daemon {
             command: DaemonCommand::Stop,
         } => {
-            let resp = client::shutdown().await?;
+            let resp = client::shutdown(&paths).await?;
             println!("{resp}");
         }
         RootCommand::Daemon {
-            command: DaemonCommand::Logs(DaemonLogsArgs { lines, follow }),
+            command:
+                DaemonCommand::Logs(DaemonLogsArgs {
+                    lines,
+                    format,
+                    no_color,
+                    follow,
+                    since,
+                    until,
+                    grep,
+                    regex,
+                    context,
+                }),
         } => {
+            let (since_ms, until_ms) = log_window::log_window(since.as_deref(), until.as_deref())?;
+            let output = LogOutput::new(format, no_color);
+            let filter = log_filter(grep, regex, context);
             if follow {
-                follow_logs(lines, true).await?;
+                follow_logs(&paths, lines, since_ms, filter, output, true).await?;
             } else {
-                let resp = client::daemon_logs_tail(lines).await?;
+                let resp =
+                    client::daemon_logs_tail(&paths, lines, since_ms, until_ms, filter).await?;
                 for line in resp.lines {
-                    print_log_line(&line);
+                    print_log_line(&line, output);
                 }
                 if resp.truncated {
                     eprintln!("log output was truncated; use --lines or --follow for more output");
                 }
             }
         }
+        RootCommand::Config {
+            command: Some(ConfigCommand::Get(ConfigGetArgs { key })),
+            ..
+        } => {
+            let properties = client::get_server_properties(&paths).await?;
+            match key {
+                Some(key) => match properties.get(&key) {
+                    Some(value) => println!("{value}"),
+                    None => anyhow::bail!("{key} is not set in server.properties"),
+                },
+                None => {
+                    for (key, value) in properties {
+                        println!("{key}={value}");
+                    }
+                }
+            }
+        }
+        RootCommand::Config {
+            command: Some(ConfigCommand::Set(ConfigSetArgs { key, value })),
+            ..
+        } => {
+            let changes = std::collections::BTreeMap::from([(key.clone(), value)]);
+            let update = client::set_server_properties(&paths, changes).await?;
+            if update.changed.is_empty() {
+                println!("{key} is unchanged");
+            } else {
+                println!("updated {}", update.changed.join(", "));
+            }
+            if update.restart_required {
+                println!("restart the server for the change to take effect");
+            }
+        }
+        RootCommand::Config {
+            command: None,
+            pin,
+            unpin,
+        } => {
+            if pin.is_none() && !unpin {
+                anyhow::bail!("expected a subcommand, --pin <VERSION> or --unpin");
+            }
+            let resp = client::set_pinned_version(&paths, pin).await?;
+            println!("{resp}");
+        }
+        RootCommand::Channel { channel } => {
+            let resp = client::set_channel(&paths, channel).await?;
+            println!("{resp}");
+        }
+        RootCommand::Token {
+            command: TokenCommand::Rotate(TokenRotateArgs { token_name }),
+        } => {
+            let resp = client::rotate_token(token_name).await?;
+            println!("{resp}");
+        }
+        RootCommand::Ps { json } => {
+            let servers = client::list_servers(&paths).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&servers)?);
+            } else {
+                print!("{}", format_server_table(&servers));
+            }
+        }
+        RootCommand::Maintenance { command } => {
+            let on = matches!(command, MaintenanceCommand::On);
+            let resp = client::set_maintenance(&paths, on).await?;
+            println!("{resp}");
+        }
         RootCommand::Host {
             command: HostCommand::Path(HostPathArgs { server_root }),
         } => {
-            let root = resolve_server_root(server_root);
+            let root = resolve_server_root(&paths, &profile, server_root);
             println!("{}", root.display());
         }
         RootCommand::Host {
@@ -270,13 +617,22 @@ async fn main() -> anyhow::Result<()> {
     }
     Ok(())
 }
-async fn follow_logs(lines: usize, daemon_logs: bool) -> anyhow::Result<()> {
+async fn follow_logs(
+    paths: &RuntimePaths,
+    lines: usize,
+    since_ms: Option<u64>,
+    filter: Option<LogFilter>,
+    output: LogOutput,
+    daemon_logs: bool,
+) -> anyhow::Result<()> {
     let mut last_at_ms = 0u64;
     let mut last_lines: Vec<String> = Vec::new();
 
     loop {
         let resp = if daemon_logs {
-            match client::daemon_logs_tail_follow(lines).await {
+            match client::daemon_logs_tail_follow(paths, lines, since_ms, None, filter.clone())
+                .await
+            {
                 Ok(resp) => resp,
                 Err(err) => {
                     eprintln!("Daemon connection lost: {}", err);
@@ -284,7 +640,7 @@ async fn follow_logs(lines: usize, daemon_logs: bool) -> anyhow::Result<()> {
                 }
             }
         } else {
-            match client::logs_tail_follow(lines).await {
+            match client::logs_tail_follow(paths, lines, since_ms, None, filter.clone()).await {
                 Ok(resp) => resp,
                 Err(err) => {
                     eprintln!("Daemon connection lost: {}", err);
@@ -297,13 +653,13 @@ async fn follow_logs(lines: usize, daemon_logs: bool) -> anyhow::Result<()> {
                 last_at_ms = line.at_ms;
                 last_lines.clear();
                 last_lines.push(line.line.clone());
-                print_log_line(&line);
+                print_log_line(&line, output);
                 continue;
             }
 
             if line.at_ms == last_at_ms && !last_lines.contains(&line.line) {
                 last_lines.push(line.line.clone());
-                print_log_line(&line);
+                print_log_line(&line, output);
             }
         }
 
@@ -312,20 +668,183 @@ async fn follow_logs(lines: usize, daemon_logs: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn print_log_line(line: &LogLine) {
+fn log_filter(grep: Option<String>, regex: Option<String>, context: usize) -> Option<LogFilter> {
+    (grep.is_some() || regex.is_some()).then_some(LogFilter {
+        contains: grep,
+        regex,
+        context,
+    })
+}
+
+fn print_log_line(line: &LogLine, output: LogOutput) {
+    use std::io::Write;
+
     let stream = match line.stream {
         LogStream::Stdout => "stdout",
         LogStream::Stderr => "stderr",
     };
-    println!("[{}] {}", stream, line.line.trim_end());
+    let text = line.line.trim_end();
+    let color = match line.level() {
+        Some(LogLevel::Error) if output.color => Some("\x1b[31m"),
+        Some(LogLevel::Warn) if output.color => Some("\x1b[33m"),
+        Some(LogLevel::Debug) if output.color => Some("\x1b[2m"),
+        _ => None,
+    };
+    let mut out = std::io::stdout().lock();
+    let written = match (output.format, color) {
+        (LogFormat::Json, _) => writeln!(
+            out,
+            "{}",
+            serde_json::json!({ "at_ms": line.at_ms, "stream": stream, "line": text })
+        ),
+        (LogFormat::Text, None) => writeln!(out, "[{}] {}", stream, text),
+        (LogFormat::Text, Some(color)) => writeln!(out, "{color}[{}] {}\x1b[0m", stream, text),
+        (LogFormat::Raw, None) => writeln!(out, "{}", text),
+        (LogFormat::Raw, Some(color)) => writeln!(out, "{color}{}\x1b[0m", text),
+    };
+    // Flush per line so `--follow | grep` sees output immediately, and stop
+    // quietly once the reader goes away.
+    if let Err(err) = written.and_then(|_| out.flush()) {
+        if err.kind() == std::io::ErrorKind::BrokenPipe {
+            std::process::exit(0);
+        }
+    }
+}
+
+fn describe_server_status(status: &client::StatusInfo) -> String {
+    let mut out = match &status.server {
+        ServerStatus::Idle {} => "server is stopped".to_string(),
+        ServerStatus::Starting { .. } => "server is starting".to_string(),
+        ServerStatus::Running { pid, .. } => format!("server is running (pid {pid})"),
+        ServerStatus::Stopping { .. } => "server is stopping".to_string(),
+        ServerStatus::Exited { .. } | ServerStatus::Crashed { .. } => {
+            "server is stopped".to_string()
+        }
+    };
+    if let Some(last_exit) = &status.last_exit {
+        let running = matches!(
+            status.server,
+            ServerStatus::Running { .. } | ServerStatus::Starting { .. }
+        );
+        let exit = describe_exit(last_exit);
+        if running {
+            out.push_str(&format!("\nlast exit: {exit}"));
+        } else {
+            out.push_str(&format!(" because it {exit}"));
+        }
+    }
+    if status.crash_looping {
+        out.push_str("\ncrash loop detected; automatic restarts are paused");
+    }
+    if let Some(provision) = &status.last_provision {
+        out.push_str(&format!(
+            "\nlast provision: {}",
+            describe_provision(provision)
+        ));
+    }
+    out
 }
 
-fn resolve_server_root(server_root: Option<PathBuf>) -> PathBuf {
+fn describe_provision(provision: &ProvisionSummary) -> String {
+    let phases = provision
+        .phases
+        .iter()
+        .map(|phase| format!("{} {}ms", phase.name, phase.duration_ms))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{}ms ({phases}); {} files written, {} artifacts linked",
+        provision.total_ms, provision.files_written, provision.artifacts_linked
+    )
+}
+
+fn describe_exit(last_exit: &LastExit) -> String {
+    let status = match (last_exit.exit.code, last_exit.exit.signal) {
+        (Some(code), _) => format!("code {code}"),
+        (None, Some(signal)) => format!("signal {signal}"),
+        (None, None) => "an unknown status".to_string(),
+    };
+    match last_exit.reason {
+        ExitReason::Graceful => format!("shut down cleanly with {status}"),
+        ExitReason::Crash => format!("crashed with {status}"),
+        ExitReason::OutOfMemory => format!("ran out of memory ({status})"),
+        ExitReason::ForceKilled => "was force-killed".to_string(),
+    }
+}
+
+fn format_server_table(servers: &[ServerSummary]) -> String {
+    let header = [
+        "PROFILE", "STATE", "PID", "UPTIME", "PACK", "CHANNEL", "VERSION", "PORT", "PLAYERS",
+        "FLAGS",
+    ]
+    .map(String::from);
+    let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+    let mut rows = vec![header.to_vec()];
+    for server in servers {
+        let mut flags = Vec::new();
+        if server.maintenance {
+            flags.push("maintenance");
+        }
+        if server.crash_looping {
+            flags.push("crash-loop");
+        }
+        rows.push(vec![
+            server.profile.clone(),
+            server.state.clone(),
+            or_dash(server.pid.map(|pid| pid.to_string())),
+            or_dash(server.uptime_ms.map(format_uptime)),
+            or_dash(server.pack_id.clone()),
+            or_dash(server.channel.clone()),
+            or_dash(server.version.clone()),
+            or_dash(server.port.map(|port| port.to_string())),
+            server.players.to_string(),
+            if flags.is_empty() {
+                "-".to_string()
+            } else {
+                flags.join(",")
+            },
+        ]);
+    }
+
+    let mut widths = vec![0; rows[0].len()];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut out = String::new();
+    for row in rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn format_uptime(ms: u64) -> String {
+    let secs = ms / 1000;
+    match (secs / 86_400, secs / 3600 % 24, secs / 60 % 60) {
+        (0, 0, 0) => format!("{secs}s"),
+        (0, 0, m) => format!("{m}m"),
+        (0, h, m) => format!("{h}h{m:02}m"),
+        (d, h, _) => format!("{d}d{h:02}h"),
+    }
+}
+
+fn resolve_server_root(
+    paths: &RuntimePaths,
+    profile: &str,
+    server_root: Option<PathBuf>,
+) -> PathBuf {
     if let Some(value) = server_root {
         return value;
     }
-    let paths = runtime_paths_v2();
-    paths.runtime_dir.join("servers").join("default")
+    paths.runtime_dir.join("servers").join(profile)
 }
 
 async fn install_systemd(
@@ -366,7 +885,7 @@ async fn ensure_runner_binary_up_to_date() -> anyhow::Result<bool> {
         }
     }
 
-    let arch = normalize_distribution_arch(std::env::consts::ARCH)?;
+    let arch = protocol::current_arch()?;
     let release = hub
         .get_latest_distribution_release("runner", "linux", arch)
         .await?;
@@ -389,7 +908,7 @@ async fn download_runnerd_via_distribution_api() -> anyhow::Result<PathBuf> {
         }
     }
 
-    let arch = normalize_distribution_arch(std::env::consts::ARCH)?;
+    let arch = protocol::current_arch()?;
     let release = hub
         .get_latest_distribution_release("runnerd", "linux", arch)
         .await?;
@@ -484,7 +1003,7 @@ fn resolve_install_hub_url() -> anyhow::Result<String> {
     if let Ok(url) = std::env::var("ATLAS_HUB_URL") {
         let trimmed = url.trim();
         if !trimmed.is_empty() {
-            return Ok(trimmed.to_string());
+            return Ok(normalize_hub_url_string(trimmed)?);
         }
     }
 
@@ -513,7 +1032,7 @@ fn resolve_install_hub_url() -> anyhow::Result<String> {
                 deploy_config_path.display()
             )
         })?;
-    Ok(hub_url.to_string())
+    Ok(normalize_hub_url_string(hub_url)?)
 }
 
 fn resolve_runnerd_deploy_config_path() -> anyhow::Result<PathBuf> {
@@ -528,16 +1047,6 @@ fn resolve_runnerd_deploy_config_path() -> anyhow::Result<PathBuf> {
     ))
 }
 
-fn normalize_distribution_arch(arch: &str) -> anyhow::Result<&'static str> {
-    match arch {
-        "x86_64" | "amd64" => Ok("x64"),
-        "aarch64" | "arm64" => Ok("arm64"),
-        other => Err(anyhow::anyhow!(
-            "Unsupported architecture '{other}' for runnerd distribution install"
-        )),
-    }
-}
-
 fn run_systemctl<const N: usize>(args: [&str; N]) -> anyhow::Result<()> {
     let status = Command::new("systemctl").args(args).status()?;
     if !status.success() {
@@ -1,16 +1,28 @@
 use anyhow::{Context, Result};
 use protocol::PackBlob;
-use std::collections::HashSet;
+use protocol::config::atlas::parse_config;
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 use tokio::fs;
 
+pub mod template;
+
 pub struct Assembler {
     runtime_dir: PathBuf,
+    template_vars: BTreeMap<String, String>,
 }
 
 impl Assembler {
     pub fn new(runtime_dir: PathBuf) -> Self {
-        Self { runtime_dir }
+        Self {
+            runtime_dir,
+            template_vars: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_template_vars(mut self, vars: BTreeMap<String, String>) -> Self {
+        self.template_vars = vars;
+        self
     }
 
     pub async fn assemble(&self, blob: &PackBlob) -> Result<()> {
@@ -21,8 +33,21 @@ impl Assembler {
 
         println!("Writing {} files...", blob.files.len());
 
+        // Templates are `*.tmpl` files (written without the suffix) plus any path
+        // listed under `[templates] files` in atlas.toml; everything else is byte-exact.
+        let listed_templates = blob
+            .files
+            .get("atlas.toml")
+            .and_then(|bytes| std::str::from_utf8(bytes).ok())
+            .and_then(|contents| parse_config(contents).ok())
+            .and_then(|config| config.templates)
+            .map(|templates| templates.files)
+            .unwrap_or_default();
+
         for (rel_path, data) in &blob.files {
-            let target_path = self.runtime_dir.join(rel_path);
+            let template_path = rel_path.strip_suffix(template::TEMPLATE_SUFFIX);
+            let is_template = template_path.is_some() || listed_templates.contains(rel_path);
+            let target_path = self.runtime_dir.join(template_path.unwrap_or(rel_path));
 
             // Ensure parent directory exists
             if let Some(parent) = target_path.parent() {
@@ -32,6 +57,15 @@ impl Assembler {
             // Write the file
             // TODO: Handle config safety policy (don't overwrite if modified locally)
             // For now, we'll just write it.
+            if is_template {
+                let contents = std::str::from_utf8(data)
+                    .with_context(|| format!("Template {} is not valid UTF-8", rel_path))?;
+                let rendered = template::render(rel_path, contents, &self.template_vars)?;
+                fs::write(target_path, rendered)
+                    .await
+                    .context(format!("Failed to write file: {}", rel_path))?;
+                continue;
+            }
             fs::write(target_path, data)
                 .await
                 .context(format!("Failed to write file: {}", rel_path))?;
@@ -0,0 +1,178 @@
+use crate::hub::whitelist::InstanceConfig;
+use anyhow::{Result, bail};
+use protocol::PackBlob;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+pub const TEMPLATE_SUFFIX: &str = ".tmpl";
+pub const VAR_PREFIX: &str = "ATLAS_VAR_";
+const DEFAULT_PORT: u16 = 25565;
+
+/// Built-in variables, then `[template_vars]` from instance.toml, then any
+/// `ATLAS_VAR_*` environment variables; later sources win.
+pub fn template_vars(blob: &PackBlob, config: &InstanceConfig) -> BTreeMap<String, String> {
+    let mut vars = BTreeMap::new();
+    let builtins = [
+        ("PACK_ID", blob.metadata.pack_id.clone()),
+        ("PACK_VERSION", blob.metadata.version.clone()),
+        ("MINECRAFT_VERSION", blob.metadata.minecraft_version.clone()),
+    ];
+    for (name, value) in builtins {
+        vars.insert(format!("{VAR_PREFIX}{name}"), value);
+    }
+    vars.extend(instance_vars(config));
+    vars
+}
+
+/// Hash of the variables that come from the instance rather than the pack
+/// build, so a cached build is re-rendered when `config` changes them.
+pub fn instance_vars_hash(config: &InstanceConfig) -> String {
+    let mut hasher = Sha256::new();
+    for (name, value) in instance_vars(config) {
+        hasher.update(name.as_bytes());
+        hasher.update([0]);
+        hasher.update(value.as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn instance_vars(config: &InstanceConfig) -> BTreeMap<String, String> {
+    let mut vars = BTreeMap::new();
+    vars.insert(format!("{VAR_PREFIX}CHANNEL"), config.channel.clone());
+    vars.insert(
+        format!("{VAR_PREFIX}PORT"),
+        config.port.unwrap_or(DEFAULT_PORT).to_string(),
+    );
+    for (name, value) in &config.template_vars {
+        vars.insert(format!("{VAR_PREFIX}{name}"), value.clone());
+    }
+    vars.extend(std::env::vars().filter(|(name, _)| name.starts_with(VAR_PREFIX)));
+    vars
+}
+
+/// Replaces every `${ATLAS_VAR_*}` placeholder, failing if any is undefined.
+pub fn render(path: &str, contents: &str, vars: &BTreeMap<String, String>) -> Result<String> {
+    let mut output = String::with_capacity(contents.len());
+    let mut missing = Vec::new();
+    let mut rest = contents;
+
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let name = after
+            .find('}')
+            .map(|end| &after[..end])
+            .filter(|name| is_variable_name(name));
+        match name {
+            Some(name) => {
+                match vars.get(name) {
+                    Some(value) => output.push_str(value),
+                    None if !missing.contains(&name) => missing.push(name),
+                    None => {}
+                }
+                rest = &after[name.len() + 1..];
+            }
+            None => {
+                output.push_str("${");
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+
+    if !missing.is_empty() {
+        bail!(
+            "Template {} references undefined variable(s): {}. \
+Set them with `atlas-runner config --var NAME=VALUE`.",
+            path,
+            missing.join(", ")
+        );
+    }
+    Ok(output)
+}
+
+fn is_variable_name(name: &str) -> bool {
+    name.strip_prefix(VAR_PREFIX).is_some_and(|suffix| {
+        !suffix.is_empty()
+            && suffix
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{instance_vars_hash, render};
+    use crate::hub::whitelist::InstanceConfig;
+    use std::collections::BTreeMap;
+
+    fn vars() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("ATLAS_VAR_PORT".to_string(), "25570".to_string()),
+            ("ATLAS_VAR_SERVER_NAME".to_string(), "Survival".to_string()),
+        ])
+    }
+
+    fn instance() -> InstanceConfig {
+        toml::from_str(
+            r#"
+pack_id = "pack"
+channel = "production"
+hub_url = "https://hub.example"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn substitutes_variables() {
+        let rendered = render(
+            "server.properties.tmpl",
+            "server-port=${ATLAS_VAR_PORT}\nmotd=${ATLAS_VAR_SERVER_NAME} ${ATLAS_VAR_PORT}\n",
+            &vars(),
+        )
+        .unwrap();
+        assert_eq!(rendered, "server-port=25570\nmotd=Survival 25570\n");
+    }
+
+    #[test]
+    fn undefined_variables_are_an_error() {
+        let err = render(
+            "server.properties.tmpl",
+            "a=${ATLAS_VAR_MISSING}\nb=${ATLAS_VAR_MISSING}\nc=${ATLAS_VAR_OTHER}",
+            &vars(),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("server.properties.tmpl"));
+        assert!(err.contains("ATLAS_VAR_MISSING, ATLAS_VAR_OTHER"));
+    }
+
+    #[test]
+    fn leaves_other_dollar_sequences_alone() {
+        let contents =
+            "home=${JAVA_HOME}\ncost=$$5\nopen=${ATLAS_VAR_PORT\nlower=${ATLAS_VAR_port}";
+        assert_eq!(render("run.sh.tmpl", contents, &vars()).unwrap(), contents);
+    }
+
+    #[test]
+    fn instance_vars_hash_changes_only_with_instance_vars() {
+        let mut config = instance();
+        let hash = instance_vars_hash(&config);
+        assert_eq!(instance_vars_hash(&instance()), hash);
+        assert_eq!(hash.len(), 64);
+
+        config.minecraft_version = Some("1.21.1".to_string());
+        assert_eq!(instance_vars_hash(&config), hash);
+
+        config
+            .template_vars
+            .insert("SERVER_NAME".to_string(), "Survival".to_string());
+        let with_var = instance_vars_hash(&config);
+        assert_ne!(with_var, hash);
+
+        config.port = Some(25570);
+        assert_ne!(instance_vars_hash(&config), with_var);
+    }
+}
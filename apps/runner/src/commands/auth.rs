@@ -81,12 +81,16 @@ pub async fn exec(
         hub_url: hub_url.to_string(),
         token: None,
         service_token: Some(resolved_service_token),
-        memory: memory.or(Some(runner_config::default_memory()?)),
+        memory: memory.or(Some(runner_config::default_memory(None)?)),
+        memory_reserve: None,
         port,
         minecraft_version: None,
         java_major: None,
+        java_auto_download: None,
         modloader: None,
         modloader_version: None,
+        pinned_version: None,
+        template_vars: Default::default(),
     };
 
     config.save(&PathBuf::from("instance.toml")).await?;
@@ -1,22 +1,40 @@
+use crate::assemble::template::VAR_PREFIX;
 use crate::hub::whitelist::InstanceConfig;
+use crate::java::required_java_major;
 use crate::runner_config;
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use std::path::PathBuf;
 
 pub async fn exec(
     memory: Option<String>,
+    memory_reserve: Option<String>,
     port: Option<u16>,
     java_major: Option<u32>,
+    java_auto_download: Option<bool>,
+    vars: Vec<String>,
+    pinned_version: Option<Option<String>>,
 ) -> Result<()> {
     let instance_path = PathBuf::from("instance.toml");
     let mut config = InstanceConfig::load(&instance_path)
         .await
         .context("No instance.toml found. Run `atlas-runner auth` first.")?;
 
+    let reserve_changed = memory_reserve.is_some();
+    if let Some(value) = memory_reserve {
+        runner_config::parse_memory_kb(&value)
+            .with_context(|| format!("Invalid memory reserve '{}'", value))?;
+        config.memory_reserve = Some(value);
+    }
+
     if let Some(value) = memory {
         config.memory = Some(value);
-    } else if config.memory.is_none() {
-        config.memory = Some(runner_config::default_memory()?);
+    } else if config.memory.is_none() || reserve_changed {
+        config.memory = Some(runner_config::default_memory(
+            config.memory_reserve.as_deref(),
+        )?);
+    }
+    if let Some(memory) = config.memory.as_deref() {
+        runner_config::warn_if_exceeds_available(memory);
     }
 
     if let Some(value) = port {
@@ -24,9 +42,54 @@ pub async fn exec(
     }
 
     if let Some(value) = java_major {
+        if let Some(mc_version) = config.minecraft_version.as_deref() {
+            let minimum = required_java_major(mc_version, &PathBuf::from("runtime/current"));
+            if value < minimum {
+                bail!(
+                    "Java {} is below the minimum Java {} required by Minecraft {}",
+                    value,
+                    minimum,
+                    mc_version
+                );
+            }
+        }
         config.java_major = Some(value);
     }
 
+    if let Some(value) = java_auto_download {
+        config.java_auto_download = Some(value);
+    }
+
+    for var in vars {
+        let Some((name, value)) = var.split_once('=') else {
+            bail!("--var expects NAME=VALUE, got '{}'", var);
+        };
+        let name = name.trim().to_ascii_uppercase();
+        let name = name.strip_prefix(VAR_PREFIX).unwrap_or(&name).to_string();
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        {
+            bail!("Invalid template variable name '{}'", var);
+        }
+        if value.is_empty() {
+            config.template_vars.remove(&name);
+        } else {
+            config.template_vars.insert(name, value.to_string());
+        }
+    }
+
+    if let Some(version) = pinned_version {
+        let version = version
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+        if let Some(version) = version.as_deref() {
+            println!("Pinned to pack version {version}; run `atlas-runner restart` to deploy it.");
+        }
+        config.pinned_version = version;
+    }
+
     config.save(&instance_path).await?;
     println!("Instance configuration updated.");
     Ok(())
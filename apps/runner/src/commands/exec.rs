@@ -1,21 +1,12 @@
 use crate::RUNNER_BASE_DIR;
 use crate::hub::whitelist::InstanceConfig;
 use crate::rcon::{RconClient, load_rcon_settings};
-use anyhow::{Context, Result};
-use std::path::PathBuf;
+use anyhow::{Context, Result, bail};
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 pub async fn exec(command: String, it: bool) -> Result<()> {
-    let instance_path = PathBuf::from("instance.toml");
-    let _config = InstanceConfig::load(&instance_path)
-        .await
-        .context("No instance.toml found in current directory")?;
-
-    let runtime_dir = PathBuf::from(RUNNER_BASE_DIR).join("runtime/current");
-    let settings = load_rcon_settings(&runtime_dir)
-        .await
-        .context("RCON not configured in server.properties")?;
-    let settings = settings.context("RCON not enabled")?;
-    let rcon = RconClient::new(settings.address, settings.password);
+    let rcon = connect().await?;
 
     if it {
         println!(
@@ -28,3 +19,71 @@ pub async fn exec(command: String, it: bool) -> Result<()> {
 
     Ok(())
 }
+
+/// Runs each non-empty, non-`#` line of `file` (or stdin for `-`) as a command
+/// over a single RCON session, printing output prefixed with its line number.
+pub async fn exec_batch(file: &Path, continue_on_error: bool) -> Result<()> {
+    let script = if file == Path::new("-") {
+        let mut script = String::new();
+        std::io::stdin()
+            .read_to_string(&mut script)
+            .context("Failed to read commands from stdin")?;
+        script
+    } else {
+        tokio::fs::read_to_string(file)
+            .await
+            .with_context(|| format!("Failed to read {}", file.display()))?
+    };
+    let commands: Vec<(usize, &str)> = script
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| (number, line.strip_prefix('/').unwrap_or(line)))
+        .collect();
+    if commands.is_empty() {
+        bail!("No commands found in {}", file.display());
+    }
+
+    let mut session = connect().await?.open().await?;
+    let mut failed = 0;
+    for (number, command) in &commands {
+        println!("[{number}] > {command}");
+        match session.execute(command).await {
+            Ok(response) => {
+                for line in response.lines() {
+                    println!("[{number}] {line}");
+                }
+            }
+            Err(err) => {
+                eprintln!("[{number}] error: {err}");
+                failed += 1;
+                if !continue_on_error {
+                    break;
+                }
+                // A failed command can leave the connection unusable.
+                session = connect().await?.open().await?;
+            }
+        }
+    }
+    session.close().await?;
+
+    if failed > 0 {
+        bail!("{failed} of {} commands failed", commands.len());
+    }
+    Ok(())
+}
+
+async fn connect() -> Result<RconClient> {
+    let instance_path = PathBuf::from("instance.toml");
+    let _config = InstanceConfig::load(&instance_path)
+        .await
+        .context("No instance.toml found in current directory")?;
+
+    let runtime_dir = PathBuf::from(RUNNER_BASE_DIR).join("runtime/current");
+    let settings = load_rcon_settings(&runtime_dir)
+        .await
+        .context("RCON not configured in server.properties")?;
+    let settings = settings.context("RCON not enabled")?;
+    Ok(RconClient::new(settings.address, settings.password))
+}
@@ -4,6 +4,7 @@ pub mod down;
 pub mod exec;
 pub mod install;
 pub mod logs;
+pub mod preflight;
 pub mod restart;
 pub mod status;
 pub mod up;
@@ -0,0 +1,341 @@
+use crate::hub::HubClient;
+use crate::hub::whitelist::InstanceConfig;
+use crate::java::{installed_java, required_java_major};
+use crate::runner_config::{default_memory, effective_memory_kb, parse_memory_kb};
+use crate::supervisor::Supervisor;
+use anyhow::{Result, bail};
+use protocol::Platform;
+use serde::Serialize;
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const DEFAULT_PORT: u16 = 25565;
+// The manifest does not carry artifact sizes, so this is a fixed floor that fits
+// the server jar, libraries, a typical mod set, a Java runtime and a fresh world.
+const MIN_FREE_DISK_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+const HUB_TIMEOUT: Duration = Duration::from_secs(10);
+
+// Same shape as the launcher's readiness checklist, plus whether a failure blocks `up`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PreflightItem {
+    key: &'static str,
+    label: &'static str,
+    ready: bool,
+    required: bool,
+    detail: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PreflightReport {
+    ready: bool,
+    checklist: Vec<PreflightItem>,
+}
+
+pub async fn exec(json: bool) -> Result<()> {
+    let report = run_checks().await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for item in &report.checklist {
+            let mark = match (item.ready, item.required) {
+                (true, _) => "[ok]  ",
+                (false, true) => "[fail]",
+                (false, false) => "[warn]",
+            };
+            match &item.detail {
+                Some(detail) => println!("{} {}: {}", mark, item.label, detail),
+                None => println!("{} {}", mark, item.label),
+            }
+        }
+    }
+
+    let failed = report
+        .checklist
+        .iter()
+        .filter(|item| item.required && !item.ready)
+        .count();
+    if failed > 0 {
+        bail!("{} preflight check(s) failed", failed);
+    }
+    Ok(())
+}
+
+async fn run_checks() -> PreflightReport {
+    let mut checklist = Vec::new();
+
+    match InstanceConfig::load(&PathBuf::from("instance.toml")).await {
+        Ok(config) => {
+            checklist.push(item("config", "Instance configuration", true, true, None));
+            checklist.push(check_hub(&config).await);
+            checklist.push(check_auth(&config).await);
+            checklist.push(check_port(&config).await);
+            checklist.push(check_memory(&config));
+            checklist.push(check_java(&config));
+        }
+        Err(_) => checklist.push(item(
+            "config",
+            "Instance configuration",
+            false,
+            true,
+            Some("No instance.toml found. Run `atlas-runner auth` first.".to_string()),
+        )),
+    }
+    checklist.push(check_disk(Path::new(".")));
+
+    PreflightReport {
+        ready: checklist.iter().all(|item| item.ready || !item.required),
+        checklist,
+    }
+}
+
+async fn check_hub(config: &InstanceConfig) -> PreflightItem {
+    let client = reqwest::Client::builder().timeout(HUB_TIMEOUT).build();
+    let result = match client {
+        Ok(client) => client.get(&config.hub_url).send().await,
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(response) => item(
+            "hub",
+            "Hub reachable",
+            true,
+            true,
+            Some(format!("{} (HTTP {})", config.hub_url, response.status())),
+        ),
+        Err(err) => item(
+            "hub",
+            "Hub reachable",
+            false,
+            true,
+            Some(format!("{}: {}", config.hub_url, err)),
+        ),
+    }
+}
+
+async fn check_auth(config: &InstanceConfig) -> PreflightItem {
+    let Some(service_token) = config.service_token.clone() else {
+        let detail = if config.token.is_some() {
+            "Using a user token; re-run `atlas-runner auth` to create a service token."
+        } else {
+            "No credentials configured. Run `atlas-runner auth` first."
+        };
+        return item(
+            "auth",
+            "Service token",
+            false,
+            config.token.is_none(),
+            Some(detail.to_string()),
+        );
+    };
+
+    let mut hub = match HubClient::new(&config.hub_url) {
+        Ok(hub) => hub,
+        Err(err) => return item("auth", "Service token", false, true, Some(err.to_string())),
+    };
+    hub.set_service_token(service_token);
+    match hub.validate_service_token().await {
+        Ok(exchange) if exchange.pack_id == config.pack_id => {
+            item("auth", "Service token", true, true, None)
+        }
+        Ok(exchange) => item(
+            "auth",
+            "Service token",
+            false,
+            true,
+            Some(format!(
+                "Token belongs to pack {}, but instance.toml targets {}.",
+                exchange.pack_id, config.pack_id
+            )),
+        ),
+        Err(err) => item("auth", "Service token", false, true, Some(err.to_string())),
+    }
+}
+
+async fn check_port(config: &InstanceConfig) -> PreflightItem {
+    let port = config.port.unwrap_or(DEFAULT_PORT);
+    let supervisor = Supervisor::new(
+        PathBuf::from("runtime/current"),
+        "java".to_string(),
+        vec![],
+        Vec::new(),
+    );
+    if supervisor.is_running().await {
+        return item(
+            "port",
+            "Server port free",
+            true,
+            true,
+            Some(format!("{} is used by the running server", port)),
+        );
+    }
+
+    match std::net::TcpListener::bind(("0.0.0.0", port)) {
+        Ok(_) => item(
+            "port",
+            "Server port free",
+            true,
+            true,
+            Some(port.to_string()),
+        ),
+        Err(err) => item(
+            "port",
+            "Server port free",
+            false,
+            true,
+            Some(format!("{} is unavailable: {}", port, err)),
+        ),
+    }
+}
+
+fn check_memory(config: &InstanceConfig) -> PreflightItem {
+    let memory = match config
+        .memory
+        .clone()
+        .map(Ok)
+        .unwrap_or_else(|| default_memory(config.memory_reserve.as_deref()))
+    {
+        Ok(memory) => memory,
+        Err(err) => return item("memory", "Memory", false, true, Some(err.to_string())),
+    };
+    let Some(requested_kb) = parse_memory_kb(&memory) else {
+        return item(
+            "memory",
+            "Memory",
+            false,
+            true,
+            Some(format!("Invalid memory setting '{}'", memory)),
+        );
+    };
+    match effective_memory_kb() {
+        Ok(available_kb) => item(
+            "memory",
+            "Memory",
+            requested_kb <= available_kb,
+            true,
+            Some(format!(
+                "{} requested, {} MiB available",
+                memory,
+                available_kb / 1024
+            )),
+        ),
+        Err(err) => item("memory", "Memory", false, false, Some(err.to_string())),
+    }
+}
+
+fn check_java(config: &InstanceConfig) -> PreflightItem {
+    let Some(mc_version) = config.minecraft_version.as_deref() else {
+        return item(
+            "java",
+            "Java runtime",
+            false,
+            false,
+            Some("Minecraft version is unknown until the first `atlas-runner up`.".to_string()),
+        );
+    };
+
+    let minimum = required_java_major(mc_version, Path::new("runtime/current"));
+    if let Some(major) = config.java_major.filter(|major| *major < minimum) {
+        return item(
+            "java",
+            "Java runtime",
+            false,
+            true,
+            Some(format!(
+                "--java-major {} is below the minimum Java {} for Minecraft {}",
+                major, minimum, mc_version
+            )),
+        );
+    }
+
+    let major = config.java_major.unwrap_or(minimum);
+    if installed_java(major).is_some() {
+        return item(
+            "java",
+            "Java runtime",
+            true,
+            true,
+            Some(format!("Java {} installed", major)),
+        );
+    }
+
+    if !config.java_auto_download.unwrap_or(true) {
+        return item(
+            "java",
+            "Java runtime",
+            false,
+            true,
+            Some(format!(
+                "Java {} is not installed and automatic download is disabled",
+                major
+            )),
+        );
+    }
+    let installable = matches!(Platform::from_current(), Platform::Linux | Platform::Macos)
+        && protocol::current_arch().is_ok();
+    let detail = if installable {
+        format!("Java {} will be downloaded on first start", major)
+    } else {
+        format!(
+            "Java {} cannot be installed automatically on {}/{}",
+            major,
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )
+    };
+    item("java", "Java runtime", installable, true, Some(detail))
+}
+
+fn check_disk(path: &Path) -> PreflightItem {
+    match free_disk_bytes(path) {
+        Some(free) => item(
+            "disk",
+            "Free disk space",
+            free >= MIN_FREE_DISK_BYTES,
+            true,
+            Some(format!(
+                "{} MiB free, {} MiB recommended",
+                free / 1024 / 1024,
+                MIN_FREE_DISK_BYTES / 1024 / 1024
+            )),
+        ),
+        None => item(
+            "disk",
+            "Free disk space",
+            false,
+            false,
+            Some("Unable to determine free disk space".to_string()),
+        ),
+    }
+}
+
+// statvfs field widths differ between Linux and macOS.
+#[allow(clippy::useless_conversion)]
+fn free_disk_bytes(path: &Path) -> Option<u64> {
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    Some(u64::from(stat.f_bavail).saturating_mul(u64::from(stat.f_frsize)))
+}
+
+fn item(
+    key: &'static str,
+    label: &'static str,
+    ready: bool,
+    required: bool,
+    detail: Option<String>,
+) -> PreflightItem {
+    PreflightItem {
+        key,
+        label,
+        ready,
+        required,
+        detail,
+    }
+}
@@ -3,6 +3,6 @@ use anyhow::Result;
 
 pub async fn exec() -> Result<()> {
     down::exec().await?;
-    up::exec(false, true, false).await?;
+    up::exec(false, true, false, None).await?;
     Ok(())
 }
@@ -1,4 +1,5 @@
 use crate::hub::whitelist::InstanceConfig;
+use crate::runner_config;
 use crate::supervisor::Supervisor;
 use anyhow::{Context, Result};
 use std::path::PathBuf;
@@ -18,6 +19,15 @@ pub async fn exec() -> Result<()> {
 
     println!("Pack: {}", config.pack_id);
     println!("Channel: {}", config.channel);
+    let memory = config
+        .memory
+        .clone()
+        .map(Ok)
+        .unwrap_or_else(|| runner_config::default_memory(config.memory_reserve.as_deref()));
+    match memory {
+        Ok(memory) => println!("Memory: {}", memory),
+        Err(err) => println!("Memory: unknown ({})", err),
+    }
 
     if supervisor.is_running().await {
         println!("Status: RUNNING");
@@ -1,6 +1,6 @@
 use crate::backup;
 use crate::hub::whitelist::InstanceConfig;
-use crate::hub::{HubClient, whitelist::WhitelistSync};
+use crate::hub::{ARTIFACT_DOWNLOAD_TIMEOUT, HubClient, whitelist::WhitelistSync};
 use crate::java::ensure_java_for_minecraft;
 use crate::rcon::{RconClient, load_rcon_settings};
 use crate::reconcile::Reconciler;
@@ -106,13 +106,32 @@ async fn run_pack_update_listener(
     }
 }
 
-pub async fn exec(_force_config: bool, attach: bool, skip_setup: bool) -> Result<()> {
+pub async fn exec(
+    _force_config: bool,
+    attach: bool,
+    skip_setup: bool,
+    wait_ready: Option<u64>,
+) -> Result<()> {
     if !skip_setup {
         run_setup(_force_config).await?;
 
         if !attach {
-            spawn_background_after_setup()?;
+            let log_path = PathBuf::from("runtime/current/runner.log");
+            let log_offset = fs::metadata(&log_path)
+                .await
+                .map(|meta| meta.len())
+                .unwrap_or(0);
+            let pid = spawn_background_after_setup()?;
             println!("Runner handed off to background. Logs: runtime/current/runner.log");
+            if let Some(timeout_secs) = wait_ready {
+                wait_until_ready(
+                    &log_path,
+                    log_offset,
+                    pid,
+                    Duration::from_secs(timeout_secs),
+                )
+                .await?;
+            }
             return Ok(());
         }
     }
@@ -129,12 +148,17 @@ async fn run_setup(_force_config: bool) -> Result<()> {
         .context("Missing instance.toml. Run `atlas-runner auth` first.")?;
 
     if config.memory.is_none() {
-        config.memory = Some(crate::runner_config::default_memory()?);
+        config.memory = Some(crate::runner_config::default_memory(
+            config.memory_reserve.as_deref(),
+        )?);
         let _ = config.save(&instance_path).await;
     }
 
     let _hub = Arc::new(HubClient::new(&config.hub_url)?);
-    let mut hub_mut = HubClient::new(&config.hub_url)?;
+    let mut hub_mut = HubClient::builder(&config.hub_url)
+        .timeout(ARTIFACT_DOWNLOAD_TIMEOUT)
+        .user_agent(concat!("atlas-runner/", env!("CARGO_PKG_VERSION")))
+        .build()?;
     if let Some(service_token) = config.service_token.clone() {
         hub_mut.set_service_token(service_token);
     } else if let Some(token) = config.token.clone() {
@@ -158,7 +182,11 @@ async fn run_setup(_force_config: bool) -> Result<()> {
         PathBuf::from("."),
     );
     reconciler
-        .reconcile(&config.pack_id, &config.channel)
+        .reconcile(
+            &config.pack_id,
+            &config.channel,
+            config.pinned_version.as_deref(),
+        )
         .await
         .context("Failed to pull build. Run `atlas-runner auth` to refresh credentials.")?;
 
@@ -218,12 +246,17 @@ async fn run_server() -> Result<()> {
     let mut restart_backoff = Duration::from_secs(2);
 
     let (update_tx, mut update_rx) = mpsc::channel::<()>(4);
-    tokio::spawn(run_pack_update_listener(
-        hub.clone(),
-        config.pack_id.clone(),
-        config.channel.clone(),
-        update_tx,
-    ));
+    let pinned = config.pinned_version.is_some();
+    if let Some(version) = config.pinned_version.as_deref() {
+        println!("Pinned to pack version {version}; automatic updates are off.");
+    } else {
+        tokio::spawn(run_pack_update_listener(
+            hub.clone(),
+            config.pack_id.clone(),
+            config.channel.clone(),
+            update_tx,
+        ));
+    }
 
     tokio::spawn(run_daily_backups(runtime_dir.clone()));
 
@@ -233,7 +266,7 @@ async fn run_server() -> Result<()> {
             _ = ticker.tick() => {
                 sync_whitelist_and_reload(&whitelist, &config.pack_id, &runtime_dir).await;
             }
-            update = update_rx.recv() => {
+            update = update_rx.recv(), if !pinned => {
                 if update.is_some() {
                     println!("Pack update detected. Restarting server...");
                     if let Ok(Some(settings)) = load_rcon_settings(&runtime_dir).await {
@@ -289,7 +322,7 @@ async fn run_server() -> Result<()> {
     }
 }
 
-fn spawn_background_after_setup() -> Result<()> {
+fn spawn_background_after_setup() -> Result<u32> {
     let current_exe = std::env::current_exe().context("Failed to resolve current executable")?;
     let runtime_dir = PathBuf::from("runtime/current");
     std::fs::create_dir_all(&runtime_dir).context("Failed to create runtime directory")?;
@@ -335,7 +368,70 @@ fn spawn_background_after_setup() -> Result<()> {
     std::fs::write(&pid_file, child.id().to_string()).ok();
 
     println!("Runner started in background (pid {}).", child.id());
-    Ok(())
+    Ok(child.id())
+}
+
+// Ready once the server logs its "Done (12.3s)! For help, type ..." line, or
+// once RCON answers `list`, whichever comes first.
+async fn wait_until_ready(
+    log_path: &PathBuf,
+    log_offset: u64,
+    runner_pid: u32,
+    timeout: Duration,
+) -> Result<()> {
+    let started = tokio::time::Instant::now();
+    let runtime_dir = PathBuf::from("runtime/current");
+    let mut last_report = 0;
+    println!(
+        "Waiting up to {}s for the server to become ready...",
+        timeout.as_secs()
+    );
+
+    loop {
+        let log = fs::read(log_path).await.unwrap_or_default();
+        let new_output = log.get(log_offset as usize..).unwrap_or_default();
+        if let Some(line) = String::from_utf8_lossy(new_output)
+            .lines()
+            .find(|line| is_ready_line(line))
+        {
+            println!("Server is ready: {}", line.trim());
+            return Ok(());
+        }
+        if let Ok(Some(settings)) = load_rcon_settings(&runtime_dir).await {
+            let rcon = RconClient::new(settings.address, settings.password);
+            if rcon.execute("list").await.is_ok() {
+                println!("Server is ready (RCON answered `list`).");
+                return Ok(());
+            }
+        }
+
+        let runner_alive = StdCommand::new("kill")
+            .arg("-0")
+            .arg(runner_pid.to_string())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+        if !runner_alive {
+            bail!("Runner exited before the server became ready. See runtime/current/runner.log");
+        }
+
+        let elapsed = started.elapsed().as_secs();
+        if started.elapsed() >= timeout {
+            bail!(
+                "Server was not ready after {}s. See runtime/current/runner.log",
+                timeout.as_secs()
+            );
+        }
+        if elapsed >= last_report + 10 {
+            last_report = elapsed;
+            println!("Still starting ({elapsed}s elapsed)...");
+        }
+        sleep(Duration::from_secs(1)).await;
+    }
+}
+
+fn is_ready_line(line: &str) -> bool {
+    line.contains("Done (") && line.contains("For help, type")
 }
 
 async fn ensure_server_stopped() {
@@ -424,9 +520,17 @@ async fn ensure_java_for_runtime() -> Result<String> {
     let config = InstanceConfig::load(&instance_path)
         .await
         .context("Missing instance.toml. Run `atlas-runner auth` first.")?;
-    let mc_version = config.minecraft_version.as_deref().unwrap_or("1.20.1");
-
-    let java_bin = ensure_java_for_minecraft(mc_version, config.java_major).await?;
+    let mc_version = config.minecraft_version.as_deref().context(
+        "Missing minecraft_version in instance.toml. Run `atlas-runner up` to pull the pack.",
+    )?;
+
+    let java_bin = ensure_java_for_minecraft(
+        mc_version,
+        &PathBuf::from("runtime/current"),
+        config.java_major,
+        config.java_auto_download.unwrap_or(true),
+    )
+    .await?;
     Ok(java_bin.to_string_lossy().to_string())
 }
 
@@ -16,14 +16,29 @@ impl Fetcher {
         }
     }
 
-    pub async fn fetch_artifact(&self, url: String, expected_hash: String) -> Result<()> {
+    /// Downloads from the first of `urls` that answers with the expected hash.
+    pub async fn fetch_artifact(&self, urls: Vec<String>, expected_hash: String) -> Result<()> {
         if self.cache.exists(&expected_hash).await {
             println!("Artifact cached: {}", expected_hash);
             return Ok(());
         }
 
+        let mut last_error = None;
+        for url in &urls {
+            match self.download(url, &expected_hash).await {
+                Ok(()) => return Ok(()),
+                Err(err) => {
+                    println!("Download failed from {}: {}", url, err);
+                    last_error = Some(err);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No download URL for {}", expected_hash)))
+    }
+
+    async fn download(&self, url: &str, expected_hash: &str) -> Result<()> {
         println!("Downloading artifact: {}", url);
-        let response = self.client.get(&url).send().await?.error_for_status()?;
+        let response = self.client.get(url).send().await?.error_for_status()?;
         let data = response.bytes().await?;
 
         let actual_hash = self.cache.compute_hash(&data);
@@ -40,10 +55,10 @@ impl Fetcher {
         Ok(())
     }
 
-    pub async fn fetch_multiple(&self, artifacts: Vec<(String, String)>) -> Result<()> {
+    pub async fn fetch_multiple(&self, artifacts: Vec<(Vec<String>, String)>) -> Result<()> {
         let mut futures = Vec::new();
-        for (url, hash) in artifacts {
-            futures.push(self.fetch_artifact(url, hash));
+        for (urls, hash) in artifacts {
+            futures.push(self.fetch_artifact(urls, hash));
         }
 
         futures::future::try_join_all(futures).await?;
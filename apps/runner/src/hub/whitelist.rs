@@ -1,6 +1,7 @@
 use crate::hub::{HubClient, WhitelistEntry};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::fs;
@@ -13,11 +14,17 @@ pub struct InstanceConfig {
     pub token: Option<String>,
     pub service_token: Option<String>,
     pub memory: Option<String>,
+    pub memory_reserve: Option<String>,
     pub port: Option<u16>,
     pub minecraft_version: Option<String>,
     pub java_major: Option<u32>,
+    pub java_auto_download: Option<bool>,
     pub modloader: Option<String>,
     pub modloader_version: Option<String>,
+    /// Build version to deploy instead of the channel's latest; disables automatic updates.
+    pub pinned_version: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub template_vars: BTreeMap<String, String>,
 }
 
 impl InstanceConfig {
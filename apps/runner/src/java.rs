@@ -1,17 +1,120 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
+use std::io::Read;
 use std::path::{Path, PathBuf};
 
 const LEGACY_JAVA_ROOT: &str = "/var/lib/atlas-runner/java";
 
+pub fn installed_java(major: u32) -> Option<PathBuf> {
+    runner_provision_v2::java::installed_java_bin(Path::new(LEGACY_JAVA_ROOT), major)
+}
+
+/// Minimum Java major for the server: the version table, raised by the
+/// `java_version` the vanilla server jar declares once it is on disk.
+pub fn required_java_major(mc_version: &str, runtime_dir: &Path) -> u32 {
+    let table = runner_provision_v2::java::java_version_for_minecraft(mc_version);
+    let candidates = [
+        runtime_dir.join("server.jar"),
+        runtime_dir
+            .join("libraries/net/minecraft/server")
+            .join(mc_version)
+            .join(format!("server-{mc_version}.jar")),
+    ];
+    candidates
+        .iter()
+        .filter_map(|path| embedded_java_major(path))
+        .fold(table, u32::max)
+}
+
+/// Picks the Java runtime for `mc_version`. `override_major` may raise but never
+/// lower the requirement; without auto-download only installed runtimes are used.
 pub async fn ensure_java_for_minecraft(
     mc_version: &str,
+    runtime_dir: &Path,
     override_major: Option<u32>,
+    auto_download: bool,
 ) -> Result<PathBuf> {
-    runner_provision_v2::java::ensure_java_for_minecraft_with_root(
-        Path::new(LEGACY_JAVA_ROOT),
+    let minimum = required_java_major(mc_version, runtime_dir);
+    let major = match override_major {
+        Some(value) if value < minimum => bail!(
+            "--java-major {} is below the minimum Java {} required by Minecraft {}",
+            value,
+            minimum,
+            mc_version
+        ),
+        Some(value) => value,
+        None => minimum,
+    };
+
+    if auto_download {
+        return runner_provision_v2::java::ensure_java_major_with_root(
+            Path::new(LEGACY_JAVA_ROOT),
+            major,
+        )
+        .await
+        .map_err(anyhow::Error::from);
+    }
+
+    if let Some(java_bin) = installed_java(major) {
+        return Ok(java_bin);
+    }
+    let installed = installed_majors();
+    // An explicit override means exactly that major; otherwise any newer runtime works.
+    if let Some(java_bin) = installed
+        .iter()
+        .filter(|value| override_major.is_none() && **value > major)
+        .find_map(|value| installed_java(*value))
+    {
+        return Ok(java_bin);
+    }
+
+    let found = match installed.last() {
+        Some(newest) => format!("the newest installed runtime is Java {}", newest),
+        None => "no Java runtime is installed".to_string(),
+    };
+    bail!(
+        "Minecraft {} needs Java {}, but {} and automatic download is disabled. \
+Install it under {} or run `atlas-runner config --java-auto-download true`.",
         mc_version,
-        override_major,
+        major,
+        found,
+        LEGACY_JAVA_ROOT
     )
-    .await
-    .map_err(anyhow::Error::from)
+}
+
+fn installed_majors() -> Vec<u32> {
+    let Ok(entries) = std::fs::read_dir(LEGACY_JAVA_ROOT) else {
+        return Vec::new();
+    };
+    let mut majors = entries
+        .flatten()
+        .filter_map(|entry| {
+            entry
+                .file_name()
+                .to_str()?
+                .strip_prefix("jdk-")?
+                .parse::<u32>()
+                .ok()
+        })
+        .filter(|major| installed_java(*major).is_some())
+        .collect::<Vec<_>>();
+    majors.sort_unstable();
+    majors
+}
+
+fn embedded_java_major(jar: &Path) -> Option<u32> {
+    let file = std::fs::File::open(jar).ok()?;
+    let mut archive = zip::ZipArchive::new(file).ok()?;
+    let mut contents = String::new();
+    archive
+        .by_name("version.json")
+        .ok()?
+        .read_to_string(&mut contents)
+        .ok()?;
+    parse_embedded_java_major(&contents)
+}
+
+fn parse_embedded_java_major(version_json: &str) -> Option<u32> {
+    let value: serde_json::Value = serde_json::from_str(version_json).ok()?;
+    let major = value.get("java_version")?.as_u64()?;
+    u32::try_from(major).ok()
 }
@@ -61,20 +61,51 @@ enum Commands {
         /// Skip setup and use existing runtime (internal)
         #[arg(long, hide = true)]
         skip_setup: bool,
+        /// Wait until the server accepts players, failing after the timeout in seconds
+        #[arg(
+            long,
+            value_name = "SECS",
+            num_args = 0..=1,
+            default_missing_value = "300",
+            conflicts_with = "attach"
+        )]
+        wait_ready: Option<u64>,
+    },
+    /// Check that this host is ready to run the server
+    Preflight {
+        /// Print the checklist as JSON
+        #[arg(long)]
+        json: bool,
     },
     /// Gracefully stop the server
     Down,
     /// Update runner configuration
     Config {
-        /// Max memory (e.g., 6G). Defaults to system RAM minus 2G
+        /// Max memory (e.g., 6G). Defaults to system RAM minus the memory reserve
         #[arg(long)]
         memory: Option<String>,
+        /// RAM left for the OS when computing the default memory (e.g., 2G).
+        /// Defaults to 1G up to 4G of RAM, 2G up to 16G and 10% above
+        #[arg(long = "memory-reserve")]
+        memory_reserve: Option<String>,
         /// Server port
         #[arg(long)]
         port: Option<u16>,
         /// Override Java major version (must be >= minimum required)
         #[arg(long = "java-major")]
         java_major: Option<u32>,
+        /// Download the required Java runtime when it is not installed (default true)
+        #[arg(long = "java-auto-download")]
+        java_auto_download: Option<bool>,
+        /// Set a config template variable as NAME=VALUE (empty VALUE removes it)
+        #[arg(long = "var", value_name = "NAME=VALUE")]
+        vars: Vec<String>,
+        /// Deploy this build version instead of the channel's latest and stop automatic updates
+        #[arg(long, value_name = "VERSION", conflicts_with = "unpin")]
+        pin: Option<String>,
+        /// Follow the channel's latest build again
+        #[arg(long)]
+        unpin: bool,
     },
     /// Restart the server
     Restart,
@@ -95,17 +126,27 @@ enum Commands {
     /// Execute a command via RCON
     Exec {
         /// Command to execute
-        command: String,
+        #[arg(required_unless_present = "file", conflicts_with = "file")]
+        command: Option<String>,
         /// Interactive shell mode
         #[arg(short, long)]
         it: bool,
+        /// Run newline-separated commands from a file (`-` for stdin) over one RCON session
+        #[arg(long, value_name = "PATH")]
+        file: Option<std::path::PathBuf>,
+        /// Keep running the remaining commands after one fails
+        #[arg(long, requires = "file")]
+        continue_on_error: bool,
     },
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer())
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(|| protocol::redact::RedactingWriter::new(std::io::stdout())),
+        )
         .with(tracing_subscriber::EnvFilter::from_default_env())
         .init();
 
@@ -129,18 +170,36 @@ async fn main() -> anyhow::Result<()> {
             force_config,
             attach,
             skip_setup,
+            wait_ready,
         } => {
-            commands::up::exec(force_config, attach, skip_setup).await?;
+            commands::up::exec(force_config, attach, skip_setup, wait_ready).await?;
+        }
+        Commands::Preflight { json } => {
+            commands::preflight::exec(json).await?;
         }
         Commands::Down => {
             commands::down::exec().await?;
         }
         Commands::Config {
             memory,
+            memory_reserve,
             port,
             java_major,
+            java_auto_download,
+            vars,
+            pin,
+            unpin,
         } => {
-            commands::config::exec(memory, port, java_major).await?;
+            commands::config::exec(
+                memory,
+                memory_reserve,
+                port,
+                java_major,
+                java_auto_download,
+                vars,
+                if unpin { Some(None) } else { pin.map(Some) },
+            )
+            .await?;
         }
         Commands::Restart => {
             commands::restart::exec().await?;
@@ -154,9 +213,15 @@ async fn main() -> anyhow::Result<()> {
         Commands::Install { user } => {
             commands::install::exec(user).await?;
         }
-        Commands::Exec { command, it } => {
-            commands::exec::exec(command, it).await?;
-        }
+        Commands::Exec {
+            command,
+            it,
+            file,
+            continue_on_error,
+        } => match file {
+            Some(file) => commands::exec::exec_batch(&file, continue_on_error).await?,
+            None => commands::exec::exec(command.unwrap_or_default(), it).await?,
+        },
     }
 
     Ok(())
@@ -20,23 +20,63 @@ impl RconClient {
     }
 
     pub async fn execute(&self, command: &str) -> Result<String> {
+        let mut session = self.open().await?;
+        let response = session.execute(command).await;
+        session.close().await?;
+        response
+    }
+
+    /// Connects and authenticates once, for running several commands.
+    pub async fn open(&self) -> Result<RconSession> {
         let address = self.address.clone();
         let password = self.password.clone();
-        let command = command.to_string();
 
-        spawn_blocking(move || {
+        let client = spawn_blocking(move || {
             let mut client =
                 Client::new(address).map_err(|err| anyhow::anyhow!(err.to_string()))?;
             client
                 .authenticate(password)
                 .map_err(|err| anyhow::anyhow!(err.to_string()))?;
+            Ok::<_, anyhow::Error>(client)
+        })
+        .await
+        .with_context(|| "RCON task failed")??;
+        Ok(RconSession {
+            client: Some(client),
+        })
+    }
+}
+
+pub struct RconSession {
+    client: Option<Client>,
+}
+
+impl RconSession {
+    pub async fn execute(&mut self, command: &str) -> Result<String> {
+        let mut client = self.client.take().context("RCON session is closed")?;
+        let command = command.to_string();
+
+        let (client, response) = spawn_blocking(move || {
             let response = client
                 .send_command(command)
-                .map_err(|err| anyhow::anyhow!(err.to_string()))?;
+                .map(|message| message.body)
+                .map_err(|err| anyhow::anyhow!(err.to_string()));
+            (client, response)
+        })
+        .await
+        .with_context(|| "RCON task failed")?;
+        self.client = Some(client);
+        response
+    }
+
+    pub async fn close(mut self) -> Result<()> {
+        let Some(mut client) = self.client.take() else {
+            return Ok(());
+        };
+        spawn_blocking(move || {
             client
                 .close()
-                .map_err(|err| anyhow::anyhow!(err.to_string()))?;
-            Ok::<_, anyhow::Error>(response.body)
+                .map_err(|err| anyhow::anyhow!(err.to_string()))
         })
         .await
         .with_context(|| "RCON task failed")?
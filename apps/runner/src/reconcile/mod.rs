@@ -1,10 +1,10 @@
-use crate::assemble::Assembler;
+use crate::assemble::{Assembler, template};
 use crate::backup;
 use crate::cache::Cache;
 use crate::fetch::Fetcher;
-use crate::hub::BuildBlobResult;
 use crate::hub::HubClient;
 use crate::hub::whitelist::InstanceConfig;
+use crate::hub::{BlobValidators, BuildBlobResult};
 use anyhow::{Context, Result};
 use protocol::config::atlas::parse_config;
 use std::path::PathBuf;
@@ -33,23 +33,54 @@ impl Reconciler {
         }
     }
 
-    pub async fn reconcile(&self, pack_id: &str, channel: &str) -> Result<()> {
-        println!(
-            "Reconciling instance for pack: {} (channel: {})",
-            pack_id, channel
-        );
+    pub async fn reconcile(
+        &self,
+        pack_id: &str,
+        channel: &str,
+        pinned_version: Option<&str>,
+    ) -> Result<()> {
+        match pinned_version {
+            Some(version) => println!(
+                "Reconciling instance for pack: {} (channel: {}, pinned to {})",
+                pack_id, channel, version
+            ),
+            None => println!(
+                "Reconciling instance for pack: {} (channel: {})",
+                pack_id, channel
+            ),
+        }
 
-        // 1. Fetch latest blob
+        // 1. Fetch latest blob, unless the installed one is still current
         println!("Downloading pack build from Hub...");
-        let build = self
+        let instance = InstanceConfig::load(&self.base_dir.join("instance.toml"))
+            .await
+            .context("Missing instance.toml while assembling templates")?;
+        let vars_hash = template::instance_vars_hash(&instance);
+        let cached = read_blob_validators(&self.base_dir, &vars_hash).await;
+        let progress = atlas_client::progress::throttled(|progress| {
+            println!("Downloaded {progress}");
+        });
+        let Some(build) = self
             .hub
-            .get_build_blob(pack_id, channel)
+            .get_build_blob_if_modified(
+                pack_id,
+                channel,
+                pinned_version,
+                cached.as_ref(),
+                &progress,
+            )
             .await
-            .context("Failed to fetch build blob")?;
+            .context("Failed to fetch build blob")?
+        else {
+            println!("Pack build already current; nothing to reconcile.");
+            return Ok(());
+        };
 
         // 2. Decode blob
         println!("Decoding pack build...");
-        let blob = protocol::decode_blob(&build.bytes).context("Failed to decode build blob")?;
+        let blob =
+            protocol::decode_blob_with_limits(&build.bytes, &protocol::DecodeLimits::untrusted())
+                .context("Failed to decode build blob")?;
 
         println!(
             "Minecraft {} with {} loader.",
@@ -62,13 +93,15 @@ impl Reconciler {
             println!("Full reinstall required. Archiving world directories...");
         }
 
-        write_pack_metadata(&self.base_dir, &blob).await?;
         update_instance_metadata(&self.base_dir, &blob).await?;
 
         // 3. Fetch artifacts from manifest
         let mut artifacts = Vec::new();
         for dep in &blob.manifest.dependencies {
-            artifacts.push((dep.url.clone(), dep.hash.hex.clone()));
+            let urls = std::iter::once(dep.url.clone())
+                .chain(dep.mirrors.iter().cloned())
+                .collect::<Vec<_>>();
+            artifacts.push((urls, dep.hash.hex.clone()));
         }
 
         println!("Pulling {} mod artifacts...", artifacts.len());
@@ -76,7 +109,8 @@ impl Reconciler {
 
         // 4. Assemble runtime in staging area
         let staging_dir = self.base_dir.join("runtime/staging");
-        let assembler = Assembler::new(staging_dir.clone());
+        let assembler = Assembler::new(staging_dir.clone())
+            .with_template_vars(template::template_vars(&blob, &instance));
 
         println!("Writing server files and configs...");
         assembler.assemble(&blob).await?;
@@ -89,6 +123,7 @@ impl Reconciler {
         // 5. Finalize (Stop server, Swap, Start server)
         // This will be implemented when Supervisor is ready
         self.finalize(&staging_dir, reinstall_required).await?;
+        write_pack_metadata(&self.base_dir, &blob, &build.validators, &vars_hash).await?;
 
         Ok(())
     }
@@ -172,19 +207,44 @@ async fn copy_dir_recursive(src: &PathBuf, dest: &PathBuf) -> Result<()> {
     Ok(())
 }
 
-async fn write_pack_metadata(base_dir: &PathBuf, blob: &protocol::PackBlob) -> Result<()> {
+async fn write_pack_metadata(
+    base_dir: &PathBuf,
+    blob: &protocol::PackBlob,
+    validators: &BlobValidators,
+    vars_hash: &str,
+) -> Result<()> {
     let meta_dir = base_dir.join("runtime/current");
     tokio::fs::create_dir_all(&meta_dir).await?;
     let meta_path = meta_dir.join("pack-meta.json");
     let payload = serde_json::json!({
         "minecraftVersion": blob.metadata.minecraft_version,
         "loader": format_loader(blob.metadata.loader),
+        "etag": validators.etag,
+        "lastModified": validators.last_modified,
+        "templateVarsHash": vars_hash,
     });
     let content = serde_json::to_string_pretty(&payload)?;
     tokio::fs::write(meta_path, content).await?;
     Ok(())
 }
 
+// The validators only count while the template variables they were rendered
+// with are unchanged; otherwise the build is fetched and assembled again.
+async fn read_blob_validators(base_dir: &PathBuf, vars_hash: &str) -> Option<BlobValidators> {
+    let meta_path = base_dir.join("runtime/current/pack-meta.json");
+    let content = tokio::fs::read_to_string(&meta_path).await.ok()?;
+    let meta: serde_json::Value = serde_json::from_str(&content).ok()?;
+    if meta
+        .get("templateVarsHash")
+        .and_then(|value| value.as_str())
+        != Some(vars_hash)
+    {
+        return None;
+    }
+    let validators: BlobValidators = serde_json::from_value(meta).ok()?;
+    (!validators.is_empty()).then_some(validators)
+}
+
 async fn update_instance_metadata(base_dir: &PathBuf, blob: &protocol::PackBlob) -> Result<()> {
     let instance_path = base_dir.join("instance.toml");
     let mut config = InstanceConfig::load(&instance_path)
@@ -243,3 +303,30 @@ fn format_loader(loader: protocol::Loader) -> &'static str {
         protocol::Loader::Neo => "NeoForge",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::read_blob_validators;
+
+    #[tokio::test]
+    async fn validators_are_ignored_after_template_vars_change() {
+        let base_dir =
+            std::env::temp_dir().join(format!("atlas-runner-reconcile-{}", std::process::id()));
+        let current = base_dir.join("runtime/current");
+        tokio::fs::create_dir_all(&current).await.unwrap();
+        tokio::fs::write(
+            current.join("pack-meta.json"),
+            r#"{"etag":"\"abc\"","lastModified":null,"templateVarsHash":"old"}"#,
+        )
+        .await
+        .unwrap();
+
+        let cached = read_blob_validators(&base_dir, "old")
+            .await
+            .expect("current");
+        assert_eq!(cached.etag.as_deref(), Some("\"abc\""));
+        assert!(read_blob_validators(&base_dir, "new").await.is_none());
+
+        let _ = tokio::fs::remove_dir_all(&base_dir).await;
+    }
+}
@@ -1,12 +1,81 @@
 use anyhow::{Context, Result};
 
-pub fn default_memory() -> Result<String> {
+/// Smallest heap the runner will pick on its own, however little RAM is left.
+pub const MIN_HEAP_MB: u64 = 1024;
+
+/// Default `-Xmx`: effective RAM minus `reserve` (e.g. `2G`), or minus an
+/// adaptive reserve when none is configured.
+pub fn default_memory(reserve: Option<&str>) -> Result<String> {
+    let total_mb = effective_memory_kb()? / 1024;
+    let reserve_mb = match reserve {
+        Some(value) => {
+            parse_memory_kb(value).with_context(|| format!("Invalid memory reserve '{}'", value))?
+                / 1024
+        }
+        None => adaptive_reserve_mb(total_mb),
+    };
+    Ok(format_memory_mb(heap_mb(total_mb, reserve_mb)))
+}
+
+/// Leaves 1G for the OS on hosts up to 4G, 2G up to 16G and 10% above that.
+pub fn adaptive_reserve_mb(total_mb: u64) -> u64 {
+    match total_mb {
+        0..=4096 => 1024,
+        4097..=16384 => 2048,
+        _ => total_mb / 10,
+    }
+}
+
+pub fn heap_mb(total_mb: u64, reserve_mb: u64) -> u64 {
+    total_mb.saturating_sub(reserve_mb).max(MIN_HEAP_MB)
+}
+
+pub fn format_memory_mb(mb: u64) -> String {
+    if mb.is_multiple_of(1024) {
+        format!("{}G", mb / 1024)
+    } else {
+        format!("{}M", mb)
+    }
+}
+
+/// Warns when `memory` asks for more heap than this host can give it.
+pub fn warn_if_exceeds_available(memory: &str) {
+    let (Some(requested_kb), Ok(available_kb)) = (parse_memory_kb(memory), effective_memory_kb())
+    else {
+        return;
+    };
+    if requested_kb > available_kb {
+        eprintln!(
+            "Warning: memory {} exceeds the {} MiB available on this host",
+            memory,
+            available_kb / 1024
+        );
+    }
+}
+
+/// Physical memory, capped by the cgroup limit when running in a container.
+pub fn effective_memory_kb() -> Result<u64> {
     let mem_kb = read_mem_total_kb()?;
     let limit_kb = read_cgroup_memory_limit_kb().unwrap_or(mem_kb);
-    let effective_kb = mem_kb.min(limit_kb);
-    let mem_gb = effective_kb / 1024 / 1024;
-    let adjusted = mem_gb.saturating_sub(2).max(1);
-    Ok(format!("{}G", adjusted))
+    Ok(mem_kb.min(limit_kb))
+}
+
+/// Parses a JVM heap size such as `6G`, `6144M` or `512k` into KiB.
+pub fn parse_memory_kb(value: &str) -> Option<u64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (digits, unit) = value.split_at(split);
+    let amount = digits.parse::<u64>().ok()?;
+    let factor = match unit.to_ascii_lowercase().as_str() {
+        "" => return Some(amount / 1024),
+        "k" => 1,
+        "m" => 1024,
+        "g" => 1024 * 1024,
+        _ => return None,
+    };
+    amount.checked_mul(factor)
 }
 
 fn read_mem_total_kb() -> Result<u64> {
@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DeployKeyConfig {
     pub hub_url: String,
     pub pack_id: String,
@@ -11,12 +11,68 @@ pub struct DeployKeyConfig {
     pub deploy_key: String,
     #[serde(default)]
     pub prefix: Option<String>,
+    /// Hub id of the service token in `deploy_key`, used to revoke it on rotation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_id: Option<String>,
     #[serde(default)]
     pub max_ram: Option<u32>,
+    /// Extra JVM flags placed before `-jar`; they replace flags the pack's
+    /// launch script sets for the same option.
+    #[serde(default)]
+    pub jvm_args: Option<Vec<String>>,
     #[serde(default)]
     pub should_autostart: Option<bool>,
     #[serde(default)]
     pub eula_accepted: Option<bool>,
+    /// Opt-in: reconcile `ops.json` with the hub's operator list.
+    #[serde(default)]
+    pub sync_operators: Option<bool>,
+    /// When syncing operators, only deop players a previous sync added (default true).
+    #[serde(default)]
+    pub keep_local_operators: Option<bool>,
+    /// Crashes within `crash_loop_window_secs` that stop automatic restarts (default 3 in 60s).
+    #[serde(default)]
+    pub crash_loop_max_restarts: Option<u32>,
+    #[serde(default)]
+    pub crash_loop_window_secs: Option<u64>,
+    /// Seconds a graceful stop waits for the server to exit before killing it
+    /// (default 30, at most 90 so it fits the client's stop timeout).
+    #[serde(default)]
+    pub stop_grace_secs: Option<u64>,
+    /// Server output lines kept in memory per profile (default 2000, minimum 100).
+    /// Read at daemon startup.
+    #[serde(default)]
+    pub log_buffer_lines: Option<usize>,
+    /// Daemon log lines kept in memory (default 2000, minimum 100).
+    #[serde(default)]
+    pub daemon_log_buffer_lines: Option<usize>,
+    /// Keeps the server stopped even when auto-start is enabled.
+    #[serde(default)]
+    pub maintenance: Option<bool>,
+    #[serde(default)]
+    pub restart_schedule: Option<RestartSchedule>,
+    /// Build version to deploy instead of the channel's latest; disables automatic updates.
+    #[serde(default)]
+    pub pinned_version: Option<String>,
+}
+
+/// Periodic graceful restart; `daily_at` ("HH:MM", local time) and
+/// `interval_hours` (since the server started) may be combined.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestartSchedule {
+    #[serde(default)]
+    pub daily_at: Option<String>,
+    #[serde(default)]
+    pub interval_hours: Option<u64>,
+    #[serde(default)]
+    pub skip_if_players_online: bool,
+    /// How long before the restart players are warned over RCON.
+    #[serde(default = "default_restart_warning_secs")]
+    pub warning_secs: u64,
+}
+
+fn default_restart_warning_secs() -> u64 {
+    300
 }
 
 pub fn save_deploy_key(config: &DeployKeyConfig) -> Result<(), String> {
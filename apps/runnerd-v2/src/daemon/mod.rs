@@ -1,123 +1,148 @@
 use std::process;
 use std::sync::Arc;
-use tokio::net::UnixListener;
 use tokio::signal;
 use tokio::sync::Mutex;
 use tracing::{info, warn};
 
+use atlas_client::hub_url::normalize_hub_url_string;
 use runner_core_v2::proto::*;
-use runner_ipc_v2::framing;
+use runner_ipc_v2::framing::{self, FramedStream};
+use runner_ipc_v2::transport::Listener;
 
 use crate::config::{DeployKeyConfig, save_deploy_key};
 use crate::supervisor::{
-    LogStore, ServerState, SharedState, build_status, default_server_root, ensure_rcon_available,
-    ensure_watchers, execute_rcon_command, start_server_from_deploy, stop_server,
+    LineMatcher, LogStore, Profiles, ServerState, SharedState, accept_eula, build_metrics,
+    build_status, clear_crash_loop_state, default_server_root, ensure_rcon_available,
+    ensure_watchers, execute_rcon_command, find_unmanaged_minecraft_process, get_server_properties,
+    list_servers, read_pack_blob, set_channel, set_maintenance, set_pinned_version,
+    set_server_properties, start_server_from_deploy, stop_server, sync_whitelist_now,
 };
 
-pub async fn serve(listener: UnixListener, logs: LogStore) -> std::io::Result<()> {
+pub async fn serve(listener: Listener, logs: LogStore) -> std::io::Result<()> {
     let state: SharedState = Arc::new(Mutex::new(ServerState::new(logs)));
+    let profiles = Profiles::new(state.clone());
     // Start daily backup scheduler (non-blocking)
     let server_root_for_scheduler = crate::supervisor::default_server_root("default");
     crate::backup::start_daily_scheduler(server_root_for_scheduler.clone(), state.clone());
     let start_ms = crate::supervisor::now_millis();
+    {
+        let mut guard = state.lock().await;
+        guard.maintenance = crate::supervisor::maintenance_configured();
+        guard.pinned_version = crate::supervisor::configured_pinned_version();
+    }
     let auto_state = state.clone();
     // Run auto-start synchronously in this task to avoid requiring `start_server_from_deploy` to be Send.
     start_server_from_deploy(auto_state).await;
+    crate::supervisor::ensure_crash_recovery(state.clone());
+    crate::supervisor::ensure_restart_scheduler(state.clone());
     crate::self_update::start_background_update_loop(server_root_for_scheduler, state.clone());
 
     // Signal handler for SIGTERM (graceful shutdown)
-    let state_for_signal = state.clone();
+    let profiles_for_signal = profiles.clone();
     tokio::spawn(async move {
         // Wait for SIGTERM
         signal::unix::signal(signal::unix::SignalKind::terminate())
             .unwrap()
             .recv()
             .await;
-        info!("Received SIGTERM, stopping Minecraft server gracefully...");
-        if let Err(err) = crate::supervisor::stop_server(false, state_for_signal.clone()).await {
-            warn!("SIGTERM graceful shutdown failed: {}", err.message);
-            let _ = crate::supervisor::stop_server(true, state_for_signal).await;
-        }
+        info!("Received SIGTERM, stopping Minecraft servers gracefully...");
+        stop_all_profiles(&profiles_for_signal).await;
         info!("Graceful shutdown complete. Exiting daemon.");
         std::process::exit(0);
     });
 
-    // Signal handler for SIGINT: attempt graceful shutdown on first Ctrl-C, escalate on subsequent presses
-    let state_for_sigint = state.clone();
+    // Signal handler for SIGINT: same bounded shutdown as SIGTERM. Each server
+    // gets its grace period before it is killed, so repeated Ctrl-C only logs.
+    let profiles_for_sigint = profiles.clone();
     tokio::spawn(async move {
         let mut sigint = signal::unix::signal(signal::unix::SignalKind::interrupt()).unwrap();
-        use std::time::{Duration as StdDuration, Instant};
-        let mut last = None::<Instant>;
-        let mut count = 0usize;
-        // define a window in which successive Ctrl-C presses count towards escalation
-        let window = StdDuration::from_secs(10);
-
+        sigint.recv().await;
+        info!("Received SIGINT (Ctrl-C), stopping Minecraft servers gracefully...");
+        let profiles = profiles_for_sigint.clone();
+        tokio::spawn(async move {
+            stop_all_profiles(&profiles).await;
+            info!("Graceful shutdown complete. Exiting daemon.");
+            std::process::exit(0);
+        });
         loop {
-            // wait for a SIGINT
             sigint.recv().await;
-            let now = Instant::now();
-            if let Some(prev) = last {
-                if now.duration_since(prev) > window {
-                    count = 0; // reset if outside the window
-                }
-            }
-            last = Some(now);
-            count = count.saturating_add(1);
-
-            if count == 1 {
-                info!(
-                    "Received SIGINT (Ctrl-C): attempting graceful shutdown. Press Ctrl-C two more times within 10s to force kill."
-                );
-                let state_clone = state_for_sigint.clone();
-                tokio::spawn(async move {
-                    if let Err(err) =
-                        crate::supervisor::stop_server(false, state_clone.clone()).await
-                    {
-                        warn!("SIGINT graceful shutdown failed: {}", err.message);
-                        let _ = crate::supervisor::stop_server(true, state_clone).await;
-                    }
-                    info!("Graceful shutdown complete. Exiting daemon.");
-                    std::process::exit(0);
-                });
-            } else if count == 2 {
-                warn!("Received second Ctrl-C: press once more to force immediate kill.");
-            } else {
-                // third (or more) Ctrl-C within window -> force kill immediately
-                warn!(
-                    "Received Ctrl-C {} times: force killing Minecraft server...",
-                    count
-                );
-                let mut guard = state_for_sigint.lock().await;
-                if let Some(child) = guard.child.as_mut() {
-                    let _ = child.kill().await;
-                    info!("Minecraft server process killed.");
-                }
-                process::exit(1);
-            }
+            warn!("Shutdown already in progress; servers are killed once their grace period ends.");
         }
     });
 
     loop {
-        let (stream, _addr) = listener.accept().await?;
-        let state = Arc::clone(&state);
+        let incoming = listener.accept().await?;
+        let profiles = profiles.clone();
         let start_ms = start_ms;
         tokio::spawn(async move {
-            let _ = handle_conn(stream, state, start_ms).await;
+            let framed = match incoming.handshake().await {
+                Ok(framed) => framed,
+                Err(err) => {
+                    warn!("rejected IPC connection: {}", err);
+                    return;
+                }
+            };
+            let _ = handle_conn(framed, profiles, start_ms).await;
         });
     }
 }
 
+async fn stop_all_profiles(profiles: &Profiles) {
+    for (profile, state) in profiles.all().await {
+        match stop_server(false, None, state).await {
+            Ok(Response::Stopped { forced: true, .. }) => {
+                warn!("profile {} was killed after its stop grace period", profile);
+            }
+            Ok(_) => {}
+            Err(err) => warn!("shutdown of profile {} failed: {}", profile, err.message),
+        }
+    }
+}
+
+fn inverted_log_window(since_ms: Option<u64>, until_ms: Option<u64>) -> Option<String> {
+    match (since_ms, until_ms) {
+        (Some(since), Some(until)) if since > until => Some(format!(
+            "log window is inverted: since ({since}) is after until ({until})"
+        )),
+        _ => None,
+    }
+}
+
+fn log_matcher(filter: Option<&LogFilter>) -> Result<Option<LineMatcher>, String> {
+    filter
+        .map(LineMatcher::new)
+        .transpose()
+        .map_err(|err| format!("invalid log filter regex: {err}"))
+}
+
+fn rpc_error(req_id: RequestId, code: ErrorCode, message: String) -> Outbound {
+    Outbound::Response(Envelope {
+        id: req_id,
+        profile: None,
+        payload: Response::Error(RpcError {
+            code,
+            message,
+            details: Default::default(),
+        }),
+    })
+}
+
 async fn handle_conn(
-    stream: tokio::net::UnixStream,
-    state: SharedState,
+    mut framed: FramedStream,
+    profiles: Profiles,
     daemon_start_ms: u64,
 ) -> std::io::Result<()> {
-    let mut framed = framing::framed(stream);
     let (resp_tx, mut resp_rx) = tokio::sync::mpsc::channel::<PendingOutbound>(32);
 
     // Per-connection session state. Simplest possible.
     let mut next_session_id: SessionId = 1;
     let mut active_session: Option<SessionId> = None;
+    // Set when the client's Ping announced an incompatible protocol; later
+    // requests on this connection are refused, except Ping and Shutdown so an
+    // outdated daemon can still be stopped for an upgrade.
+    let mut rejected_protocol: Option<u32> = None;
+    // Forwards player presence events while the client is subscribed to `Topic::Players`.
+    let mut players_forwarder: Option<tokio::task::JoinHandle<()>> = None;
 
     loop {
         tokio::select! {
@@ -141,41 +166,111 @@ async fn handle_conn(
                 };
         let req_id = req_env.id;
 
+        let always_allowed = matches!(req_env.payload, Request::Ping { .. } | Request::Shutdown {});
+        if let Some(client) = rejected_protocol.filter(|_| !always_allowed) {
+            let err = RpcError::version_mismatch(client, runner_core_v2::PROTOCOL_VERSION);
+            let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: Response::Error(err) });
+            framing::send_outbound(&mut framed, &out).await?;
+            continue;
+        }
+
+        let profile = req_env.profile.clone().unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+        let daemon_wide = matches!(
+            req_env.payload,
+            Request::Ping { .. }
+                | Request::Shutdown {}
+                | Request::DaemonLogsTail { .. }
+                | Request::ListServers {}
+                | Request::Start { .. }
+        );
+        let deploy_only = matches!(
+            req_env.payload,
+            Request::SetMaintenance { .. }
+                | Request::SetChannel { .. }
+                | Request::SetPinnedVersion { .. }
+                | Request::SyncWhitelist {}
+                | Request::SaveDeployKey { .. }
+        );
+        if deploy_only && profile != DEFAULT_PROFILE {
+            let message = format!("profile {profile} has no deploy key; this request only applies to the {DEFAULT_PROFILE} profile");
+            framing::send_outbound(&mut framed, &rpc_error(req_id, ErrorCode::BadRequest, message)).await?;
+            continue;
+        }
+        let state = if daemon_wide {
+            profiles.default_state().await
+        } else {
+            match profiles.get(&profile).await {
+                Ok(state) => state,
+                Err(err) => {
+                    let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: Response::Error(err) });
+                    framing::send_outbound(&mut framed, &out).await?;
+                    continue;
+                }
+            }
+        };
+
         match req_env.payload {
             Request::Shutdown {} => {
                 let tx = resp_tx.clone();
-                let state = state.clone();
+                let profiles = profiles.clone();
                 tokio::spawn(async move {
-                    if let Err(err) = stop_server(false, state.clone()).await {
-                        warn!("shutdown stop failed: {}", err.message);
-                        let _ = stop_server(true, state).await;
-                    }
+                    stop_all_profiles(&profiles).await;
                     let resp = Response::ShutdownAck {};
-                    let out = Outbound::Response(Envelope { id: req_id, payload: resp });
+                    let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
                     let _ = tx.send(PendingOutbound::SendAndExit(out)).await;
                 });
             }
 
             Request::Ping { protocol_version, .. } => {
-                let resp = Response::Pong {
-                    daemon_version: env!("ATLAS_BUILD_VERSION").to_string(),
-                    protocol_version,
+                let daemon_protocol = runner_core_v2::PROTOCOL_VERSION;
+                let resp = if runner_core_v2::protocol_compatible(protocol_version, daemon_protocol) {
+                    Response::Pong {
+                        daemon_version: env!("ATLAS_BUILD_VERSION").to_string(),
+                        protocol_version: daemon_protocol,
+                    }
+                } else {
+                    warn!(
+                        "rejecting client with protocol {} (daemon speaks {})",
+                        protocol_version, daemon_protocol
+                    );
+                    rejected_protocol = Some(protocol_version);
+                    Response::Error(RpcError::version_mismatch(protocol_version, daemon_protocol))
                 };
-                let out = Outbound::Response(Envelope { id: req_id, payload: resp });
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
                 framing::send_outbound(&mut framed, &out).await?;
             }
 
             Request::Status {} => {
-                let (daemon, server) = build_status(daemon_start_ms, &state).await;
-                let resp = Response::Status { daemon, server };
-                let out = Outbound::Response(Envelope { id: req_id, payload: resp });
+                let resp = build_status(daemon_start_ms, &state).await;
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
+                framing::send_outbound(&mut framed, &out).await?;
+            }
+
+            Request::ListServers {} => {
+                let resp = list_servers(&profiles).await;
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
                 framing::send_outbound(&mut framed, &out).await?;
             }
 
             Request::Start { profile, env } => {
+                let state = match profiles.get_or_create(&profile).await {
+                    Ok(state) => state,
+                    Err(err) => {
+                        let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: Response::Error(err) });
+                        framing::send_outbound(&mut framed, &out).await?;
+                        continue;
+                    }
+                };
+                // Other managed servers are fine; one this daemon did not start is not.
+                if let Some((pid, cmdline)) = find_unmanaged_minecraft_process(&profiles.managed_pids().await) {
+                    let message = format!("an unmanaged Minecraft process is running (pid={pid}): {cmdline}");
+                    framing::send_outbound(&mut framed, &rpc_error(req_id, ErrorCode::BadRequest, message)).await?;
+                    continue;
+                }
                 let tx = resp_tx.clone();
-                let state = state.clone();
                 tokio::spawn(async move {
+                    // An explicit start is how operators resume a crash-looping server.
+                    clear_crash_loop_state(&state).await;
                     let pack_blob_path = match env.get("ATLAS_PACK_BLOB") {
                         Some(path) if !path.trim().is_empty() => path.clone(),
                         _ => {
@@ -184,20 +279,15 @@ async fn handle_conn(
                                 message: "ATLAS_PACK_BLOB is required".into(),
                                 details: Default::default(),
                             });
-                            let out = Outbound::Response(Envelope { id: req_id, payload: err });
+                            let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: err });
                             let _ = tx.send(PendingOutbound::Send(out)).await;
                             return;
                         }
                     };
-                    let pack_blob_bytes = match tokio::fs::read(&pack_blob_path).await {
+                    let pack_blob_bytes = match read_pack_blob(std::path::Path::new(&pack_blob_path)).await {
                         Ok(bytes) => bytes,
                         Err(err) => {
-                            let err = Response::Error(RpcError {
-                                code: ErrorCode::IoError,
-                                message: format!("failed to read pack blob: {err}"),
-                                details: Default::default(),
-                            });
-                            let out = Outbound::Response(Envelope { id: req_id, payload: err });
+                            let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: Response::Error(err) });
                             let _ = tx.send(PendingOutbound::Send(out)).await;
                             return;
                         }
@@ -206,35 +296,57 @@ async fn handle_conn(
                         .get("ATLAS_SERVER_ROOT")
                         .map(|value| std::path::PathBuf::from(value))
                         .unwrap_or_else(|| default_server_root(&profile));
-                    let payload = match crate::supervisor::start_server(profile, &pack_blob_bytes, server_root, state).await {
+                    let progress_tx = tx.clone();
+                    let progress = move |step: runner_provision_v2::ProvisionProgress| {
+                        let event = Event::StartProgress {
+                            phase: step.phase.to_string(),
+                            message: step.message,
+                            current: step.current,
+                            total: step.total,
+                        };
+                        // Progress is best effort; never block provisioning on a slow client.
+                        let _ = progress_tx.try_send(PendingOutbound::Send(Outbound::Event(event)));
+                    };
+                    let payload = match crate::supervisor::start_server(profile, &pack_blob_bytes, server_root, state, &progress).await {
                         Ok(resp) => resp,
                         Err(err) => Response::Error(err),
                     };
-                    let out = Outbound::Response(Envelope { id: req_id, payload });
+                    let out = Outbound::Response(Envelope { id: req_id, profile: None, payload });
                     let _ = tx.send(PendingOutbound::Send(out)).await;
                 });
             }
 
-            Request::Stop { force, .. } => {
+            Request::Stop { force, grace_ms } => {
                 let tx = resp_tx.clone();
                 let state = state.clone();
                 tokio::spawn(async move {
-                    let payload = match stop_server(force, state).await {
+                    let payload = match stop_server(force, grace_ms, state).await {
                         Ok(resp) => resp,
                         Err(err) => Response::Error(err),
                     };
-                    let out = Outbound::Response(Envelope { id: req_id, payload });
+                    let out = Outbound::Response(Envelope { id: req_id, profile: None, payload });
                     let _ = tx.send(PendingOutbound::Send(out)).await;
                 });
             }
 
-            Request::LogsTail { lines } => {
+            Request::LogsTail { lines, since_ms, until_ms, filter } => {
+                if let Some(message) = inverted_log_window(since_ms, until_ms) {
+                    framing::send_outbound(&mut framed, &rpc_error(req_id, ErrorCode::BadRequest, message)).await?;
+                    continue;
+                }
+                let matcher = match log_matcher(filter.as_ref()) {
+                    Ok(matcher) => matcher,
+                    Err(message) => {
+                        framing::send_outbound(&mut framed, &rpc_error(req_id, ErrorCode::BadRequest, message)).await?;
+                        continue;
+                    }
+                };
                 let logs = {
                     let guard = state.lock().await;
                     guard.logs.clone()
                 };
-                let mut log_lines = logs.tail_server(lines);
-                if log_lines.is_empty() {
+                let (mut log_lines, truncated) = logs.window_server(lines, since_ms, until_ms, matcher.as_ref());
+                if log_lines.is_empty() && since_ms.is_none() && until_ms.is_none() && matcher.is_none() {
                     log_lines.push(runner_core_v2::proto::LogLine {
                         at_ms: crate::supervisor::now_millis(),
                         stream: runner_core_v2::proto::LogStream::Stdout,
@@ -243,22 +355,31 @@ async fn handle_conn(
                 }
                 let payload = Response::LogsTail {
                     lines: log_lines,
-                    truncated: false,
+                    truncated,
                 };
-                let out = Outbound::Response(Envelope { id: req_id, payload });
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload });
                 framing::send_outbound(&mut framed, &out).await?;
             }
 
-            Request::DaemonLogsTail { lines } => {
+            Request::DaemonLogsTail { lines, since_ms, until_ms, filter } => {
+                if let Some(message) = inverted_log_window(since_ms, until_ms) {
+                    framing::send_outbound(&mut framed, &rpc_error(req_id, ErrorCode::BadRequest, message)).await?;
+                    continue;
+                }
+                let matcher = match log_matcher(filter.as_ref()) {
+                    Ok(matcher) => matcher,
+                    Err(message) => {
+                        framing::send_outbound(&mut framed, &rpc_error(req_id, ErrorCode::BadRequest, message)).await?;
+                        continue;
+                    }
+                };
                 let logs = {
                     let guard = state.lock().await;
                     guard.logs.clone()
                 };
-                let payload = Response::LogsTail {
-                    lines: logs.tail_daemon(lines),
-                    truncated: false,
-                };
-                let out = Outbound::Response(Envelope { id: req_id, payload });
+                let (lines, truncated) = logs.window_daemon(lines, since_ms, until_ms, matcher.as_ref());
+                let payload = Response::LogsTail { lines, truncated };
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload });
                 framing::send_outbound(&mut framed, &out).await?;
             }
 
@@ -266,12 +387,13 @@ async fn handle_conn(
                 match execute_rcon_command(&state, &command).await {
                     Ok(text) => {
                         let resp = Response::RconResult { text };
-                        let out = Outbound::Response(Envelope { id: req_id, payload: resp });
+                        let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
                         framing::send_outbound(&mut framed, &out).await?;
                     }
                     Err(err) => {
                         let out = Outbound::Response(Envelope {
                             id: req_id,
+                            profile: None,
                             payload: Response::Error(RpcError {
                                 code: ErrorCode::InvalidConfig,
                                 message: err,
@@ -287,6 +409,7 @@ async fn handle_conn(
                 if active_session.is_some() {
                     let out = Outbound::Response(Envelope {
                         id: req_id,
+                        profile: None,
                         payload: Response::Error(RpcError {
                             code: ErrorCode::BadRequest,
                             message: "RCON session already open on this connection".into(),
@@ -300,6 +423,7 @@ async fn handle_conn(
                 if let Err(err) = ensure_rcon_available(&state).await {
                     let out = Outbound::Response(Envelope {
                         id: req_id,
+                        profile: None,
                         payload: Response::Error(RpcError {
                             code: ErrorCode::InvalidConfig,
                             message: err,
@@ -315,7 +439,7 @@ async fn handle_conn(
                 active_session = Some(sid);
 
                 let resp = Response::RconOpened { session: sid, prompt: "rcon> ".into() };
-                let out = Outbound::Response(Envelope { id: req_id, payload: resp });
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
                 framing::send_outbound(&mut framed, &out).await?;
             }
 
@@ -323,6 +447,7 @@ async fn handle_conn(
                 if active_session != Some(session) {
                     let out = Outbound::Response(Envelope {
                         id: req_id,
+                        profile: None,
                         payload: Response::Error(RpcError {
                             code: ErrorCode::BadRequest,
                             message: "invalid or inactive session".into(),
@@ -351,6 +476,7 @@ async fn handle_conn(
                 if active_session != Some(session) {
                     let out = Outbound::Response(Envelope {
                         id: req_id,
+                        profile: None,
                         payload: Response::Error(RpcError {
                             code: ErrorCode::BadRequest,
                             message: "invalid or inactive session".into(),
@@ -363,7 +489,131 @@ async fn handle_conn(
 
                 active_session = None;
                 let resp = Response::RconClosed { session };
-                let out = Outbound::Response(Envelope { id: req_id, payload: resp });
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
+                framing::send_outbound(&mut framed, &out).await?;
+            }
+
+            Request::AcceptEula { server_root } => {
+                let server_root = server_root.map(std::path::PathBuf::from);
+                let payload = match accept_eula(&state, server_root).await {
+                    Ok(resp) => resp,
+                    Err(err) => Response::Error(err),
+                };
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload });
+                framing::send_outbound(&mut framed, &out).await?;
+            }
+
+            Request::Subscribe { topics, .. } => {
+                if let Some(task) = players_forwarder.take() {
+                    task.abort();
+                }
+                // Only player presence is streamed today; the reply lists what was subscribed.
+                let topics = topics
+                    .into_iter()
+                    .filter(|topic| matches!(topic, Topic::Players))
+                    .collect::<Vec<_>>();
+                if !topics.is_empty() {
+                    let mut events = state.lock().await.players.subscribe();
+                    let tx = resp_tx.clone();
+                    players_forwarder = Some(tokio::spawn(async move {
+                        loop {
+                            match events.recv().await {
+                                Ok(event) => {
+                                    let out = Outbound::Event(event);
+                                    if tx.send(PendingOutbound::Send(out)).await.is_err() {
+                                        break;
+                                    }
+                                }
+                                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                            }
+                        }
+                    }));
+                }
+                let resp = Response::Subscribed { topics };
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
+                framing::send_outbound(&mut framed, &out).await?;
+            }
+
+            Request::Unsubscribe {} => {
+                if let Some(task) = players_forwarder.take() {
+                    task.abort();
+                }
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: Response::Unsubscribed {} });
+                framing::send_outbound(&mut framed, &out).await?;
+            }
+
+            Request::ClearCrashLoop {} => {
+                clear_crash_loop_state(&state).await;
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: Response::CrashLoopCleared {} });
+                framing::send_outbound(&mut framed, &out).await?;
+            }
+
+            Request::SetMaintenance { on } => {
+                let payload = match set_maintenance(&state, on).await {
+                    Ok(resp) => resp,
+                    Err(err) => Response::Error(err),
+                };
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload });
+                framing::send_outbound(&mut framed, &out).await?;
+            }
+
+            Request::SetChannel { channel } => {
+                let tx = resp_tx.clone();
+                let state = state.clone();
+                tokio::spawn(async move {
+                    let payload = match set_channel(&state, channel).await {
+                        Ok(resp) => resp,
+                        Err(err) => Response::Error(err),
+                    };
+                    let out = Outbound::Response(Envelope { id: req_id, profile: None, payload });
+                    let _ = tx.send(PendingOutbound::Send(out)).await;
+                });
+            }
+
+            Request::SetPinnedVersion { version } => {
+                let payload = match set_pinned_version(&state, version).await {
+                    Ok(resp) => resp,
+                    Err(err) => Response::Error(err),
+                };
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload });
+                framing::send_outbound(&mut framed, &out).await?;
+            }
+
+            Request::Metrics {} => {
+                let resp = build_metrics(&state).await;
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
+                framing::send_outbound(&mut framed, &out).await?;
+            }
+
+            Request::SyncWhitelist {} => {
+                let tx = resp_tx.clone();
+                let state = state.clone();
+                tokio::spawn(async move {
+                    let payload = match sync_whitelist_now(&state).await {
+                        Ok(resp) => resp,
+                        Err(err) => Response::Error(err),
+                    };
+                    let out = Outbound::Response(Envelope { id: req_id, profile: None, payload });
+                    let _ = tx.send(PendingOutbound::Send(out)).await;
+                });
+            }
+
+            Request::GetServerProperties {} => {
+                let payload = match get_server_properties(&state).await {
+                    Ok(resp) => resp,
+                    Err(err) => Response::Error(err),
+                };
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload });
+                framing::send_outbound(&mut framed, &out).await?;
+            }
+
+            Request::SetServerProperties { changes } => {
+                let payload = match set_server_properties(&state, changes).await {
+                    Ok(resp) => resp,
+                    Err(err) => Response::Error(err),
+                };
+                let out = Outbound::Response(Envelope { id: req_id, profile: None, payload });
                 framing::send_outbound(&mut framed, &out).await?;
             }
 
@@ -374,18 +624,31 @@ async fn handle_conn(
                 deploy_key,
                 prefix,
             } => {
-                let config = DeployKeyConfig {
-                    hub_url,
-                    pack_id,
-                    channel,
-                    deploy_key,
-                    prefix,
-                    max_ram: None,
-                    should_autostart: None,
-                    eula_accepted: None,
-                };
+                // Keep settings recorded earlier (EULA acceptance, operator sync, crash-loop
+                // limits) when the key is re-saved.
+                let existing = crate::config::load_deploy_key().ok().flatten().unwrap_or_default();
+                // The recorded token id only describes the key it was saved with.
+                let token_id = existing
+                    .token_id
+                    .clone()
+                    .filter(|_| existing.deploy_key == deploy_key);
+                let saved = normalize_hub_url_string(&hub_url)
+                    .map_err(|err| err.to_string())
+                    .and_then(|hub_url| {
+                        save_deploy_key(&DeployKeyConfig {
+                            hub_url,
+                            pack_id,
+                            channel,
+                            deploy_key,
+                            prefix,
+                            token_id,
+                            max_ram: None,
+                            should_autostart: None,
+                            ..existing
+                        })
+                    });
 
-                match save_deploy_key(&config) {
+                match saved {
                     Ok(()) => {
                         // ensure_watchers may be non-Send; run it on a current-thread runtime inside spawn_blocking
                         let state_for_watchers = state.clone();
@@ -399,12 +662,13 @@ async fn handle_conn(
                             });
                         });
                         let resp = Response::DeployKeySaved {};
-                        let out = Outbound::Response(Envelope { id: req_id, payload: resp });
+                        let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
                         framing::send_outbound(&mut framed, &out).await?;
                     }
                     Err(err) => {
                         let out = Outbound::Response(Envelope {
                             id: req_id,
+                            profile: None,
                             payload: Response::Error(RpcError {
                                 code: ErrorCode::InvalidConfig,
                                 message: err,
@@ -425,7 +689,7 @@ async fn handle_conn(
                         Some(r) => r,
                         None => {
                             let err = Response::Error(RpcError { code: ErrorCode::BadRequest, message: "server root not configured".into(), details: Default::default() });
-                            let out = Outbound::Response(Envelope { id: req_id, payload: err });
+                            let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: err });
                             let _ = tx.send(PendingOutbound::Send(out)).await;
                             return;
                         }
@@ -434,34 +698,25 @@ async fn handle_conn(
                     match crate::backup::backup_before_update(&server_root, state.clone()).await {
                         Ok(path) => {
                             let resp = Response::BackupCreated { path: path.display().to_string() };
-                            let out = Outbound::Response(Envelope { id: req_id, payload: resp });
+                            let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
                             let _ = tx.send(PendingOutbound::Send(out)).await;
                         }
                         Err(err) => {
                             let resp = Response::Error(RpcError { code: ErrorCode::Internal, message: err, details: Default::default() });
-                            let out = Outbound::Response(Envelope { id: req_id, payload: resp });
+                            let out = Outbound::Response(Envelope { id: req_id, profile: None, payload: resp });
                             let _ = tx.send(PendingOutbound::Send(out)).await;
                         }
                     }
                 });
             }
-
-            _ => {
-                let out = Outbound::Response(Envelope {
-                    id: req_id,
-                    payload: Response::Error(RpcError {
-                        code: ErrorCode::UnsupportedProtocol,
-                        message: "unsupported request type".into(),
-                        details: Default::default(),
-                    }),
-                });
-                framing::send_outbound(&mut framed, &out).await?;
-            }
         }
             }
         }
     }
 
+    if let Some(task) = players_forwarder {
+        task.abort();
+    }
     Ok(())
 }
 
@@ -1,17 +1,153 @@
 use fs2::FileExt;
-use std::{fs::File, fs::OpenOptions, path::Path};
+use runner_v2_utils::{IpcEndpoint, RuntimePaths};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::{fs::File, fs::OpenOptions};
+use tracing::{info, warn};
 
 pub struct LockGuard {
     _file: File,
 }
 
-pub fn acquire_lock(path: &Path) -> std::io::Result<LockGuard> {
-    let file = OpenOptions::new()
+pub enum RuntimeState {
+    /// Another daemon holds the lock or answers on the socket.
+    AlreadyRunning,
+    /// This process owns the lock and the socket path is free to bind.
+    Recovered(LockGuard),
+}
+
+/// Settles the lock and socket together before binding. The flock is the
+/// authority: whoever holds it owns the runtime dir, so a stale socket is only
+/// removed while holding it and a lock left by a dead daemon is reclaimed in
+/// place (deleting the file would let a second daemon lock a fresh inode).
+pub async fn recover_runtime_state(paths: &RuntimePaths) -> std::io::Result<RuntimeState> {
+    let mut file = OpenOptions::new()
         .create(true)
+        .truncate(false)
         .read(true)
         .write(true)
-        .open(path)?;
+        .open(&paths.lock_path)?;
+    let recorded_pid = read_pid(&mut file);
+
+    if let Err(err) = file.try_lock_exclusive() {
+        if err.kind() != std::io::ErrorKind::WouldBlock {
+            return Err(err);
+        }
+        let socket_alive = runner_ipc_v2::transport::alive(paths).await;
+        match recorded_pid {
+            Some(pid) if !socket_alive => warn!(
+                "daemon pid {} holds the lock but its socket is not answering; leaving it alone",
+                pid
+            ),
+            Some(pid) => info!("daemon already running (pid {})", pid),
+            None => info!("daemon already running (lock held)"),
+        }
+        return Ok(RuntimeState::AlreadyRunning);
+    }
+
+    if let Some(pid) = recorded_pid.filter(|pid| *pid != std::process::id()) {
+        if pid_alive(pid) {
+            warn!(
+                "reclaimed lock recorded for pid {} (process exists but does not hold the lock)",
+                pid
+            );
+        } else {
+            info!("reclaimed stale lock left by pid {}", pid);
+        }
+    }
+
+    let endpoint = runner_ipc_v2::transport::endpoint(paths)?;
+    if runner_ipc_v2::transport::alive(paths).await {
+        warn!(
+            "{} is answering without holding the lock; exiting",
+            runner_ipc_v2::transport::describe(&endpoint)
+        );
+        return Ok(RuntimeState::AlreadyRunning);
+    }
+    if let IpcEndpoint::Unix(path) = &endpoint
+        && path.exists()
+    {
+        runner_ipc_v2::socket::remove_stale_socket(path)?;
+        info!("removed stale socket {:?}", path);
+    }
+
+    write_pid(&mut file)?;
+    Ok(RuntimeState::Recovered(LockGuard { _file: file }))
+}
+
+fn read_pid(file: &mut File) -> Option<u32> {
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).ok()?;
+    contents.trim().parse().ok()
+}
+
+fn write_pid(file: &mut File) -> std::io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    writeln!(file, "{}", std::process::id())?;
+    file.sync_all()
+}
+
+fn pid_alive(pid: u32) -> bool {
+    let Ok(pid) = i32::try_from(pid) else {
+        return false;
+    };
+    // Signal 0 only checks existence; EPERM still means the process exists.
+    let alive = unsafe { libc::kill(pid, 0) } == 0;
+    alive || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_paths(name: &str) -> RuntimePaths {
+        let runtime_dir =
+            std::env::temp_dir().join(format!("runnerd-lock-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&runtime_dir);
+        std::fs::create_dir_all(&runtime_dir).unwrap();
+        RuntimePaths {
+            socket_path: runtime_dir.join("runnerd.sock"),
+            lock_path: runtime_dir.join("runnerd.lock"),
+            token_path: runtime_dir.join("runnerd.token"),
+            runtime_dir,
+        }
+    }
+
+    #[tokio::test]
+    async fn reclaims_dead_lock_and_removes_stale_socket() {
+        let paths = temp_paths("stale");
+        std::fs::write(&paths.lock_path, "999999999\n").unwrap();
+        std::fs::write(&paths.socket_path, b"").unwrap();
+
+        let state = recover_runtime_state(&paths).await.unwrap();
+        assert!(matches!(state, RuntimeState::Recovered(_)));
+        assert!(!paths.socket_path.exists());
+        assert_eq!(
+            std::fs::read_to_string(&paths.lock_path).unwrap().trim(),
+            std::process::id().to_string()
+        );
+
+        drop(state);
+        let _ = std::fs::remove_dir_all(&paths.runtime_dir);
+    }
+
+    #[tokio::test]
+    async fn reports_running_daemon_when_lock_is_held() {
+        let paths = temp_paths("held");
+        let held = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&paths.lock_path)
+            .unwrap();
+        held.try_lock_exclusive().unwrap();
+        std::fs::write(&paths.socket_path, b"").unwrap();
+
+        let state = recover_runtime_state(&paths).await.unwrap();
+        assert!(matches!(state, RuntimeState::AlreadyRunning));
+        assert!(paths.socket_path.exists());
 
-    file.try_lock_exclusive()?;
-    Ok(LockGuard { _file: file })
+        drop(held);
+        let _ = std::fs::remove_dir_all(&paths.runtime_dir);
+    }
 }
@@ -158,7 +158,7 @@ pub async fn maybe_apply_staged_update(
 }
 
 async fn check_and_stage_updates(server_root: &PathBuf, state: SharedState) -> Result<(), String> {
-    let arch = normalize_distribution_arch(std::env::consts::ARCH)?;
+    let arch = protocol::current_arch().map_err(|err| format!("{err} for self-update"))?;
     let mut hub = HubClient::new(&resolve_hub_url())
         .map_err(|err| format!("failed to create hub client for self-update: {err}"))?;
     if let Ok(token) = std::env::var("ATLAS_TOKEN") {
@@ -329,28 +329,18 @@ fn evaluate_activation(os: &str, systemd_managed: bool, uid: u32) -> Result<(),
     Ok(())
 }
 
-fn normalize_distribution_arch(arch: &str) -> Result<&'static str, String> {
-    match arch {
-        "x86_64" | "amd64" => Ok("x64"),
-        "aarch64" | "arm64" => Ok("arm64"),
-        other => Err(format!(
-            "unsupported architecture '{other}' for self-update"
-        )),
-    }
-}
-
 fn resolve_hub_url() -> String {
     if let Ok(Some(config)) = config::load_deploy_key() {
         let trimmed = config.hub_url.trim();
         if !trimmed.is_empty() {
-            return trimmed.trim_end_matches('/').to_string();
+            return trimmed.to_string();
         }
     }
 
     if let Ok(value) = std::env::var("ATLAS_HUB_URL") {
         let trimmed = value.trim();
         if !trimmed.is_empty() {
-            return trimmed.trim_end_matches('/').to_string();
+            return trimmed.to_string();
         }
     }
 
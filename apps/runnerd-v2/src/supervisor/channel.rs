@@ -0,0 +1,84 @@
+use atlas_client::hub::HubClient;
+use runner_core_v2::proto::{ErrorCode, Response, RpcError};
+use tracing::{info, warn};
+
+use crate::config;
+
+use super::state::SharedState;
+use super::updates::reconcile_now;
+
+const CHANNELS: &[&str] = &["dev", "beta", "production"];
+
+pub async fn set_channel(state: &SharedState, channel: String) -> Result<Response, RpcError> {
+    let channel = channel.trim().to_ascii_lowercase();
+    if !CHANNELS.contains(&channel.as_str()) {
+        return Err(RpcError {
+            code: ErrorCode::BadRequest,
+            message: format!(
+                "unknown channel '{channel}'; expected one of: {}",
+                CHANNELS.join(", ")
+            ),
+            details: Default::default(),
+        });
+    }
+
+    let mut deploy = config::load_deploy_key()
+        .map_err(internal)?
+        .ok_or_else(|| RpcError {
+            code: ErrorCode::InvalidConfig,
+            message: "deploy key not configured".into(),
+            details: Default::default(),
+        })?;
+
+    let mut hub = HubClient::new(&deploy.hub_url).map_err(|err| internal(err.to_string()))?;
+    hub.set_service_token(deploy.deploy_key.clone());
+    let allowed = hub
+        .has_channel_access(&deploy.pack_id, &channel)
+        .await
+        .map_err(|err| internal(format!("failed to check channel access: {err}")))?;
+    if !allowed {
+        return Err(RpcError {
+            code: ErrorCode::PermissionDenied,
+            message: format!(
+                "the deploy key for pack {} cannot download builds from the {channel} channel",
+                deploy.pack_id
+            ),
+            details: Default::default(),
+        });
+    }
+
+    deploy.channel = channel.clone();
+    config::save_deploy_key(&deploy).map_err(internal)?;
+
+    let reconciling = {
+        let guard = state.lock().await;
+        guard
+            .logs
+            .push_daemon(format!("switched to channel {channel}"));
+        guard.is_running() && !guard.maintenance && guard.pinned_version.is_none()
+    };
+    info!("switched to channel {channel}");
+
+    // A stopped server picks the channel up on its next start.
+    if reconciling {
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(err) = reconcile_now(state).await {
+                warn!("reconcile after channel switch failed: {err}");
+            }
+        });
+    }
+
+    Ok(Response::ChannelSet {
+        channel,
+        reconciling,
+    })
+}
+
+fn internal(message: String) -> RpcError {
+    RpcError {
+        code: ErrorCode::Internal,
+        message,
+        details: Default::default(),
+    }
+}
@@ -0,0 +1,144 @@
+use tokio::time::{Duration, sleep};
+use tracing::{info, warn};
+
+use runner_core_v2::proto::CrashInfo;
+
+use crate::config;
+
+use super::server::restart_after_crash;
+use super::state::{ServerState, SharedState};
+use super::util::now_millis;
+
+const DEFAULT_MAX_CRASHES: u32 = 3;
+const DEFAULT_WINDOW_SECS: u64 = 60;
+const CRASH_LOG_TAIL_LINES: usize = 50;
+const MAX_RESTART_DELAY_SECS: u64 = 60;
+
+/// Records a crash and either schedules an automatic restart or, when the server
+/// crashed too often within the configured window, marks it as crash-looping so
+/// only an explicit `Start` or `ClearCrashLoop` resumes it.
+pub(crate) fn record_crash(state: &mut ServerState, exit_code: Option<i32>, uptime_ms: u64) {
+    record_crash_with_limits(state, exit_code, uptime_ms, crash_loop_limits());
+}
+
+fn record_crash_with_limits(
+    state: &mut ServerState,
+    exit_code: Option<i32>,
+    uptime_ms: u64,
+    (max_crashes, window_secs): (u32, u64),
+) {
+    let now = now_millis();
+    let window_ms = window_secs.saturating_mul(1000);
+
+    state.crash_times.push_back(now);
+    while state
+        .crash_times
+        .front()
+        .is_some_and(|at| now.saturating_sub(*at) > window_ms)
+    {
+        state.crash_times.pop_front();
+    }
+
+    state.last_crash = Some(CrashInfo {
+        exit_code,
+        at_ms: now,
+        uptime_ms,
+        log_tail: state
+            .logs
+            .tail_server(CRASH_LOG_TAIL_LINES)
+            .into_iter()
+            .map(|line| line.line)
+            .collect(),
+    });
+
+    if state.crash_times.len() as u32 >= max_crashes {
+        state.crash_looping = true;
+        state.restart_pending_at_ms = None;
+        state.logs.push_daemon(format!(
+            "crash loop detected: {} crashes within {}s; automatic restarts paused until the server is started again",
+            state.crash_times.len(),
+            window_secs
+        ));
+        return;
+    }
+
+    let delay_secs =
+        (5u64 << state.crash_times.len().saturating_sub(1)).min(MAX_RESTART_DELAY_SECS);
+    state.restart_pending_at_ms = Some(now + delay_secs * 1000);
+    state
+        .logs
+        .push_daemon(format!("restarting server in {delay_secs}s"));
+}
+
+pub(crate) fn clear_crash_loop(state: &mut ServerState) {
+    state.crash_looping = false;
+    state.crash_times.clear();
+    state.restart_pending_at_ms = None;
+}
+
+pub fn ensure_crash_recovery(state: SharedState) {
+    tokio::spawn(async move {
+        loop {
+            sleep(Duration::from_secs(1)).await;
+            let due = {
+                let mut guard = state.lock().await;
+                let due = guard
+                    .restart_pending_at_ms
+                    .is_some_and(|at| now_millis() >= at);
+                if due {
+                    guard.restart_pending_at_ms = None;
+                }
+                due && !guard.crash_looping && !guard.is_running()
+            };
+            if due {
+                info!("restarting server after crash");
+                restart_after_crash(state.clone()).await;
+            }
+        }
+    });
+}
+
+fn crash_loop_limits() -> (u32, u64) {
+    match config::load_deploy_key() {
+        Ok(Some(deploy)) => (
+            deploy
+                .crash_loop_max_restarts
+                .filter(|max| *max > 0)
+                .unwrap_or(DEFAULT_MAX_CRASHES),
+            deploy
+                .crash_loop_window_secs
+                .filter(|secs| *secs > 0)
+                .unwrap_or(DEFAULT_WINDOW_SECS),
+        ),
+        Ok(None) => (DEFAULT_MAX_CRASHES, DEFAULT_WINDOW_SECS),
+        Err(err) => {
+            warn!("failed to load crash loop limits: {err}");
+            (DEFAULT_MAX_CRASHES, DEFAULT_WINDOW_SECS)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clear_crash_loop, record_crash_with_limits};
+    use crate::supervisor::logs::LogStore;
+    use crate::supervisor::state::ServerState;
+
+    #[test]
+    fn stops_restarting_after_repeated_crashes() {
+        let mut state = ServerState::new(LogStore::new(100, 100));
+        record_crash_with_limits(&mut state, Some(1), 500, (3, 60));
+        assert!(state.restart_pending_at_ms.is_some());
+        record_crash_with_limits(&mut state, Some(1), 500, (3, 60));
+        assert!(!state.crash_looping);
+
+        record_crash_with_limits(&mut state, Some(1), 500, (3, 60));
+        assert!(state.crash_looping);
+        assert!(state.restart_pending_at_ms.is_none());
+        assert_eq!(state.last_crash.as_ref().and_then(|c| c.exit_code), Some(1));
+
+        clear_crash_loop(&mut state);
+        assert!(!state.crash_looping);
+        assert!(state.crash_times.is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+
+use runner_core_v2::proto::{DEFAULT_PROFILE, ErrorCode, Response, RpcError};
+
+use crate::config;
+
+use super::state::SharedState;
+use super::util::{current_server_root, default_server_root};
+
+const EULA_CONTENTS: &str = "# Generated by atlas-runnerd\neula=true\n";
+
+pub async fn accept_eula(
+    state: &SharedState,
+    server_root: Option<PathBuf>,
+) -> Result<Response, RpcError> {
+    let server_root = match server_root {
+        Some(root) => root,
+        None => current_server_root(state)
+            .await
+            .unwrap_or_else(|| default_server_root(DEFAULT_PROFILE)),
+    };
+
+    let current = server_root.join("current");
+    let eula_path = current.join("eula.txt");
+    tokio::fs::create_dir_all(&current)
+        .await
+        .map_err(|err| io_error(&current, err))?;
+    tokio::fs::write(&eula_path, EULA_CONTENTS)
+        .await
+        .map_err(|err| io_error(&eula_path, err))?;
+
+    let recorded = match config::load_deploy_key() {
+        Ok(Some(mut deploy)) => {
+            deploy.eula_accepted = Some(true);
+            config::save_deploy_key(&deploy).map_err(|message| RpcError {
+                code: ErrorCode::IoError,
+                message,
+                details: Default::default(),
+            })?;
+            true
+        }
+        _ => false,
+    };
+
+    Ok(Response::EulaAccepted {
+        path: eula_path.display().to_string(),
+        recorded,
+    })
+}
+
+// Acceptance comes from deploy.json, or from an eula.txt that already says
+// `eula=true` so servers provisioned before acceptance was tracked keep starting.
+pub(crate) async fn eula_accepted(server_root: &Path) -> bool {
+    if matches!(
+        config::load_deploy_key(),
+        Ok(Some(deploy)) if deploy.eula_accepted == Some(true)
+    ) {
+        return true;
+    }
+
+    let eula_path = server_root.join("current").join("eula.txt");
+    tokio::fs::read_to_string(&eula_path)
+        .await
+        .map(|contents| eula_file_accepted(&contents))
+        .unwrap_or(false)
+}
+
+pub(crate) async fn ensure_eula_accepted(server_root: &Path) -> Result<(), RpcError> {
+    if eula_accepted(server_root).await {
+        return Ok(());
+    }
+
+    Err(RpcError {
+        code: ErrorCode::EulaNotAccepted,
+        message: "Minecraft EULA has not been accepted; run `atlas-runner server accept-eula` or start with --accept-eula".into(),
+        details: [("eula_url".into(), "https://aka.ms/MinecraftEULA".into())]
+            .into_iter()
+            .collect(),
+    })
+}
+
+fn eula_file_accepted(contents: &str) -> bool {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .any(|(key, value)| key.trim() == "eula" && value.trim().eq_ignore_ascii_case("true"))
+}
+
+fn io_error(path: &Path, err: std::io::Error) -> RpcError {
+    RpcError {
+        code: ErrorCode::IoError,
+        message: format!("failed to write {}: {err}", path.display()),
+        details: Default::default(),
+    }
+}
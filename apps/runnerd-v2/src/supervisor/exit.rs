@@ -0,0 +1,88 @@
+use std::process::ExitStatus;
+
+use runner_core_v2::proto::{ExitInfo, ExitReason, LastExit};
+
+use super::state::ServerState;
+use super::util::now_millis;
+
+const SIGKILL: i32 = 9;
+const OOM_MARKERS: [&str; 2] = ["java.lang.OutOfMemoryError", "Out of memory"];
+const OOM_LOG_TAIL_LINES: usize = 50;
+
+pub(crate) fn exit_info_from_status(status: ExitStatus) -> ExitInfo {
+    #[cfg(unix)]
+    let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+    #[cfg(not(unix))]
+    let signal = None;
+    ExitInfo {
+        code: status.code(),
+        signal,
+    }
+}
+
+/// Keeps `exit` as the server's last exit until the next one. `reason` is
+/// known for stops the daemon asked for; otherwise it is read from the exit
+/// status and the log tail.
+pub(crate) fn record_exit(state: &mut ServerState, exit: &ExitInfo, reason: Option<ExitReason>) {
+    let reason = reason.unwrap_or_else(|| {
+        let log_tail = state.logs.tail_server(OOM_LOG_TAIL_LINES);
+        classify_unexpected_exit(exit, log_tail.iter().map(|line| line.line.as_str()))
+    });
+    state.last_exit = Some(LastExit {
+        exit: exit.clone(),
+        reason,
+        at_ms: now_millis(),
+    });
+}
+
+// The kernel OOM killer sends SIGKILL; the JVM logs OutOfMemoryError first
+// when it is the heap that ran out.
+fn classify_unexpected_exit<'a>(
+    exit: &ExitInfo,
+    mut log_tail: impl Iterator<Item = &'a str>,
+) -> ExitReason {
+    if exit.signal == Some(SIGKILL)
+        || log_tail.any(|line| OOM_MARKERS.iter().any(|marker| line.contains(marker)))
+    {
+        return ExitReason::OutOfMemory;
+    }
+    if exit.code == Some(0) {
+        return ExitReason::Graceful;
+    }
+    ExitReason::Crash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::classify_unexpected_exit;
+    use runner_core_v2::proto::{ExitInfo, ExitReason};
+
+    fn exit(code: Option<i32>, signal: Option<i32>) -> ExitInfo {
+        ExitInfo { code, signal }
+    }
+
+    #[test]
+    fn classifies_unexpected_exits() {
+        let quiet = std::iter::empty::<&str>;
+        assert_eq!(
+            classify_unexpected_exit(&exit(Some(1), None), quiet()),
+            ExitReason::Crash
+        );
+        assert_eq!(
+            classify_unexpected_exit(&exit(Some(0), None), quiet()),
+            ExitReason::Graceful
+        );
+        assert_eq!(
+            classify_unexpected_exit(&exit(None, Some(9)), quiet()),
+            ExitReason::OutOfMemory
+        );
+        assert_eq!(
+            classify_unexpected_exit(
+                &exit(Some(1), None),
+                ["Exception in thread \"Server thread\" java.lang.OutOfMemoryError: Java heap space"]
+                    .into_iter()
+            ),
+            ExitReason::OutOfMemory
+        );
+    }
+}
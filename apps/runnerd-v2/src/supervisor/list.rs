@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use runner_core_v2::proto::{DEFAULT_PROFILE, Response, ServerStatus, ServerSummary};
+use serde::Deserialize;
+
+use crate::config;
+
+use super::profiles::Profiles;
+use super::properties::parse_properties;
+use super::server::refresh_child_status;
+use super::state::SharedState;
+use super::util::now_millis;
+
+/// Pack recorded by provisioning in `current/.runner/applied.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct AppliedPack {
+    pub pack_id: String,
+    pub version: String,
+}
+
+/// Caches what `ListServers` shows about the pack and port, so listing never
+/// touches the server files or RCON.
+pub(crate) async fn cache_listing_details(state: &SharedState, server_root: &Path) {
+    let current = server_root.join("current");
+    let pack = tokio::fs::read(current.join(".runner").join("applied.json"))
+        .await
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<AppliedPack>(&bytes).ok());
+    let port = tokio::fs::read_to_string(current.join("server.properties"))
+        .await
+        .ok()
+        .and_then(|contents| parse_properties(&contents).get("server-port")?.parse().ok());
+
+    let mut guard = state.lock().await;
+    guard.applied_pack = pack;
+    guard.server_port = port;
+}
+
+pub async fn list_servers(profiles: &Profiles) -> Response {
+    // Only the default profile has a deploy key, and with it a channel.
+    let default_channel = config::load_deploy_key()
+        .ok()
+        .flatten()
+        .map(|deploy| deploy.channel);
+
+    let mut servers = Vec::new();
+    for (profile, state) in profiles.all().await {
+        let mut guard = state.lock().await;
+        refresh_child_status(&mut guard).await;
+        let (state_name, pid, uptime_ms) = match &guard.status {
+            ServerStatus::Idle {} => ("idle", None, None),
+            ServerStatus::Starting { .. } => ("starting", None, None),
+            ServerStatus::Running {
+                pid, started_at_ms, ..
+            } => (
+                "running",
+                Some(*pid),
+                Some(now_millis().saturating_sub(*started_at_ms)),
+            ),
+            ServerStatus::Stopping { pid, .. } => ("stopping", Some(*pid), None),
+            ServerStatus::Exited { .. } => ("exited", None, None),
+            ServerStatus::Crashed { .. } => ("crashed", None, None),
+        };
+        let pack = guard.applied_pack.clone();
+        servers.push(ServerSummary {
+            state: state_name.to_string(),
+            pid,
+            uptime_ms,
+            pack_id: pack.as_ref().map(|pack| pack.pack_id.clone()),
+            channel: default_channel
+                .clone()
+                .filter(|_| profile == DEFAULT_PROFILE),
+            version: pack.map(|pack| pack.version),
+            port: guard.server_port,
+            players: guard.players.snapshot().count,
+            maintenance: guard.maintenance,
+            crash_looping: guard.crash_looping,
+            profile,
+        });
+    }
+    Response::ServerList { servers }
+}
@@ -1,75 +1,149 @@
-use runner_core_v2::proto::{LogLine, LogStream};
+use protocol::redact::redact_text;
+use regex::Regex;
+use runner_core_v2::proto::{LogFilter, LogLine, LogStream};
 use std::collections::VecDeque;
 use std::io::{self, Write};
 use std::sync::{Arc, Mutex};
 use tokio::sync::broadcast;
 use tracing_subscriber::fmt::MakeWriter;
 
+/// Server output and daemon logs. Profiles get their own server buffer via
+/// `for_profile` but share the daemon buffer.
 #[derive(Clone)]
 pub struct LogStore {
-    inner: Arc<Mutex<LogState>>,
+    server: Arc<Mutex<LogBuffer>>,
+    daemon: Arc<Mutex<LogBuffer>>,
 }
 
-struct LogState {
-    server: VecDeque<LogLine>,
-    daemon: VecDeque<LogLine>,
-    server_tx: broadcast::Sender<LogLine>,
-    daemon_tx: broadcast::Sender<LogLine>,
+const MIN_BUFFER_LINES: usize = 100;
+
+struct LogBuffer {
+    lines: VecDeque<LogLine>,
+    tx: broadcast::Sender<LogLine>,
     max_lines: usize,
+    // Set once the first line is dropped to make room.
+    evicted: bool,
+}
+
+impl LogBuffer {
+    fn new(max_lines: usize) -> Arc<Mutex<Self>> {
+        let (tx, _) = broadcast::channel(1024);
+        Arc::new(Mutex::new(Self {
+            lines: VecDeque::with_capacity(max_lines.min(1024)),
+            tx,
+            max_lines: max_lines.max(MIN_BUFFER_LINES),
+            evicted: false,
+        }))
+    }
+
+    fn push(buffer: &Mutex<Self>, entry: LogLine) {
+        let mut guard = buffer.lock().expect("log lock poisoned");
+        let max_lines = guard.max_lines;
+        if push_bounded(&mut guard.lines, max_lines, entry.clone()) {
+            guard.evicted = true;
+        }
+        let _ = guard.tx.send(entry);
+    }
+
+    /// Like [`window`], but when the window reaches back past lines that were
+    /// evicted, it starts with a marker line and reports truncation.
+    fn window(
+        &self,
+        lines: usize,
+        since_ms: Option<u64>,
+        until_ms: Option<u64>,
+        matcher: Option<&LineMatcher>,
+    ) -> (Vec<LogLine>, bool) {
+        let (mut tail, truncated) = window(&self.lines, lines, since_ms, until_ms, matcher);
+        let Some(oldest) = self.lines.front() else {
+            return (tail, truncated);
+        };
+        let reaches_start = since_ms.is_none_or(|since| since < oldest.at_ms)
+            && tail.first().is_none_or(|first| first.at_ms == oldest.at_ms);
+        if !self.evicted || truncated || !reaches_start {
+            return (tail, truncated);
+        }
+        tail.insert(
+            0,
+            LogLine {
+                at_ms: oldest.at_ms,
+                stream: LogStream::Stdout,
+                line: format!(
+                    "[runnerd] earlier lines evicted; the buffer keeps the last {} lines",
+                    self.max_lines
+                ),
+            },
+        );
+        (tail, true)
+    }
 }
 
 impl LogStore {
-    pub fn new(max_lines: usize) -> Self {
-        let (server_tx, _) = broadcast::channel(1024);
-        let (daemon_tx, _) = broadcast::channel(1024);
+    /// Separate capacities for server output (per profile) and daemon logs.
+    pub fn new(server_max_lines: usize, daemon_max_lines: usize) -> Self {
+        Self {
+            server: LogBuffer::new(server_max_lines),
+            daemon: LogBuffer::new(daemon_max_lines),
+        }
+    }
+
+    pub fn for_profile(&self) -> Self {
+        let max_lines = self.server.lock().expect("log lock poisoned").max_lines;
         Self {
-            inner: Arc::new(Mutex::new(LogState {
-                server: VecDeque::with_capacity(max_lines.min(1024)),
-                daemon: VecDeque::with_capacity(max_lines.min(1024)),
-                server_tx,
-                daemon_tx,
-                max_lines: max_lines.max(100),
-            })),
+            server: LogBuffer::new(max_lines),
+            daemon: self.daemon.clone(),
         }
     }
 
     pub fn push_server(&self, stream: LogStream, line: String) {
-        let mut guard = self.inner.lock().expect("log lock poisoned");
-        let max_lines = guard.max_lines;
         let entry = LogLine {
             at_ms: now_millis(),
             stream,
-            line,
+            line: redact_text(&line),
         };
-        push_bounded(&mut guard.server, max_lines, entry.clone());
-        let _ = guard.server_tx.send(entry);
+        LogBuffer::push(&self.server, entry);
     }
 
     pub fn push_daemon(&self, line: String) {
-        let mut guard = self.inner.lock().expect("log lock poisoned");
-        let max_lines = guard.max_lines;
         let entry = LogLine {
             at_ms: now_millis(),
             stream: LogStream::Stdout,
-            line,
+            line: redact_text(&line),
         };
-        push_bounded(&mut guard.daemon, max_lines, entry.clone());
-        let _ = guard.daemon_tx.send(entry);
+        LogBuffer::push(&self.daemon, entry);
     }
 
     pub fn tail_server(&self, lines: usize) -> Vec<LogLine> {
-        let guard = self.inner.lock().expect("log lock poisoned");
-        tail(&guard.server, lines)
+        self.window_server(lines, None, None, None).0
+    }
+
+    /// The last `lines` server lines stamped within `since_ms..=until_ms` that
+    /// `matcher` keeps, and whether more lines matched than were returned.
+    pub fn window_server(
+        &self,
+        lines: usize,
+        since_ms: Option<u64>,
+        until_ms: Option<u64>,
+        matcher: Option<&LineMatcher>,
+    ) -> (Vec<LogLine>, bool) {
+        let guard = self.server.lock().expect("log lock poisoned");
+        guard.window(lines, since_ms, until_ms, matcher)
     }
 
-    pub fn tail_daemon(&self, lines: usize) -> Vec<LogLine> {
-        let guard = self.inner.lock().expect("log lock poisoned");
-        tail(&guard.daemon, lines)
+    pub fn window_daemon(
+        &self,
+        lines: usize,
+        since_ms: Option<u64>,
+        until_ms: Option<u64>,
+        matcher: Option<&LineMatcher>,
+    ) -> (Vec<LogLine>, bool) {
+        let guard = self.daemon.lock().expect("log lock poisoned");
+        guard.window(lines, since_ms, until_ms, matcher)
     }
 
     pub fn server_subscribe(&self) -> broadcast::Receiver<LogLine> {
-        let guard = self.inner.lock().expect("log lock poisoned");
-        guard.server_tx.subscribe()
+        let guard = self.server.lock().expect("log lock poisoned");
+        guard.tx.subscribe()
     }
 
     pub fn daemon_writer(&self) -> LogWriterFactory {
@@ -79,19 +153,81 @@ impl LogStore {
     }
 }
 
-fn push_bounded(buf: &mut VecDeque<LogLine>, max_lines: usize, entry: LogLine) {
+/// Returns whether older lines were dropped to make room.
+fn push_bounded(buf: &mut VecDeque<LogLine>, max_lines: usize, entry: LogLine) -> bool {
+    let mut evicted = false;
     while buf.len() >= max_lines {
         buf.pop_front();
+        evicted = true;
     }
     buf.push_back(entry);
+    evicted
 }
 
-fn tail(buf: &VecDeque<LogLine>, lines: usize) -> Vec<LogLine> {
-    let count = lines.min(buf.len());
-    buf.iter()
-        .skip(buf.len().saturating_sub(count))
-        .cloned()
-        .collect()
+/// A compiled [`LogFilter`].
+pub struct LineMatcher {
+    contains: Option<String>,
+    regex: Option<Regex>,
+    context: usize,
+}
+
+impl LineMatcher {
+    pub fn new(filter: &LogFilter) -> Result<Self, regex::Error> {
+        Ok(Self {
+            contains: filter.contains.clone().filter(|text| !text.is_empty()),
+            regex: filter.regex.as_deref().map(Regex::new).transpose()?,
+            context: filter.context,
+        })
+    }
+
+    fn is_match(&self, line: &str) -> bool {
+        self.contains
+            .as_deref()
+            .is_none_or(|text| line.contains(text))
+            && self.regex.as_ref().is_none_or(|regex| regex.is_match(line))
+    }
+
+    /// Matching lines plus up to `context` neighbours on each side.
+    fn select<'a>(&self, lines: Vec<&'a LogLine>) -> Vec<&'a LogLine> {
+        let mut keep = vec![false; lines.len()];
+        for (index, line) in lines.iter().enumerate() {
+            if self.is_match(&line.line) {
+                let end = index
+                    .saturating_add(self.context)
+                    .saturating_add(1)
+                    .min(lines.len());
+                keep[index.saturating_sub(self.context)..end].fill(true);
+            }
+        }
+        lines
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(line, keep)| keep.then_some(line))
+            .collect()
+    }
+}
+
+fn window(
+    buf: &VecDeque<LogLine>,
+    lines: usize,
+    since_ms: Option<u64>,
+    until_ms: Option<u64>,
+    matcher: Option<&LineMatcher>,
+) -> (Vec<LogLine>, bool) {
+    let mut matching: Vec<&LogLine> = buf
+        .iter()
+        .filter(|line| since_ms.is_none_or(|since| line.at_ms >= since))
+        .filter(|line| until_ms.is_none_or(|until| line.at_ms <= until))
+        .collect();
+    if let Some(matcher) = matcher {
+        matching = matcher.select(matching);
+    }
+    let skip = matching.len().saturating_sub(lines);
+    let tail = matching[skip..]
+        .iter()
+        .map(|line| (*line).clone())
+        .collect();
+    (tail, skip > 0)
 }
 
 pub struct LogWriterFactory {
@@ -117,16 +253,18 @@ pub struct LogWriter {
 impl Write for LogWriter {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         self.buffer.extend_from_slice(buf);
+        let mut stdout = io::stdout();
         while let Some(pos) = self.buffer.iter().position(|b| *b == b'\n') {
             let line_bytes: Vec<u8> = self.buffer.drain(..=pos).collect();
             let line = String::from_utf8_lossy(&line_bytes);
-            let line = line.trim_end_matches('\n').to_string();
+            let line = redact_text(line.trim_end_matches('\n'));
+            stdout.write_all(line.as_bytes())?;
+            stdout.write_all(b"\n")?;
             if !line.trim().is_empty() {
                 self.store.push_daemon(line);
             }
         }
 
-        io::stdout().write_all(buf)?;
         Ok(buf.len())
     }
 
@@ -142,3 +280,113 @@ fn now_millis() -> u64 {
         .unwrap_or_default()
         .as_millis() as u64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_filters_by_time_and_caps_to_the_newest_lines() {
+        let buf: VecDeque<LogLine> = (1..=5)
+            .map(|at_ms| LogLine {
+                at_ms,
+                stream: LogStream::Stdout,
+                line: format!("line {at_ms}"),
+            })
+            .collect();
+
+        let (lines, truncated) = window(&buf, 10, Some(2), Some(4), None);
+        assert_eq!(lines.iter().map(|l| l.at_ms).collect::<Vec<_>>(), [2, 3, 4]);
+        assert!(!truncated);
+
+        let (lines, truncated) = window(&buf, 2, Some(2), None, None);
+        assert_eq!(lines.iter().map(|l| l.at_ms).collect::<Vec<_>>(), [4, 5]);
+        assert!(truncated);
+    }
+
+    #[test]
+    fn tails_reaching_evicted_lines_start_with_a_marker() {
+        let buffer = LogBuffer::new(MIN_BUFFER_LINES);
+        for at_ms in 1..=MIN_BUFFER_LINES as u64 {
+            LogBuffer::push(
+                &buffer,
+                LogLine {
+                    at_ms,
+                    stream: LogStream::Stdout,
+                    line: format!("line {at_ms}"),
+                },
+            );
+        }
+        let guard = buffer.lock().unwrap();
+        let (lines, truncated) = guard.window(1000, None, None, None);
+        assert_eq!(lines.len(), MIN_BUFFER_LINES);
+        assert!(!truncated);
+        drop(guard);
+
+        LogBuffer::push(
+            &buffer,
+            LogLine {
+                at_ms: 1000,
+                stream: LogStream::Stdout,
+                line: "newest".into(),
+            },
+        );
+        let guard = buffer.lock().unwrap();
+        let (lines, truncated) = guard.window(1000, None, None, None);
+        assert!(truncated);
+        assert!(lines[0].line.contains("earlier lines evicted"));
+        assert_eq!(lines[1].line, "line 2");
+        assert_eq!(lines.len(), MIN_BUFFER_LINES + 1);
+
+        // Windows that start after the oldest kept line are complete.
+        let (lines, truncated) = guard.window(1000, Some(50), None, None);
+        assert!(!truncated);
+        assert_eq!(lines[0].line, "line 50");
+    }
+
+    #[test]
+    fn filters_lines_with_context_before_the_cap() {
+        let buf: VecDeque<LogLine> = [
+            "starting",
+            "loading mods",
+            "ERROR: bad mod",
+            "retrying",
+            "ok",
+            "tick",
+            "Error 2",
+        ]
+        .iter()
+        .zip(1..)
+        .map(|(line, at_ms)| LogLine {
+            at_ms,
+            stream: LogStream::Stdout,
+            line: line.to_string(),
+        })
+        .collect();
+        let matcher = |contains: Option<&str>, regex: Option<&str>, context| {
+            LineMatcher::new(&LogFilter {
+                contains: contains.map(str::to_string),
+                regex: regex.map(str::to_string),
+                context,
+            })
+        };
+
+        let grep = matcher(Some("ERROR"), None, 1).unwrap();
+        let (lines, truncated) = window(&buf, 10, None, None, Some(&grep));
+        let text: Vec<&str> = lines.iter().map(|l| l.line.as_str()).collect();
+        assert_eq!(text, ["loading mods", "ERROR: bad mod", "retrying"]);
+        assert!(!truncated);
+
+        let regex = matcher(None, Some("(?i)^error"), 0).unwrap();
+        let (lines, truncated) = window(&buf, 1, None, None, Some(&regex));
+        assert_eq!(lines[0].line, "Error 2");
+        assert!(truncated);
+
+        assert!(matcher(None, Some("(unclosed"), 0).is_err());
+
+        let everything = matcher(Some("tick"), None, usize::MAX).unwrap();
+        let (lines, truncated) = window(&buf, 10, None, None, Some(&everything));
+        assert_eq!(lines.len(), buf.len());
+        assert!(!truncated);
+    }
+}
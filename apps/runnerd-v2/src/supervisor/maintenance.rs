@@ -0,0 +1,54 @@
+use runner_core_v2::proto::{ErrorCode, Response, RpcError};
+use tracing::info;
+
+use crate::config;
+
+use super::server::start_server_from_deploy;
+use super::state::SharedState;
+
+pub async fn set_maintenance(state: &SharedState, on: bool) -> Result<Response, RpcError> {
+    let mut deploy = config::load_deploy_key()
+        .map_err(internal)?
+        .ok_or_else(|| RpcError {
+            code: ErrorCode::InvalidConfig,
+            message: "deploy key not configured".into(),
+            details: Default::default(),
+        })?;
+    deploy.maintenance = Some(on);
+    config::save_deploy_key(&deploy).map_err(internal)?;
+
+    let start = {
+        let mut guard = state.lock().await;
+        guard.maintenance = on;
+        guard.logs.push_daemon(format!(
+            "maintenance mode {}",
+            if on { "on" } else { "off" }
+        ));
+        !on && deploy.should_autostart.unwrap_or(false) && !guard.is_running()
+    };
+
+    if start {
+        info!("maintenance mode off; starting server");
+        let state = state.clone();
+        tokio::spawn(async move {
+            start_server_from_deploy(state).await;
+        });
+    }
+
+    Ok(Response::MaintenanceSet {
+        on,
+        starting: start,
+    })
+}
+
+pub fn maintenance_configured() -> bool {
+    matches!(config::load_deploy_key(), Ok(Some(deploy)) if deploy.maintenance == Some(true))
+}
+
+fn internal(message: String) -> RpcError {
+    RpcError {
+        code: ErrorCode::Internal,
+        message,
+        details: Default::default(),
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::VecDeque;
+use std::path::Path;
+
+use protocol::Loader;
+use runner_core_v2::proto::{PerformanceSample, PerformanceSummary};
+use runner_v2_rcon::{RconClient, load_rcon_settings};
+use serde::Deserialize;
+use tokio::time::{Duration, sleep};
+use tracing::info;
+
+use super::state::SharedState;
+use super::util::now_millis;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+const HISTORY_LEN: usize = 60;
+
+// Paper-style `tps` is the last resort for every loader since plugin/hybrid servers
+// replace the loader's own command.
+const VANILLA_COMMANDS: &[&str] = &["tick query", "tps"];
+const FORGE_COMMANDS: &[&str] = &["forge tps", "tps"];
+const NEOFORGE_COMMANDS: &[&str] = &["neoforge tps", "forge tps", "tps"];
+
+#[derive(Default)]
+pub struct PerformanceHistory {
+    samples: VecDeque<PerformanceSample>,
+    command: Option<&'static str>,
+    unsupported: bool,
+}
+
+impl PerformanceHistory {
+    pub fn summary(&self) -> PerformanceSummary {
+        let count = self.samples.len() as f64;
+        let mspt = self
+            .samples
+            .iter()
+            .filter_map(|sample| sample.mspt)
+            .collect::<Vec<_>>();
+        PerformanceSummary {
+            latest: self.samples.back().cloned(),
+            avg_tps: (count > 0.0)
+                .then(|| self.samples.iter().map(|sample| sample.tps).sum::<f64>() / count),
+            avg_mspt: (!mspt.is_empty()).then(|| mspt.iter().sum::<f64>() / mspt.len() as f64),
+            command: self.command.map(str::to_string),
+            unsupported: self.unsupported,
+        }
+    }
+
+    pub fn history(&self) -> Vec<PerformanceSample> {
+        self.samples.iter().cloned().collect()
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    fn push(&mut self, sample: PerformanceSample) {
+        while self.samples.len() >= HISTORY_LEN {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+}
+
+#[derive(Deserialize)]
+struct AppliedLoader {
+    loader: Loader,
+}
+
+pub async fn ensure_metrics_poller(state: SharedState) {
+    {
+        let mut guard = state.lock().await;
+        if guard.metrics_started {
+            return;
+        }
+        guard.metrics_started = true;
+    }
+
+    tokio::spawn(async move {
+        loop {
+            sleep(POLL_INTERVAL).await;
+            poll_once(&state).await;
+        }
+    });
+}
+
+async fn poll_once(state: &SharedState) {
+    let (server_root, known_command) = {
+        let guard = state.lock().await;
+        if !guard.is_running() || guard.performance.unsupported {
+            return;
+        }
+        let Some(root) = guard.server_root.clone() else {
+            return;
+        };
+        (root, guard.performance.command)
+    };
+
+    let current = server_root.join("current");
+    let Ok(Some(settings)) = load_rcon_settings(&current).await else {
+        return;
+    };
+    let rcon = RconClient::new(settings.address, settings.password);
+
+    let candidates = match known_command {
+        Some(command) => vec![command],
+        None => candidate_commands(&current).await.to_vec(),
+    };
+    for command in &candidates {
+        let Ok(reply) = rcon.execute(command).await else {
+            // The server may still be booting; try again next round.
+            return;
+        };
+        if let Some((tps, mspt)) = parse_performance(&reply) {
+            let mut guard = state.lock().await;
+            guard.performance.command = Some(command);
+            guard.performance.push(PerformanceSample {
+                at_ms: now_millis(),
+                tps,
+                mspt,
+            });
+            return;
+        }
+    }
+
+    if known_command.is_none() {
+        info!(
+            "no supported performance command found (tried: {}); TPS metrics disabled until restart",
+            candidates.join(", ")
+        );
+        state.lock().await.performance.unsupported = true;
+    }
+}
+
+async fn candidate_commands(current: &Path) -> &'static [&'static str] {
+    let loader = tokio::fs::read(current.join(".runner").join("applied.json"))
+        .await
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<AppliedLoader>(&bytes).ok())
+        .map(|applied| applied.loader);
+    match loader {
+        Some(Loader::Forge) => FORGE_COMMANDS,
+        Some(Loader::Neo) => NEOFORGE_COMMANDS,
+        Some(Loader::Fabric) | None => VANILLA_COMMANDS,
+    }
+}
+
+// Returns (tps, mspt). Understands vanilla `tick query`, Forge/NeoForge `tps`
+// ("Overall" line) and Paper `tps` (1m average, no MSPT).
+pub(crate) fn parse_performance(reply: &str) -> Option<(f64, Option<f64>)> {
+    let reply = strip_formatting(reply);
+
+    if let Some(mspt) = number_after(&reply, "Average time per tick:") {
+        let target = number_after(&reply, "Target tick rate:").unwrap_or(20.0);
+        let tps = if mspt > 0.0 {
+            (1000.0 / mspt).min(target)
+        } else {
+            target
+        };
+        return Some((tps, Some(mspt)));
+    }
+
+    if let Some(overall) = reply.find("Overall").map(|idx| &reply[idx..]) {
+        if let Some(tps) = number_after(overall, "Mean TPS:") {
+            return Some((tps, number_after(overall, "Mean tick time:")));
+        }
+        // NeoForge: "Overall: 20.000 TPS (1.234 ms/tick)"
+        if let Some(tps) = number_after(overall, "Overall:") {
+            return Some((tps, number_after(overall, "TPS (")));
+        }
+    }
+
+    number_after(&reply, "TPS from last 1m, 5m, 15m:").map(|tps| (tps, None))
+}
+
+fn number_after(text: &str, label: &str) -> Option<f64> {
+    let start = text.find(label)? + label.len();
+    let value = text[start..]
+        .trim_start()
+        .trim_start_matches('*')
+        .chars()
+        .take_while(|ch| ch.is_ascii_digit() || *ch == '.')
+        .collect::<String>();
+    value.parse().ok()
+}
+
+fn strip_formatting(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch == '§' {
+            chars.next();
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_performance;
+
+    #[test]
+    fn parses_loader_specific_outputs() {
+        let vanilla = "The game is running normally\nTarget tick rate: 20.0 per second.\nAverage time per tick: 4.0ms (Target: 50.0ms)";
+        assert_eq!(parse_performance(vanilla), Some((20.0, Some(4.0))));
+
+        let lagging = "Target tick rate: 20.0 per second.\nAverage time per tick: 100.0ms";
+        assert_eq!(parse_performance(lagging), Some((10.0, Some(100.0))));
+
+        let forge = "Dim minecraft:overworld: Mean tick time: 2.500 ms. Mean TPS: 20.000\nOverall: Mean tick time: 3.000 ms. Mean TPS: 19.500";
+        assert_eq!(parse_performance(forge), Some((19.5, Some(3.0))));
+
+        let neoforge = "Overall: 18.000 TPS (55.500 ms/tick)";
+        assert_eq!(parse_performance(neoforge), Some((18.0, Some(55.5))));
+
+        let paper = "§6TPS from last 1m, 5m, 15m: §a*20.0, §a20.0, §a20.0";
+        assert_eq!(parse_performance(paper), Some((20.0, None)));
+
+        assert_eq!(parse_performance("Unknown or incomplete command"), None);
+    }
+}
@@ -1,14 +1,40 @@
+mod channel;
+mod crash;
+mod eula;
+mod exit;
+mod list;
 mod logs;
+mod maintenance;
+mod metrics;
 mod monitor;
+mod pin;
+mod presence;
+mod profiles;
+mod properties;
 mod rcon;
+mod schedule;
 mod server;
 mod state;
 mod updates;
 mod util;
+mod whitelist;
 
-pub use logs::LogStore;
+pub use channel::set_channel;
+pub use crash::ensure_crash_recovery;
+pub use eula::accept_eula;
+pub use list::list_servers;
+pub use logs::{LineMatcher, LogStore};
+pub use maintenance::{maintenance_configured, set_maintenance};
+pub use pin::{configured_pinned_version, set_pinned_version};
+pub use profiles::{Profiles, find_unmanaged_minecraft_process};
+pub use properties::{get_server_properties, set_server_properties};
 pub use rcon::{ensure_rcon_available, execute_rcon_command};
-pub use server::{build_status, start_server, start_server_from_deploy, stop_server};
+pub use schedule::ensure_restart_scheduler;
+pub use server::{
+    build_metrics, build_status, clear_crash_loop_state, read_pack_blob, start_server,
+    start_server_from_deploy, stop_server,
+};
 pub use state::{ServerState, SharedState};
 pub use updates::ensure_watchers;
 pub use util::{current_server_root, default_server_root, now_millis};
+pub use whitelist::sync_whitelist_now;
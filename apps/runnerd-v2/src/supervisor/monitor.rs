@@ -1,8 +1,10 @@
 use tokio::time::{Duration, sleep};
 
+use super::crash::record_crash;
+use super::exit::{exit_info_from_status, record_exit};
 use super::state::SharedState;
 use super::util::now_millis;
-use runner_core_v2::proto::{ExitInfo, ServerStatus};
+use runner_core_v2::proto::{DEFAULT_PROFILE, ServerStatus};
 
 pub async fn ensure_monitor(state: SharedState) {
     let start_monitor = {
@@ -31,13 +33,15 @@ pub async fn ensure_monitor(state: SharedState) {
                             .last_start_ms
                             .map(|start| now_millis().saturating_sub(start))
                             .unwrap_or(0);
-                        let exit_code = status.code();
-                        let exit = ExitInfo {
-                            code: exit_code,
-                            signal: None,
-                        };
-                        let profile = guard.profile.clone().unwrap_or_else(|| "default".into());
+                        let exit = exit_info_from_status(status);
+                        let exit_code = exit.code;
+                        record_exit(&mut guard, &exit, None);
+                        let profile = guard
+                            .profile
+                            .clone()
+                            .unwrap_or_else(|| DEFAULT_PROFILE.into());
                         guard.child = None;
+                        guard.players.reset();
                         guard.status = ServerStatus::Exited {
                             profile: profile.clone(),
                             exit,
@@ -48,7 +52,7 @@ pub async fn ensure_monitor(state: SharedState) {
                             "server crashed: profile={} exit_code={:?} uptime_ms={}",
                             profile, exit_code, uptime_ms
                         ));
-                        guard.restart_disabled = true;
+                        record_crash(&mut guard, exit_code, uptime_ms);
                         continue;
                     } else {
                         continue;
@@ -0,0 +1,62 @@
+use runner_core_v2::proto::{ErrorCode, Response, RpcError};
+use tracing::info;
+
+use crate::config;
+
+use super::state::SharedState;
+
+// Pinning only changes what the next deploy fetches; the running server keeps its
+// build until it is restarted so operators choose when the switch happens.
+pub async fn set_pinned_version(
+    state: &SharedState,
+    version: Option<String>,
+) -> Result<Response, RpcError> {
+    let version = version
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    let mut deploy = config::load_deploy_key()
+        .map_err(internal)?
+        .ok_or_else(|| RpcError {
+            code: ErrorCode::InvalidConfig,
+            message: "deploy key not configured".into(),
+            details: Default::default(),
+        })?;
+    deploy.pinned_version = version.clone();
+    config::save_deploy_key(&deploy).map_err(internal)?;
+
+    let mut guard = state.lock().await;
+    guard.pinned_version = version.clone();
+    match &version {
+        Some(version) => {
+            info!("pinned pack version to {version}");
+            guard
+                .logs
+                .push_daemon(format!("pinned pack version to {version}"));
+        }
+        None => {
+            info!("pack version unpinned");
+            guard.logs.push_daemon("pack version unpinned".to_string());
+            // Forget the cached metadata ETag so the next poll compares the channel's
+            // build against the pinned one instead of getting a 304.
+            guard.pack_etag = None;
+        }
+    }
+
+    Ok(Response::PinnedVersionSet { version })
+}
+
+pub fn configured_pinned_version() -> Option<String> {
+    config::load_deploy_key()
+        .ok()
+        .flatten()
+        .and_then(|deploy| deploy.pinned_version)
+}
+
+fn internal(message: String) -> RpcError {
+    RpcError {
+        code: ErrorCode::Internal,
+        message,
+        details: Default::default(),
+    }
+}
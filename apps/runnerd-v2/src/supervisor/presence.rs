@@ -0,0 +1,222 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use runner_core_v2::proto::{Event, PlayersOnline};
+use runner_v2_rcon::{RconClient, load_rcon_settings};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::time::{Duration, MissedTickBehavior, interval};
+
+use super::logs::LogStore;
+
+// Join/leave lines can be missed (log lag, mods changing messages), so the parsed
+// set is periodically replaced with the server's own `list` output.
+const RECONCILE_INTERVAL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum PresenceChange {
+    Joined(String),
+    Left(String),
+}
+
+#[derive(Clone)]
+pub struct PlayerTracker {
+    players: Arc<Mutex<BTreeSet<String>>>,
+    generation: Arc<AtomicU64>,
+    events: broadcast::Sender<Event>,
+}
+
+impl PlayerTracker {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(256);
+        Self {
+            players: Arc::new(Mutex::new(BTreeSet::new())),
+            generation: Arc::new(AtomicU64::new(0)),
+            events,
+        }
+    }
+
+    pub fn snapshot(&self) -> PlayersOnline {
+        let players = self.players.lock().expect("player lock poisoned");
+        PlayersOnline {
+            count: players.len() as u32,
+            names: players.iter().cloned().collect(),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Event> {
+        self.events.subscribe()
+    }
+
+    /// Empties the set (emitting `PlayerLeft` for anyone still listed) and stops the
+    /// watcher of the previous server run.
+    pub fn reset(&self) -> u64 {
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.replace(BTreeSet::new());
+        generation
+    }
+
+    fn is_current(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::SeqCst) == generation
+    }
+
+    fn apply(&self, change: PresenceChange) {
+        let mut players = self.players.lock().expect("player lock poisoned");
+        let event = match change {
+            PresenceChange::Joined(name) => {
+                players.insert(name.clone()).then(|| Event::PlayerJoined {
+                    name,
+                    online: players.len() as u32,
+                })
+            }
+            PresenceChange::Left(name) => players.remove(&name).then(|| Event::PlayerLeft {
+                name,
+                online: players.len() as u32,
+            }),
+        };
+        if let Some(event) = event {
+            let _ = self.events.send(event);
+        }
+    }
+
+    fn replace(&self, names: BTreeSet<String>) {
+        let current = self.players.lock().expect("player lock poisoned").clone();
+        for name in current.difference(&names) {
+            self.apply(PresenceChange::Left(name.clone()));
+        }
+        for name in names.difference(&current) {
+            self.apply(PresenceChange::Joined(name.clone()));
+        }
+    }
+}
+
+pub(crate) fn spawn_presence_watcher(
+    tracker: PlayerTracker,
+    logs: &LogStore,
+    server_root: PathBuf,
+) {
+    let generation = tracker.reset();
+    let mut lines = logs.server_subscribe();
+    tokio::spawn(async move {
+        let mut reconcile = interval(RECONCILE_INTERVAL);
+        reconcile.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        reconcile.tick().await;
+
+        while tracker.is_current(generation) {
+            tokio::select! {
+                line = lines.recv() => match line {
+                    Ok(line) => {
+                        if let Some(change) = parse_presence_line(&line.line) {
+                            tracker.apply(change);
+                        }
+                    }
+                    Err(RecvError::Lagged(_)) => reconcile_with_rcon(&tracker, &server_root).await,
+                    Err(RecvError::Closed) => break,
+                },
+                _ = reconcile.tick() => reconcile_with_rcon(&tracker, &server_root).await,
+            }
+        }
+    });
+}
+
+async fn reconcile_with_rcon(tracker: &PlayerTracker, server_root: &Path) {
+    let Ok(Some(settings)) = load_rcon_settings(&server_root.join("current")).await else {
+        return;
+    };
+    let rcon = RconClient::new(settings.address, settings.password);
+    if let Some(names) = rcon
+        .execute("list")
+        .await
+        .ok()
+        .and_then(|reply| parse_list_reply(&reply))
+    {
+        tracker.replace(names);
+    }
+}
+
+// Matches vanilla/Forge/Fabric console lines such as
+// `[12:00:00] [Server thread/INFO]: Steve joined the game`; chat lines start with
+// `<name>` and are ignored so players cannot spoof presence.
+pub(crate) fn parse_presence_line(line: &str) -> Option<PresenceChange> {
+    let message = line.split_once("]: ").map_or(line, |(_, message)| message);
+    let message = message.trim();
+    if message.starts_with('<') {
+        return None;
+    }
+    if let Some(name) = message.strip_suffix(" joined the game") {
+        return valid_player_name(name).then(|| PresenceChange::Joined(name.to_string()));
+    }
+    if let Some(name) = message.strip_suffix(" left the game") {
+        return valid_player_name(name).then(|| PresenceChange::Left(name.to_string()));
+    }
+    None
+}
+
+// `There are 2 of a max of 20 players online: Steve, Alex`
+pub(crate) fn parse_list_reply(reply: &str) -> Option<BTreeSet<String>> {
+    let (_, names) = reply.split_once(':')?;
+    Some(
+        names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| valid_player_name(name))
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+fn valid_player_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 16
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PresenceChange, parse_list_reply, parse_presence_line};
+
+    #[test]
+    fn parses_join_and_leave_lines() {
+        assert_eq!(
+            parse_presence_line("[12:00:00] [Server thread/INFO]: Steve joined the game"),
+            Some(PresenceChange::Joined("Steve".into()))
+        );
+        assert_eq!(
+            parse_presence_line(
+                "[12:00:01] [Server thread/INFO] [minecraft/MinecraftServer]: Alex_2 left the game"
+            ),
+            Some(PresenceChange::Left("Alex_2".into()))
+        );
+        assert_eq!(
+            parse_presence_line("[12:00:02] [Server thread/INFO]: <Steve> Bob joined the game"),
+            None
+        );
+    }
+
+    #[test]
+    fn ignores_chat_that_embeds_a_log_prefix() {
+        assert_eq!(
+            parse_presence_line("[12:00:00 INFO]: <bob> x]: Steve joined the game"),
+            None
+        );
+        assert_eq!(
+            parse_presence_line("[12:00:00] [Server thread/INFO]: <bob> ]: Steve left the game"),
+            None
+        );
+    }
+
+    #[test]
+    fn parses_list_reply() {
+        let names =
+            parse_list_reply("There are 2 of a max of 20 players online: Steve, Alex").unwrap();
+        assert_eq!(names.into_iter().collect::<Vec<_>>(), vec!["Alex", "Steve"]);
+        assert!(
+            parse_list_reply("There are 0 of a max of 20 players online: ")
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
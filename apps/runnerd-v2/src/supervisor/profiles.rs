@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::process::Command;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use runner_core_v2::proto::{DEFAULT_PROFILE, ErrorCode, ProfileId, RpcError};
+
+use super::crash::ensure_crash_recovery;
+use super::state::{ServerState, SharedState};
+
+const MAX_PROFILE_LEN: usize = 32;
+
+/// Named servers managed by this daemon. `DEFAULT_PROFILE` always exists and
+/// owns the deploy key; other profiles are created by their first `Start` and
+/// live until the daemon exits.
+#[derive(Clone)]
+pub struct Profiles {
+    states: Arc<Mutex<BTreeMap<ProfileId, SharedState>>>,
+}
+
+impl Profiles {
+    pub fn new(default: SharedState) -> Self {
+        Self {
+            states: Arc::new(Mutex::new(BTreeMap::from([(
+                DEFAULT_PROFILE.to_string(),
+                default,
+            )]))),
+        }
+    }
+
+    pub async fn default_state(&self) -> SharedState {
+        self.states.lock().await[DEFAULT_PROFILE].clone()
+    }
+
+    pub async fn get(&self, profile: &str) -> Result<SharedState, RpcError> {
+        self.states
+            .lock()
+            .await
+            .get(profile)
+            .cloned()
+            .ok_or_else(|| RpcError {
+                code: ErrorCode::UnknownProfile,
+                message: format!("unknown profile: {profile}"),
+                details: Default::default(),
+            })
+    }
+
+    pub async fn get_or_create(&self, profile: &str) -> Result<SharedState, RpcError> {
+        validate_profile_name(profile)?;
+        let mut states = self.states.lock().await;
+        if let Some(state) = states.get(profile) {
+            return Ok(state.clone());
+        }
+
+        let logs = states[DEFAULT_PROFILE].lock().await.logs.for_profile();
+        let mut server = ServerState::new(logs);
+        server.profile = Some(profile.to_string());
+        let state: SharedState = Arc::new(Mutex::new(server));
+        ensure_crash_recovery(state.clone());
+        states.insert(profile.to_string(), state.clone());
+        Ok(state)
+    }
+
+    pub async fn all(&self) -> Vec<(ProfileId, SharedState)> {
+        self.states
+            .lock()
+            .await
+            .iter()
+            .map(|(profile, state)| (profile.clone(), state.clone()))
+            .collect()
+    }
+
+    pub async fn managed_pids(&self) -> Vec<i32> {
+        let mut pids = Vec::new();
+        for (_, state) in self.all().await {
+            if let Some(pid) = state.lock().await.child.as_ref().and_then(|c| c.id()) {
+                pids.push(pid as i32);
+            }
+        }
+        pids
+    }
+}
+
+/// Profile names become directory names under `servers/`.
+pub fn validate_profile_name(profile: &str) -> Result<(), RpcError> {
+    let valid = !profile.is_empty()
+        && profile.len() <= MAX_PROFILE_LEN
+        && profile
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-' || ch == '_');
+    if valid {
+        return Ok(());
+    }
+    Err(RpcError {
+        code: ErrorCode::BadRequest,
+        message: format!(
+            "invalid profile '{profile}': use up to {MAX_PROFILE_LEN} lowercase letters, digits, '-' or '_'"
+        ),
+        details: Default::default(),
+    })
+}
+
+/// A Minecraft server on this host that no profile of this daemon started.
+pub fn find_unmanaged_minecraft_process(managed: &[i32]) -> Option<(i32, String)> {
+    // Use `ps` to list processes and look for common MC server markers in the command line.
+    // This avoids depending on sysinfo API version differences and keeps the check simple.
+    let output = Command::new("ps")
+        .args(["-axo", "pid,comm,args"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let own_pid = std::process::id() as i32;
+    find_minecraft_process(&String::from_utf8_lossy(&output.stdout), |pid| {
+        pid == own_pid || managed.contains(&pid)
+    })
+}
+
+fn find_minecraft_process(
+    ps_output: &str,
+    is_managed: impl Fn(i32) -> bool,
+) -> Option<(i32, String)> {
+    let candidate_substrs = [
+        "minecraft",
+        "minecraft_server",
+        "server.jar",
+        "paper.jar",
+        "spigot.jar",
+        "fabric-server-launch.jar",
+        "vanilla.jar",
+    ];
+
+    for line in ps_output.lines().skip(1) {
+        let mut parts = line.split_whitespace();
+        let Some(pid) = parts.next().and_then(|pid| pid.parse::<i32>().ok()) else {
+            continue;
+        };
+        if is_managed(pid) {
+            continue;
+        }
+        let rest = parts.collect::<Vec<_>>().join(" ");
+        let lc = rest.to_ascii_lowercase();
+        if candidate_substrs.iter().any(|sub| lc.contains(sub)) {
+            return Some((pid, rest));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_managed_minecraft_processes() {
+        let ps = "  PID COMM ARGS\n  10 java java -jar server.jar nogui\n  11 java java -jar /srv/paper.jar\n  12 bash bash\n";
+        assert_eq!(
+            find_minecraft_process(ps, |pid| pid == 10).map(|(pid, _)| pid),
+            Some(11)
+        );
+        assert_eq!(find_minecraft_process(ps, |pid| pid != 12), None);
+    }
+
+    #[test]
+    fn profile_names_must_be_safe_directory_names() {
+        assert!(validate_profile_name("default").is_ok());
+        assert!(validate_profile_name("creative-2").is_ok());
+        assert!(validate_profile_name("").is_err());
+        assert!(validate_profile_name("../etc").is_err());
+        assert!(validate_profile_name("Survival").is_err());
+    }
+}
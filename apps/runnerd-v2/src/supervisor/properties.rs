@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use runner_core_v2::proto::{DEFAULT_PROFILE, ErrorCode, Response, RpcError};
+
+use super::state::SharedState;
+use super::util::{current_server_root, default_server_root};
+
+// Keys the daemon rewrites on every start; editing them remotely would either be
+// undone or break RCON/whitelist management.
+const MANAGED_KEYS: &[&str] = &[
+    "enable-rcon",
+    "rcon.password",
+    "white-list",
+    "enforce-whitelist",
+];
+
+const PORT_KEYS: &[&str] = &["server-port", "rcon.port", "query.port"];
+
+const INTEGER_KEYS: &[&str] = &[
+    "max-players",
+    "view-distance",
+    "simulation-distance",
+    "spawn-protection",
+    "max-world-size",
+    "max-tick-time",
+    "network-compression-threshold",
+    "op-permission-level",
+    "function-permission-level",
+    "entity-broadcast-range-percentage",
+    "rate-limit",
+    "player-idle-timeout",
+    "max-chained-neighbor-updates",
+];
+
+const BOOLEAN_KEYS: &[&str] = &[
+    "online-mode",
+    "pvp",
+    "hardcore",
+    "allow-flight",
+    "allow-nether",
+    "enable-command-block",
+    "spawn-monsters",
+    "spawn-npcs",
+    "spawn-animals",
+    "generate-structures",
+    "force-gamemode",
+    "enable-query",
+    "enable-status",
+    "sync-chunk-writes",
+    "use-native-transport",
+    "prevent-proxy-connections",
+    "hide-online-players",
+    "enforce-secure-profile",
+    "require-resource-pack",
+    "broadcast-console-to-ops",
+    "broadcast-rcon-to-ops",
+    "enable-jmx-monitoring",
+];
+
+pub async fn get_server_properties(state: &SharedState) -> Result<Response, RpcError> {
+    let path = properties_path(state).await;
+    let contents = read_properties(&path).await?;
+    Ok(Response::ServerProperties {
+        properties: parse_properties(&contents),
+    })
+}
+
+pub async fn set_server_properties(
+    state: &SharedState,
+    changes: BTreeMap<String, String>,
+) -> Result<Response, RpcError> {
+    for (key, value) in &changes {
+        validate_property(key, value).map_err(bad_request)?;
+    }
+
+    let path = properties_path(state).await;
+    let contents = read_properties(&path).await?;
+    let current = parse_properties(&contents);
+    let changed = changes
+        .iter()
+        .filter(|(key, value)| current.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .collect::<Vec<_>>();
+
+    if !changed.is_empty() {
+        let merged = merge_properties(&contents, &changes);
+        write_atomically(&path, &merged).await?;
+    }
+
+    // server.properties is only read at startup, so any change to a running
+    // server takes effect after a restart.
+    let running = state.lock().await.is_running();
+    Ok(Response::ServerPropertiesUpdated {
+        restart_required: running && !changed.is_empty(),
+        changed,
+    })
+}
+
+pub(crate) fn parse_properties(contents: &str) -> BTreeMap<String, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with('!'))
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+// Replaces values in place so comments, ordering and unknown keys survive;
+// keys that were not present are appended.
+pub(crate) fn merge_properties(contents: &str, changes: &BTreeMap<String, String>) -> String {
+    let mut pending = changes.clone();
+    let mut lines = Vec::new();
+    for line in contents.lines() {
+        let key = line
+            .trim_start()
+            .split_once('=')
+            .map(|(key, _)| key.trim())
+            .filter(|key| !key.starts_with('#') && !key.starts_with('!'));
+        match key.and_then(|key| pending.remove_entry(key)) {
+            Some((key, value)) => lines.push(format!("{}={}", key, value)),
+            None => lines.push(line.to_string()),
+        }
+    }
+    for (key, value) in pending {
+        lines.push(format!("{}={}", key, value));
+    }
+    format!("{}\n", lines.join("\n"))
+}
+
+pub(crate) fn validate_property(key: &str, value: &str) -> Result<(), String> {
+    if key.trim().is_empty() || key.contains(['=', '\n', '#']) {
+        return Err(format!("invalid property key '{}'", key));
+    }
+    if value.contains('\n') {
+        return Err(format!("{} must be a single line", key));
+    }
+    if MANAGED_KEYS.contains(&key) {
+        return Err(format!("{} is managed by atlas-runnerd", key));
+    }
+    if PORT_KEYS.contains(&key) && !matches!(value.parse::<u16>(), Ok(port) if port > 0) {
+        return Err(format!("{} must be a port between 1 and 65535", key));
+    }
+    if INTEGER_KEYS.contains(&key) && value.parse::<i64>().is_err() {
+        return Err(format!("{} must be a whole number", key));
+    }
+    if BOOLEAN_KEYS.contains(&key) && value != "true" && value != "false" {
+        return Err(format!("{} must be true or false", key));
+    }
+    let allowed: &[&str] = match key {
+        "gamemode" => &["survival", "creative", "adventure", "spectator"],
+        "difficulty" => &["peaceful", "easy", "normal", "hard"],
+        _ => &[],
+    };
+    if !allowed.is_empty() && !allowed.contains(&value) {
+        return Err(format!("{} must be one of: {}", key, allowed.join(", ")));
+    }
+    Ok(())
+}
+
+async fn properties_path(state: &SharedState) -> PathBuf {
+    current_server_root(state)
+        .await
+        .unwrap_or_else(|| default_server_root(DEFAULT_PROFILE))
+        .join("current")
+        .join("server.properties")
+}
+
+async fn read_properties(path: &Path) -> Result<String, RpcError> {
+    tokio::fs::read_to_string(path)
+        .await
+        .map_err(|err| RpcError {
+            code: ErrorCode::IoError,
+            message: format!("failed to read {}: {err}", path.display()),
+            details: Default::default(),
+        })
+}
+
+async fn write_atomically(path: &Path, contents: &str) -> Result<(), RpcError> {
+    let tmp_path = path.with_extension("properties.tmp");
+    let io_error = |err: std::io::Error| RpcError {
+        code: ErrorCode::IoError,
+        message: format!("failed to write {}: {err}", path.display()),
+        details: Default::default(),
+    };
+    tokio::fs::write(&tmp_path, contents)
+        .await
+        .map_err(io_error)?;
+    tokio::fs::rename(&tmp_path, path).await.map_err(io_error)
+}
+
+fn bad_request(message: String) -> RpcError {
+    RpcError {
+        code: ErrorCode::BadRequest,
+        message,
+        details: Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{merge_properties, parse_properties, validate_property};
+
+    #[test]
+    fn merge_preserves_comments_and_unknown_keys() {
+        let contents = "#Minecraft server properties\nmotd=Hello\ncustom-key=1\nmax-players=20\n";
+        let changes = BTreeMap::from([
+            ("max-players".to_string(), "40".to_string()),
+            ("pvp".to_string(), "false".to_string()),
+        ]);
+        let merged = merge_properties(contents, &changes);
+        assert_eq!(
+            merged,
+            "#Minecraft server properties\nmotd=Hello\ncustom-key=1\nmax-players=40\npvp=false\n"
+        );
+        assert_eq!(
+            parse_properties(&merged).get("custom-key"),
+            Some(&"1".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_known_values() {
+        assert!(validate_property("server-port", "abc").is_err());
+        assert!(validate_property("server-port", "0").is_err());
+        assert!(validate_property("pvp", "yes").is_err());
+        assert!(validate_property("difficulty", "insane").is_err());
+        assert!(validate_property("rcon.password", "x").is_err());
+        assert!(validate_property("server-port", "25566").is_ok());
+        assert!(validate_property("motd", "Any text").is_ok());
+    }
+}
@@ -0,0 +1,175 @@
+use chrono::{DateTime, Duration as ChronoDuration, Local, NaiveTime, TimeZone};
+use runner_v2_rcon::{RconClient, load_rcon_settings};
+use tokio::time::{Duration, sleep};
+use tracing::{info, warn};
+
+use crate::config::{self, RestartSchedule};
+
+use super::server::restart_server;
+use super::state::SharedState;
+use super::util::current_server_root;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const COUNTDOWN_MARKS_SECS: &[u64] = &[900, 600, 300, 120, 60, 30, 10, 5, 4, 3, 2, 1];
+
+// The schedule is re-read from `deploy.json` on every check, so it applies from
+// daemon startup and picks up edits without a restart.
+pub fn ensure_restart_scheduler(state: SharedState) {
+    tokio::spawn(async move {
+        let mut last_handled: Option<DateTime<Local>> = None;
+        loop {
+            sleep(CHECK_INTERVAL).await;
+
+            let schedule = match config::load_deploy_key() {
+                Ok(Some(deploy)) => deploy.restart_schedule,
+                _ => None,
+            };
+            let (running, maintenance, last_start_ms) = {
+                let guard = state.lock().await;
+                (guard.is_running(), guard.maintenance, guard.last_start_ms)
+            };
+            let now = Local::now();
+            let after = last_handled.map_or(now, |handled| handled.max(now));
+            let last_start =
+                last_start_ms.and_then(|ms| Local.timestamp_millis_opt(ms as i64).single());
+            let next = schedule
+                .as_ref()
+                .and_then(|schedule| next_restart_after(schedule, after, last_start));
+            state.lock().await.next_restart_at_ms = next
+                .filter(|_| running)
+                .map(|at| at.timestamp_millis() as u64);
+
+            let (Some(schedule), Some(next)) = (schedule, next) else {
+                continue;
+            };
+            let warning = ChronoDuration::seconds(schedule.warning_secs as i64);
+            if !running || maintenance || next - now > warning {
+                continue;
+            }
+            last_handled = Some(next);
+
+            let online = state.lock().await.players.snapshot().count;
+            if schedule.skip_if_players_online && online > 0 {
+                info!("skipping scheduled restart: {online} player(s) online");
+                continue;
+            }
+
+            announce_countdown(&state, next).await;
+            info!("performing scheduled restart");
+            if let Err(err) = restart_server(state.clone(), schedule.skip_if_players_online).await {
+                warn!("scheduled restart failed: {}", err.message);
+            }
+        }
+    });
+}
+
+/// Earliest upcoming restart strictly after `after`: the next `daily_at` time of day
+/// and/or `interval_hours` after the server last started.
+pub(crate) fn next_restart_after(
+    schedule: &RestartSchedule,
+    after: DateTime<Local>,
+    last_start: Option<DateTime<Local>>,
+) -> Option<DateTime<Local>> {
+    let daily = schedule
+        .daily_at
+        .as_deref()
+        .and_then(|value| NaiveTime::parse_from_str(value.trim(), "%H:%M").ok())
+        .and_then(|time| {
+            let today = after.date_naive().and_time(time);
+            let candidate = Local.from_local_datetime(&today).earliest()?;
+            if candidate > after {
+                Some(candidate)
+            } else {
+                Local
+                    .from_local_datetime(&(today + ChronoDuration::days(1)))
+                    .earliest()
+            }
+        });
+
+    let interval = schedule
+        .interval_hours
+        .filter(|hours| *hours > 0)
+        .zip(last_start)
+        .map(|(hours, started)| {
+            let step = ChronoDuration::hours(hours as i64);
+            let mut candidate = started + step;
+            while candidate <= after {
+                candidate += step;
+            }
+            candidate
+        });
+
+    match (daily, interval) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+async fn announce_countdown(state: &SharedState, at: DateTime<Local>) {
+    let rcon = match current_server_root(state).await {
+        Some(root) => load_rcon_settings(&root.join("current"))
+            .await
+            .ok()
+            .flatten()
+            .map(|settings| RconClient::new(settings.address, settings.password)),
+        None => None,
+    };
+
+    for mark in COUNTDOWN_MARKS_SECS {
+        let remaining = (at - Local::now()).num_seconds();
+        if remaining < *mark as i64 {
+            continue;
+        }
+        sleep(Duration::from_secs((remaining - *mark as i64) as u64)).await;
+        if let Some(rcon) = &rcon {
+            let _ = rcon
+                .execute(&format!(
+                    "say Server restarting in {}",
+                    format_remaining(*mark)
+                ))
+                .await;
+        }
+    }
+
+    let remaining = (at - Local::now()).num_seconds().max(0) as u64;
+    sleep(Duration::from_secs(remaining)).await;
+}
+
+fn format_remaining(secs: u64) -> String {
+    match secs {
+        s if s >= 120 => format!("{} minutes", s / 60),
+        60 => "1 minute".to_string(),
+        1 => "1 second".to_string(),
+        s => format!("{s} seconds"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Local, TimeZone};
+
+    use super::next_restart_after;
+    use crate::config::RestartSchedule;
+
+    #[test]
+    fn picks_earliest_of_daily_and_interval() {
+        let now = Local.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let mut schedule = RestartSchedule {
+            daily_at: Some("04:00".into()),
+            interval_hours: None,
+            skip_if_players_online: false,
+            warning_secs: 300,
+        };
+        assert_eq!(
+            next_restart_after(&schedule, now, None),
+            Some(Local.with_ymd_and_hms(2026, 3, 11, 4, 0, 0).unwrap())
+        );
+
+        schedule.interval_hours = Some(6);
+        let started = Local.with_ymd_and_hms(2026, 3, 10, 9, 0, 0).unwrap();
+        assert_eq!(
+            next_restart_after(&schedule, now, Some(started)),
+            Some(Local.with_ymd_and_hms(2026, 3, 10, 15, 0, 0).unwrap())
+        );
+    }
+}
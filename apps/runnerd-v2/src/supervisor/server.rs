@@ -1,24 +1,63 @@
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use atlas_client::hub::HubClient;
 use runner_core_v2::proto::*;
-use runner_provision_v2::{DependencyProvider, LaunchPlan, ensure_applied_from_packblob_bytes};
+use runner_provision_v2::progress::ProgressFn;
+use runner_provision_v2::{
+    DependencyProvider, JvmSettings, LaunchPlan, ProvisionReport,
+    ensure_applied_from_packblob_bytes_with_progress,
+};
 use runner_v2_rcon::{RconClient, load_rcon_settings};
 use sysinfo::System;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
-use tokio::time::{Duration, sleep};
+use tokio::time::Duration;
 use tracing::{debug, info, warn};
 
+use super::crash::clear_crash_loop;
+use super::exit::{exit_info_from_status, record_exit};
+use super::list::cache_listing_details;
 use super::logs::LogStore;
+use super::metrics::ensure_metrics_poller;
 use super::monitor::ensure_monitor;
+use super::presence::spawn_presence_watcher;
 use super::state::{ServerState, SharedState};
 use super::updates::sync_whitelist_to_root;
 use super::util::{default_server_root, now_millis};
+use super::whitelist::sync_operators_to_root;
 use crate::config;
 
+const DEFAULT_STOP_GRACE_SECS: u64 = 30;
+// Clients wait 120s for `Stop`/`Shutdown` and the exit watcher adds up to 10s,
+// so longer grace periods would make `server stop` time out.
+const MAX_STOP_GRACE_SECS: u64 = 90;
+
+/// How a stop ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StopOutcome {
+    NotRunning,
+    Graceful,
+    ForceKilled,
+}
+
+/// How long a graceful stop waits before killing the server: the request's
+/// `grace_ms`, then `deploy.json` `stop_grace_secs`, then 30s; at most 90s.
+pub(crate) fn stop_grace_period(requested_ms: Option<u64>) -> Duration {
+    let period = match requested_ms {
+        Some(ms) => Duration::from_millis(ms),
+        None => {
+            let secs = match config::load_deploy_key() {
+                Ok(Some(deploy)) => deploy.stop_grace_secs,
+                _ => None,
+            };
+            Duration::from_secs(secs.unwrap_or(DEFAULT_STOP_GRACE_SECS))
+        }
+    };
+    period.min(Duration::from_secs(MAX_STOP_GRACE_SECS))
+}
+
 fn get_default_max_ram_mb() -> u32 {
     let mut system = System::new();
     system.refresh_memory();
@@ -59,59 +98,23 @@ fn normalize_max_ram_mb(value: u32) -> u32 {
     value
 }
 
-fn is_java_command_token(value: &str) -> bool {
-    let token = value.rsplit(['/', '\\']).next().unwrap_or(value);
-    token.eq_ignore_ascii_case("java")
-        || token.eq_ignore_ascii_case("java.exe")
-        || token.eq_ignore_ascii_case("javaw.exe")
-}
-
-fn ensure_memory_flags(argv: &mut Vec<String>, max_ram_mb: u32) -> bool {
-    if !argv.iter().any(|arg| is_java_command_token(arg)) {
-        return false;
-    }
-
-    argv.retain(|arg| {
-        let lower = arg.to_ascii_lowercase();
-        !(lower.starts_with("-xmx") || lower.starts_with("-xms"))
-    });
-
-    let Some(java_pos) = argv.iter().position(|arg| is_java_command_token(arg)) else {
-        return false;
-    };
-    let insert_pos = java_pos + 1;
-
-    argv.insert(insert_pos, format!("-Xms{}m", max_ram_mb));
-    argv.insert(insert_pos + 1, format!("-Xmx{}m", max_ram_mb));
-    true
-}
-
 #[cfg(test)]
 mod tests {
-    use super::{ensure_memory_flags, is_java_command_token, normalize_max_ram_mb};
+    use std::time::Duration;
 
-    #[test]
-    fn java_token_detection_accepts_absolute_paths() {
-        assert!(is_java_command_token("java"));
-        assert!(is_java_command_token("/opt/jdk/bin/java"));
-        assert!(is_java_command_token("C:\\Java\\bin\\java.exe"));
-        assert!(is_java_command_token("C:\\Java\\bin\\javaw.exe"));
-        assert!(!is_java_command_token("python"));
-    }
+    use super::{MAX_STOP_GRACE_SECS, normalize_max_ram_mb, read_pack_blob, stop_grace_period};
 
-    #[test]
-    fn memory_flags_insert_after_java_path_and_replace_existing() {
-        let mut argv = vec![
-            "/opt/jdk/bin/java".to_string(),
-            "-Xmx512m".to_string(),
-            "-jar".to_string(),
-            "server.jar".to_string(),
-        ];
-        ensure_memory_flags(&mut argv, 4096);
-        assert_eq!(argv[0], "/opt/jdk/bin/java");
-        assert_eq!(argv[1], "-Xms4096m");
-        assert_eq!(argv[2], "-Xmx4096m");
-        assert_eq!(argv[3], "-jar");
+    #[tokio::test]
+    async fn pack_blob_errors_distinguish_missing_and_invalid_files() {
+        let path = std::env::temp_dir().join(format!("runnerd-blob-{}.bin", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let missing = read_pack_blob(&path).await.unwrap_err();
+        assert_eq!(missing.details["reason"], "not_found");
+
+        std::fs::write(&path, b"not a blob").unwrap();
+        let invalid = read_pack_blob(&path).await.unwrap_err();
+        assert_eq!(invalid.details["reason"], "invalid_blob");
+        let _ = std::fs::remove_file(&path);
     }
 
     #[test]
@@ -121,45 +124,11 @@ mod tests {
     }
 
     #[test]
-    fn memory_flags_not_inserted_for_non_java_commands() {
-        let mut argv = vec!["python".to_string(), "server.py".to_string()];
-        let changed = ensure_memory_flags(&mut argv, 4096);
-        assert!(!changed);
-        assert_eq!(argv, vec!["python".to_string(), "server.py".to_string()]);
-    }
-
-    #[test]
-    fn memory_flags_are_idempotent_on_repeated_application() {
-        let mut argv = vec![
-            "/opt/jdk/bin/java".to_string(),
-            "-jar".to_string(),
-            "server.jar".to_string(),
-        ];
-
-        let first_changed = ensure_memory_flags(&mut argv, 4096);
-        assert!(first_changed);
-        assert_eq!(
-            argv,
-            vec![
-                "/opt/jdk/bin/java".to_string(),
-                "-Xms4096m".to_string(),
-                "-Xmx4096m".to_string(),
-                "-jar".to_string(),
-                "server.jar".to_string(),
-            ]
-        );
-
-        let second_changed = ensure_memory_flags(&mut argv, 4096);
-        assert!(second_changed);
+    fn grace_period_stays_within_the_client_stop_timeout() {
+        assert_eq!(stop_grace_period(Some(5_000)), Duration::from_secs(5));
         assert_eq!(
-            argv,
-            vec![
-                "/opt/jdk/bin/java".to_string(),
-                "-Xms4096m".to_string(),
-                "-Xmx4096m".to_string(),
-                "-jar".to_string(),
-                "server.jar".to_string(),
-            ]
+            stop_grace_period(Some(u64::MAX)),
+            Duration::from_secs(MAX_STOP_GRACE_SECS)
         );
     }
 }
@@ -170,9 +139,9 @@ pub async fn start_server_from_deploy(state: SharedState) {
         if guard.is_running() {
             return;
         }
-        // Ensure logs are initialized for auto-start
+        // Fresh server log for auto-start, still feeding the shared daemon log
         if guard.logs.server_subscribe().is_empty() {
-            guard.logs = super::logs::LogStore::new(2000);
+            guard.logs = guard.logs.for_profile();
         }
     }
 
@@ -193,6 +162,16 @@ pub async fn start_server_from_deploy(state: SharedState) {
         return;
     }
 
+    if state.lock().await.maintenance {
+        info!("maintenance mode active; not starting server");
+        return;
+    }
+
+    if state.lock().await.crash_looping {
+        warn!("server is crash-looping; skipping auto-start until it is started explicitly");
+        return;
+    }
+
     let mut hub = match HubClient::new(&deploy.hub_url) {
         Ok(value) => value,
         Err(err) => {
@@ -204,7 +183,7 @@ pub async fn start_server_from_deploy(state: SharedState) {
     let hub = Arc::new(hub);
 
     info!("auto-starting server from deploy key");
-    let server_root = default_server_root("default");
+    let server_root = default_server_root(DEFAULT_PROFILE);
     let current_build_id = load_current_build_id(&server_root).await;
     // reflect on-disk current build id in shared state
     if let Some(ref id) = current_build_id {
@@ -213,9 +192,10 @@ pub async fn start_server_from_deploy(state: SharedState) {
     }
 
     let artifact = match hub
-        .get_launcher_artifact(
+        .get_launcher_artifact_for_version(
             &deploy.pack_id,
             &deploy.channel,
+            deploy.pinned_version.as_deref(),
             current_build_id.as_deref(),
         )
         .await
@@ -263,9 +243,11 @@ pub async fn start_server_from_deploy(state: SharedState) {
         }
     };
 
-    let profile = "default".to_string();
+    let profile = DEFAULT_PROFILE.to_string();
 
-    if let Err(err) = start_server(profile, &build, server_root.clone(), state.clone()).await {
+    if let Err(err) =
+        start_server(profile, &build, server_root.clone(), state.clone(), &|_| {}).await
+    {
         warn!("failed to auto-start server: {}", err.message);
         return;
     }
@@ -333,10 +315,7 @@ pub async fn start_server_from_deploy(state: SharedState) {
     }
 }
 
-pub async fn build_status(
-    daemon_start_ms: u64,
-    state: &SharedState,
-) -> (DaemonStatus, ServerStatus) {
+pub async fn build_status(daemon_start_ms: u64, state: &SharedState) -> Response {
     let mut guard = state.lock().await;
     refresh_child_status(&mut guard).await;
 
@@ -347,7 +326,31 @@ pub async fn build_status(
         uptime_ms: now_millis().saturating_sub(daemon_start_ms),
     };
 
-    (daemon, guard.status.clone())
+    Response::Status {
+        daemon,
+        server: guard.status.clone(),
+        players: guard.players.snapshot(),
+        performance: Box::new(guard.performance.summary()),
+        maintenance: guard.maintenance,
+        next_restart_at_ms: guard.next_restart_at_ms,
+        pinned_version: guard.pinned_version.clone(),
+        crash_looping: guard.crash_looping,
+        last_crash: guard.last_crash.clone().map(Box::new),
+        last_provision: guard.last_provision.clone().map(Box::new),
+        last_exit: guard.last_exit.clone().map(Box::new),
+    }
+}
+
+pub async fn clear_crash_loop_state(state: &SharedState) {
+    clear_crash_loop(&mut *state.lock().await);
+}
+
+pub async fn build_metrics(state: &SharedState) -> Response {
+    let guard = state.lock().await;
+    Response::Metrics {
+        summary: guard.performance.summary(),
+        history: guard.performance.history(),
+    }
 }
 
 pub async fn start_server(
@@ -355,6 +358,7 @@ pub async fn start_server(
     pack_blob_bytes: &[u8],
     server_root: PathBuf,
     state: SharedState,
+    progress: &ProgressFn,
 ) -> Result<Response, RpcError> {
     // Quick check for already running. We will also acquire the lifecycle lock
     // to serialize start/stop/update operations.
@@ -387,16 +391,34 @@ pub async fn start_server(
         }
     }
 
-    let launch_plan = apply_pack_blob(&server_root, &pack_blob_bytes).await?;
-    if let Ok(Some(deploy)) = config::load_deploy_key() {
+    super::eula::ensure_eula_accepted(&server_root).await?;
+    let provider = HttpDependencyProvider::default();
+    let (launch_plan, provision) =
+        apply_pack_blob(&server_root, &pack_blob_bytes, &provider, progress).await?;
+    state.lock().await.last_provision = Some(provision);
+    // The deploy key's whitelist and operators belong to the default profile's pack.
+    let deploy = match config::load_deploy_key() {
+        Ok(Some(deploy)) if profile == DEFAULT_PROFILE => Some(deploy),
+        _ => None,
+    };
+    if let Some(deploy) = deploy {
         if let Ok(mut hub) = HubClient::new(&deploy.hub_url) {
             hub.set_service_token(deploy.deploy_key.clone());
             let hub = Arc::new(hub);
-            if let Err(err) =
-                sync_whitelist_to_root(hub, &deploy.pack_id, &server_root, state.clone()).await
+            if let Err(err) = sync_whitelist_to_root(
+                hub.clone(),
+                &deploy.pack_id,
+                &server_root,
+                state.clone(),
+                false,
+            )
+            .await
             {
                 warn!("whitelist sync failed on start: {err}");
             }
+            if let Err(err) = sync_operators_to_root(&hub, &deploy, &server_root, &state).await {
+                warn!("operator sync failed on start: {err}");
+            }
         }
     }
     let logs = {
@@ -404,12 +426,22 @@ pub async fn start_server(
         guard.logs.clone()
     };
     let spawn_logs = logs.clone();
+    let players = {
+        let mut guard = state.lock().await;
+        guard.performance.reset();
+        guard.players.clone()
+    };
+    spawn_presence_watcher(players.clone(), &logs, server_root.clone());
+    cache_listing_details(&state, &server_root).await;
     let child = spawn_server(&launch_plan, &server_root, &BTreeMap::new(), spawn_logs)
         .await
-        .map_err(|err| RpcError {
-            code: ErrorCode::Internal,
-            message: format!("failed to start server: {err}"),
-            details: Default::default(),
+        .map_err(|err| {
+            players.reset();
+            RpcError {
+                code: ErrorCode::Internal,
+                message: format!("failed to start server: {err}"),
+                details: Default::default(),
+            }
         })?;
 
     let pid = child.id().unwrap_or_default() as i32;
@@ -422,7 +454,6 @@ pub async fn start_server(
         guard.server_root = Some(server_root.clone());
         guard.launch_plan = Some(launch_plan);
         guard.restart_attempts = 0;
-        guard.restart_disabled = false;
         guard.last_start_ms = Some(started_at_ms);
         guard.status = ServerStatus::Running {
             profile: profile.clone(),
@@ -439,9 +470,18 @@ pub async fn start_server(
         server_root.display()
     ));
 
+    // Named profiles have no deploy key to redeploy from, so crash recovery
+    // restarts the pack they were started with.
+    if profile != DEFAULT_PROFILE {
+        if let Err(err) = save_pack_blob(&server_root, pack_blob_bytes).await {
+            warn!("failed to save pack_blob for profile {profile}: {err}");
+        }
+    }
+
     // Start monitor; update watchers are started by the daemon bootstrap path to avoid
     // mutually recursive async dependencies between modules.
     ensure_monitor(state.clone()).await;
+    ensure_metrics_poller(state.clone()).await;
 
     Ok(Response::Started {
         profile,
@@ -450,7 +490,99 @@ pub async fn start_server(
     })
 }
 
-pub async fn stop_server(force: bool, state: SharedState) -> Result<Response, RpcError> {
+/// Gracefully stops the server and starts it again with the pack it was running.
+/// Does nothing when the server was stopped, put into maintenance or (with
+/// `skip_if_players_online`) joined by players since the restart was scheduled.
+pub(crate) async fn restart_server(
+    state: SharedState,
+    skip_if_players_online: bool,
+) -> Result<(), RpcError> {
+    let lifecycle_lock = {
+        let guard = state.lock().await;
+        guard.lifecycle_lock.clone()
+    };
+    let lifecycle_guard =
+        match tokio::time::timeout(Duration::from_secs(5), lifecycle_lock.lock()).await {
+            Ok(guard) => guard,
+            Err(_) => {
+                return Err(RpcError {
+                    code: ErrorCode::Internal,
+                    message: "another lifecycle operation in progress".into(),
+                    details: Default::default(),
+                });
+            }
+        };
+
+    let (running, maintenance, online, server_root, profile) = {
+        let guard = state.lock().await;
+        (
+            guard.is_running(),
+            guard.maintenance,
+            guard.players.snapshot().count,
+            guard.server_root.clone(),
+            guard
+                .profile
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PROFILE.into()),
+        )
+    };
+    if !running || maintenance {
+        info!("server stopped or in maintenance; skipping restart");
+        return Ok(());
+    }
+    if skip_if_players_online && online > 0 {
+        info!("skipping restart: {online} player(s) online");
+        return Ok(());
+    }
+    let server_root = server_root.ok_or_else(|| RpcError {
+        code: ErrorCode::InvalidConfig,
+        message: "server root not configured".into(),
+        details: Default::default(),
+    })?;
+
+    let (outcome, _) = stop_server_internal(state.clone(), false, stop_grace_period(None)).await?;
+    if outcome == StopOutcome::NotRunning {
+        info!("server was already stopped; skipping restart");
+        return Ok(());
+    }
+    // start_server takes the lifecycle lock itself.
+    drop(lifecycle_guard);
+    match load_pack_blob(&server_root).await {
+        Some(blob) => {
+            start_server(profile, &blob, server_root, state, &|_| {}).await?;
+        }
+        None => start_server_from_deploy(state).await,
+    }
+    Ok(())
+}
+
+/// Restarts a crashed server: the default profile redeploys from its deploy key,
+/// named profiles start their saved pack again.
+pub(crate) async fn restart_after_crash(state: SharedState) {
+    let (profile, server_root) = {
+        let guard = state.lock().await;
+        (guard.profile.clone(), guard.server_root.clone())
+    };
+    let (Some(profile), Some(server_root)) = (profile, server_root) else {
+        return start_server_from_deploy(state).await;
+    };
+    if profile == DEFAULT_PROFILE {
+        return start_server_from_deploy(state).await;
+    }
+    let Some(blob) = load_pack_blob(&server_root).await else {
+        warn!("no saved pack for profile {profile}; not restarting");
+        return;
+    };
+    if let Err(err) = start_server(profile.clone(), &blob, server_root, state, &|_| {}).await {
+        warn!("failed to restart profile {profile}: {}", err.message);
+    }
+}
+
+pub async fn stop_server(
+    force: bool,
+    grace_ms: Option<u64>,
+    state: SharedState,
+) -> Result<Response, RpcError> {
     // Acquire lifecycle lock to serialize stop with other lifecycle operations
     let lifecycle_lock = {
         let guard = state.lock().await;
@@ -484,13 +616,17 @@ pub async fn stop_server(force: bool, state: SharedState) -> Result<Response, Rp
     };
 
     // Stop the server (graceful or force) using the internal helper which will handle killing the child
-    if let Err(err) = stop_server_internal(state.clone(), force).await {
-        return Err(RpcError {
-            code: err.code,
-            message: format!("failed to stop server: {}", err.message),
-            details: err.details,
-        });
-    }
+    let (outcome, exit_info) =
+        match stop_server_internal(state.clone(), force, stop_grace_period(grace_ms)).await {
+            Ok(stopped) => stopped,
+            Err(err) => {
+                return Err(RpcError {
+                    code: err.code,
+                    message: format!("failed to stop server: {}", err.message),
+                    details: err.details,
+                });
+            }
+        };
 
     // Wait for watcher to signal done via the atomic flag (up to 10s).
     if let Some(done_flag) = watcher_done_opt {
@@ -510,13 +646,18 @@ pub async fn stop_server(force: bool, state: SharedState) -> Result<Response, Rp
     }
 
     let mut guard = state.lock().await;
-    let profile = guard.profile.clone().unwrap_or_else(|| "default".into());
+    let profile = guard
+        .profile
+        .clone()
+        .unwrap_or_else(|| DEFAULT_PROFILE.into());
     let stopped_at_ms = now_millis();
-    let exit_info = ExitInfo {
+    let exit_info = exit_info.unwrap_or(ExitInfo {
         code: None,
         signal: None,
-    };
+    });
     guard.child = None;
+    guard.players.reset();
+    guard.restart_pending_at_ms = None;
     guard.status = ServerStatus::Exited {
         profile: profile.clone(),
         exit: exit_info.clone(),
@@ -524,27 +665,37 @@ pub async fn stop_server(force: bool, state: SharedState) -> Result<Response, Rp
     };
 
     let logs = guard.logs.clone();
-    logs.push_daemon(format!("server stopped: profile={profile}"));
+    let forced = outcome == StopOutcome::ForceKilled;
+    if forced && !force {
+        logs.push_daemon(format!(
+            "server did not exit within the grace period and was killed: profile={profile}"
+        ));
+    } else {
+        logs.push_daemon(format!("server stopped: profile={profile}"));
+    }
 
     Ok(Response::Stopped {
         exit: Some(exit_info),
         stopped_at_ms,
+        forced,
     })
 }
 
-async fn refresh_child_status(state: &mut ServerState) {
+pub(crate) async fn refresh_child_status(state: &mut ServerState) {
     let Some(child) = state.child.as_mut() else {
         return;
     };
 
     match child.try_wait() {
         Ok(Some(status)) => {
-            let exit = ExitInfo {
-                code: status.code(),
-                signal: None,
-            };
-            let profile = state.profile.clone().unwrap_or_else(|| "default".into());
+            let exit = exit_info_from_status(status);
+            record_exit(state, &exit, None);
+            let profile = state
+                .profile
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PROFILE.into());
             state.child = None;
+            state.players.reset();
             state.status = ServerStatus::Exited {
                 profile,
                 exit,
@@ -556,39 +707,144 @@ async fn refresh_child_status(state: &mut ServerState) {
     }
 }
 
+/// Reads and decodes the pack blob a `Start` points at before anything is
+/// provisioned, so a missing or unreadable blob fails with a specific error.
+pub async fn read_pack_blob(path: &Path) -> Result<Vec<u8>, RpcError> {
+    let details = |reason: &str| {
+        BTreeMap::from([
+            ("reason".to_string(), reason.to_string()),
+            ("path".to_string(), path.display().to_string()),
+        ])
+    };
+    let bytes = match tokio::fs::read(path).await {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(RpcError {
+                code: ErrorCode::IoError,
+                message: format!(
+                    "pack blob not found at {}; it may have been removed before the daemon read it",
+                    path.display()
+                ),
+                details: details("not_found"),
+            });
+        }
+        Err(err) => {
+            return Err(RpcError {
+                code: ErrorCode::IoError,
+                message: format!("failed to read pack blob: {err}"),
+                details: details("unreadable"),
+            });
+        }
+    };
+
+    match protocol::decode_blob_with_limits(&bytes, &protocol::DecodeLimits::untrusted()) {
+        Ok(_) => Ok(bytes),
+        // Enum values this build does not know come from a newer blob format.
+        Err(err @ protocol::ProtocolError::InvalidEnum { .. }) => Err(RpcError {
+            code: ErrorCode::UnsupportedProtocol,
+            message: format!(
+                "pack blob uses a format this daemon does not support ({err}); update atlas-runnerd"
+            ),
+            details: details("unsupported_version"),
+        }),
+        Err(err) => Err(RpcError {
+            code: ErrorCode::BadRequest,
+            message: format!("not a valid atlas pack blob: {err}"),
+            details: details("invalid_blob"),
+        }),
+    }
+}
+
+/// Memory and extra JVM flags from `deploy.json`. Without a deploy config no
+/// flags are added.
+fn jvm_settings() -> JvmSettings {
+    match config::load_deploy_key() {
+        Ok(Some(deploy_config)) => JvmSettings {
+            max_ram_mb: Some(
+                deploy_config
+                    .max_ram
+                    .map(normalize_max_ram_mb)
+                    .unwrap_or_else(get_default_max_ram_mb),
+            ),
+            extra_args: deploy_config.jvm_args.unwrap_or_default(),
+        },
+        _ => JvmSettings::default(),
+    }
+}
+
 pub(crate) async fn apply_pack_blob(
     server_root: &PathBuf,
     pack_blob: &[u8],
-) -> Result<LaunchPlan, RpcError> {
-    let provider = HttpDependencyProvider::default();
-    let mut launch_plan = ensure_applied_from_packblob_bytes(server_root, pack_blob, &provider)
-        .await
-        .map_err(|err| RpcError {
-            code: ErrorCode::InvalidConfig,
-            message: format!("provision failed: {err}"),
-            details: Default::default(),
-        })?;
+    provider: &dyn DependencyProvider,
+    progress: &ProgressFn,
+) -> Result<(LaunchPlan, ProvisionSummary), RpcError> {
+    let jvm = jvm_settings();
+    let eula_accepted = super::eula::eula_accepted(server_root).await;
+    let (launch_plan, report) = ensure_applied_from_packblob_bytes_with_progress(
+        server_root,
+        pack_blob,
+        provider,
+        &jvm,
+        eula_accepted,
+        progress,
+    )
+    .await
+    .map_err(|err| RpcError {
+        code: ErrorCode::InvalidConfig,
+        message: format!("provision failed: {err}"),
+        details: Default::default(),
+    })?;
 
-    // Add RAM limits if specified in config
-    if let Ok(Some(deploy_config)) = config::load_deploy_key() {
-        let max_ram_mb = deploy_config
-            .max_ram
-            .map(normalize_max_ram_mb)
-            .unwrap_or_else(get_default_max_ram_mb);
-        if ensure_memory_flags(&mut launch_plan.argv, max_ram_mb) {
-            info!(
-                "applied JVM memory flags: -Xms{}m -Xmx{}m",
-                max_ram_mb, max_ram_mb
-            );
-        } else {
-            warn!("launch plan command is not java; skipping JVM memory flags");
-        }
+    if !launch_plan.injected_jvm_args.is_empty() {
+        info!(
+            "applied JVM flags: {}",
+            launch_plan.injected_jvm_args.join(" ")
+        );
+    } else if jvm.max_ram_mb.is_some() {
+        warn!("launch plan command is not java; skipping JVM memory flags");
     }
 
-    Ok(launch_plan)
+    let phases = report
+        .phases
+        .iter()
+        .map(|(name, ms)| format!("{name} {ms}ms"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    info!(
+        "provisioned in {}ms ({phases}); {} files written, {} artifacts linked",
+        report.total_ms(),
+        report.files_written,
+        report.artifacts_linked
+    );
+
+    Ok((launch_plan, provision_summary(&report)))
 }
 
-pub(crate) async fn stop_server_internal(state: SharedState, force: bool) -> Result<(), RpcError> {
+fn provision_summary(report: &ProvisionReport) -> ProvisionSummary {
+    let millis = |ms: u128| u64::try_from(ms).unwrap_or(u64::MAX);
+    ProvisionSummary {
+        at_ms: now_millis(),
+        total_ms: millis(report.total_ms()),
+        phases: report
+            .phases
+            .iter()
+            .map(|(name, ms)| ProvisionPhase {
+                name: name.clone(),
+                duration_ms: millis(*ms),
+            })
+            .collect(),
+        files_written: report.files_written as u64,
+        artifacts_linked: report.artifacts_linked as u64,
+    }
+}
+
+/// Asks the server to stop over RCON and waits up to `grace` for it to exit,
+/// then kills it. `force` skips straight to the kill.
+pub(crate) async fn stop_server_internal(
+    state: SharedState,
+    force: bool,
+    grace: Duration,
+) -> Result<(StopOutcome, Option<ExitInfo>), RpcError> {
     let server_root = super::util::current_server_root(&state)
         .await
         .ok_or_else(|| RpcError {
@@ -597,36 +853,49 @@ pub(crate) async fn stop_server_internal(state: SharedState, force: bool) -> Res
             details: Default::default(),
         })?;
 
-    if let Ok(Some(settings)) = load_rcon_settings(&server_root.join("current")).await {
-        let rcon = RconClient::new(settings.address, settings.password);
-        let _ = rcon.execute("stop").await;
-    }
-
     let mut child = {
         let mut guard = state.lock().await;
         guard.child.take()
     };
+    let Some(child) = child.as_mut() else {
+        return Ok((StopOutcome::NotRunning, None));
+    };
 
-    if let Some(ref mut child) = child {
-        if !force {
-            // Wait up to 30 seconds (60 * 500ms) for the server process to exit gracefully
-            info!("attempting graceful shutdown, waiting up to 30 seconds for process to exit...");
-            for _ in 0..60 {
-                if let Ok(Some(_)) = child.try_wait() {
-                    return Ok(());
-                }
-                sleep(Duration::from_millis(500)).await;
-            }
+    if !force {
+        if let Ok(Some(settings)) = load_rcon_settings(&server_root.join("current")).await {
+            let rcon = RconClient::new(settings.address, settings.password);
+            let _ = rcon.execute("stop").await;
         }
-
-        child.kill().await.map_err(|err| RpcError {
-            code: ErrorCode::IoError,
-            message: format!("failed to kill server: {err}"),
-            details: Default::default(),
-        })?;
+        info!(
+            "attempting graceful shutdown, waiting up to {}s for process to exit...",
+            grace.as_secs()
+        );
+        if let Ok(Ok(status)) = tokio::time::timeout(grace, child.wait()).await {
+            let exit = exit_info_from_status(status);
+            record_exit(&mut *state.lock().await, &exit, Some(ExitReason::Graceful));
+            return Ok((StopOutcome::Graceful, Some(exit)));
+        }
+        warn!(
+            "server did not exit within {}s; killing it",
+            grace.as_secs()
+        );
     }
 
-    Ok(())
+    child.kill().await.map_err(|err| RpcError {
+        code: ErrorCode::IoError,
+        message: format!("failed to kill server: {err}"),
+        details: Default::default(),
+    })?;
+    let exit = child.try_wait().ok().flatten().map(exit_info_from_status);
+    record_exit(
+        &mut *state.lock().await,
+        exit.as_ref().unwrap_or(&ExitInfo {
+            code: None,
+            signal: None,
+        }),
+        Some(ExitReason::ForceKilled),
+    );
+    Ok((StopOutcome::ForceKilled, exit))
 }
 
 pub(crate) async fn spawn_server(
@@ -1,13 +1,17 @@
+use std::collections::VecDeque;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use tokio::process::Child;
 use tokio::sync::Mutex;
 
-use runner_core_v2::proto::{ProfileId, ServerStatus};
+use runner_core_v2::proto::{CrashInfo, LastExit, ProfileId, ProvisionSummary, ServerStatus};
 use runner_provision_v2::LaunchPlan;
 
+use super::list::AppliedPack;
 use super::logs::LogStore;
+use super::metrics::PerformanceHistory;
+use super::presence::PlayerTracker;
 
 pub type SharedState = Arc<Mutex<ServerState>>;
 
@@ -18,14 +22,28 @@ pub struct ServerState {
     pub(crate) server_root: Option<PathBuf>,
     pub(crate) launch_plan: Option<LaunchPlan>,
     pub(crate) restart_attempts: u32,
-    pub(crate) restart_disabled: bool,
+    // Crash timestamps inside the crash-loop window, oldest first.
+    pub(crate) crash_times: VecDeque<u64>,
+    pub(crate) crash_looping: bool,
+    pub(crate) maintenance: bool,
+    pub(crate) next_restart_at_ms: Option<u64>,
+    pub(crate) pinned_version: Option<String>,
+    pub(crate) last_crash: Option<CrashInfo>,
+    pub(crate) last_provision: Option<ProvisionSummary>,
+    pub(crate) last_exit: Option<LastExit>,
+    pub(crate) restart_pending_at_ms: Option<u64>,
     pub(crate) watchers_started: bool,
     pub(crate) monitor_started: bool,
+    pub(crate) metrics_started: bool,
     pub(crate) last_start_ms: Option<u64>,
     pub(crate) logs: LogStore,
+    pub(crate) players: PlayerTracker,
+    pub(crate) performance: PerformanceHistory,
     pub(crate) pack_etag: Option<String>,
     pub(crate) whitelist_etag: Option<String>,
     pub(crate) current_pack_build_id: Option<String>,
+    pub(crate) applied_pack: Option<AppliedPack>,
+    pub(crate) server_port: Option<u16>,
     pub(crate) watcher_stop: Option<Arc<AtomicBool>>,
     // Flag set by watcher worker when it has fully exited
     pub(crate) watcher_done: Option<Arc<AtomicBool>>,
@@ -46,14 +64,27 @@ impl ServerState {
             server_root: None,
             launch_plan: None,
             restart_attempts: 0,
-            restart_disabled: false,
+            crash_times: VecDeque::new(),
+            crash_looping: false,
+            maintenance: false,
+            next_restart_at_ms: None,
+            pinned_version: None,
+            last_crash: None,
+            last_provision: None,
+            last_exit: None,
+            restart_pending_at_ms: None,
             watchers_started: false,
             monitor_started: false,
+            metrics_started: false,
             last_start_ms: None,
             logs,
+            players: PlayerTracker::new(),
+            performance: PerformanceHistory::default(),
             pack_etag: None,
             whitelist_etag: None,
             current_pack_build_id: None,
+            applied_pack: None,
+            server_port: None,
             watcher_stop: None,
             watcher_done: None,
             lifecycle_lock: Arc::new(Mutex::new(())),
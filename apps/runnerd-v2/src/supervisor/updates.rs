@@ -1,17 +1,17 @@
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use atlas_client::hub::HubClient;
+use atlas_client::hub::{ARTIFACT_DOWNLOAD_TIMEOUT, HubClient, HubError};
+use runner_core_v2::proto::DEFAULT_PROFILE;
 use tokio::time::{Duration, sleep};
 use tracing::{debug, info, warn};
 
-use runner_v2_rcon::{RconClient, load_rcon_settings};
-
 use crate::config::DeployKeyConfig;
 
-use super::server::{start_server, stop_server_internal};
+use super::server::{start_server, stop_grace_period, stop_server_internal};
 use super::state::SharedState;
 use super::util::current_server_root;
+use super::whitelist::{PlayerListChanges, reconcile_whitelist, sync_operators_to_root};
 
 const POLL_INTERVAL_SECS: u64 = 60;
 const PACK_ETAG_FILENAME: &str = ".runner/pack_etag.txt";
@@ -109,9 +109,14 @@ pub async fn ensure_watchers(state: SharedState) {
                             break;
                         }
 
+                        // Re-read so a rotated deploy key is used without restarting the watcher.
+                        let deploy_key = match crate::config::load_deploy_key() {
+                            Ok(Some(value)) => value.deploy_key,
+                            _ => wdeploy.clone(),
+                        };
                         match HubClient::new(&whub) {
                             Ok(mut h) => {
-                                h.set_service_token(wdeploy.clone());
+                                h.set_service_token(deploy_key);
                                 let h = Arc::new(h);
                                 if let Err(err) =
                                     poll_whitelist(h, &w_whitelist_cfg, w_state_whitelist.clone())
@@ -142,12 +147,17 @@ pub async fn ensure_watchers(state: SharedState) {
                             break;
                         }
 
-                        match HubClient::new(&whub) {
+                        // Re-read so channel and pin changes apply without restarting the watcher.
+                        let update_cfg = match crate::config::load_deploy_key() {
+                            Ok(Some(value)) => value,
+                            _ => w_update_cfg.clone(),
+                        };
+                        match artifact_hub_client(&whub) {
                             Ok(mut h) => {
-                                h.set_service_token(wdeploy.clone());
+                                h.set_service_token(update_cfg.deploy_key.clone());
                                 let h = Arc::new(h);
                                 if let Err(err) =
-                                    poll_pack_update(h, &w_update_cfg, w_state_update.clone()).await
+                                    poll_pack_update(h, &update_cfg, w_state_update.clone()).await
                                 {
                                     warn!("pack update poll error: {err}");
                                 }
@@ -216,7 +226,19 @@ async fn poll_whitelist(
     state: SharedState,
 ) -> Result<(), String> {
     // Always sync whitelist on poll
-    sync_whitelist(hub, &config.pack_id, state).await
+    sync_whitelist(hub.clone(), &config.pack_id, state.clone()).await?;
+
+    let server_root = current_server_root(&state)
+        .await
+        .ok_or_else(|| "server root not configured".to_string())?;
+    let operators = sync_operators_to_root(&hub, config, &server_root, &state).await?;
+    if !operators.is_empty() {
+        info!(
+            "operators reconciled (added: {:?}, removed: {:?})",
+            operators.added, operators.removed
+        );
+    }
+    Ok(())
 }
 
 async fn poll_pack_update(
@@ -224,6 +246,16 @@ async fn poll_pack_update(
     config: &DeployKeyConfig,
     state: SharedState,
 ) -> Result<(), String> {
+    // Updates restart the server, which maintenance mode is meant to prevent.
+    if state.lock().await.maintenance {
+        debug!("maintenance mode active; skipping pack update check");
+        return Ok(());
+    }
+    if let Some(version) = state.lock().await.pinned_version.clone() {
+        debug!("pinned to {version}; skipping pack update check");
+        return Ok(());
+    }
+
     // First check pack metadata with ETag caching
     let current_etag = {
         let guard = state.lock().await;
@@ -324,6 +356,18 @@ async fn poll_pack_update(
     apply_pack_update(hub, config, state).await
 }
 
+/// Checks the configured channel now instead of waiting for the next poll, updating a
+/// running server if its build differs.
+pub(crate) async fn reconcile_now(state: SharedState) -> Result<(), String> {
+    let config =
+        crate::config::load_deploy_key()?.ok_or_else(|| "deploy key not configured".to_string())?;
+    let mut hub = artifact_hub_client(&config.hub_url).map_err(|err| err.to_string())?;
+    hub.set_service_token(config.deploy_key.clone());
+
+    state.lock().await.pack_etag = None;
+    poll_pack_update(Arc::new(hub), &config, state).await
+}
+
 async fn sync_whitelist(
     hub: Arc<HubClient>,
     pack_id: &str,
@@ -332,17 +376,29 @@ async fn sync_whitelist(
     let server_root = current_server_root(&state)
         .await
         .ok_or_else(|| "server root not configured".to_string())?;
-    sync_whitelist_to_root(hub, pack_id, &server_root, state).await
+    let changes = sync_whitelist_to_root(hub, pack_id, &server_root, state, false).await?;
+    if !changes.is_empty() {
+        info!(
+            "whitelist reconciled (added: {:?}, removed: {:?})",
+            changes.added, changes.removed
+        );
+    }
+    Ok(())
 }
 
+// `force` skips the stored ETag so an on-demand sync always compares against the
+// hub's full list.
 pub(crate) async fn sync_whitelist_to_root(
     hub: Arc<HubClient>,
     pack_id: &str,
     server_root: &PathBuf,
     state: SharedState,
-) -> Result<(), String> {
+    force: bool,
+) -> Result<PlayerListChanges, String> {
     // Try to use ETag-aware endpoint to avoid unnecessary writes
-    let current_etag = {
+    let current_etag = if force {
+        None
+    } else {
         let guard = state.lock().await;
         guard.whitelist_etag.clone()
     };
@@ -356,7 +412,7 @@ pub(crate) async fn sync_whitelist_to_root(
         .map_err(|err| format!("whitelist fetch failed: {err}"))?;
 
     // If the server returned 304 (represented by empty players vec), skip writing and persist etag
-    if players.is_empty() {
+    if players.is_empty() && !force {
         if !etag.is_empty() {
             let normalized = normalize_etag_value(&etag);
             // set in-memory state then persist without holding the lock
@@ -366,49 +422,17 @@ pub(crate) async fn sync_whitelist_to_root(
             }
             debug!("whitelist not modified; stored etag={}", etag);
 
-            if let Some(_parent) = server_root.join(".runner").parent() {
-                let _ = write_whitelist_etag_to_disk(server_root, &normalized).await;
-            }
+            let _ = write_whitelist_etag_to_disk(server_root, &normalized).await;
         } else {
             debug!("whitelist not modified; no ETag returned");
         }
-        return Ok(());
+        return Ok(PlayerListChanges::default());
     }
 
-    let whitelist_data = players
-        .iter()
-        .map(|player| {
-            serde_json::json!({
-                "name": player.name,
-                "uuid": format_uuid_with_dashes(&player.uuid),
-            })
-        })
-        .collect::<Vec<_>>();
-
-    let content = serde_json::to_string_pretty(&whitelist_data)
-        .map_err(|err| format!("whitelist serialize failed: {err}"))?;
-
-    let path = server_root.join("current").join("whitelist.json");
-    let previous = tokio::fs::read_to_string(&path).await.ok();
-    if previous.as_deref() == Some(content.as_str()) {
-        // Persist the returned etag even if content matches
-        if !etag.is_empty() {
-            let normalized = normalize_etag_value(&etag);
-            // update in-memory state then persist without holding lock
-            {
-                let mut guard = state.lock().await;
-                guard.whitelist_etag = Some(normalized.clone());
-            }
-            let _ = write_whitelist_etag_to_disk(server_root, &normalized).await;
-        }
-        return Ok(());
-    }
+    let running = state.lock().await.is_running();
+    let changes = reconcile_whitelist(server_root, &players, running).await?;
 
-    tokio::fs::write(&path, content)
-        .await
-        .map_err(|err| format!("whitelist write failed: {err}"))?;
-
-    // Persist etag after successful write: normalize, set in-memory, then persist to disk
+    // Persist etag after a successful reconcile: normalize, set in-memory, then persist to disk
     if !etag.is_empty() {
         let normalized = normalize_etag_value(&etag);
         {
@@ -418,27 +442,7 @@ pub(crate) async fn sync_whitelist_to_root(
         let _ = write_whitelist_etag_to_disk(server_root, &normalized).await;
     }
 
-    if let Ok(Some(settings)) = load_rcon_settings(&server_root.join("current")).await {
-        let rcon = RconClient::new(settings.address, settings.password);
-        let _ = rcon.execute("whitelist reload").await;
-    }
-
-    Ok(())
-}
-
-fn format_uuid_with_dashes(value: &str) -> String {
-    let compact: String = value.chars().filter(|ch| ch.is_ascii_hexdigit()).collect();
-    if compact.len() != 32 {
-        return value.to_string();
-    }
-    format!(
-        "{}-{}-{}-{}-{}",
-        &compact[0..8],
-        &compact[8..12],
-        &compact[12..16],
-        &compact[16..20],
-        &compact[20..32]
-    )
+    Ok(changes)
 }
 
 async fn apply_pack_update(
@@ -449,8 +453,11 @@ async fn apply_pack_update(
     info!("pack update detected; applying update");
 
     // Download the build first so we don't stop the running server unless the download succeeds.
+    let progress = atlas_client::progress::throttled(|progress| {
+        info!("downloading pack build: {progress}");
+    });
     let build = hub
-        .get_build_blob(&config.pack_id, &config.channel)
+        .get_build_blob_with_progress(&config.pack_id, &config.channel, None, &progress)
         .await
         .map_err(|err| format!("download build failed: {err}"))?;
 
@@ -472,7 +479,7 @@ async fn apply_pack_update(
     };
 
     // Stop the server (graceful) before applying the update
-    if let Err(err) = stop_server_internal(state.clone(), false).await {
+    if let Err(err) = stop_server_internal(state.clone(), false, stop_grace_period(None)).await {
         return Err(format!("failed to stop server: {}", err.message));
     }
 
@@ -483,7 +490,10 @@ async fn apply_pack_update(
     // Use the higher-level start_server path to apply the pack and start the server.
     let profile = {
         let guard = state.lock().await;
-        guard.profile.clone().unwrap_or_else(|| "default".into())
+        guard
+            .profile
+            .clone()
+            .unwrap_or_else(|| DEFAULT_PROFILE.into())
     };
 
     // Always attempt to backup the current server state before applying an update.
@@ -522,6 +532,7 @@ async fn apply_pack_update(
         &build.bytes,
         server_root.clone(),
         state.clone(),
+        &|_| {},
     )
     .await
     .map_err(|err| format!("failed to start server: {}", err.message))?;
@@ -537,6 +548,15 @@ async fn apply_pack_update(
     Ok(())
 }
 
+/// Hub client for paths that may download a pack build, which can take far longer
+/// than the default request timeout.
+fn artifact_hub_client(hub_url: &str) -> Result<HubClient, HubError> {
+    HubClient::builder(hub_url)
+        .timeout(ARTIFACT_DOWNLOAD_TIMEOUT)
+        .user_agent(concat!("atlas-runnerd/", env!("ATLAS_BUILD_VERSION")))
+        .build()
+}
+
 // Helper: normalize an ETag string to its bare token (strip surrounding quotes)
 fn normalize_etag_value(s: &str) -> String {
     let s = s.trim();
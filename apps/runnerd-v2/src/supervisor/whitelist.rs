@@ -0,0 +1,462 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use atlas_client::hub::{HubClient, OperatorEntry, WhitelistEntry};
+use runner_core_v2::proto::{ErrorCode, Response, RpcError};
+use runner_v2_rcon::{RconClient, load_rcon_settings};
+use serde::de::DeserializeOwned;
+
+use crate::config::{self, DeployKeyConfig};
+
+use super::state::SharedState;
+use super::updates::sync_whitelist_to_root;
+use super::util::current_server_root;
+
+const MANAGED_OPS_FILENAME: &str = ".runner/managed_ops.json";
+
+#[derive(Debug, Default)]
+pub(crate) struct PlayerListChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub written_directly: Vec<String>,
+}
+
+impl PlayerListChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+trait ListedPlayer: Clone {
+    fn uuid(&self) -> &str;
+    fn name(&self) -> &str;
+}
+
+impl ListedPlayer for WhitelistEntry {
+    fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl ListedPlayer for OperatorEntry {
+    fn uuid(&self) -> &str {
+        &self.uuid
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+struct RconCommands {
+    add: &'static str,
+    remove: &'static str,
+}
+
+const WHITELIST_COMMANDS: RconCommands = RconCommands {
+    add: "whitelist add",
+    remove: "whitelist remove",
+};
+
+const OPERATOR_COMMANDS: RconCommands = RconCommands {
+    add: "op",
+    remove: "deop",
+};
+
+pub async fn sync_whitelist_now(state: &SharedState) -> Result<Response, RpcError> {
+    let deploy = config::load_deploy_key()
+        .map_err(internal)?
+        .ok_or_else(|| RpcError {
+            code: ErrorCode::BadRequest,
+            message: "deploy key not configured".into(),
+            details: Default::default(),
+        })?;
+    let server_root = current_server_root(state).await.ok_or_else(|| RpcError {
+        code: ErrorCode::BadRequest,
+        message: "server root not configured".into(),
+        details: Default::default(),
+    })?;
+
+    let mut hub = HubClient::new(&deploy.hub_url).map_err(|err| internal(err.to_string()))?;
+    hub.set_service_token(deploy.deploy_key.clone());
+    let hub = Arc::new(hub);
+    let changes = sync_whitelist_to_root(
+        hub.clone(),
+        &deploy.pack_id,
+        &server_root,
+        state.clone(),
+        true,
+    )
+    .await
+    .map_err(internal)?;
+    let operators = sync_operators_to_root(&hub, &deploy, &server_root, state)
+        .await
+        .map_err(internal)?;
+
+    Ok(Response::WhitelistSynced {
+        added: changes.added,
+        removed: changes.removed,
+        written_directly: changes.written_directly,
+        operators_added: operators.added,
+        operators_removed: operators.removed,
+    })
+}
+
+// Applies the hub list to `whitelist.json`. While the server is running changes go
+// through RCON so they take effect immediately; players RCON cannot resolve (offline
+// mode, unknown to Mojang) are written to the file by UUID followed by a reload.
+pub(crate) async fn reconcile_whitelist(
+    server_root: &Path,
+    desired: &[WhitelistEntry],
+    running: bool,
+) -> Result<PlayerListChanges, String> {
+    let current_dir = server_root.join("current");
+    let path = current_dir.join("whitelist.json");
+    let current = read_player_list::<WhitelistEntry>(&path).await;
+    let (additions, removals) = diff_players(&current, desired);
+
+    let mut changes = changes_for(&additions, &removals);
+    if changes.is_empty() {
+        return Ok(changes);
+    }
+
+    let rcon = running_rcon(&current_dir, running).await;
+    let write_file = match &rcon {
+        Some(rcon) => {
+            apply_via_rcon(
+                rcon,
+                &WHITELIST_COMMANDS,
+                &additions,
+                &removals,
+                &mut changes,
+            )
+            .await
+        }
+        None => true,
+    };
+
+    if write_file {
+        let entries = desired
+            .iter()
+            .map(|player| {
+                serde_json::json!({
+                    "name": player.name,
+                    "uuid": format_uuid_with_dashes(&player.uuid),
+                })
+            })
+            .collect::<Vec<_>>();
+        write_json(&path, &entries).await?;
+        if let Some(rcon) = &rcon {
+            let _ = rcon.execute("whitelist reload").await;
+        }
+    }
+
+    Ok(changes)
+}
+
+pub(crate) async fn sync_operators_to_root(
+    hub: &HubClient,
+    deploy: &DeployKeyConfig,
+    server_root: &Path,
+    state: &SharedState,
+) -> Result<PlayerListChanges, String> {
+    if deploy.sync_operators != Some(true) {
+        return Ok(PlayerListChanges::default());
+    }
+    let operators = hub
+        .get_operators(&deploy.pack_id)
+        .await
+        .map_err(|err| format!("operators fetch failed: {err}"))?;
+    let running = state.lock().await.is_running();
+    let keep_local = deploy.keep_local_operators.unwrap_or(true);
+    reconcile_operators(server_root, &operators, running, keep_local).await
+}
+
+// Same flow as the whitelist, except there is no `ops reload`: RCON op/deop makes
+// membership live, while levels from the hub only reach the server through
+// `ops.json` on the next start. With `keep_local`, only operators a previous sync
+// added are deopped, so console-added operators survive.
+pub(crate) async fn reconcile_operators(
+    server_root: &Path,
+    desired: &[OperatorEntry],
+    running: bool,
+    keep_local: bool,
+) -> Result<PlayerListChanges, String> {
+    let current_dir = server_root.join("current");
+    let path = current_dir.join("ops.json");
+    let managed_path = server_root.join(MANAGED_OPS_FILENAME);
+    let current = read_player_list::<OperatorEntry>(&path).await;
+    let managed = read_player_list::<String>(&managed_path)
+        .await
+        .iter()
+        .map(|uuid| normalize_uuid(uuid))
+        .collect::<BTreeSet<_>>();
+
+    let (additions, mut removals) = diff_players(&current, desired);
+    if keep_local {
+        removals.retain(|entry| managed.contains(&normalize_uuid(&entry.uuid)));
+    }
+    let settings_changed = desired.iter().any(|wanted| {
+        current.iter().any(|existing| {
+            normalize_uuid(&existing.uuid) == normalize_uuid(&wanted.uuid)
+                && (existing.level != wanted.level
+                    || existing.bypasses_player_limit != wanted.bypasses_player_limit)
+        })
+    });
+
+    let mut changes = changes_for(&additions, &removals);
+    if !changes.is_empty() || settings_changed {
+        if let Some(rcon) = &running_rcon(&current_dir, running).await {
+            apply_via_rcon(
+                rcon,
+                &OPERATOR_COMMANDS,
+                &additions,
+                &removals,
+                &mut changes,
+            )
+            .await;
+        }
+
+        let removed_ids = removals
+            .iter()
+            .map(|entry| normalize_uuid(&entry.uuid))
+            .collect::<BTreeSet<_>>();
+        let desired_ids = desired
+            .iter()
+            .map(|entry| normalize_uuid(&entry.uuid))
+            .collect::<BTreeSet<_>>();
+        let entries = desired
+            .iter()
+            .chain(current.iter().filter(|entry| {
+                let id = normalize_uuid(&entry.uuid);
+                !desired_ids.contains(&id) && !removed_ids.contains(&id)
+            }))
+            .map(|player| {
+                serde_json::json!({
+                    "uuid": format_uuid_with_dashes(&player.uuid),
+                    "name": player.name,
+                    "level": player.level,
+                    "bypassesPlayerLimit": player.bypasses_player_limit,
+                })
+            })
+            .collect::<Vec<_>>();
+        write_json(&path, &entries).await?;
+    }
+
+    let managed_now = desired
+        .iter()
+        .map(|entry| normalize_uuid(&entry.uuid))
+        .collect::<Vec<_>>();
+    if let Some(parent) = managed_path.parent() {
+        let _ = tokio::fs::create_dir_all(parent).await;
+    }
+    write_json(&managed_path, &managed_now).await?;
+
+    Ok(changes)
+}
+
+// Returns true when some change could not be applied over RCON and the list file
+// has to be written instead.
+async fn apply_via_rcon<T: ListedPlayer>(
+    rcon: &RconClient,
+    commands: &RconCommands,
+    additions: &[T],
+    removals: &[T],
+    changes: &mut PlayerListChanges,
+) -> bool {
+    let mut write_file = false;
+    for entry in removals {
+        if rcon
+            .execute(&format!("{} {}", commands.remove, entry.name()))
+            .await
+            .is_err()
+        {
+            write_file = true;
+        }
+    }
+    for entry in additions {
+        let added = rcon
+            .execute(&format!("{} {}", commands.add, entry.name()))
+            .await
+            .is_ok_and(|reply| !reply.contains("does not exist"));
+        if !added {
+            changes.written_directly.push(entry.name().to_string());
+            write_file = true;
+        }
+    }
+    write_file
+}
+
+fn changes_for<T: ListedPlayer>(additions: &[T], removals: &[T]) -> PlayerListChanges {
+    PlayerListChanges {
+        added: additions.iter().map(|e| e.name().to_string()).collect(),
+        removed: removals.iter().map(|e| e.name().to_string()).collect(),
+        written_directly: Vec::new(),
+    }
+}
+
+fn diff_players<T: ListedPlayer>(current: &[T], desired: &[T]) -> (Vec<T>, Vec<T>) {
+    let current_ids = current
+        .iter()
+        .map(|entry| normalize_uuid(entry.uuid()))
+        .collect::<BTreeSet<_>>();
+    let desired_ids = desired
+        .iter()
+        .map(|entry| normalize_uuid(entry.uuid()))
+        .collect::<BTreeSet<_>>();
+
+    let additions = desired
+        .iter()
+        .filter(|entry| !current_ids.contains(&normalize_uuid(entry.uuid())))
+        .cloned()
+        .collect();
+    let removals = current
+        .iter()
+        .filter(|entry| !desired_ids.contains(&normalize_uuid(entry.uuid())))
+        .cloned()
+        .collect();
+    (additions, removals)
+}
+
+async fn running_rcon(current_dir: &Path, running: bool) -> Option<RconClient> {
+    if !running {
+        return None;
+    }
+    load_rcon_settings(current_dir)
+        .await
+        .ok()
+        .flatten()
+        .map(|settings| RconClient::new(settings.address, settings.password))
+}
+
+pub(crate) fn format_uuid_with_dashes(value: &str) -> String {
+    let compact = normalize_uuid(value);
+    if compact.len() != 32 {
+        return value.to_string();
+    }
+    format!(
+        "{}-{}-{}-{}-{}",
+        &compact[0..8],
+        &compact[8..12],
+        &compact[12..16],
+        &compact[16..20],
+        &compact[20..32]
+    )
+}
+
+fn normalize_uuid(value: &str) -> String {
+    value
+        .chars()
+        .filter(|ch| ch.is_ascii_hexdigit())
+        .map(|ch| ch.to_ascii_lowercase())
+        .collect()
+}
+
+async fn read_player_list<T: DeserializeOwned>(path: &Path) -> Vec<T> {
+    tokio::fs::read_to_string(path)
+        .await
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+async fn write_json<T: serde::Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(value)
+        .map_err(|err| format!("failed to serialize {}: {err}", path.display()))?;
+    tokio::fs::write(path, content)
+        .await
+        .map_err(|err| format!("failed to write {}: {err}", path.display()))
+}
+
+fn internal(message: String) -> RpcError {
+    RpcError {
+        code: ErrorCode::Internal,
+        message,
+        details: Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use atlas_client::hub::{OperatorEntry, WhitelistEntry};
+
+    use super::{diff_players, read_player_list, reconcile_operators};
+
+    fn entry(name: &str, uuid: &str) -> WhitelistEntry {
+        WhitelistEntry {
+            uuid: uuid.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    fn operator(name: &str, uuid: &str, level: u8) -> OperatorEntry {
+        OperatorEntry {
+            uuid: uuid.to_string(),
+            name: name.to_string(),
+            level,
+            bypasses_player_limit: false,
+        }
+    }
+
+    #[test]
+    fn diff_matches_players_by_uuid_regardless_of_format() {
+        let current = vec![
+            entry("Alex", "0f1e2d3c-4b5a-6978-8796-a5b4c3d2e1f0"),
+            entry("Steve", "11111111-2222-3333-4444-555555555555"),
+        ];
+        let desired = vec![
+            entry("Alex", "0F1E2D3C4B5A69788796A5B4C3D2E1F0"),
+            entry("Notch", "aaaaaaaabbbbccccddddeeeeeeeeeeee"),
+        ];
+        let (additions, removals) = diff_players(&current, &desired);
+        assert_eq!(
+            additions
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Notch"]
+        );
+        assert_eq!(
+            removals.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(),
+            vec!["Steve"]
+        );
+    }
+
+    #[tokio::test]
+    async fn operator_sync_keeps_console_operators() {
+        let root = std::env::temp_dir().join(format!("atlas-ops-sync-{}", std::process::id()));
+        let current = root.join("current");
+        std::fs::create_dir_all(&current).unwrap();
+
+        let hub_admin = operator("Admin", "aaaaaaaa-bbbb-cccc-dddd-eeeeeeeeeeee", 4);
+        let console_op = operator("Local", "11111111-2222-3333-4444-555555555555", 4);
+        let first = reconcile_operators(&root, std::slice::from_ref(&hub_admin), false, true)
+            .await
+            .unwrap();
+        assert_eq!(first.added, vec!["Admin"]);
+
+        // An operator added from the console is not known to the hub.
+        let ops_path = current.join("ops.json");
+        let mut on_disk = read_player_list::<OperatorEntry>(&ops_path).await;
+        on_disk.push(console_op);
+        std::fs::write(&ops_path, serde_json::to_string(&on_disk).unwrap()).unwrap();
+
+        let second = reconcile_operators(&root, &[], false, true).await.unwrap();
+        assert_eq!(second.removed, vec!["Admin"]);
+        let remaining = read_player_list::<OperatorEntry>(&ops_path).await;
+        assert_eq!(
+            remaining
+                .iter()
+                .map(|e| e.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Local"]
+        );
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -1,5 +1,5 @@
 import { NextResponse } from "next/server";
-import { and, eq, gt, inArray, lte } from "drizzle-orm";
+import { and, desc, eq, gt, inArray, lte } from "drizzle-orm";
 
 import { getAuthenticatedUserId } from "@/lib/auth/request-user";
 import { getAuthenticatedRunnerPackId } from "@/lib/auth/runner-tokens";
@@ -120,6 +120,21 @@ export async function GET(
 
   const requestedChannel = parseChannelName(requestUrl.searchParams.get("channel"));
   const currentBuildId = requestUrl.searchParams.get("currentBuildId")?.trim() || null;
+  const requestedVersion = requestUrl.searchParams.get("version")?.trim() || null;
+
+  // Pinned versions bypass channel assignment, so only runners and pack
+  // maintainers may request one.
+  if (requestedVersion) {
+    if (!runnerPackId && membership?.role !== "admin" && membership?.role !== "creator") {
+      return NextResponse.json({ error: "Forbidden" }, { status: 403 });
+    }
+    return pinnedVersionResponse({
+      packId,
+      version: requestedVersion,
+      channel: requestedChannel,
+      currentBuildId,
+    });
+  }
 
   let allowed: readonly ChannelName[];
   if (runnerPackId) {
@@ -220,6 +235,74 @@ export async function GET(
   );
 }
 
+async function pinnedVersionResponse({
+  packId,
+  version,
+  channel,
+  currentBuildId,
+}: {
+  packId: string;
+  version: string;
+  channel: ChannelName | null;
+  currentBuildId: string | null;
+}) {
+  const [build] = await db
+    .select({
+      id: builds.id,
+      artifactKey: builds.artifactKey,
+      minecraftVersion: builds.minecraftVersion,
+      modloader: builds.modloader,
+      modloaderVersion: builds.modloaderVersion,
+      forceReinstall: builds.forceReinstall,
+      createdAt: builds.createdAt,
+    })
+    .from(builds)
+    .where(and(eq(builds.packId, packId), eq(builds.version, version)))
+    .orderBy(desc(builds.createdAt))
+    .limit(1);
+  if (!build) {
+    return NextResponse.json(
+      { error: `Build version ${version} was not found for this pack.` },
+      { status: 404 }
+    );
+  }
+
+  const artifactRef = decodeArtifactRef(build.artifactKey);
+  const resolvedArtifactKey = normalizeArtifactKey(artifactRef.key);
+  if (!isStorageProviderEnabled(artifactRef.provider) || !resolvedArtifactKey) {
+    return NextResponse.json(
+      { error: `Build version ${version} is not downloadable.` },
+      { status: 404 }
+    );
+  }
+
+  const downloadUrl = await createDownloadUrlForArtifactRef({
+    provider: artifactRef.provider,
+    key: resolvedArtifactKey,
+  });
+  const requiresFullReinstall = await shouldRequireFullReinstall({
+    packId,
+    targetBuildId: build.id,
+    targetBuildCreatedAt: build.createdAt,
+    currentBuildId,
+  });
+
+  return NextResponse.json({
+    packId,
+    channel,
+    buildId: build.id,
+    buildVersion: version,
+    artifactKey: resolvedArtifactKey,
+    artifactProvider: artifactRef.provider,
+    downloadUrl,
+    minecraftVersion: build.minecraftVersion,
+    modloader: build.modloader,
+    modloaderVersion: build.modloaderVersion,
+    forceReinstall: build.forceReinstall,
+    requiresFullReinstall,
+  });
+}
+
 async function shouldRequireFullReinstall({
   packId,
   targetBuildId,
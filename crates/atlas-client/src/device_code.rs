@@ -1,12 +1,17 @@
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::time::Duration;
 use thiserror::Error;
 
 pub const DEFAULT_ATLAS_HUB_URL: &str = "https://atlas.nathanm.org";
 pub const DEFAULT_ATLAS_DEVICE_CLIENT_ID: &str = "atlas-launcher";
 pub const DEFAULT_ATLAS_DEVICE_SCOPE: &str = "openid profile email offline_access";
 pub const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+/// Polling interval used when the device-code response does not set one.
+pub const DEFAULT_DEVICE_POLL_INTERVAL_SECS: u64 = 5;
+/// Added to the polling interval on every `slow_down` (RFC 8628, section 3.5).
+pub const SLOW_DOWN_INCREMENT_SECS: u64 = 5;
 
 #[derive(Debug, Serialize)]
 pub struct DeviceCodeRequest<'a> {
@@ -75,22 +80,52 @@ pub enum DeviceTokenPollStatus<T> {
     Fatal(String),
 }
 
+/// Device-token polling interval. A `slow_down` raises it for every later poll
+/// of the same device code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DevicePollInterval {
+    seconds: u64,
+}
+
+impl DevicePollInterval {
+    pub fn new(interval_seconds: u64) -> Self {
+        Self {
+            seconds: if interval_seconds == 0 {
+                DEFAULT_DEVICE_POLL_INTERVAL_SECS
+            } else {
+                interval_seconds
+            },
+        }
+    }
+
+    pub fn get(&self) -> Duration {
+        Duration::from_secs(self.seconds)
+    }
+
+    pub fn slow_down(&mut self) -> Duration {
+        self.seconds = self.seconds.saturating_add(SLOW_DOWN_INCREMENT_SECS);
+        self.get()
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum DeviceCodeParseError {
     #[error("Failed to parse OAuth2 device token response: {0}")]
     Parse(#[from] serde_json::Error),
 }
 
-pub fn normalize_hub_url(value: &str) -> String {
-    value.trim().trim_end_matches('/').to_string()
-}
-
 pub fn hub_device_code_endpoint(hub_url: &str) -> String {
-    format!("{}/api/auth/device/code", normalize_hub_url(hub_url))
+    format!(
+        "{}/api/auth/device/code",
+        hub_url.trim().trim_end_matches('/')
+    )
 }
 
 pub fn hub_device_token_endpoint(hub_url: &str) -> String {
-    format!("{}/api/auth/device/token", normalize_hub_url(hub_url))
+    format!(
+        "{}/api/auth/device/token",
+        hub_url.trim().trim_end_matches('/')
+    )
 }
 
 pub fn parse_device_token_poll_json<T: DeserializeOwned>(
@@ -141,3 +176,17 @@ fn map_device_token_error<T>(err: OAuthDeviceTokenError) -> DeviceTokenPollStatu
         DeviceTokenPollStatus::Success(_) => unreachable!(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slow_down_grows_the_interval_for_later_polls() {
+        let mut interval = DevicePollInterval::new(0);
+        assert_eq!(interval.get(), Duration::from_secs(5));
+        assert_eq!(interval.slow_down(), Duration::from_secs(10));
+        assert_eq!(interval.slow_down(), Duration::from_secs(15));
+        assert_eq!(interval.get(), Duration::from_secs(15));
+    }
+}
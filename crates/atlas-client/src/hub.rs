@@ -1,9 +1,11 @@
-use anyhow::{Context, Result};
-use reqwest::{header, Client, Response};
+use anyhow::Context;
+use reqwest::{header, Client, Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::future::Future;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
+use tracing::Instrument;
 use url::Url;
 
 use crate::device_code::{
@@ -11,6 +13,22 @@ use crate::device_code::{
     DeviceCodeRequest, DeviceCodeResponse, DeviceTokenPollStatus, DeviceTokenRequest,
     StandardDeviceTokenResponse, DEFAULT_ATLAS_DEVICE_CLIENT_ID, DEFAULT_ATLAS_DEVICE_SCOPE,
 };
+use crate::hub_url::{hub_endpoint, hub_url_string, normalize_hub_url};
+
+pub use crate::hub_error::HubError;
+pub use crate::progress::{DownloadProgress, DownloadProgressFn};
+
+type Result<T, E = HubError> = std::result::Result<T, E>;
+
+const MAX_ATTEMPTS: u32 = 3;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+const MAX_LAUNCHER_PACK_PAGES: usize = 50;
+
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Total timeout for clients that download pack builds or other large artifacts.
+pub const ARTIFACT_DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+const DEFAULT_USER_AGENT: &str = concat!("atlas-client/", env!("CARGO_PKG_VERSION"));
 
 pub struct HubClient {
     client: Client,
@@ -103,6 +121,37 @@ pub struct BuildBlobResult {
     pub minecraft_version: Option<String>,
     pub modloader: Option<String>,
     pub modloader_version: Option<String>,
+    pub validators: BlobValidators,
+}
+
+/// Cache validators returned with a build blob download. Sent back as
+/// `If-None-Match` / `If-Modified-Since` so an unchanged blob is not downloaded again.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobValidators {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+}
+
+impl BlobValidators {
+    fn from_headers(headers: &reqwest::header::HeaderMap) -> Self {
+        let header = |name: reqwest::header::HeaderName| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        Self {
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -130,6 +179,17 @@ pub struct RunnerServiceTokenResponse {
     pub prefix: String,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RunnerServiceToken {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub token_prefix: String,
+    #[serde(default)]
+    pub revoked_at: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct LauncherLinkSession {
@@ -158,8 +218,20 @@ pub struct MojangInfoResponse {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct LauncherPacksResponse {
     packs: Vec<LauncherPack>,
+    #[serde(default)]
+    next_cursor: Option<String>,
+}
+
+impl LauncherPacksResponse {
+    fn next_cursor(&self) -> Option<&str> {
+        self.next_cursor
+            .as_deref()
+            .map(str::trim)
+            .filter(|cursor| !cursor.is_empty())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -212,17 +284,110 @@ pub struct PackChannel {
     pub build_commit: Option<String>,
 }
 
-impl HubClient {
-    pub fn new(base_url: &str) -> Result<Self> {
-        let base_url = Url::parse(base_url)?;
-        Ok(Self {
-            client: Client::builder().timeout(Duration::from_secs(30)).build()?,
+/// Configures the HTTP client behind a [`HubClient`].
+#[derive(Debug, Clone)]
+pub struct HubClientBuilder {
+    base_url: String,
+    connect_timeout: Duration,
+    timeout: Duration,
+    user_agent: String,
+}
+
+impl HubClientBuilder {
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Total time allowed for a request, including reading the body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Identifies the calling app, e.g. `atlas-runner/1.2.3`. The atlas-client
+    /// version is appended.
+    pub fn user_agent(mut self, product: &str) -> Self {
+        self.user_agent = format!("{product} {DEFAULT_USER_AGENT}");
+        self
+    }
+
+    pub fn build(self) -> Result<HubClient> {
+        let base_url = normalize_hub_url(&self.base_url)?;
+        let client = Client::builder()
+            .connect_timeout(self.connect_timeout)
+            .timeout(self.timeout)
+            .user_agent(self.user_agent)
+            .build()?;
+        Ok(HubClient {
+            client,
             base_url,
             auth: Mutex::new(AuthState::None),
             pack_deploy_token: Mutex::new(None),
             ci_oidc_token: Mutex::new(None),
         })
     }
+}
+
+impl HubClient {
+    pub fn new(base_url: &str) -> Result<Self> {
+        Self::builder(base_url).build()
+    }
+
+    pub fn builder(base_url: &str) -> HubClientBuilder {
+        HubClientBuilder {
+            base_url: base_url.to_string(),
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+        }
+    }
+
+    fn endpoint(&self, path: &str) -> Url {
+        hub_endpoint(&self.base_url, path)
+    }
+
+    /// Sends `request` and maps error statuses to [`HubError`]. Rate limits and
+    /// server errors are retried with exponential backoff. `304 Not Modified` is
+    /// passed through for the etag-aware callers.
+    async fn send(&self, request: RequestBuilder) -> Result<Response> {
+        let request = request.build()?;
+        let span = tracing::debug_span!(
+            "hub_request",
+            method = %request.method(),
+            path = request.url().path()
+        );
+        self.send_with_retry(request).instrument(span).await
+    }
+
+    async fn send_with_retry(&self, request: reqwest::Request) -> Result<Response> {
+        let idempotent = request.method() == Method::GET;
+        let mut delay = RETRY_BASE_DELAY;
+        let mut attempt = 1;
+        loop {
+            let retry = if attempt < MAX_ATTEMPTS {
+                request.try_clone()
+            } else {
+                None
+            };
+            let Some(current) = retry else {
+                let response = self.client.execute(request).await?;
+                tracing::debug!(attempt, status = response.status().as_u16(), "hub response");
+                return check_status(response).await;
+            };
+            let response = self.client.execute(current).await?;
+            tracing::debug!(attempt, status = response.status().as_u16(), "hub response");
+            match check_status(response).await {
+                Err(err) if err.is_retryable(idempotent) => {
+                    tracing::debug!("retrying in {delay:?}: {err}");
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    attempt += 1;
+                }
+                outcome => return outcome,
+            }
+        }
+    }
 
     pub fn set_token(&mut self, token: String) {
         let mut auth = self.auth.lock().expect("auth lock poisoned");
@@ -275,14 +440,16 @@ impl HubClient {
             AuthState::UserToken(token) => {
                 headers.insert(
                     header::AUTHORIZATION,
-                    header::HeaderValue::from_str(&format!("Bearer {}", token))?,
+                    header::HeaderValue::from_str(&format!("Bearer {}", token))
+                        .context("Invalid access token")?,
                 );
             }
             AuthState::ServiceToken(state) => {
                 let access = self.ensure_service_access_token(state).await?;
                 headers.insert(
                     header::AUTHORIZATION,
-                    header::HeaderValue::from_str(&format!("Bearer {}", access))?,
+                    header::HeaderValue::from_str(&format!("Bearer {}", access))
+                        .context("Invalid access token")?,
                 );
             }
         }
@@ -310,35 +477,27 @@ impl HubClient {
     }
 
     async fn exchange_service_token(&self, token: &str) -> Result<RunnerTokenExchange> {
-        let url = self.base_url.join("/api/v1/runner/exchange")?;
-        let response = self
-            .client
-            .post(url)
-            .header("x-atlas-service-token", token)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            let prefix_hint = if token.trim_start().starts_with("atlas_runner_") {
-                ""
-            } else {
-                " (token does not start with expected runner prefix `atlas_runner_`)"
-            };
-            anyhow::bail!(
-                "Runner service token exchange failed (HTTP {}): {}{}. \
-Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a pack/app deploy token.",
-                status.as_u16(),
-                body,
-                prefix_hint
-            );
-        }
+        let url = self.endpoint("/api/v1/runner/exchange");
+        let request = self.client.post(url).header("x-atlas-service-token", token);
+        let response = match self.send(request).await {
+            Err(HubError::Unauthorized { url, body }) => {
+                let prefix_hint = if token.trim_start().starts_with("atlas_runner_") {
+                    ""
+                } else {
+                    " (token does not start with expected runner prefix `atlas_runner_`)"
+                };
+                return Err(HubError::Unauthorized {
+                    url,
+                    body: format!(
+                        "{body}{prefix_hint}. Ensure you are using a runner service token from \
+`/api/v1/runner/tokens`, not a pack/app deploy token."
+                    ),
+                });
+            }
+            response => response?,
+        };
 
-        response
-            .json()
-            .await
-            .context("Failed to parse runner token exchange")
+        read_json(response, "runner token exchange").await
     }
 
     pub async fn validate_service_token(&self) -> Result<RunnerTokenExchange> {
@@ -347,7 +506,7 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
             AuthState::ServiceToken(state) => {
                 self.exchange_service_token(&state.service_token).await
             }
-            _ => anyhow::bail!("No service token configured"),
+            _ => Err(anyhow::anyhow!("No service token configured").into()),
         }
     }
 
@@ -356,32 +515,16 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
         pack_id: &str,
         name: Option<String>,
     ) -> Result<RunnerServiceTokenResponse> {
-        let url = self.base_url.join("/api/v1/runner/tokens")?;
-        let response = self
+        let url = self.endpoint("/api/v1/runner/tokens");
+        let request = self
             .client
             .post(url)
             .headers(self.get_auth_headers().await?)
             .json(&RunnerServiceTokenRequest {
                 pack_id: pack_id.to_string(),
                 name,
-            })
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "Runner token request failed (HTTP {}): {}",
-                status.as_u16(),
-                body
-            );
-        }
-
-        let body = response
-            .text()
-            .await
-            .context("Failed to read runner service token response")?;
+            });
+        let body = self.send(request).await?.text().await?;
 
         if std::env::var("ATLAS_DEBUG_RUNNER_TOKENS").is_ok() {
             eprintln!("Runner service token response: {body}");
@@ -439,57 +582,92 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
         })
     }
 
-    pub async fn get_pack_metadata(&self, pack_id: &str) -> Result<PackMetadata> {
-        let url = self.base_url.join(&format!("/api/v1/packs/{pack_id}"))?;
+    pub async fn list_runner_service_tokens(
+        &self,
+        pack_id: &str,
+    ) -> Result<Vec<RunnerServiceToken>> {
+        let mut url = self.endpoint("/api/v1/runner/tokens");
+        url.query_pairs_mut().append_pair("packId", pack_id);
         let response = self
-            .client
-            .get(url)
-            .headers(self.get_auth_headers().await?)
-            .send()
-            .await?
-            .error_for_status()?;
+            .send(self.client.get(url).headers(self.get_auth_headers().await?))
+            .await?;
 
-        response
-            .json()
+        #[derive(Deserialize)]
+        struct TokensResponse {
+            tokens: Vec<RunnerServiceToken>,
+        }
+
+        read_json::<TokensResponse>(response, "runner service tokens")
             .await
-            .context("Failed to parse pack metadata")
+            .map(|payload| payload.tokens)
     }
 
-    pub async fn list_launcher_packs(&self) -> Result<Vec<LauncherPack>> {
-        let url = self.base_url.join("/api/v1/launcher/packs")?;
-        let response = self
+    pub async fn revoke_runner_service_token(&self, pack_id: &str, token_id: &str) -> Result<()> {
+        let url = self.endpoint("/api/v1/runner/tokens");
+        let request = self
             .client
-            .get(url)
+            .delete(url)
             .headers(self.get_auth_headers().await?)
-            .send()
-            .await?
-            .error_for_status()?;
+            .json(&serde_json::json!({
+                "packId": pack_id,
+                "tokenId": token_id,
+            }));
+        self.send(request).await?;
+        Ok(())
+    }
 
-        response
-            .json::<LauncherPacksResponse>()
-            .await
-            .context("Failed to parse launcher packs")
-            .map(|payload| payload.packs)
+    pub async fn get_pack_metadata(&self, pack_id: &str) -> Result<PackMetadata> {
+        let url = self.endpoint(&format!("/api/v1/packs/{pack_id}"));
+        let response = self
+            .send(self.client.get(url).headers(self.get_auth_headers().await?))
+            .await?;
+
+        read_json(response, "pack metadata").await
+    }
+
+    /// Lists every pack the current user can launch, following `nextCursor`
+    /// until the hub reports no more pages.
+    pub async fn list_launcher_packs(&self) -> Result<Vec<LauncherPack>> {
+        let mut packs = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_LAUNCHER_PACK_PAGES {
+            let mut url = self.endpoint("/api/v1/launcher/packs");
+            if let Some(cursor) = cursor.as_deref() {
+                url.query_pairs_mut().append_pair("cursor", cursor);
+            }
+            let response = self
+                .send(self.client.get(url).headers(self.get_auth_headers().await?))
+                .await?;
+            let page: LauncherPacksResponse = read_json(response, "launcher packs").await?;
+            let next = page.next_cursor().map(str::to_string);
+            packs.extend(page.packs);
+
+            match next {
+                Some(next) if cursor.as_deref() != Some(next.as_str()) => cursor = Some(next),
+                _ => return Ok(packs),
+            }
+        }
+
+        tracing::warn!(
+            pages = MAX_LAUNCHER_PACK_PAGES,
+            loaded = packs.len(),
+            "stopped listing launcher packs at the page limit; more may exist"
+        );
+        Ok(packs)
     }
 
     pub async fn check_creator_permission(&self, pack_id: &str) -> Result<bool> {
-        let url = self
-            .base_url
-            .join(&format!("/api/v1/packs/{pack_id}/access"))?;
+        let url = self.endpoint(&format!("/api/v1/packs/{pack_id}/access"));
         let response = self
-            .client
-            .get(url)
-            .headers(self.get_auth_headers().await?)
-            .send()
-            .await?
-            .error_for_status()?;
+            .send(self.client.get(url).headers(self.get_auth_headers().await?))
+            .await?;
 
         #[derive(Deserialize)]
         struct AccessResponse {
             allowed: bool,
         }
 
-        let access: AccessResponse = response.json().await?;
+        let access: AccessResponse = read_json(response, "pack access response").await?;
         Ok(access.allowed)
     }
 
@@ -499,128 +677,223 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
         channel: &str,
         current_build_id: Option<&str>,
     ) -> Result<LauncherArtifactResponse> {
-        let mut url = self
-            .base_url
-            .join(&format!("/api/v1/launcher/packs/{pack_id}/artifact"))?;
+        self.get_launcher_artifact_for_version(pack_id, channel, None, current_build_id)
+            .await
+    }
+
+    /// Like [`Self::get_launcher_artifact`], but resolves the build with `version`
+    /// instead of the channel's current build when one is given.
+    pub async fn get_launcher_artifact_for_version(
+        &self,
+        pack_id: &str,
+        channel: &str,
+        version: Option<&str>,
+        current_build_id: Option<&str>,
+    ) -> Result<LauncherArtifactResponse> {
+        let mut url = self.endpoint(&format!("/api/v1/launcher/packs/{pack_id}/artifact"));
         url.query_pairs_mut().append_pair("channel", channel);
+        if let Some(value) = version {
+            url.query_pairs_mut().append_pair("version", value);
+        }
         if let Some(value) = current_build_id {
             url.query_pairs_mut().append_pair("currentBuildId", value);
         }
 
         let response = self
-            .client
-            .get(url)
-            .headers(self.get_auth_headers().await?)
-            .send()
-            .await?
-            .error_for_status()?;
+            .send(self.client.get(url).headers(self.get_auth_headers().await?))
+            .await?;
 
-        response
-            .json()
-            .await
-            .context("Failed to parse launcher artifact response")
+        read_json(response, "launcher artifact response").await
+    }
+
+    /// Whether the current credentials can download a build from `channel`. The
+    /// artifact endpoint falls back to another channel instead of failing, so the
+    /// channel it answers with is compared as well.
+    pub async fn has_channel_access(&self, pack_id: &str, channel: &str) -> Result<bool> {
+        let mut url = self.endpoint(&format!("/api/v1/launcher/packs/{pack_id}/artifact"));
+        url.query_pairs_mut().append_pair("channel", channel);
+
+        let request = self.client.get(url).headers(self.get_auth_headers().await?);
+        let response = match self.send(request).await {
+            Err(HubError::Forbidden { .. } | HubError::NotFound { .. }) => return Ok(false),
+            response => response?,
+        };
+
+        let artifact: LauncherArtifactResponse =
+            read_json(response, "launcher artifact response").await?;
+        Ok(artifact.channel.as_deref() == Some(channel))
     }
 
     pub async fn download_blob(&self, download_url: &str) -> Result<Vec<u8>> {
-        let response = self
-            .client
-            .get(download_url)
-            .send()
-            .await?
-            .error_for_status()?;
+        let response = self.send(self.client.get(download_url)).await?;
         Ok(response.bytes().await?.to_vec())
     }
 
+    /// Downloads a blob unless it still matches `cached`, reporting progress as
+    /// chunks arrive. Returns `None` on `304 Not Modified`.
+    async fn download_blob_if_modified(
+        &self,
+        download_url: &str,
+        cached: Option<&BlobValidators>,
+        progress: &DownloadProgressFn,
+    ) -> Result<Option<(Vec<u8>, BlobValidators)>> {
+        let mut request = self.client.get(download_url);
+        if let Some(cached) = cached {
+            if let Some(etag) = cached.etag.as_deref() {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = cached.last_modified.as_deref() {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let mut response = self.send(request).await?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+
+        let validators = BlobValidators::from_headers(response.headers());
+        let total = response.content_length();
+        let mut bytes = Vec::new();
+        progress(DownloadProgress {
+            downloaded: 0,
+            total,
+        });
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            progress(DownloadProgress {
+                downloaded: bytes.len() as u64,
+                total,
+            });
+        }
+        Ok(Some((bytes, validators)))
+    }
+
     pub async fn get_latest_distribution_release(
         &self,
         product: &str,
         os: &str,
         arch: &str,
     ) -> Result<DistributionReleaseResponse> {
-        let url = self
-            .base_url
-            .join(&format!("/api/v1/releases/{product}/latest/{os}/{arch}"))?;
+        let url = self.endpoint(&format!("/api/v1/releases/{product}/latest/{os}/{arch}"));
         let response = self
-            .client
-            .get(url)
-            .headers(self.get_auth_headers().await?)
-            .send()
-            .await?
-            .error_for_status()?;
+            .send(self.client.get(url).headers(self.get_auth_headers().await?))
+            .await?;
 
-        response
-            .json::<DistributionReleaseResponse>()
-            .await
-            .context("Failed to parse distribution release response")
+        read_json::<DistributionReleaseResponse>(response, "distribution release response").await
     }
 
     pub async fn download_distribution_asset(&self, download_id: &str) -> Result<Vec<u8>> {
-        let url = self
-            .base_url
-            .join(&format!("/api/v1/download/{download_id}"))?;
-        let response = self.client.get(url).send().await?.error_for_status()?;
+        let url = self.endpoint(&format!("/api/v1/download/{download_id}"));
+        let response = self.send(self.client.get(url)).await?;
         Ok(response.bytes().await?.to_vec())
     }
 
     pub async fn get_build_blob(&self, pack_id: &str, channel: &str) -> Result<BuildBlobResult> {
-        let artifact = self.get_launcher_artifact(pack_id, channel, None).await?;
-        let bytes = self.download_blob(&artifact.download_url).await?;
+        self.get_build_blob_for_version(pack_id, channel, None)
+            .await
+    }
+
+    pub async fn get_build_blob_for_version(
+        &self,
+        pack_id: &str,
+        channel: &str,
+        version: Option<&str>,
+    ) -> Result<BuildBlobResult> {
+        self.get_build_blob_with_progress(pack_id, channel, version, &|_| {})
+            .await
+    }
+
+    /// Like [`Self::get_build_blob_for_version`], calling `progress` as the blob
+    /// downloads.
+    pub async fn get_build_blob_with_progress(
+        &self,
+        pack_id: &str,
+        channel: &str,
+        version: Option<&str>,
+        progress: &DownloadProgressFn,
+    ) -> Result<BuildBlobResult> {
+        let artifact = self
+            .get_launcher_artifact_for_version(pack_id, channel, version, None)
+            .await?;
+        self.download_build_blob(artifact, None, progress)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("build blob download returned 304 unexpectedly").into())
+    }
 
-        Ok(BuildBlobResult {
+    /// Like [`Self::get_build_blob_with_progress`], but returns `None` when the blob
+    /// still matches `cached`. Builds flagged for a (full) reinstall are always downloaded.
+    pub async fn get_build_blob_if_modified(
+        &self,
+        pack_id: &str,
+        channel: &str,
+        version: Option<&str>,
+        cached: Option<&BlobValidators>,
+        progress: &DownloadProgressFn,
+    ) -> Result<Option<BuildBlobResult>> {
+        let artifact = self
+            .get_launcher_artifact_for_version(pack_id, channel, version, None)
+            .await?;
+        self.download_build_blob(artifact, cached, progress).await
+    }
+
+    async fn download_build_blob(
+        &self,
+        artifact: LauncherArtifactResponse,
+        cached: Option<&BlobValidators>,
+        progress: &DownloadProgressFn,
+    ) -> Result<Option<BuildBlobResult>> {
+        let force_reinstall = artifact.force_reinstall.unwrap_or(false);
+        let requires_full_reinstall = artifact.requires_full_reinstall.unwrap_or(false);
+        let cached = cached.filter(|_| !force_reinstall && !requires_full_reinstall);
+
+        let Some((bytes, validators)) = self
+            .download_blob_if_modified(&artifact.download_url, cached, progress)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(Some(BuildBlobResult {
             bytes,
-            force_reinstall: artifact.force_reinstall.unwrap_or(false),
-            requires_full_reinstall: artifact.requires_full_reinstall.unwrap_or(false),
+            force_reinstall,
+            requires_full_reinstall,
             minecraft_version: artifact.minecraft_version,
             modloader: artifact.modloader,
             modloader_version: artifact.modloader_version,
-        })
+            validators,
+        }))
     }
 
     pub async fn list_pack_builds(&self, pack_id: &str) -> Result<Vec<PackBuild>> {
-        let url = self
-            .base_url
-            .join(&format!("/api/v1/packs/{pack_id}/builds"))?;
+        let url = self.endpoint(&format!("/api/v1/packs/{pack_id}/builds"));
         let response = self
-            .client
-            .get(url)
-            .headers(self.get_auth_headers().await?)
-            .send()
-            .await?
-            .error_for_status()?;
+            .send(self.client.get(url).headers(self.get_auth_headers().await?))
+            .await?;
 
         #[derive(Debug, Deserialize)]
         struct BuildsResponse {
             builds: Vec<PackBuild>,
         }
 
-        response
-            .json::<BuildsResponse>()
+        read_json::<BuildsResponse>(response, "pack builds response")
             .await
-            .context("Failed to parse pack builds response")
             .map(|payload| payload.builds)
     }
 
     pub async fn list_pack_channels(&self, pack_id: &str) -> Result<Vec<PackChannel>> {
-        let url = self
-            .base_url
-            .join(&format!("/api/v1/packs/{pack_id}/channels"))?;
+        let url = self.endpoint(&format!("/api/v1/packs/{pack_id}/channels"));
         let response = self
-            .client
-            .get(url)
-            .headers(self.get_auth_headers().await?)
-            .send()
-            .await?
-            .error_for_status()?;
+            .send(self.client.get(url).headers(self.get_auth_headers().await?))
+            .await?;
 
         #[derive(Debug, Deserialize)]
         struct ChannelsResponse {
             channels: Vec<PackChannel>,
         }
 
-        response
-            .json::<ChannelsResponse>()
+        read_json::<ChannelsResponse>(response, "pack channels response")
             .await
-            .context("Failed to parse pack channels response")
             .map(|payload| payload.channels)
     }
 
@@ -630,28 +903,16 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
         channel: &str,
         build_id: &str,
     ) -> Result<()> {
-        let url = self
-            .base_url
-            .join(&format!("/api/v1/packs/{pack_id}/channels"))?;
-        let response = self
+        let url = self.endpoint(&format!("/api/v1/packs/{pack_id}/channels"));
+        let request = self
             .client
             .post(url)
             .headers(self.get_auth_headers().await?)
             .json(&serde_json::json!({
                 "channel": channel,
                 "buildId": build_id,
-            }))
-            .send()
-            .await?;
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "Channel promotion failed (HTTP {}): {}",
-                status.as_u16(),
-                body
-            );
-        }
+            }));
+        self.send(request).await?;
         Ok(())
     }
 
@@ -660,15 +921,21 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
         Ok(whitelist)
     }
 
+    pub async fn get_operators(&self, pack_id: &str) -> Result<Vec<OperatorEntry>> {
+        let url = self.endpoint(&format!("/api/v1/runner/packs/{pack_id}/operators"));
+        let response = self
+            .send(self.client.get(url).headers(self.get_auth_headers().await?))
+            .await?;
+        read_json(response, "operators response").await
+    }
+
     pub async fn get_pack_metadata_with_etag(
         &self,
         pack_id: &str,
         channel: &str,
         etag: Option<&str>,
     ) -> Result<(Option<PackMetadataResponse>, String)> {
-        let mut url = self
-            .base_url
-            .join(&format!("/api/v1/runner/packs/{pack_id}/metadata"))?;
+        let mut url = self.endpoint(&format!("/api/v1/runner/packs/{pack_id}/metadata"));
         url.query_pairs_mut().append_pair("channel", channel);
 
         let mut request = self.client.get(url).headers(self.get_auth_headers().await?);
@@ -677,9 +944,9 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
             request = request.header(reqwest::header::IF_NONE_MATCH, etag);
         }
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
 
-        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        if response.status() == StatusCode::NOT_MODIFIED {
             // Prefer header if present; otherwise reuse the caller-provided etag.
             let returned = response
                 .headers()
@@ -696,16 +963,6 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
             return Ok((None, effective));
         }
 
-        let status = response.status();
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "Pack metadata request failed (HTTP {}): {}",
-                status.as_u16(),
-                body
-            );
-        }
-
         let etag = response
             .headers()
             .get(reqwest::header::ETAG)
@@ -713,7 +970,7 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
             .ok_or_else(|| anyhow::anyhow!("metadata response missing ETag header"))?
             .to_string();
 
-        let metadata: PackMetadataResponse = response.json().await?;
+        let metadata: PackMetadataResponse = read_json(response, "pack metadata").await?;
         Ok((Some(metadata), etag))
     }
 
@@ -722,18 +979,16 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
         pack_id: &str,
         etag: Option<&str>,
     ) -> Result<(Vec<WhitelistEntry>, String)> {
-        let url = self
-            .base_url
-            .join(&format!("/api/v1/runner/packs/{pack_id}/whitelist"))?;
+        let url = self.endpoint(&format!("/api/v1/runner/packs/{pack_id}/whitelist"));
         let mut request = self.client.get(url).headers(self.get_auth_headers().await?);
 
         if let Some(etag) = etag {
             request = request.header("if-none-match", etag);
         }
 
-        let response = request.send().await?;
+        let response = self.send(request).await?;
 
-        if response.status() == 304 {
+        if response.status() == StatusCode::NOT_MODIFIED {
             // Not modified, return empty vec with the same etag
             let etag = response
                 .headers()
@@ -743,16 +998,6 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
             return Ok((Vec::new(), etag.to_string()));
         }
 
-        let status = response.status();
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "Whitelist request failed (HTTP {}): {}",
-                status.as_u16(),
-                body
-            );
-        }
-
         let etag = response
             .headers()
             .get("etag")
@@ -760,48 +1005,36 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
             .unwrap_or("")
             .to_string();
 
-        let whitelist: Vec<WhitelistEntry> = response.json().await?;
+        let whitelist: Vec<WhitelistEntry> = read_json(response, "whitelist").await?;
         Ok((whitelist, etag))
     }
 
     pub async fn open_pack_update_events(&self, pack_id: &str) -> Result<Response> {
-        let url = self
-            .base_url
-            .join(&format!("/api/v1/packs/{pack_id}/updates/stream"))?;
+        let url = self.endpoint(&format!("/api/v1/packs/{pack_id}/updates/stream"));
         let response = self
-            .client
-            .get(url)
-            .headers(self.get_auth_headers().await?)
-            .send()
-            .await?
-            .error_for_status()?;
+            .send(self.client.get(url).headers(self.get_auth_headers().await?))
+            .await?;
 
         Ok(response)
     }
 
     pub async fn login(&self) -> Result<DeviceCodeResponse> {
-        let url = hub_device_code_endpoint(self.base_url.as_str());
+        let url = hub_device_code_endpoint(&hub_url_string(&self.base_url));
         let request = DeviceCodeRequest {
             client_id: DEFAULT_ATLAS_DEVICE_CLIENT_ID,
             scope: DEFAULT_ATLAS_DEVICE_SCOPE,
         };
 
-        let response = self
-            .client
-            .post(url)
-            .json(&request)
-            .send()
-            .await?
-            .error_for_status()?;
+        let response = self.send(self.client.post(url).json(&request)).await?;
 
-        Ok(response.json().await?)
+        read_json(response, "device code response").await
     }
 
     pub async fn poll_token_response(
         &mut self,
         device_code: &str,
     ) -> Result<Option<StandardDeviceTokenResponse>> {
-        let url = hub_device_token_endpoint(self.base_url.as_str());
+        let url = hub_device_token_endpoint(&hub_url_string(&self.base_url));
         let request = DeviceTokenRequest::new(DEFAULT_ATLAS_DEVICE_CLIENT_ID, device_code);
 
         let response = self.client.post(url).json(&request).send().await?;
@@ -809,7 +1042,7 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
         let body = response.text().await?;
 
         let poll_status: DeviceTokenPollStatus<StandardDeviceTokenResponse> =
-            parse_device_token_poll_body(status, &body)?;
+            parse_device_token_poll_body(status, &body).map_err(anyhow::Error::from)?;
 
         match poll_status {
             DeviceTokenPollStatus::Success(token) => {
@@ -819,9 +1052,13 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
             DeviceTokenPollStatus::AuthorizationPending | DeviceTokenPollStatus::SlowDown => {
                 Ok(None)
             }
-            DeviceTokenPollStatus::ExpiredToken => anyhow::bail!("Device code expired"),
-            DeviceTokenPollStatus::AccessDenied => anyhow::bail!("Access denied"),
-            DeviceTokenPollStatus::Fatal(err) => anyhow::bail!("Authentication failed: {}", err),
+            DeviceTokenPollStatus::ExpiredToken => {
+                Err(anyhow::anyhow!("Device code expired").into())
+            }
+            DeviceTokenPollStatus::AccessDenied => Err(anyhow::anyhow!("Access denied").into()),
+            DeviceTokenPollStatus::Fatal(err) => {
+                Err(anyhow::anyhow!("Authentication failed: {}", err).into())
+            }
         }
     }
 
@@ -833,53 +1070,33 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
     }
 
     pub async fn create_launcher_link_session(&self) -> Result<LauncherLinkSession> {
-        let url = self.base_url.join("/api/v1/launcher/link-sessions")?;
+        let url = self.endpoint("/api/v1/launcher/link-sessions");
         let response = self
-            .client
-            .post(url)
-            .json(&serde_json::json!({}))
-            .send()
-            .await?
-            .error_for_status()?;
-        response
-            .json::<LauncherLinkSession>()
-            .await
-            .context("Failed to parse launcher link session")
+            .send(self.client.post(url).json(&serde_json::json!({})))
+            .await?;
+        read_json::<LauncherLinkSession>(response, "launcher link session").await
     }
 
     pub async fn complete_launcher_link_session(
         &self,
         payload: &LauncherLinkCompleteRequest,
     ) -> Result<LauncherLinkComplete> {
-        let url = self
-            .base_url
-            .join("/api/v1/launcher/link-sessions/complete")?;
-        let response = self
-            .client
-            .post(url)
-            .json(payload)
-            .send()
-            .await?
-            .error_for_status()?;
-        response
-            .json::<LauncherLinkComplete>()
-            .await
-            .context("Failed to parse launcher link completion")
+        let url = self.endpoint("/api/v1/launcher/link-sessions/complete");
+        let response = match self.send(self.client.post(url).json(payload)).await {
+            Err(err) if err.status() == Some(StatusCode::CONFLICT.as_u16()) => {
+                return Err(anyhow::anyhow!("Link session not claimed").into());
+            }
+            response => response?,
+        };
+        read_json::<LauncherLinkComplete>(response, "launcher link completion").await
     }
 
     pub async fn get_mojang_info(&self, access_token: &str) -> Result<MojangInfoResponse> {
-        let url = self.base_url.join("/api/v1/user/mojang/info")?;
+        let url = self.endpoint("/api/v1/user/mojang/info");
         let response = self
-            .client
-            .get(url)
-            .bearer_auth(access_token)
-            .send()
-            .await?
-            .error_for_status()?;
-        response
-            .json::<MojangInfoResponse>()
-            .await
-            .context("Failed to parse Mojang info")
+            .send(self.client.get(url).bearer_auth(access_token))
+            .await?;
+        read_json::<MojangInfoResponse>(response, "Mojang info").await
     }
 
     /// Downloads the CI workflow template from the Atlas Hub.
@@ -891,14 +1108,10 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
     ///
     /// Returns a `CiWorkflowResponse` containing the workflow file content and path.
     pub async fn download_ci_workflow(&self) -> Result<CiWorkflowResponse> {
-        let url = self.base_url.join("/download/ci/workflow")?;
+        let url = self.endpoint("/download/ci/workflow");
         let response = self
-            .client
-            .get(url)
-            .headers(self.get_auth_headers().await?)
-            .send()
-            .await?
-            .error_for_status()?;
+            .send(self.client.get(url).headers(self.get_auth_headers().await?))
+            .await?;
 
         let workflow_path = response
             .headers()
@@ -908,10 +1121,7 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
             .unwrap_or(".github/workflows/atlas-build.yml")
             .to_string();
 
-        let content = response
-            .text()
-            .await
-            .context("Failed to read CI workflow download response")?;
+        let content = response.text().await?;
 
         Ok(CiWorkflowResponse {
             workflow_path,
@@ -930,28 +1140,14 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
     /// Returns `Some(String)` containing the GitHub access token if the user has linked
     /// their GitHub account, or `None` if no GitHub account is linked.
     pub async fn get_github_token(&self) -> Result<Option<String>> {
-        let url = self.base_url.join("/api/v1/launcher/github/token")?;
-        let response = self
-            .client
-            .get(url)
-            .headers(self.get_auth_headers().await?)
-            .send()
-            .await?;
-
-        let status = response.status().as_u16();
-        if status == 404 || status == 409 {
-            return Ok(None);
-        }
-        if !(200..300).contains(&status) {
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "Failed to fetch linked GitHub credentials (HTTP {}): {}",
-                status,
-                body
-            );
-        }
+        let url = self.endpoint("/api/v1/launcher/github/token");
+        let request = self.client.get(url).headers(self.get_auth_headers().await?);
+        let response = match self.send(request).await {
+            Err(err) if matches!(err.status(), Some(404 | 409)) => return Ok(None),
+            response => response?,
+        };
 
-        let payload: GithubTokenResponse = response.json().await?;
+        let payload: GithubTokenResponse = read_json(response, "GitHub token response").await?;
         Ok(Some(payload.access_token))
     }
 
@@ -969,7 +1165,7 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
     ///
     /// Returns a `CiPresignResponse` containing the build ID, artifact key, and upload URL.
     pub async fn presign_ci_upload(&self, pack_id: &str) -> Result<CiPresignResponse> {
-        let url = self.base_url.join("/api/v1/ci/presign")?;
+        let url = self.endpoint("/api/v1/ci/presign");
         let mut request = self
             .client
             .post(url)
@@ -983,12 +1179,9 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
         if let Some(token) = self.get_ci_oidc_token() {
             request = request.header("x-atlas-oidc-token", token);
         }
-        let response = request.send().await?.error_for_status()?;
+        let response = self.send(request).await?;
 
-        response
-            .json::<CiPresignResponse>()
-            .await
-            .context("Failed to parse CI presign response")
+        read_json::<CiPresignResponse>(response, "CI presign response").await
     }
 
     /// Completes a CI build and updates pack channels.
@@ -1005,7 +1198,7 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
     ///
     /// Returns `Ok(())` on successful completion.
     pub async fn complete_ci_build(&self, request: &CiCompleteRequest) -> Result<()> {
-        let url = self.base_url.join("/api/v1/ci/complete")?;
+        let url = self.endpoint("/api/v1/ci/complete");
         let mut req = self
             .client
             .post(url)
@@ -1017,7 +1210,7 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
         if let Some(token) = self.get_ci_oidc_token() {
             req = req.header("x-atlas-oidc-token", token);
         }
-        req.send().await?.error_for_status()?;
+        self.send(req).await?;
         Ok(())
     }
 
@@ -1029,7 +1222,8 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
                 }
                 tokio::runtime::RuntimeFlavor::CurrentThread => Err(anyhow::anyhow!(
                     "HubClient blocking_* methods cannot run inside a current-thread Tokio runtime."
-                )),
+                )
+                .into()),
                 _ => tokio::task::block_in_place(|| handle.block_on(future)),
             };
         }
@@ -1112,6 +1306,10 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
         self.block_on_hub_future(self.list_launcher_packs())
     }
 
+    pub fn blocking_get_build_blob(&self, pack_id: &str, channel: &str) -> Result<BuildBlobResult> {
+        self.block_on_hub_future(self.get_build_blob(pack_id, channel))
+    }
+
     pub fn blocking_list_pack_builds(&self, pack_id: &str) -> Result<Vec<PackBuild>> {
         self.block_on_hub_future(self.list_pack_builds(pack_id))
     }
@@ -1130,6 +1328,25 @@ Ensure you are using a runner service token from `/api/v1/runner/tokens`, not a
     }
 }
 
+async fn check_status(response: Response) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() || status == StatusCode::NOT_MODIFIED {
+        return Ok(response);
+    }
+    // Presigned download URLs carry credentials in the query string.
+    let mut url = response.url().clone();
+    url.set_query(None);
+    let body = response.text().await.unwrap_or_default();
+    Err(HubError::from_status(status, url.to_string(), body))
+}
+
+async fn read_json<T: DeserializeOwned>(response: Response, what: &'static str) -> Result<T> {
+    response
+        .json()
+        .await
+        .map_err(|source| HubError::Decode { what, source })
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct LauncherLinkCompleteRequest {
@@ -1151,6 +1368,22 @@ pub struct WhitelistEntry {
     pub name: String,
 }
 
+/// A pack operator as returned by the hub; also matches the `ops.json` entry shape.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OperatorEntry {
+    pub uuid: String,
+    pub name: String,
+    #[serde(default = "default_operator_level")]
+    pub level: u8,
+    #[serde(default)]
+    pub bypasses_player_limit: bool,
+}
+
+fn default_operator_level() -> u8 {
+    4
+}
+
 /// Response from downloading a CI workflow template.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CiWorkflowResponse {
@@ -1223,3 +1456,24 @@ pub struct CiCompleteRequest {
     /// Channel to update with this build ("dev", "beta", or "production")
     pub channel: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn treats_missing_or_blank_next_cursor_as_last_page() {
+        let page = |json: &str| serde_json::from_str::<LauncherPacksResponse>(json).unwrap();
+
+        assert_eq!(page(r#"{"packs":[]}"#).next_cursor(), None);
+        assert_eq!(
+            page(r#"{"packs":[],"nextCursor":null}"#).next_cursor(),
+            None
+        );
+        assert_eq!(page(r#"{"packs":[],"nextCursor":" "}"#).next_cursor(), None);
+        assert_eq!(
+            page(r#"{"packs":[],"nextCursor":"abc"}"#).next_cursor(),
+            Some("abc")
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use reqwest::StatusCode;
+use thiserror::Error;
+
+use crate::hub_url::HubUrlError;
+
+#[derive(Debug, Error)]
+pub enum HubError {
+    #[error("Atlas Hub rejected the credentials for {url} (HTTP 401){}", body_suffix(.body))]
+    Unauthorized { url: String, body: String },
+    #[error("Atlas Hub denied access to {url} (HTTP 403){}", body_suffix(.body))]
+    Forbidden { url: String, body: String },
+    #[error("Atlas Hub has no resource at {url} (HTTP 404){}", body_suffix(.body))]
+    NotFound { url: String, body: String },
+    #[error("Atlas Hub rate limited {url} (HTTP 429){}", body_suffix(.body))]
+    RateLimited { url: String, body: String },
+    #[error("Atlas Hub failed to handle {url} (HTTP {status}){}", body_suffix(.body))]
+    Server {
+        status: u16,
+        url: String,
+        body: String,
+    },
+    #[error("Atlas Hub request to {url} failed (HTTP {status}){}", body_suffix(.body))]
+    Status {
+        status: u16,
+        url: String,
+        body: String,
+    },
+    #[error("Could not reach Atlas Hub: {0}")]
+    Network(#[source] reqwest::Error),
+    #[error("Failed to parse {what}: {source}")]
+    Decode {
+        what: &'static str,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error(transparent)]
+    InvalidUrl(#[from] HubUrlError),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl HubError {
+    pub fn from_status(status: StatusCode, url: String, body: String) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED => Self::Unauthorized { url, body },
+            StatusCode::FORBIDDEN => Self::Forbidden { url, body },
+            StatusCode::NOT_FOUND => Self::NotFound { url, body },
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited { url, body },
+            status if status.is_server_error() => Self::Server {
+                status: status.as_u16(),
+                url,
+                body,
+            },
+            status => Self::Status {
+                status: status.as_u16(),
+                url,
+                body,
+            },
+        }
+    }
+
+    /// HTTP status the hub answered with, if it answered at all.
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Self::Unauthorized { .. } => Some(401),
+            Self::Forbidden { .. } => Some(403),
+            Self::NotFound { .. } => Some(404),
+            Self::RateLimited { .. } => Some(429),
+            Self::Server { status, .. } | Self::Status { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// The credentials are missing, expired or revoked; signing in again may help.
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, Self::Unauthorized { .. })
+    }
+
+    /// The hub could not be reached or was temporarily unavailable, as opposed
+    /// to rejecting the request.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Network(err) => err.is_timeout() || err.is_connect() || err.is_request(),
+            Self::RateLimited { .. } | Self::Server { .. } => true,
+            Self::Status { status, .. } => *status == StatusCode::REQUEST_TIMEOUT.as_u16(),
+            _ => false,
+        }
+    }
+
+    /// Worth sending again after a delay. Requests that may have changed state
+    /// on the hub are only retried when the hub says it did not process them.
+    pub(crate) fn is_retryable(&self, idempotent: bool) -> bool {
+        match self {
+            Self::RateLimited { .. } => true,
+            Self::Server { status, .. } => idempotent || matches!(status, 502..=504),
+            _ => false,
+        }
+    }
+}
+
+impl From<reqwest::Error> for HubError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Network(err)
+    }
+}
+
+fn body_suffix(body: &str) -> String {
+    let body = body.trim();
+    if body.is_empty() {
+        String::new()
+    } else {
+        format!(": {body}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_statuses() {
+        let err = |status: u16| {
+            HubError::from_status(
+                StatusCode::from_u16(status).unwrap(),
+                "https://hub/api".to_string(),
+                String::new(),
+            )
+        };
+
+        assert!(err(401).is_unauthorized());
+        assert!(matches!(err(404), HubError::NotFound { .. }));
+        assert!(err(429).is_transient() && err(429).is_retryable(false));
+        assert!(err(500).is_retryable(true) && !err(500).is_retryable(false));
+        assert!(err(503).is_retryable(false));
+        assert!(!err(400).is_transient());
+        assert_eq!(err(409).status(), Some(409));
+        assert_eq!(
+            err(401).to_string(),
+            "Atlas Hub rejected the credentials for https://hub/api (HTTP 401)"
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use thiserror::Error;
+use url::Url;
+
+#[derive(Debug, Error)]
+pub enum HubUrlError {
+    #[error("Hub URL is empty")]
+    Empty,
+    #[error("Hub URL '{0}' is missing http:// or https://")]
+    MissingScheme(String),
+    #[error("Hub URL '{url}' is invalid: {source}")]
+    Invalid {
+        url: String,
+        #[source]
+        source: url::ParseError,
+    },
+    #[error("Hub URL '{0}' must use http or https")]
+    UnsupportedScheme(String),
+    #[error("Hub URL '{0}' must not include a query or fragment")]
+    QueryOrFragment(String),
+}
+
+/// Parses a user-supplied hub URL. Trailing slashes are stripped so the hub
+/// path (if any) is kept when endpoints are appended with [`hub_endpoint`].
+pub fn normalize_hub_url(value: &str) -> Result<Url, HubUrlError> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(HubUrlError::Empty);
+    }
+
+    let mut url = Url::parse(trimmed).map_err(|source| match source {
+        url::ParseError::RelativeUrlWithoutBase => HubUrlError::MissingScheme(trimmed.to_string()),
+        source => HubUrlError::Invalid {
+            url: trimmed.to_string(),
+            source,
+        },
+    })?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(HubUrlError::UnsupportedScheme(trimmed.to_string()));
+    }
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(HubUrlError::QueryOrFragment(trimmed.to_string()));
+    }
+
+    let path = url.path().trim_end_matches('/').to_string();
+    url.set_path(&path);
+    Ok(url)
+}
+
+/// Canonical string form of a normalized hub URL, without a trailing slash.
+/// Used for storage keys, config files and messages.
+pub fn hub_url_string(url: &Url) -> String {
+    url.as_str().trim_end_matches('/').to_string()
+}
+
+/// [`normalize_hub_url`] followed by [`hub_url_string`].
+pub fn normalize_hub_url_string(value: &str) -> Result<String, HubUrlError> {
+    normalize_hub_url(value).map(|url| hub_url_string(&url))
+}
+
+/// Appends an API path to the hub URL. Unlike `Url::join` with an absolute
+/// path, this keeps any path the hub is mounted under.
+pub fn hub_endpoint(hub_url: &Url, path: &str) -> Url {
+    let mut url = hub_url.clone();
+    url.set_path(&format!(
+        "{}/{}",
+        hub_url.path().trim_end_matches('/'),
+        path.trim_start_matches('/')
+    ));
+    url
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_trailing_slashes() {
+        let url = normalize_hub_url(" http://h/ ").unwrap();
+        assert_eq!(hub_url_string(&url), "http://h");
+        assert_eq!(
+            hub_endpoint(&url, "/api/v1/packs").as_str(),
+            "http://h/api/v1/packs"
+        );
+    }
+
+    #[test]
+    fn rejects_missing_or_unsupported_scheme() {
+        assert!(matches!(
+            normalize_hub_url("h"),
+            Err(HubUrlError::MissingScheme(_))
+        ));
+        assert!(matches!(
+            normalize_hub_url("localhost:3000"),
+            Err(HubUrlError::UnsupportedScheme(_))
+        ));
+        assert!(matches!(normalize_hub_url("  "), Err(HubUrlError::Empty)));
+    }
+
+    #[test]
+    fn keeps_hub_path_when_building_endpoints() {
+        let url = normalize_hub_url("https://example.com/atlas//").unwrap();
+        assert_eq!(hub_url_string(&url), "https://example.com/atlas");
+        assert_eq!(
+            hub_endpoint(&url, "/api/v1/runner/exchange").as_str(),
+            "https://example.com/atlas/api/v1/runner/exchange"
+        );
+    }
+}
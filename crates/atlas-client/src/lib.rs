@@ -1,4 +1,7 @@
 pub mod device_code;
 pub mod hub;
+pub mod hub_error;
+pub mod hub_url;
 pub mod oauth;
+pub mod progress;
 pub mod sse;
@@ -0,0 +1,92 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const MIB: f64 = 1024.0 * 1024.0;
+const UNKNOWN_TOTAL_STEP_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Bytes received so far while downloading a build blob. `total` comes from
+/// `Content-Length` and is `None` when the server does not send one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+pub type DownloadProgressFn = dyn Fn(DownloadProgress) + Send + Sync;
+
+impl DownloadProgress {
+    pub fn percent(&self) -> Option<u64> {
+        self.total
+            .filter(|total| *total > 0)
+            .map(|total| (self.downloaded.min(total) * 100) / total)
+    }
+
+    /// Every 10% when the size is known, otherwise every 8 MiB.
+    fn step(&self) -> u64 {
+        match self.percent() {
+            Some(percent) => percent / 10,
+            None => self.downloaded / UNKNOWN_TOTAL_STEP_BYTES,
+        }
+    }
+}
+
+impl fmt::Display for DownloadProgress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let downloaded = self.downloaded as f64 / MIB;
+        match (self.total, self.percent()) {
+            (Some(total), Some(percent)) => write!(
+                f,
+                "{downloaded:.1} / {:.1} MiB ({percent}%)",
+                total as f64 / MIB
+            ),
+            _ => write!(f, "{downloaded:.1} MiB"),
+        }
+    }
+}
+
+/// Wraps `report` so it only sees the first update of each progress step,
+/// instead of one per received chunk.
+pub fn throttled(
+    report: impl Fn(DownloadProgress) + Send + Sync,
+) -> impl Fn(DownloadProgress) + Send + Sync {
+    let last_step = AtomicU64::new(u64::MAX);
+    move |progress| {
+        let step = progress.step();
+        if last_step.swap(step, Ordering::Relaxed) != step {
+            report(progress);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn reports_each_step_once() {
+        let seen = Mutex::new(Vec::new());
+        let report = throttled(|progress| seen.lock().unwrap().push(progress.downloaded));
+        for downloaded in [0, 5, 10, 15, 20, 99, 100] {
+            report(DownloadProgress {
+                downloaded,
+                total: Some(100),
+            });
+        }
+        assert_eq!(*seen.lock().unwrap(), vec![0, 10, 20, 99, 100]);
+    }
+
+    #[test]
+    fn formats_known_and_unknown_sizes() {
+        let progress = DownloadProgress {
+            downloaded: 3 * 1024 * 1024,
+            total: Some(12 * 1024 * 1024),
+        };
+        assert_eq!(progress.to_string(), "3.0 / 12.0 MiB (25%)");
+        let progress = DownloadProgress {
+            total: None,
+            ..progress
+        };
+        assert_eq!(progress.to_string(), "3.0 MiB");
+    }
+}
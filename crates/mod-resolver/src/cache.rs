@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Mutex;
+
+use anyhow::Result;
+
+use crate::Provider;
+
+/// Provider version lists fetched during one command, so the list shown in a
+/// version picker is the same one the chosen version is resolved against.
+/// Create one per invocation; it never expires entries.
+#[derive(Debug, Default)]
+pub struct VersionCache {
+    lists: Mutex<HashMap<VersionListKey, String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct VersionListKey {
+    provider: Provider,
+    project_id: String,
+    loader: String,
+    minecraft_version: String,
+    pack_type: String,
+}
+
+impl VersionListKey {
+    pub(crate) fn new(
+        provider: Provider,
+        project_id: &str,
+        loader: &str,
+        minecraft_version: &str,
+        pack_type: &str,
+    ) -> Self {
+        Self {
+            provider,
+            project_id: project_id.trim().to_string(),
+            loader: loader.trim().to_ascii_lowercase(),
+            minecraft_version: minecraft_version.trim().to_string(),
+            pack_type: pack_type.to_string(),
+        }
+    }
+}
+
+impl VersionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached response body for `key`, fetching it on first use.
+    pub(crate) async fn get_or_fetch<F, Fut>(&self, key: VersionListKey, fetch: F) -> Result<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        if let Some(body) = self.lock().get(&key) {
+            return Ok(body.clone());
+        }
+        let body = fetch().await?;
+        self.lock().insert(key, body.clone());
+        Ok(body)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<VersionListKey, String>> {
+        self.lists
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::{VersionCache, VersionListKey};
+    use crate::Provider;
+
+    #[tokio::test]
+    async fn reuses_version_lists_per_key() {
+        let cache = VersionCache::new();
+        let fetches = AtomicUsize::new(0);
+        let fetch = |body: &'static str| {
+            let fetches = &fetches;
+            move || async move {
+                fetches.fetch_add(1, Ordering::SeqCst);
+                Ok(body.to_string())
+            }
+        };
+        let key = |project: &str| {
+            VersionListKey::new(Provider::Modrinth, project, "Fabric", "1.20.1", "mod")
+        };
+
+        let first = cache
+            .get_or_fetch(key("sodium"), fetch("[1]"))
+            .await
+            .unwrap();
+        let second = cache
+            .get_or_fetch(key("sodium"), fetch("[2]"))
+            .await
+            .unwrap();
+        let other = cache
+            .get_or_fetch(key("lithium"), fetch("[3]"))
+            .await
+            .unwrap();
+        assert_eq!(first, "[1]");
+        assert_eq!(second, "[1]");
+        assert_eq!(other, "[3]");
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    }
+}
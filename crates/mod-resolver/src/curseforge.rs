@@ -1,7 +1,8 @@
 use anyhow::{Context, Result, bail};
 use serde::Deserialize;
 
-use crate::{CompatibleVersion, ResolvedDependency, ResolvedMod, SearchCandidate};
+use crate::cache::{VersionCache, VersionListKey};
+use crate::{CompatibleVersion, Provider, ResolvedDependency, ResolvedMod, SearchCandidate};
 use protocol::config::mods::{ModDownload, ModEntry, ModHashes, ModMetadata, ModSide};
 
 const GAME_ID_MINECRAFT: i32 = 432;
@@ -39,6 +40,8 @@ struct CfFile {
     file_name: String,
     #[serde(rename = "downloadUrl")]
     download_url: Option<String>,
+    #[serde(rename = "fileLength", default)]
+    file_length: Option<u64>,
     #[serde(rename = "gameVersions", default)]
     game_versions: Vec<String>,
     #[serde(default)]
@@ -122,6 +125,7 @@ pub async fn search(
 
 pub async fn resolve_by_project_id(
     client: &reqwest::Client,
+    cache: &VersionCache,
     project_id: &str,
     loader: &str,
     minecraft_version: &str,
@@ -149,33 +153,17 @@ pub async fn resolve_by_project_id(
         .await
         .context("Failed to parse CurseForge project response")?;
 
-    let mut files_url = reqwest::Url::parse(&format!(
-        "https://api.curseforge.com/v1/mods/{mod_id}/files"
-    ))
-    .context("Failed to build CurseForge files URL")?;
-    {
-        let mut pairs = files_url.query_pairs_mut();
-        pairs.append_pair("gameVersion", minecraft_version);
-        pairs.append_pair("pageSize", "50");
-        if let Some(class_id) = class_id {
-            pairs.append_pair("classId", &class_id.to_string());
-        }
-        if include_loader_filter(pack_type) {
-            pairs.append_pair("modLoaderType", &loader_id.to_string());
-        }
-    }
-
-    let files = client
-        .get(files_url)
-        .header("x-api-key", &api_key)
-        .send()
-        .await
-        .context("Failed to load CurseForge files")?
-        .error_for_status()
-        .context("CurseForge files returned an error")?
-        .json::<CfResponse<CfFile>>()
-        .await
-        .context("Failed to parse CurseForge files response")?;
+    let files = fetch_files(
+        client,
+        cache,
+        &api_key,
+        mod_id,
+        class_id,
+        loader_id,
+        minecraft_version,
+        pack_type,
+    )
+    .await?;
 
     let file = select_compatible_file(&files.data, minecraft_version, desired_version)
         .context("No compatible CurseForge files found for this Minecraft version/loader")?;
@@ -236,6 +224,7 @@ pub async fn resolve_by_project_id(
                 side: side_for_pack_type(pack_type),
                 project_url: Some(curseforge_project_url(&project_slug)),
                 disabled_client_oses: Vec::new(),
+                extra: Default::default(),
             },
             compat: protocol::config::mods::ModCompat::default(),
             download: ModDownload {
@@ -249,7 +238,12 @@ pub async fn resolve_by_project_id(
                     sha256: None,
                     sha512: None,
                 }),
+                mirrors: Vec::new(),
+                size: file.file_length,
+                pinned: false,
+                extra: Default::default(),
             },
+            extra: Default::default(),
         },
         dependencies,
     })
@@ -257,6 +251,7 @@ pub async fn resolve_by_project_id(
 
 pub async fn compatible_versions_by_project_id(
     client: &reqwest::Client,
+    cache: &VersionCache,
     project_id: &str,
     loader: &str,
     minecraft_version: &str,
@@ -271,33 +266,17 @@ pub async fn compatible_versions_by_project_id(
     let class_id = class_id_for(pack_type)?;
     let loader_id = loader_id_for(loader)?;
 
-    let mut files_url = reqwest::Url::parse(&format!(
-        "https://api.curseforge.com/v1/mods/{mod_id}/files"
-    ))
-    .context("Failed to build CurseForge files URL")?;
-    {
-        let mut pairs = files_url.query_pairs_mut();
-        pairs.append_pair("gameVersion", minecraft_version);
-        pairs.append_pair("pageSize", "50");
-        if let Some(class_id) = class_id {
-            pairs.append_pair("classId", &class_id.to_string());
-        }
-        if include_loader_filter(pack_type) {
-            pairs.append_pair("modLoaderType", &loader_id.to_string());
-        }
-    }
-
-    let files = client
-        .get(files_url)
-        .header("x-api-key", &api_key)
-        .send()
-        .await
-        .context("Failed to load CurseForge files")?
-        .error_for_status()
-        .context("CurseForge files returned an error")?
-        .json::<CfResponse<CfFile>>()
-        .await
-        .context("Failed to parse CurseForge files response")?;
+    let files = fetch_files(
+        client,
+        cache,
+        &api_key,
+        mod_id,
+        class_id,
+        loader_id,
+        minecraft_version,
+        pack_type,
+    )
+    .await?;
 
     Ok(compatible_files(&files.data, minecraft_version)
         .into_iter()
@@ -308,6 +287,58 @@ pub async fn compatible_versions_by_project_id(
         .collect())
 }
 
+#[allow(clippy::too_many_arguments)]
+async fn fetch_files(
+    client: &reqwest::Client,
+    cache: &VersionCache,
+    api_key: &str,
+    mod_id: i64,
+    class_id: Option<i32>,
+    loader_id: i32,
+    minecraft_version: &str,
+    pack_type: &str,
+) -> Result<CfResponse<CfFile>> {
+    let key = VersionListKey::new(
+        Provider::CurseForge,
+        &mod_id.to_string(),
+        &loader_id.to_string(),
+        minecraft_version,
+        pack_type,
+    );
+    let body = cache
+        .get_or_fetch(key, || async {
+            let mut files_url = reqwest::Url::parse(&format!(
+                "https://api.curseforge.com/v1/mods/{mod_id}/files"
+            ))
+            .context("Failed to build CurseForge files URL")?;
+            {
+                let mut pairs = files_url.query_pairs_mut();
+                pairs.append_pair("gameVersion", minecraft_version);
+                pairs.append_pair("pageSize", "50");
+                if let Some(class_id) = class_id {
+                    pairs.append_pair("classId", &class_id.to_string());
+                }
+                if include_loader_filter(pack_type) {
+                    pairs.append_pair("modLoaderType", &loader_id.to_string());
+                }
+            }
+
+            client
+                .get(files_url)
+                .header("x-api-key", api_key)
+                .send()
+                .await
+                .context("Failed to load CurseForge files")?
+                .error_for_status()
+                .context("CurseForge files returned an error")?
+                .text()
+                .await
+                .context("Failed to read CurseForge files response")
+        })
+        .await?;
+    serde_json::from_str(&body).context("Failed to parse CurseForge files response")
+}
+
 fn select_compatible_file<'a>(
     files: &'a [CfFile],
     minecraft_version: &str,
@@ -1,12 +1,33 @@
 use anyhow::{Context, Result, bail};
 use serde::Deserialize;
+use std::fmt;
 
-use crate::{CompatibleVersion, ResolvedDependency, ResolvedMod, SearchCandidate};
+use crate::cache::{VersionCache, VersionListKey};
+use crate::{CompatibleVersion, Provider, ResolvedDependency, ResolvedMod, SearchCandidate};
 use protocol::config::mods::{ModDownload, ModEntry, ModHashes, ModMetadata, ModSide};
 
 const GAME_ID_MINECRAFT: i32 = 432;
 const DEPENDENCY_REQUIRED: i32 = 3;
 
+/// The proxy rejected the Atlas access token, usually because it expired.
+#[derive(Debug)]
+pub struct ProxyUnauthorized;
+
+impl fmt::Display for ProxyUnauthorized {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CurseForge proxy rejected the Atlas access token (HTTP 401)")
+    }
+}
+
+impl std::error::Error for ProxyUnauthorized {}
+
+fn reject_unauthorized(response: reqwest::Response) -> Result<reqwest::Response> {
+    if response.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(ProxyUnauthorized.into());
+    }
+    Ok(response)
+}
+
 #[derive(Deserialize)]
 struct CfResponse<T> {
     data: Vec<T>,
@@ -34,6 +55,8 @@ struct CfFile {
     file_name: String,
     #[serde(rename = "downloadUrl")]
     download_url: Option<String>,
+    #[serde(rename = "fileLength", default)]
+    file_length: Option<u64>,
     #[serde(rename = "gameVersions", default)]
     game_versions: Vec<String>,
     #[serde(default)]
@@ -92,7 +115,8 @@ pub async fn search(
         .bearer_auth(access_token)
         .send()
         .await
-        .context("CurseForge proxy search failed")?
+        .context("CurseForge proxy search failed")
+        .and_then(reject_unauthorized)?
         .error_for_status()
         .context("CurseForge proxy search returned an error")?
         .json::<CfResponse<CfMod>>()
@@ -117,6 +141,7 @@ pub async fn search(
 
 pub async fn resolve_by_project_id(
     client: &reqwest::Client,
+    cache: &VersionCache,
     proxy_base_url: &str,
     access_token: &str,
     project_id: &str,
@@ -132,31 +157,18 @@ pub async fn resolve_by_project_id(
     let loader_id = loader_id_for(loader)?;
     let base = format!("{}/api/v1/curseforge", proxy_base_url.trim_end_matches('/'));
 
-    let mut files_url = reqwest::Url::parse(&format!("{base}/mods/{mod_id}/files"))
-        .context("Failed to build CurseForge proxy files URL")?;
-    {
-        let mut pairs = files_url.query_pairs_mut();
-        pairs.append_pair("gameVersion", minecraft_version);
-        pairs.append_pair("pageSize", "50");
-        if let Some(class_id) = class_id {
-            pairs.append_pair("classId", &class_id.to_string());
-        }
-        if include_loader_filter(pack_type) {
-            pairs.append_pair("modLoaderType", &loader_id.to_string());
-        }
-    }
-
-    let files = client
-        .get(files_url)
-        .bearer_auth(access_token)
-        .send()
-        .await
-        .context("Failed to load CurseForge proxy files")?
-        .error_for_status()
-        .context("CurseForge proxy files returned an error")?
-        .json::<CfResponse<CfFile>>()
-        .await
-        .context("Failed to parse CurseForge proxy files response")?;
+    let files = fetch_files(
+        client,
+        cache,
+        &base,
+        access_token,
+        mod_id,
+        class_id,
+        loader_id,
+        minecraft_version,
+        pack_type,
+    )
+    .await?;
 
     let file = select_compatible_file(&files.data, minecraft_version, desired_version)
         .context("No compatible CurseForge files found for this Minecraft version/loader")?;
@@ -171,7 +183,8 @@ pub async fn resolve_by_project_id(
             .bearer_auth(access_token)
             .send()
             .await
-            .context("Failed to load CurseForge proxy download URL")?
+            .context("Failed to load CurseForge proxy download URL")
+            .and_then(reject_unauthorized)?
             .error_for_status()
             .context("CurseForge proxy download URL returned an error")?
             .json::<CfDownloadUrlResponse>()
@@ -209,6 +222,7 @@ pub async fn resolve_by_project_id(
                 side: side_for_pack_type(pack_type),
                 project_url: Some(curseforge_project_url(project_id)),
                 disabled_client_oses: Vec::new(),
+                extra: Default::default(),
             },
             compat: protocol::config::mods::ModCompat::default(),
             download: ModDownload {
@@ -222,7 +236,12 @@ pub async fn resolve_by_project_id(
                     sha256: None,
                     sha512: None,
                 }),
+                mirrors: Vec::new(),
+                size: file.file_length,
+                pinned: false,
+                extra: Default::default(),
             },
+            extra: Default::default(),
         },
         dependencies,
     })
@@ -230,6 +249,7 @@ pub async fn resolve_by_project_id(
 
 pub async fn compatible_versions_by_project_id(
     client: &reqwest::Client,
+    cache: &VersionCache,
     proxy_base_url: &str,
     access_token: &str,
     project_id: &str,
@@ -244,31 +264,18 @@ pub async fn compatible_versions_by_project_id(
     let loader_id = loader_id_for(loader)?;
     let base = format!("{}/api/v1/curseforge", proxy_base_url.trim_end_matches('/'));
 
-    let mut files_url = reqwest::Url::parse(&format!("{base}/mods/{mod_id}/files"))
-        .context("Failed to build CurseForge proxy files URL")?;
-    {
-        let mut pairs = files_url.query_pairs_mut();
-        pairs.append_pair("gameVersion", minecraft_version);
-        pairs.append_pair("pageSize", "50");
-        if let Some(class_id) = class_id {
-            pairs.append_pair("classId", &class_id.to_string());
-        }
-        if include_loader_filter(pack_type) {
-            pairs.append_pair("modLoaderType", &loader_id.to_string());
-        }
-    }
-
-    let files = client
-        .get(files_url)
-        .bearer_auth(access_token)
-        .send()
-        .await
-        .context("Failed to load CurseForge proxy files")?
-        .error_for_status()
-        .context("CurseForge proxy files returned an error")?
-        .json::<CfResponse<CfFile>>()
-        .await
-        .context("Failed to parse CurseForge proxy files response")?;
+    let files = fetch_files(
+        client,
+        cache,
+        &base,
+        access_token,
+        mod_id,
+        class_id,
+        loader_id,
+        minecraft_version,
+        pack_type,
+    )
+    .await?;
 
     Ok(compatible_files(&files.data, minecraft_version)
         .into_iter()
@@ -279,6 +286,58 @@ pub async fn compatible_versions_by_project_id(
         .collect())
 }
 
+#[allow(clippy::too_many_arguments)]
+async fn fetch_files(
+    client: &reqwest::Client,
+    cache: &VersionCache,
+    base: &str,
+    access_token: &str,
+    mod_id: i64,
+    class_id: Option<i32>,
+    loader_id: i32,
+    minecraft_version: &str,
+    pack_type: &str,
+) -> Result<CfResponse<CfFile>> {
+    let key = VersionListKey::new(
+        Provider::CurseForge,
+        &mod_id.to_string(),
+        &loader_id.to_string(),
+        minecraft_version,
+        pack_type,
+    );
+    let body = cache
+        .get_or_fetch(key, || async {
+            let mut files_url = reqwest::Url::parse(&format!("{base}/mods/{mod_id}/files"))
+                .context("Failed to build CurseForge proxy files URL")?;
+            {
+                let mut pairs = files_url.query_pairs_mut();
+                pairs.append_pair("gameVersion", minecraft_version);
+                pairs.append_pair("pageSize", "50");
+                if let Some(class_id) = class_id {
+                    pairs.append_pair("classId", &class_id.to_string());
+                }
+                if include_loader_filter(pack_type) {
+                    pairs.append_pair("modLoaderType", &loader_id.to_string());
+                }
+            }
+
+            client
+                .get(files_url)
+                .bearer_auth(access_token)
+                .send()
+                .await
+                .context("Failed to load CurseForge proxy files")
+                .and_then(reject_unauthorized)?
+                .error_for_status()
+                .context("CurseForge proxy files returned an error")?
+                .text()
+                .await
+                .context("Failed to read CurseForge proxy files response")
+        })
+        .await?;
+    serde_json::from_str(&body).context("Failed to parse CurseForge proxy files response")
+}
+
 fn select_compatible_file<'a>(
     files: &'a [CfFile],
     minecraft_version: &str,
@@ -1,3 +1,4 @@
+mod cache;
 mod curseforge;
 mod curseforge_proxy;
 mod modrinth;
@@ -7,8 +8,16 @@ pub mod pointer;
 use anyhow::Context;
 use anyhow::{Result, bail};
 
+pub use cache::VersionCache;
+pub use curseforge_proxy::ProxyUnauthorized;
 pub use protocol::config::mods::{ModEntry, ModHashes, ModMetadata};
 
+/// True when a CurseForge proxy call failed because the access token was
+/// rejected; callers can refresh it or sign in again and retry.
+pub fn is_proxy_unauthorized(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| cause.is::<ProxyUnauthorized>())
+}
+
 #[derive(Debug, Clone)]
 pub struct SearchCandidate {
     pub project_id: String,
@@ -36,7 +45,7 @@ pub struct CompatibleVersion {
     pub label: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Provider {
     Modrinth,
     CurseForge,
@@ -75,10 +84,12 @@ pub async fn resolve(
     let candidate = candidates
         .first()
         .ok_or_else(|| anyhow::anyhow!("No {} results found for '{}'.", provider.label(), query))?;
+    let cache = VersionCache::new();
     let resolved = match provider {
         Provider::Modrinth => {
             modrinth::resolve_by_project_id(
                 &client,
+                &cache,
                 &candidate.project_id,
                 loader,
                 minecraft_version,
@@ -90,6 +101,7 @@ pub async fn resolve(
         Provider::CurseForge => {
             curseforge::resolve_by_project_id(
                 &client,
+                &cache,
                 &candidate.project_id,
                 loader,
                 minecraft_version,
@@ -132,6 +144,7 @@ pub async fn resolve_curseforge_via_proxy(
 
     let resolved = curseforge_proxy::resolve_by_project_id(
         &client,
+        &VersionCache::new(),
         proxy_base_url,
         access_token,
         &candidate.project_id,
@@ -211,6 +224,7 @@ pub async fn search_curseforge_via_proxy(
 }
 
 pub async fn resolve_by_project_id(
+    cache: &VersionCache,
     provider: Provider,
     project_id: &str,
     loader: &str,
@@ -225,6 +239,7 @@ pub async fn resolve_by_project_id(
         Provider::Modrinth => {
             modrinth::resolve_by_project_id(
                 &client,
+                cache,
                 project_id,
                 loader,
                 minecraft_version,
@@ -236,6 +251,7 @@ pub async fn resolve_by_project_id(
         Provider::CurseForge => {
             curseforge::resolve_by_project_id(
                 &client,
+                cache,
                 project_id,
                 loader,
                 minecraft_version,
@@ -248,6 +264,7 @@ pub async fn resolve_by_project_id(
 }
 
 pub async fn compatible_versions_by_project_id(
+    cache: &VersionCache,
     provider: Provider,
     project_id: &str,
     loader: &str,
@@ -261,6 +278,7 @@ pub async fn compatible_versions_by_project_id(
         Provider::Modrinth => {
             modrinth::compatible_versions_by_project_id(
                 &client,
+                cache,
                 project_id,
                 loader,
                 minecraft_version,
@@ -271,6 +289,7 @@ pub async fn compatible_versions_by_project_id(
         Provider::CurseForge => {
             curseforge::compatible_versions_by_project_id(
                 &client,
+                cache,
                 project_id,
                 loader,
                 minecraft_version,
@@ -282,6 +301,7 @@ pub async fn compatible_versions_by_project_id(
 }
 
 pub async fn resolve_curseforge_by_project_id_via_proxy(
+    cache: &VersionCache,
     proxy_base_url: &str,
     access_token: &str,
     project_id: &str,
@@ -294,6 +314,7 @@ pub async fn resolve_curseforge_by_project_id_via_proxy(
     let client = reqwest::Client::new();
     curseforge_proxy::resolve_by_project_id(
         &client,
+        cache,
         proxy_base_url,
         access_token,
         project_id,
@@ -306,6 +327,7 @@ pub async fn resolve_curseforge_by_project_id_via_proxy(
 }
 
 pub async fn compatible_curseforge_versions_by_project_id_via_proxy(
+    cache: &VersionCache,
     proxy_base_url: &str,
     access_token: &str,
     project_id: &str,
@@ -317,6 +339,7 @@ pub async fn compatible_curseforge_versions_by_project_id_via_proxy(
     let client = reqwest::Client::new();
     curseforge_proxy::compatible_versions_by_project_id(
         &client,
+        cache,
         proxy_base_url,
         access_token,
         project_id,
@@ -417,6 +440,7 @@ pub fn search_curseforge_via_proxy_blocking(
 
 #[cfg(feature = "blocking")]
 pub fn resolve_by_project_id_blocking(
+    cache: &VersionCache,
     provider: Provider,
     project_id: &str,
     loader: &str,
@@ -426,6 +450,7 @@ pub fn resolve_by_project_id_blocking(
 ) -> Result<ResolvedMod> {
     let runtime = tokio::runtime::Runtime::new().context("failed to create tokio runtime")?;
     runtime.block_on(resolve_by_project_id(
+        cache,
         provider,
         project_id,
         loader,
@@ -437,6 +462,7 @@ pub fn resolve_by_project_id_blocking(
 
 #[cfg(feature = "blocking")]
 pub fn compatible_versions_by_project_id_blocking(
+    cache: &VersionCache,
     provider: Provider,
     project_id: &str,
     loader: &str,
@@ -445,6 +471,7 @@ pub fn compatible_versions_by_project_id_blocking(
 ) -> Result<Vec<CompatibleVersion>> {
     let runtime = tokio::runtime::Runtime::new().context("failed to create tokio runtime")?;
     runtime.block_on(compatible_versions_by_project_id(
+        cache,
         provider,
         project_id,
         loader,
@@ -455,6 +482,7 @@ pub fn compatible_versions_by_project_id_blocking(
 
 #[cfg(feature = "blocking")]
 pub fn resolve_curseforge_by_project_id_via_proxy_blocking(
+    cache: &VersionCache,
     proxy_base_url: &str,
     access_token: &str,
     project_id: &str,
@@ -465,6 +493,7 @@ pub fn resolve_curseforge_by_project_id_via_proxy_blocking(
 ) -> Result<ResolvedMod> {
     let runtime = tokio::runtime::Runtime::new().context("failed to create tokio runtime")?;
     runtime.block_on(resolve_curseforge_by_project_id_via_proxy(
+        cache,
         proxy_base_url,
         access_token,
         project_id,
@@ -477,6 +506,7 @@ pub fn resolve_curseforge_by_project_id_via_proxy_blocking(
 
 #[cfg(feature = "blocking")]
 pub fn compatible_curseforge_versions_by_project_id_via_proxy_blocking(
+    cache: &VersionCache,
     proxy_base_url: &str,
     access_token: &str,
     project_id: &str,
@@ -486,6 +516,7 @@ pub fn compatible_curseforge_versions_by_project_id_via_proxy_blocking(
 ) -> Result<Vec<CompatibleVersion>> {
     let runtime = tokio::runtime::Runtime::new().context("failed to create tokio runtime")?;
     runtime.block_on(compatible_curseforge_versions_by_project_id_via_proxy(
+        cache,
         proxy_base_url,
         access_token,
         project_id,
@@ -514,3 +545,16 @@ impl Provider {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_proxy_unauthorized_through_context() {
+        let err =
+            anyhow::Error::new(ProxyUnauthorized).context("Failed to load CurseForge proxy files");
+        assert!(is_proxy_unauthorized(&err));
+        assert!(!is_proxy_unauthorized(&anyhow::anyhow!("HTTP 500")));
+    }
+}
@@ -1,7 +1,8 @@
 use anyhow::{Context, Result, bail};
 use serde::Deserialize;
 
-use crate::{CompatibleVersion, ResolvedDependency, ResolvedMod, SearchCandidate};
+use crate::cache::{VersionCache, VersionListKey};
+use crate::{CompatibleVersion, Provider, ResolvedDependency, ResolvedMod, SearchCandidate};
 use protocol::config::mods::{ModDownload, ModEntry, ModHashes, ModMetadata, ModSide};
 
 #[derive(Deserialize)]
@@ -51,6 +52,8 @@ struct ModFile {
     url: String,
     hashes: ModHashes,
     #[serde(default)]
+    size: Option<u64>,
+    #[serde(default)]
     primary: bool,
 }
 
@@ -67,6 +70,37 @@ struct ModDependency {
 #[derive(Deserialize)]
 struct VersionLookup {
     project_id: String,
+    #[serde(default)]
+    version_number: String,
+}
+
+/// How a requested or dependency-declared version is matched against the
+/// versions Modrinth lists for the pack's Minecraft version and loader.
+#[derive(Debug, PartialEq)]
+enum VersionRequirement {
+    Any,
+    /// A version id, version number or name.
+    Selector(String),
+    Range(semver::VersionReq),
+}
+
+impl VersionRequirement {
+    fn parse(value: Option<&str>) -> Self {
+        let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+            return Self::Any;
+        };
+        if value == "*" || value.eq_ignore_ascii_case("latest") {
+            return Self::Any;
+        }
+        let looks_like_range = value.starts_with(['>', '<', '=', '^', '~'])
+            || value.contains(',')
+            || value.ends_with(".x")
+            || value.ends_with(".*");
+        if looks_like_range && let Ok(req) = semver::VersionReq::parse(value) {
+            return Self::Range(req);
+        }
+        Self::Selector(value.to_string())
+    }
 }
 
 pub async fn search(
@@ -121,6 +155,7 @@ pub async fn search(
 
 pub async fn resolve_by_project_id(
     client: &reqwest::Client,
+    cache: &VersionCache,
     project_id: &str,
     loader: &str,
     minecraft_version: &str,
@@ -139,29 +174,39 @@ pub async fn resolve_by_project_id(
         .await
         .context("Failed to parse Modrinth project response")?;
 
-    let version_url = build_version_url(project_id, loader, minecraft_version, pack_type);
-    let versions = client
-        .get(version_url)
-        .send()
-        .await
-        .context("Failed to load Modrinth versions")?
-        .error_for_status()
-        .context("Modrinth versions returned an error")?
-        .json::<Vec<VersionInfo>>()
-        .await
-        .context("Failed to parse Modrinth versions")?;
-
-    let version = if let Some(desired) = desired_version {
-        versions
-            .iter()
-            .find(|item| {
-                item.id == desired || item.version_number == desired || item.name == desired
-            })
-            .context("Requested Modrinth version not found for this Minecraft version/loader")?
-    } else {
-        versions
-            .first()
-            .context("No compatible Modrinth versions found for this Minecraft version/loader")?
+    let versions = fetch_versions(
+        client,
+        cache,
+        project_id,
+        loader,
+        minecraft_version,
+        pack_type,
+    )
+    .await?;
+
+    let requirement = VersionRequirement::parse(desired_version);
+    let version = match select_version(&versions, &requirement) {
+        Some(version) => version,
+        None => {
+            let VersionRequirement::Selector(selector) = &requirement else {
+                bail!(
+                    "No Modrinth version of {} satisfies '{}' for this Minecraft version/loader",
+                    project_id,
+                    desired_version.unwrap_or_default()
+                );
+            };
+            // Dependencies usually name a version id uploaded for another loader or
+            // Minecraft version; treat it as a minimum on its version number.
+            let minimum = lookup_version_number(client, selector).await.context(
+                "Requested Modrinth version not found for this Minecraft version/loader",
+            )?;
+            select_at_least(&versions, &minimum).with_context(|| {
+                format!(
+                    "No Modrinth version of {} at or above {} is available for this Minecraft version/loader",
+                    project_id, minimum
+                )
+            })?
+        }
     };
 
     let file = version
@@ -235,6 +280,7 @@ pub async fn resolve_by_project_id(
                 side,
                 project_url: Some(project_url),
                 disabled_client_oses: Vec::new(),
+                extra: Default::default(),
             },
             compat: protocol::config::mods::ModCompat::default(),
             download: ModDownload {
@@ -252,7 +298,12 @@ pub async fn resolve_by_project_id(
                     sha256: None,
                     sha512: file.hashes.sha512.clone(),
                 }),
+                mirrors: Vec::new(),
+                size: file.size,
+                pinned: false,
+                extra: Default::default(),
             },
+            extra: Default::default(),
         },
         dependencies,
     })
@@ -260,22 +311,21 @@ pub async fn resolve_by_project_id(
 
 pub async fn compatible_versions_by_project_id(
     client: &reqwest::Client,
+    cache: &VersionCache,
     project_id: &str,
     loader: &str,
     minecraft_version: &str,
     pack_type: &str,
 ) -> Result<Vec<CompatibleVersion>> {
-    let version_url = build_version_url(project_id, loader, minecraft_version, pack_type);
-    let versions = client
-        .get(version_url)
-        .send()
-        .await
-        .context("Failed to load Modrinth versions")?
-        .error_for_status()
-        .context("Modrinth versions returned an error")?
-        .json::<Vec<VersionInfo>>()
-        .await
-        .context("Failed to parse Modrinth versions")?;
+    let versions = fetch_versions(
+        client,
+        cache,
+        project_id,
+        loader,
+        minecraft_version,
+        pack_type,
+    )
+    .await?;
 
     Ok(versions
         .into_iter()
@@ -289,6 +339,105 @@ pub async fn compatible_versions_by_project_id(
         .collect())
 }
 
+async fn fetch_versions(
+    client: &reqwest::Client,
+    cache: &VersionCache,
+    project_id: &str,
+    loader: &str,
+    minecraft_version: &str,
+    pack_type: &str,
+) -> Result<Vec<VersionInfo>> {
+    let key = VersionListKey::new(
+        Provider::Modrinth,
+        project_id,
+        loader,
+        minecraft_version,
+        pack_type,
+    );
+    let body = cache
+        .get_or_fetch(key, || async {
+            client
+                .get(build_version_url(
+                    project_id,
+                    loader,
+                    minecraft_version,
+                    pack_type,
+                ))
+                .send()
+                .await
+                .context("Failed to load Modrinth versions")?
+                .error_for_status()
+                .context("Modrinth versions returned an error")?
+                .text()
+                .await
+                .context("Failed to read Modrinth versions")
+        })
+        .await?;
+    serde_json::from_str(&body).context("Failed to parse Modrinth versions")
+}
+
+// Modrinth lists versions newest first, so the first match is the newest.
+fn select_version<'a>(
+    versions: &'a [VersionInfo],
+    requirement: &VersionRequirement,
+) -> Option<&'a VersionInfo> {
+    match requirement {
+        VersionRequirement::Any => versions.first(),
+        VersionRequirement::Selector(selector) => versions.iter().find(|item| {
+            item.id == *selector || item.version_number == *selector || item.name == *selector
+        }),
+        VersionRequirement::Range(req) => versions.iter().find(|item| {
+            parse_loose_version(&item.version_number).is_some_and(|version| req.matches(&version))
+        }),
+    }
+}
+
+fn select_at_least<'a>(versions: &'a [VersionInfo], minimum: &str) -> Option<&'a VersionInfo> {
+    if let Some(exact) = versions.iter().find(|item| item.version_number == minimum) {
+        return Some(exact);
+    }
+    let minimum = parse_loose_version(minimum)?;
+    versions.iter().find(|item| {
+        parse_loose_version(&item.version_number).is_some_and(|version| version >= minimum)
+    })
+}
+
+async fn lookup_version_number(client: &reqwest::Client, version_id: &str) -> Result<String> {
+    let version = client
+        .get(format!("https://api.modrinth.com/v2/version/{version_id}"))
+        .send()
+        .await
+        .context("Failed to load Modrinth version")?
+        .error_for_status()
+        .context("Modrinth version returned an error")?
+        .json::<VersionLookup>()
+        .await
+        .context("Failed to parse Modrinth version response")?;
+    let version_number = version.version_number.trim();
+    if version_number.is_empty() {
+        bail!("Modrinth version {version_id} has no version number");
+    }
+    Ok(version_number.to_string())
+}
+
+// Mod version numbers are only semver-ish (`v1.2`, `0.5.8+mc1.20.1`), so pad
+// and strip them down to the leading numeric release before comparing.
+fn parse_loose_version(value: &str) -> Option<semver::Version> {
+    let value = value.trim().trim_start_matches(['v', 'V']);
+    if let Ok(version) = semver::Version::parse(value) {
+        return Some(version);
+    }
+    let release = value
+        .split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .next()?
+        .trim_end_matches('.');
+    let mut parts = release.split('.').map(str::parse::<u64>);
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().transpose().ok()?.unwrap_or(0);
+    let patch = parts.next().transpose().ok()?.unwrap_or(0);
+    Some(semver::Version::new(major, minor, patch))
+}
+
 async fn resolve_dependency_project_id(
     client: &reqwest::Client,
     dependency: &ModDependency,
@@ -455,7 +604,55 @@ impl IfEmptyThen for String {
 
 #[cfg(test)]
 mod tests {
-    use super::{ModSide, map_side, should_include_dependency_type};
+    use super::{
+        ModSide, VersionInfo, VersionRequirement, map_side, parse_loose_version, select_at_least,
+        select_version, should_include_dependency_type,
+    };
+
+    fn version(id: &str, version_number: &str) -> VersionInfo {
+        VersionInfo {
+            id: id.to_string(),
+            name: version_number.to_string(),
+            version_number: version_number.to_string(),
+            side: None,
+            client_side: None,
+            server_side: None,
+            files: Vec::new(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn selects_newest_version_satisfying_requirement() {
+        let versions = vec![
+            version("c", "2.1.0+mc1.20.1"),
+            version("b", "1.4.2"),
+            version("a", "v1.2"),
+        ];
+        let pick = |value: Option<&str>| {
+            select_version(&versions, &VersionRequirement::parse(value)).map(|item| item.id.clone())
+        };
+
+        assert_eq!(pick(None), Some("c".to_string()));
+        assert_eq!(pick(Some("latest")), Some("c".to_string()));
+        assert_eq!(pick(Some("a")), Some("a".to_string()));
+        assert_eq!(pick(Some("1.4.2")), Some("b".to_string()));
+        assert_eq!(pick(Some("^1.2")), Some("b".to_string()));
+        assert_eq!(pick(Some(">=2")), Some("c".to_string()));
+        assert_eq!(pick(Some("<1.3")), Some("a".to_string()));
+        assert_eq!(pick(Some("missing-id")), None);
+        assert_eq!(pick(Some(">=3")), None);
+
+        assert_eq!(
+            select_at_least(&versions, "1.3").map(|item| item.id.as_str()),
+            Some("c")
+        );
+        assert_eq!(
+            parse_loose_version("0.5.8-mc1.20.1"),
+            semver::Version::parse("0.5.8-mc1.20.1").ok()
+        );
+        assert_eq!(parse_loose_version("mc1.20"), None);
+    }
 
     #[test]
     fn maps_explicit_modrinth_side_values() {
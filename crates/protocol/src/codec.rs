@@ -2,25 +2,173 @@ use crate::error::ProtocolError;
 use crate::types::PackBlob;
 use crate::wire;
 use prost::Message;
-use std::io::Cursor;
+use sha2::{Digest, Sha256};
+use std::io::{Cursor, Read};
 
 pub const DEFAULT_ZSTD_LEVEL: i32 = 19;
 
+const MIB: u64 = 1024 * 1024;
+
+/// Output below this size is never rejected for its compression ratio, so
+/// small text-heavy packs that compress extremely well still decode.
+const RATIO_CHECK_FLOOR: u64 = 64 * MIB;
+
+/// Upper bounds enforced by [`decode_blob_with_limits`]. The defaults are far
+/// above any real pack; [`DecodeLimits::untrusted`] is for blobs that arrive
+/// from outside the process, such as IPC uploads and hub downloads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeLimits {
+    pub max_decompressed_bytes: u64,
+    /// Decompressed bytes allowed per compressed input byte, past
+    /// [`RATIO_CHECK_FLOOR`]. Guards against small zstd bombs.
+    pub max_compression_ratio: u64,
+    pub max_files: usize,
+    pub max_file_bytes: u64,
+    pub max_dependencies: usize,
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        Self {
+            max_decompressed_bytes: 8 * 1024 * MIB,
+            max_compression_ratio: 1_000,
+            max_files: 1_000_000,
+            max_file_bytes: 2 * 1024 * MIB,
+            max_dependencies: 100_000,
+        }
+    }
+}
+
+impl DecodeLimits {
+    pub fn untrusted() -> Self {
+        Self {
+            max_decompressed_bytes: 2 * 1024 * MIB,
+            max_compression_ratio: 200,
+            max_files: 100_000,
+            max_file_bytes: 512 * MIB,
+            max_dependencies: 20_000,
+        }
+    }
+}
+
 pub fn encode_blob(blob: &PackBlob, zstd_level: i32) -> Result<Vec<u8>, ProtocolError> {
-    let wire_blob = wire::PackBlob::try_from(blob)?;
-    let mut encoded = Vec::with_capacity(wire_blob.encoded_len());
-    wire_blob.encode(&mut encoded)?;
+    let encoded = encode_canonical(blob)?;
     let compressed = zstd::stream::encode_all(Cursor::new(encoded), zstd_level)?;
     Ok(compressed)
 }
 
+/// Stable SHA-256 of the blob's logical content. Equivalent blobs share a
+/// fingerprint regardless of dependency order or compression settings.
+pub fn blob_fingerprint(blob: &PackBlob) -> Result<String, ProtocolError> {
+    let encoded = encode_canonical(blob)?;
+    Ok(hex::encode(Sha256::digest(&encoded)))
+}
+
+/// Size of the protobuf payload before zstd compression.
+pub(crate) fn encoded_len(blob: &PackBlob) -> Result<usize, ProtocolError> {
+    Ok(wire::PackBlob::try_from(blob)?.encoded_len())
+}
+
+// Files are already key-ordered (`ByteMap` is a BTreeMap and prost is configured
+// to match); dependencies are sorted and platform filters normalized so the
+// protobuf bytes only depend on content.
+fn encode_canonical(blob: &PackBlob) -> Result<Vec<u8>, ProtocolError> {
+    for dependency in &blob.manifest.dependencies {
+        dependency.platform.validate()?;
+    }
+    let mut wire_blob = wire::PackBlob::try_from(blob)?;
+    if let Some(manifest) = wire_blob.manifest.as_mut() {
+        manifest
+            .dependencies
+            .sort_by(|a, b| (&a.url, &a.pointer_path).cmp(&(&b.url, &b.pointer_path)));
+    }
+    let mut encoded = Vec::with_capacity(wire_blob.encoded_len());
+    wire_blob.encode(&mut encoded)?;
+    Ok(encoded)
+}
+
 pub fn encode_blob_default(blob: &PackBlob) -> Result<Vec<u8>, ProtocolError> {
     encode_blob(blob, DEFAULT_ZSTD_LEVEL)
 }
 
 pub fn decode_blob(bytes: &[u8]) -> Result<PackBlob, ProtocolError> {
-    let decompressed = zstd::stream::decode_all(Cursor::new(bytes))?;
+    decode_blob_with_limits(bytes, &DecodeLimits::default())
+}
+
+pub fn decode_blob_with_limits(
+    bytes: &[u8],
+    limits: &DecodeLimits,
+) -> Result<PackBlob, ProtocolError> {
+    let decompressed = decompress_bounded(bytes, limits)?;
     let wire_blob = wire::PackBlob::decode(decompressed.as_slice())?;
+    check_limits(&wire_blob, limits)?;
     let blob = PackBlob::try_from(wire_blob)?;
     Ok(blob)
 }
+
+// The frame header's declared size is checked before anything is allocated;
+// the stream itself is capped too, since the header may omit or understate it.
+fn decompress_bounded(bytes: &[u8], limits: &DecodeLimits) -> Result<Vec<u8>, ProtocolError> {
+    let ratio_cap = (bytes.len() as u64)
+        .saturating_mul(limits.max_compression_ratio)
+        .max(RATIO_CHECK_FLOOR);
+    let max = limits.max_decompressed_bytes.min(ratio_cap);
+    let exceeded = || {
+        if max < limits.max_decompressed_bytes {
+            ProtocolError::LimitExceeded {
+                limit: "compression ratio",
+                max: limits.max_compression_ratio,
+            }
+        } else {
+            ProtocolError::LimitExceeded {
+                limit: "decompressed size",
+                max,
+            }
+        }
+    };
+    if let Ok(Some(declared)) = zstd::zstd_safe::get_frame_content_size(bytes)
+        && declared > max
+    {
+        return Err(exceeded());
+    }
+
+    let mut decompressed = Vec::new();
+    zstd::stream::read::Decoder::new(Cursor::new(bytes))?
+        .take(max.saturating_add(1))
+        .read_to_end(&mut decompressed)?;
+    if decompressed.len() as u64 > max {
+        return Err(exceeded());
+    }
+    Ok(decompressed)
+}
+
+fn check_limits(blob: &wire::PackBlob, limits: &DecodeLimits) -> Result<(), ProtocolError> {
+    if blob.files.len() > limits.max_files {
+        return Err(ProtocolError::LimitExceeded {
+            limit: "file count",
+            max: limits.max_files as u64,
+        });
+    }
+    if blob
+        .files
+        .values()
+        .any(|bytes| bytes.len() as u64 > limits.max_file_bytes)
+    {
+        return Err(ProtocolError::LimitExceeded {
+            limit: "file size",
+            max: limits.max_file_bytes,
+        });
+    }
+    let dependencies = blob
+        .manifest
+        .as_ref()
+        .map(|manifest| manifest.dependencies.len())
+        .unwrap_or_default();
+    if dependencies > limits.max_dependencies {
+        return Err(ProtocolError::LimitExceeded {
+            limit: "dependency count",
+            max: limits.max_dependencies as u64,
+        });
+    }
+    Ok(())
+}
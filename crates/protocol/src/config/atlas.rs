@@ -1,12 +1,23 @@
 use serde::{Deserialize, Serialize};
 
+use crate::config::ConfigParseError;
+use crate::config::error::parse_toml;
 use crate::{Loader, ProtocolError};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AtlasConfig {
     pub metadata: MetadataConfig,
     pub versions: VersionsConfig,
+    /// Defaults to no CLI settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cli: Option<CliConfig>,
+    /// Defaults to no extra template files.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub templates: Option<TemplatesConfig>,
+    /// Keys this version of Atlas does not know, kept so re-serializing the
+    /// file does not drop settings written by newer tooling.
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -14,6 +25,8 @@ pub struct MetadataConfig {
     pub name: String,
     pub version: Option<String>,
     pub description: Option<String>,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -21,6 +34,8 @@ pub struct VersionsConfig {
     pub mc: String,
     pub modloader: String,
     pub modloader_version: String,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -28,10 +43,22 @@ pub struct CliConfig {
     pub pack_id: Option<String>,
     pub hub_url: Option<String>,
     pub default_channel: Option<String>,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
-pub fn parse_config(contents: &str) -> Result<AtlasConfig, ProtocolError> {
-    toml::from_str(contents).map_err(|_| ProtocolError::MissingField("atlas.toml"))
+/// Bundled files the runner renders with `${ATLAS_VAR_*}` substitution, in
+/// addition to `*.tmpl` files.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct TemplatesConfig {
+    #[serde(default)]
+    pub files: Vec<String>,
+    #[serde(flatten)]
+    pub extra: toml::Table,
+}
+
+pub fn parse_config(contents: &str) -> Result<AtlasConfig, ConfigParseError> {
+    parse_toml(contents)
 }
 
 pub fn parse_loader(value: &str) -> Result<Loader, ProtocolError> {
@@ -0,0 +1,108 @@
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+/// Why a config or pointer TOML file could not be read, with the dotted path
+/// of the offending field and the 1-based position when the parser knows it.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub struct ConfigParseError {
+    pub field: Option<String>,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.field, self.line, self.column) {
+            (Some(field), Some(line), Some(column)) => {
+                write!(f, "{field} (line {line}, column {column}): ")?
+            }
+            (Some(field), _, _) => write!(f, "{field}: ")?,
+            (None, Some(line), Some(column)) => write!(f, "line {line}, column {column}: ")?,
+            _ => {}
+        }
+        f.write_str(&self.message)
+    }
+}
+
+pub(crate) fn parse_toml<T: DeserializeOwned>(contents: &str) -> Result<T, ConfigParseError> {
+    serde_path_to_error::deserialize(toml::Deserializer::new(contents)).map_err(|err| {
+        let path = err.path().to_string();
+        let err = err.into_inner();
+        let message = err.message().trim().to_string();
+
+        let mut field = (path != ".").then_some(path);
+        // Serde reports a missing field against its parent table.
+        if let Some(missing) = message
+            .strip_prefix("missing field `")
+            .and_then(|rest| rest.strip_suffix('`'))
+        {
+            field = Some(match field {
+                Some(parent) => format!("{parent}.{missing}"),
+                None => missing.to_string(),
+            });
+        }
+
+        let (line, column) = match err.span() {
+            Some(span) => {
+                let (line, column) = line_column(contents, span.start);
+                (Some(line), Some(column))
+            }
+            None => (None, None),
+        };
+        ConfigParseError {
+            field,
+            line,
+            column,
+            message,
+        }
+    })
+}
+
+fn line_column(contents: &str, offset: usize) -> (usize, usize) {
+    let before = &contents[..offset.min(contents.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map(|text| text.chars().count())
+        .unwrap_or_default()
+        + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{atlas::parse_config, mods::parse_mod_toml};
+
+    #[test]
+    fn reports_missing_fields_with_their_path() {
+        let err = parse_config("[metadata]\nname = \"Pack\"\n\n[versions]\nmc = \"1.20.1\"\n")
+            .expect_err("modloader is missing");
+        assert_eq!(err.field.as_deref(), Some("versions.modloader"));
+        assert_eq!(err.line, Some(4));
+    }
+
+    #[test]
+    fn reports_mistyped_pointer_keys() {
+        let err = parse_mod_toml(
+            "[download]\nsource = \"modrinth\"\nproject_id = \"abc\"\nversion = 3\n",
+        )
+        .expect_err("version must be a string");
+        assert_eq!(err.field.as_deref(), Some("download.version"));
+        assert_eq!((err.line, err.column), (Some(4), Some(11)));
+        assert!(
+            err.to_string()
+                .starts_with("download.version (line 4, column 11): ")
+        );
+    }
+
+    #[test]
+    fn reports_syntax_errors_by_position() {
+        let err = parse_mod_toml("[download\n").expect_err("unterminated table header");
+        assert_eq!(err.field, None);
+        assert_eq!(err.line, Some(1));
+    }
+}
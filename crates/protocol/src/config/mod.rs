@@ -1,3 +1,6 @@
 pub mod atlas;
+mod error;
 pub mod mods;
 pub mod resources;
+
+pub use error::ConfigParseError;
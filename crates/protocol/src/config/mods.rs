@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+use crate::config::ConfigParseError;
+use crate::config::error::parse_toml;
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ModEntry {
     #[serde(default)]
@@ -7,6 +10,10 @@ pub struct ModEntry {
     #[serde(default, skip_serializing_if = "ModCompat::is_empty")]
     pub compat: ModCompat,
     pub download: ModDownload,
+    /// Keys this version of Atlas does not know, kept so re-serializing the
+    /// file does not drop settings written by newer tooling.
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -19,6 +26,8 @@ pub struct ModCompat {
     pub loader_versions: Vec<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub requires: Vec<ModCompatDependency>,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 impl ModCompat {
@@ -27,6 +36,7 @@ impl ModCompat {
             && self.loaders.is_empty()
             && self.loader_versions.is_empty()
             && self.requires.is_empty()
+            && self.extra.is_empty()
     }
 }
 
@@ -48,6 +58,8 @@ pub struct ModMetadata {
     pub project_url: Option<String>,
     #[serde(default)]
     pub disabled_client_oses: Vec<ClientOs>,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 impl Default for ModMetadata {
@@ -57,6 +69,7 @@ impl Default for ModMetadata {
             side: ModSide::Both,
             project_url: None,
             disabled_client_oses: Vec::new(),
+            extra: toml::Table::new(),
         }
     }
 }
@@ -94,6 +107,14 @@ pub struct ModDownload {
     pub url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hashes: Option<ModHashes>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mirrors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    #[serde(flatten)]
+    pub extra: toml::Table,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -127,11 +148,16 @@ struct LegacyModEntry {
     hashes: Option<ModHashes>,
 }
 
-pub fn parse_mod_toml(contents: &str) -> Result<ModEntry, toml::de::Error> {
-    match toml::from_str::<ModEntry>(contents) {
+pub fn parse_mod_toml(contents: &str) -> Result<ModEntry, ConfigParseError> {
+    match parse_toml::<ModEntry>(contents) {
         Ok(parsed) => Ok(parsed),
-        Err(_) => {
-            let legacy = toml::from_str::<LegacyModEntry>(contents)?;
+        Err(err) => {
+            // Only report the legacy layout's error for files that use it.
+            let legacy = match parse_toml::<LegacyModEntry>(contents) {
+                Ok(legacy) => legacy,
+                Err(legacy_err) if is_legacy_layout(contents) => return Err(legacy_err),
+                Err(_) => return Err(err),
+            };
             let mut metadata = legacy.metadata.unwrap_or_default();
             if metadata.name.trim().is_empty() {
                 metadata.name = legacy.project_id.clone();
@@ -146,8 +172,19 @@ pub fn parse_mod_toml(contents: &str) -> Result<ModEntry, toml::de::Error> {
                     file_id: legacy.file_id,
                     url: legacy.download_url,
                     hashes: legacy.hashes,
+                    mirrors: Vec::new(),
+                    size: None,
+                    pinned: false,
+                    extra: toml::Table::new(),
                 },
+                extra: toml::Table::new(),
             })
         }
     }
 }
+
+fn is_legacy_layout(contents: &str) -> bool {
+    toml::from_str::<toml::Table>(contents)
+        .map(|table| table.contains_key("source") && !table.contains_key("download"))
+        .unwrap_or(false)
+}
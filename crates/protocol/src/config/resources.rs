@@ -1,5 +1,7 @@
 pub type ResourceEntry = crate::config::mods::ModEntry;
 
-pub fn parse_resource_toml(contents: &str) -> Result<ResourceEntry, toml::de::Error> {
+pub fn parse_resource_toml(
+    contents: &str,
+) -> Result<ResourceEntry, crate::config::ConfigParseError> {
     crate::config::mods::parse_mod_toml(contents)
 }
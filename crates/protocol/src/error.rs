@@ -14,4 +14,16 @@ pub enum ProtocolError {
     InvalidEnum { field: &'static str, value: i32 },
     #[error("hex decode error: {err}")]
     HexDecode { err: &'static str },
+    #[error("unsupported architecture: {0}")]
+    UnsupportedArch(String),
+    #[error("platform filter both includes and excludes {0:?}")]
+    ContradictoryPlatformFilter(crate::Platform),
+    #[error("pack blob exceeds the {limit} limit of {max}")]
+    LimitExceeded { limit: &'static str, max: u64 },
+    #[error("invalid pointer file {path}: {source}")]
+    InvalidPointer {
+        path: String,
+        #[source]
+        source: crate::config::ConfigParseError,
+    },
 }
@@ -3,17 +3,21 @@ pub mod config;
 mod error;
 pub mod pack;
 mod platform;
+pub mod redact;
 mod types;
 mod wire;
 
-pub use crate::codec::{DEFAULT_ZSTD_LEVEL, decode_blob, encode_blob, encode_blob_default};
+pub use crate::codec::{
+    DEFAULT_ZSTD_LEVEL, DecodeLimits, blob_fingerprint, decode_blob, decode_blob_with_limits,
+    encode_blob, encode_blob_default,
+};
 pub use crate::config::*;
 pub use crate::error::ProtocolError;
 pub use crate::pack::*;
-pub use crate::platform::{Platform, PlatformFilter};
+pub use crate::platform::{Platform, PlatformFilter, current_arch, normalize_arch};
 pub use crate::types::{
-    ByteMap, Dependency, DependencyKind, DependencySide, Hash, HashAlgorithm, Loader, Manifest,
-    PackBlob, PackMetadata,
+    ByteMap, Dependency, DependencyChange, DependencyKind, DependencySide, Hash, HashAlgorithm,
+    Loader, Manifest, ManifestDiff, PackBlob, PackMetadata,
 };
 
 #[cfg(test)]
@@ -49,6 +53,7 @@ mod tests {
                     kind: DependencyKind::Mod,
                     side: DependencySide::Both,
                     pointer_path: "mods/example.mod.toml".to_string(),
+                    mirrors: vec!["https://mirror.example.com/mod.jar".to_string()],
                 }],
             },
             files,
@@ -60,6 +65,97 @@ mod tests {
         assert_eq!(blob, decoded);
     }
 
+    #[test]
+    fn fingerprint_ignores_dependency_order_and_compression() {
+        let dependency = |name: &str| Dependency {
+            url: format!("https://example.com/{name}.jar"),
+            hash: Hash {
+                algorithm: HashAlgorithm::Sha256,
+                hex: "deadbeef".to_string(),
+            },
+            platform: PlatformFilter::default(),
+            kind: DependencyKind::Mod,
+            side: DependencySide::Both,
+            pointer_path: format!("mods/{name}.mod.toml"),
+            mirrors: Vec::new(),
+        };
+        let blob = |dependencies: Vec<Dependency>| PackBlob {
+            metadata: PackMetadata {
+                pack_id: "atlas".to_string(),
+                version: "1.2.3".to_string(),
+                minecraft_version: "1.20.1".to_string(),
+                loader: Loader::Fabric,
+                loader_version: "0.15.0".to_string(),
+                name: "Atlas Pack".to_string(),
+                description: "Test pack".to_string(),
+            },
+            manifest: Manifest { dependencies },
+            files: ByteMap::from([
+                ("config/b.toml".to_string(), b"b".repeat(4096)),
+                ("config/a.toml".to_string(), b"a".repeat(4096)),
+            ]),
+        };
+        let first = blob(vec![dependency("alpha"), dependency("beta")]);
+        let second = blob(vec![dependency("beta"), dependency("alpha")]);
+
+        assert_eq!(
+            encode_blob(&first, 3).expect("encode first"),
+            encode_blob(&second, 3).expect("encode second")
+        );
+
+        let fast = encode_blob(&first, 1).expect("encode fast");
+        let small = encode_blob(&second, DEFAULT_ZSTD_LEVEL).expect("encode small");
+        assert_ne!(fast, small);
+        assert_eq!(
+            blob_fingerprint(&decode_blob(&fast).expect("decode fast")).expect("fingerprint"),
+            blob_fingerprint(&decode_blob(&small).expect("decode small")).expect("fingerprint")
+        );
+        assert_eq!(
+            blob_fingerprint(&first).expect("fingerprint"),
+            blob_fingerprint(&second).expect("fingerprint")
+        );
+    }
+
+    #[test]
+    fn manifest_diff_tracks_added_removed_and_updated() {
+        let dependency = |path: &str, hex: &str| Dependency {
+            url: format!("https://example.com/{path}.jar"),
+            hash: Hash {
+                algorithm: HashAlgorithm::Sha256,
+                hex: hex.to_string(),
+            },
+            platform: PlatformFilter::default(),
+            kind: DependencyKind::Mod,
+            side: DependencySide::Both,
+            pointer_path: path.to_string(),
+            mirrors: Vec::new(),
+        };
+
+        let current = Manifest {
+            dependencies: vec![
+                dependency("mods/kept.mod.toml", "aa"),
+                dependency("mods/removed.mod.toml", "bb"),
+                dependency("mods/updated.mod.toml", "cc"),
+            ],
+        };
+        let next = Manifest {
+            dependencies: vec![
+                dependency("mods/kept.mod.toml", "aa"),
+                dependency("mods/updated.mod.toml", "dd"),
+                dependency("mods/added.mod.toml", "ee"),
+            ],
+        };
+
+        let diff = current.diff(&next);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].pointer_path, "mods/added.mod.toml");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].pointer_path, "mods/removed.mod.toml");
+        assert_eq!(diff.updated.len(), 1);
+        assert_eq!(diff.updated[0].after.hash.hex, "dd");
+        assert!(current.diff(&current).is_empty());
+    }
+
     #[test]
     fn platform_filter_allows() {
         let filter = PlatformFilter {
@@ -70,5 +166,236 @@ mod tests {
         assert!(filter.allows(Platform::Linux));
         assert!(!filter.allows(Platform::Windows));
         assert!(!filter.allows(Platform::Macos));
+
+        let built = PlatformFilter::new()
+            .excluding(Platform::Macos)
+            .including(Platform::Linux)
+            .excluding(Platform::Windows)
+            .excluding(Platform::Macos)
+            .build()
+            .expect("valid filter");
+        assert_eq!(built.include, vec![Platform::Linux]);
+        assert_eq!(built.exclude, vec![Platform::Windows, Platform::Macos]);
+        assert!(built.allows(Platform::Linux));
+        assert!(!built.allows(Platform::Windows));
+        assert!(!built.allows(Platform::Macos));
+
+        let exclude_only = PlatformFilter::new()
+            .excluding(Platform::Windows)
+            .excluding(Platform::Windows)
+            .build()
+            .expect("valid filter");
+        assert_eq!(exclude_only.exclude, vec![Platform::Windows]);
+        assert!(exclude_only.allows(Platform::Linux));
+        assert!(!exclude_only.allows(Platform::Windows));
+    }
+
+    #[test]
+    fn contradictory_platform_filters_are_rejected() {
+        let contradictory = PlatformFilter::new()
+            .including(Platform::Linux)
+            .excluding(Platform::Linux);
+        assert!(matches!(
+            contradictory.clone().build(),
+            Err(ProtocolError::ContradictoryPlatformFilter(Platform::Linux))
+        ));
+
+        let wire_filter = crate::wire::PlatformFilter {
+            include: vec![Platform::Linux as i32],
+            exclude: vec![Platform::Linux as i32],
+        };
+        assert!(matches!(
+            PlatformFilter::try_from(wire_filter),
+            Err(ProtocolError::ContradictoryPlatformFilter(Platform::Linux))
+        ));
+
+        let blob = PackBlob {
+            metadata: PackMetadata {
+                pack_id: "atlas".to_string(),
+                version: "1.2.3".to_string(),
+                minecraft_version: "1.20.1".to_string(),
+                loader: Loader::Fabric,
+                loader_version: "0.15.0".to_string(),
+                name: "Atlas Pack".to_string(),
+                description: "Test pack".to_string(),
+            },
+            manifest: Manifest {
+                dependencies: vec![Dependency {
+                    url: "https://example.com/mod.jar".to_string(),
+                    hash: Hash {
+                        algorithm: HashAlgorithm::Sha256,
+                        hex: "deadbeef".to_string(),
+                    },
+                    platform: contradictory,
+                    kind: DependencyKind::Mod,
+                    side: DependencySide::Both,
+                    pointer_path: "mods/example.mod.toml".to_string(),
+                    mirrors: Vec::new(),
+                }],
+            },
+            files: ByteMap::new(),
+        };
+        assert!(encode_blob_default(&blob).is_err());
+    }
+
+    #[test]
+    fn decode_limits_reject_oversized_blobs() {
+        // A zstd frame header declaring an 8 EiB payload, with no data behind it.
+        let mut huge = vec![0x28, 0xb5, 0x2f, 0xfd, 0xc0, 0x00];
+        huge.extend_from_slice(&(1u64 << 63).to_le_bytes());
+        assert!(matches!(
+            decode_blob(&huge),
+            Err(ProtocolError::LimitExceeded { .. })
+        ));
+
+        let blob = PackBlob {
+            metadata: PackMetadata {
+                pack_id: "atlas".to_string(),
+                version: "1.2.3".to_string(),
+                minecraft_version: "1.20.1".to_string(),
+                loader: Loader::Fabric,
+                loader_version: "0.15.0".to_string(),
+                name: "Atlas Pack".to_string(),
+                description: "Test pack".to_string(),
+            },
+            manifest: Manifest {
+                dependencies: Vec::new(),
+            },
+            files: ByteMap::from([
+                ("config/a.toml".to_string(), vec![b'a'; 64]),
+                ("config/b.toml".to_string(), vec![b'b'; 4096]),
+            ]),
+        };
+        let encoded = encode_blob(&blob, 3).expect("encode");
+        let limited = |limits: DecodeLimits| match decode_blob_with_limits(&encoded, &limits) {
+            Err(ProtocolError::LimitExceeded { limit, .. }) => Some(limit),
+            Ok(_) => None,
+            Err(err) => panic!("unexpected error: {err}"),
+        };
+
+        assert_eq!(limited(DecodeLimits::untrusted()), None);
+        assert_eq!(
+            limited(DecodeLimits {
+                max_files: 1,
+                ..DecodeLimits::default()
+            }),
+            Some("file count")
+        );
+        assert_eq!(
+            limited(DecodeLimits {
+                max_file_bytes: 1024,
+                ..DecodeLimits::default()
+            }),
+            Some("file size")
+        );
+        assert_eq!(
+            limited(DecodeLimits {
+                max_decompressed_bytes: 1024,
+                ..DecodeLimits::default()
+            }),
+            Some("decompressed size")
+        );
+    }
+
+    #[test]
+    fn decode_rejects_high_ratio_zstd_streams() {
+        use std::io::Write;
+
+        // 96 MiB of zeros compresses to a few KiB, far past the ratio limit.
+        let mut encoder = zstd::stream::write::Encoder::new(Vec::new(), 1).expect("encoder");
+        let chunk = vec![0u8; 1024 * 1024];
+        for _ in 0..96 {
+            encoder.write_all(&chunk).expect("write chunk");
+        }
+        let bomb = encoder.finish().expect("finish");
+        assert!(bomb.len() < 64 * 1024);
+
+        match decode_blob(&bomb) {
+            Err(ProtocolError::LimitExceeded { limit, max }) => {
+                assert_eq!(limit, "compression ratio");
+                assert_eq!(max, DecodeLimits::default().max_compression_ratio);
+            }
+            other => panic!("expected the ratio guard to fire, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_config_keys_survive_round_trip() {
+        let contents = r#"
+[metadata]
+name = "Pack"
+tagline = "from a newer CLI"
+
+[versions]
+mc = "1.20.1"
+modloader = "fabric"
+modloader_version = "0.15.0"
+
+[experimental]
+parallel_downloads = 4
+regions = ["eu", "us"]
+
+[experimental.preview]
+enabled = true
+"#;
+        let config = config::atlas::parse_config(contents).expect("parse config");
+        assert!(config.cli.is_none());
+        assert!(config.extra.contains_key("experimental"));
+
+        let rendered = toml::to_string(&config).expect("serialize config");
+        assert_eq!(
+            toml::from_str::<toml::Table>(&rendered).expect("reparse"),
+            toml::from_str::<toml::Table>(contents).expect("parse original")
+        );
+
+        let pointer = r#"
+[metadata]
+name = "Sodium"
+loading_priority = 2
+
+[download]
+source = "modrinth"
+project_id = "AANobbMI"
+version = "abc"
+channel = "beta"
+
+[download.hashes]
+sha512 = "deadbeef"
+"#;
+        let entry = config::mods::parse_mod_toml(pointer).expect("parse pointer");
+        let rendered = entry.to_toml_string().expect("serialize pointer");
+        // Missing metadata fields come back with their defaults filled in.
+        let mut expected = toml::from_str::<toml::Table>(pointer).expect("parse original");
+        let metadata = expected["metadata"].as_table_mut().expect("metadata table");
+        metadata.insert("side".into(), "both".into());
+        metadata.insert(
+            "disabled_client_oses".into(),
+            toml::Value::Array(Vec::new()),
+        );
+        assert_eq!(
+            toml::from_str::<toml::Table>(&rendered).expect("reparse"),
+            expected
+        );
+    }
+
+    #[test]
+    fn platform_and_arch_mappings() {
+        assert_eq!(Platform::from_os("linux"), Some(Platform::Linux));
+        assert_eq!(Platform::from_os("macos"), Some(Platform::Macos));
+        assert_eq!(Platform::from_os("windows"), Some(Platform::Windows));
+        assert_eq!(Platform::from_os("freebsd"), None);
+        assert_eq!(
+            Platform::from_os(std::env::consts::OS).unwrap_or(Platform::Linux),
+            Platform::from_current()
+        );
+
+        assert_eq!(normalize_arch("x86_64").unwrap(), "x64");
+        assert_eq!(normalize_arch("amd64").unwrap(), "x64");
+        assert_eq!(normalize_arch("aarch64").unwrap(), "arm64");
+        assert_eq!(normalize_arch("arm64").unwrap(), "arm64");
+        assert!(matches!(
+            normalize_arch("riscv64"),
+            Err(ProtocolError::UnsupportedArch(arch)) if arch == "riscv64"
+        ));
     }
 }
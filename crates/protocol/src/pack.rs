@@ -1,7 +1,7 @@
 use crate::{
     Dependency, DependencyKind, DependencySide, Hash, HashAlgorithm, Manifest, PackBlob,
-    PackMetadata, Platform, PlatformFilter, ProtocolError, config::atlas, config::mods,
-    encode_blob,
+    PackMetadata, Platform, PlatformFilter, ProtocolError, codec::encoded_len, config::atlas,
+    config::mods, encode_blob,
 };
 use std::collections::BTreeMap;
 
@@ -15,6 +15,29 @@ pub struct BuildInput {
 pub struct BuildOutput {
     pub bytes: Vec<u8>,
     pub metadata: PackMetadata,
+    pub stats: BuildStats,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildStats {
+    pub bundled_files: usize,
+    pub bundled_bytes: u64,
+    pub dependencies: usize,
+    /// Summed artifact size of the dependencies whose pointer records one.
+    pub dependency_bytes: u64,
+    pub dependencies_with_size: usize,
+    pub uncompressed_bytes: u64,
+    pub compressed_bytes: u64,
+}
+
+impl BuildStats {
+    /// Compressed size as a fraction of the encoded payload.
+    pub fn compression_ratio(&self) -> f64 {
+        if self.uncompressed_bytes == 0 {
+            return 1.0;
+        }
+        self.compressed_bytes as f64 / self.uncompressed_bytes as f64
+    }
 }
 
 pub fn build_pack_bytes(input: BuildInput, zstd_level: i32) -> Result<BuildOutput, ProtocolError> {
@@ -35,7 +58,7 @@ pub fn build_pack_bytes(input: BuildInput, zstd_level: i32) -> Result<BuildOutpu
         description: input.config.metadata.description.unwrap_or_default(),
     };
 
-    let manifest = build_manifest(&input.files)?;
+    let (manifest, sizes) = build_manifest(&input.files)?;
 
     let blob = PackBlob {
         metadata: metadata.clone(),
@@ -44,14 +67,28 @@ pub fn build_pack_bytes(input: BuildInput, zstd_level: i32) -> Result<BuildOutpu
     };
 
     let encoded = encode_blob(&blob, zstd_level)?;
+    let stats = BuildStats {
+        bundled_files: blob.files.len(),
+        bundled_bytes: blob.files.values().map(|bytes| bytes.len() as u64).sum(),
+        dependencies: blob.manifest.dependencies.len(),
+        dependency_bytes: sizes.iter().sum(),
+        dependencies_with_size: sizes.len(),
+        uncompressed_bytes: encoded_len(&blob)? as u64,
+        compressed_bytes: encoded.len() as u64,
+    };
     Ok(BuildOutput {
         bytes: encoded,
         metadata,
+        stats,
     })
 }
 
-fn build_manifest(files: &BTreeMap<String, Vec<u8>>) -> Result<Manifest, ProtocolError> {
+// Also returns the artifact sizes recorded by pointers, for the build report.
+fn build_manifest(
+    files: &BTreeMap<String, Vec<u8>>,
+) -> Result<(Manifest, Vec<u64>), ProtocolError> {
     let mut dependencies = Vec::new();
+    let mut sizes = Vec::new();
 
     for (path, bytes) in files {
         let Some(kind) = dependency_kind_from_path(path) else {
@@ -60,8 +97,11 @@ fn build_manifest(files: &BTreeMap<String, Vec<u8>>) -> Result<Manifest, Protoco
 
         let contents =
             std::str::from_utf8(bytes).map_err(|_| ProtocolError::MissingField("pointer.toml"))?;
-        let entry = mods::parse_mod_toml(contents)
-            .map_err(|_| ProtocolError::MissingField("pointer.toml"))?;
+        let entry =
+            mods::parse_mod_toml(contents).map_err(|source| ProtocolError::InvalidPointer {
+                path: path.clone(),
+                source,
+            })?;
 
         let url = entry
             .download
@@ -74,7 +114,20 @@ fn build_manifest(files: &BTreeMap<String, Vec<u8>>) -> Result<Manifest, Protoco
             .ok_or(ProtocolError::MissingField("download.hashes"))?;
 
         let (side, platform) = map_side_and_platform(&entry.metadata);
-
+        let mut mirrors = Vec::new();
+        for mirror in entry
+            .download
+            .mirrors
+            .iter()
+            .cloned()
+            .chain(alternate_download_urls(&url))
+        {
+            if mirror != url && !mirror.trim().is_empty() && !mirrors.contains(&mirror) {
+                mirrors.push(mirror);
+            }
+        }
+
+        sizes.extend(entry.download.size);
         dependencies.push(Dependency {
             url,
             hash,
@@ -82,10 +135,28 @@ fn build_manifest(files: &BTreeMap<String, Vec<u8>>) -> Result<Manifest, Protoco
             kind,
             side,
             pointer_path: path.clone(),
+            mirrors,
         });
     }
 
-    Ok(Manifest { dependencies })
+    Ok((Manifest { dependencies }, sizes))
+}
+
+// Hosts that serve identical files under the same path.
+const MIRRORED_HOSTS: &[&[&str]] = &[&["edge.forgecdn.net", "mediafilez.forgecdn.net"]];
+
+/// Known CDN alternates for a download URL (e.g. CurseForge's edge and media hosts).
+pub fn alternate_download_urls(url: &str) -> Vec<String> {
+    let Some(rest) = url.strip_prefix("https://") else {
+        return Vec::new();
+    };
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    MIRRORED_HOSTS
+        .iter()
+        .filter(|group| group.contains(&host))
+        .flat_map(|group| group.iter().filter(|alt| **alt != host))
+        .map(|alt| format!("https://{alt}/{path}"))
+        .collect()
 }
 
 fn dependency_kind_from_path(path: &str) -> Option<DependencyKind> {
@@ -127,22 +198,16 @@ fn map_side_and_platform(metadata: &mods::ModMetadata) -> (DependencySide, Platf
         mods::ModSide::Server => DependencySide::Server,
         mods::ModSide::Both => DependencySide::Both,
     };
-    let mut exclude = Vec::new();
-
-    for os in &metadata.disabled_client_oses {
-        let platform = match os {
+    let mut platform = metadata
+        .disabled_client_oses
+        .iter()
+        .map(|os| match os {
             mods::ClientOs::Windows => Platform::Windows,
             mods::ClientOs::Linux => Platform::Linux,
             mods::ClientOs::Macos => Platform::Macos,
-        };
-        exclude.push(platform);
-    }
+        })
+        .fold(PlatformFilter::new(), PlatformFilter::excluding);
+    platform.normalize();
 
-    (
-        side,
-        PlatformFilter {
-            include: Vec::new(),
-            exclude,
-        },
-    )
+    (side, platform)
 }
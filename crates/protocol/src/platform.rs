@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::ProtocolError;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ::prost::Enumeration)]
 #[repr(i32)]
 #[serde(rename_all = "snake_case")]
@@ -9,6 +11,42 @@ pub enum Platform {
     Macos = 2,
 }
 
+impl Platform {
+    /// Maps a `std::env::consts::OS` value.
+    pub fn from_os(os: &str) -> Option<Self> {
+        match os {
+            "windows" => Some(Self::Windows),
+            "linux" => Some(Self::Linux),
+            "macos" => Some(Self::Macos),
+            _ => None,
+        }
+    }
+
+    /// The platform this binary was built for. Other Unix-likes are treated as Linux.
+    pub fn from_current() -> Self {
+        if cfg!(target_os = "windows") {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::Macos
+        } else {
+            Self::Linux
+        }
+    }
+}
+
+/// Maps a `std::env::consts::ARCH` value to the name used by distribution releases.
+pub fn normalize_arch(arch: &str) -> Result<&'static str, ProtocolError> {
+    match arch {
+        "x86_64" | "amd64" | "x64" => Ok("x64"),
+        "aarch64" | "arm64" => Ok("arm64"),
+        other => Err(ProtocolError::UnsupportedArch(other.to_string())),
+    }
+}
+
+pub fn current_arch() -> Result<&'static str, ProtocolError> {
+    normalize_arch(std::env::consts::ARCH)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PlatformFilter {
     pub include: Vec<Platform>,
@@ -25,6 +63,47 @@ impl Default for PlatformFilter {
 }
 
 impl PlatformFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn including(mut self, platform: Platform) -> Self {
+        self.include.push(platform);
+        self
+    }
+
+    pub fn excluding(mut self, platform: Platform) -> Self {
+        self.exclude.push(platform);
+        self
+    }
+
+    /// Normalizes and validates the filter.
+    pub fn build(mut self) -> Result<Self, ProtocolError> {
+        self.normalize();
+        self.validate()?;
+        Ok(self)
+    }
+
+    /// Rejects a platform listed in both `include` and `exclude`.
+    pub fn validate(&self) -> Result<(), ProtocolError> {
+        match self
+            .include
+            .iter()
+            .find(|platform| self.exclude.contains(platform))
+        {
+            Some(platform) => Err(ProtocolError::ContradictoryPlatformFilter(*platform)),
+            None => Ok(()),
+        }
+    }
+
+    /// Sorts both lists and removes duplicates so equal filters compare and encode equally.
+    pub fn normalize(&mut self) {
+        for list in [&mut self.include, &mut self.exclude] {
+            list.sort_by_key(|platform| *platform as i32);
+            list.dedup();
+        }
+    }
+
     pub fn allows(&self, platform: Platform) -> bool {
         if !self.include.is_empty() && !self.include.contains(&platform) {
             return false;
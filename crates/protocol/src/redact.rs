@@ -0,0 +1,265 @@
+use std::io::{self, Write};
+
+use serde_json::Value;
+
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Matched case-insensitively as substrings of JSON keys (ignoring `_` and `-`,
+/// so `accessToken` matches too) and of `key: value` / `key=value` pairs in text.
+pub const SENSITIVE_KEYS: &[&str] = &[
+    "access_token",
+    "refresh_token",
+    "authorization",
+    "id_token",
+    "service_token",
+    "service-token",
+    "deploy_key",
+    "deploy-token",
+    "oidc-token",
+    "proof",
+    "linkcode",
+    "password",
+    "secret",
+    "apikey",
+    "api_key",
+];
+
+pub fn is_sensitive_key(key: &str) -> bool {
+    let key = normalize_key(key);
+    SENSITIVE_KEYS
+        .iter()
+        .any(|candidate| key.contains(&normalize_key(candidate)))
+}
+
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(|ch| *ch != '_' && *ch != '-')
+        .map(|ch| ch.to_ascii_lowercase())
+        .collect()
+}
+
+/// Masks bearer tokens and sensitive key/value pairs line by line, keeping the
+/// line structure of the input.
+pub fn redact_text(input: &str) -> String {
+    input
+        .split('\n')
+        .map(redact_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Replaces values under sensitive keys and masks string values like
+/// `redact_text`.
+pub fn redact_json(mut value: Value) -> Value {
+    redact_json_in_place(&mut value);
+    value
+}
+
+fn redact_json_in_place(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, next) in map.iter_mut() {
+                if is_sensitive_key(key) {
+                    *next = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json_in_place(next);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                redact_json_in_place(item);
+            }
+        }
+        Value::String(text) => {
+            *text = redact_line(text);
+        }
+        _ => {}
+    }
+}
+
+fn redact_line(line: &str) -> String {
+    let redacted = redact_bearer_token(line);
+    SENSITIVE_KEYS
+        .iter()
+        .fold(redacted, |out, key| redact_kv_value(&out, key))
+}
+
+fn redact_bearer_token(line: &str) -> String {
+    let marker = "bearer ";
+    let lower = line.to_ascii_lowercase();
+    let Some(start) = lower.find(marker) else {
+        return line.to_string();
+    };
+    let value_start = start + marker.len();
+    let token_end = line[value_start..]
+        .find(|c: char| c.is_whitespace() || c == ',' || c == '"' || c == '\'')
+        .map(|offset| value_start + offset)
+        .unwrap_or(line.len());
+    let mut out = String::new();
+    out.push_str(&line[..value_start]);
+    out.push_str(REDACTED);
+    out.push_str(&line[token_end..]);
+    out
+}
+
+fn redact_kv_value(line: &str, key: &str) -> String {
+    let lower = line.to_ascii_lowercase();
+    let key_lower = key.to_ascii_lowercase();
+    let mut cursor = 0usize;
+    let mut out = String::new();
+
+    while let Some(found) = lower[cursor..].find(&key_lower) {
+        let key_start = cursor + found;
+        let key_end = key_start + key_lower.len();
+        out.push_str(&line[cursor..key_end]);
+
+        let mut value_start = key_end;
+        while let Some(ch) = line[value_start..].chars().next() {
+            if ch.is_whitespace() {
+                value_start += ch.len_utf8();
+                continue;
+            }
+            if ch == ':' || ch == '=' {
+                value_start += ch.len_utf8();
+                break;
+            }
+            out.push_str(&line[key_end..]);
+            return out;
+        }
+
+        while let Some(ch) = line[value_start..].chars().next() {
+            if ch.is_whitespace() {
+                value_start += ch.len_utf8();
+            } else {
+                break;
+            }
+        }
+
+        let value_end = line[value_start..]
+            .find(|c: char| c.is_whitespace() || c == ',' || c == ';' || c == '"' || c == '\'')
+            .map(|offset| value_start + offset)
+            .unwrap_or(line.len());
+        out.push_str(&line[key_end..value_start]);
+        out.push_str(REDACTED);
+        cursor = value_end;
+    }
+
+    out.push_str(&line[cursor..]);
+    out
+}
+
+/// Buffers writes and forwards each completed line through `redact_text`, so
+/// log sinks such as `tracing_subscriber`'s fmt layer never see raw secrets.
+pub struct RedactingWriter<W: Write> {
+    inner: W,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> RedactingWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buffer: Vec::new(),
+        }
+    }
+
+    fn write_redacted(&mut self, bytes: &[u8]) -> io::Result<()> {
+        let text = String::from_utf8_lossy(bytes);
+        self.inner.write_all(redact_text(&text).as_bytes())
+    }
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if let Some(pos) = self.buffer.iter().rposition(|b| *b == b'\n') {
+            let lines: Vec<u8> = self.buffer.drain(..=pos).collect();
+            self.write_redacted(&lines)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            let rest = std::mem::take(&mut self.buffer);
+            self.write_redacted(&rest)?;
+        }
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Drop for RedactingWriter<W> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn masks_bearer_tokens() {
+        assert_eq!(
+            redact_text("Authorization header: Bearer abc.def.ghi sent"),
+            "Authorization header: Bearer [REDACTED] sent"
+        );
+        assert_eq!(redact_text("\"bearer tok-1\""), "\"bearer [REDACTED]\"");
+    }
+
+    #[test]
+    fn masks_key_value_pairs_in_text() {
+        let redacted = redact_text(
+            "refresh_token=refresh-456; password : hunter2\nx-atlas-service-token: svc-1\nplain line",
+        );
+        assert_eq!(
+            redacted,
+            "refresh_token=[REDACTED]; password : [REDACTED]\nx-atlas-service-token: [REDACTED]\nplain line"
+        );
+    }
+
+    #[test]
+    fn leaves_keys_without_values_alone() {
+        let text = "the password field is required";
+        assert_eq!(redact_text(text), text);
+    }
+
+    #[test]
+    fn masks_sensitive_json_keys_and_nested_strings() {
+        let value = json!({
+            "accessToken": "abc",
+            "profile": { "clientSecret": "xyz", "name": "Steve" },
+            "events": ["deploy_key=dk-1", "ok"],
+            "count": 3
+        });
+        assert_eq!(
+            redact_json(value),
+            json!({
+                "accessToken": "[REDACTED]",
+                "profile": { "clientSecret": "[REDACTED]", "name": "Steve" },
+                "events": ["deploy_key=[REDACTED]", "ok"],
+                "count": 3
+            })
+        );
+        assert_eq!(
+            redact_json(json!({ "service_token": { "value": "s" } })),
+            json!({ "service_token": "[REDACTED]" })
+        );
+    }
+
+    #[test]
+    fn redacting_writer_masks_complete_and_trailing_lines() {
+        let mut out = Vec::new();
+        {
+            let mut writer = RedactingWriter::new(&mut out);
+            writer.write_all(b"auth: Bearer t").unwrap();
+            writer.write_all(b"ok123\napi_key=k").unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "auth: Bearer [REDACTED]\napi_key=[REDACTED]"
+        );
+    }
+}
@@ -37,6 +37,60 @@ impl Default for Manifest {
     }
 }
 
+impl Manifest {
+    pub fn diff(&self, next: &Manifest) -> ManifestDiff {
+        let current = self
+            .dependencies
+            .iter()
+            .map(|dependency| (dependency.pointer_path.as_str(), dependency))
+            .collect::<BTreeMap<_, _>>();
+        let upcoming = next
+            .dependencies
+            .iter()
+            .map(|dependency| (dependency.pointer_path.as_str(), dependency))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut diff = ManifestDiff::default();
+        for (path, after) in &upcoming {
+            match current.get(path) {
+                None => diff.added.push((*after).clone()),
+                Some(before) if before.url != after.url || before.hash != after.hash => {
+                    diff.updated.push(DependencyChange {
+                        before: (*before).clone(),
+                        after: (*after).clone(),
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        for (path, before) in &current {
+            if !upcoming.contains_key(path) {
+                diff.removed.push((*before).clone());
+            }
+        }
+        diff
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ManifestDiff {
+    pub added: Vec<Dependency>,
+    pub removed: Vec<Dependency>,
+    pub updated: Vec<DependencyChange>,
+}
+
+impl ManifestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyChange {
+    pub before: Dependency,
+    pub after: Dependency,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Dependency {
     pub url: String,
@@ -45,6 +99,8 @@ pub struct Dependency {
     pub kind: DependencyKind,
     pub side: DependencySide,
     pub pointer_path: String,
+    #[serde(default)]
+    pub mirrors: Vec<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -69,6 +69,7 @@ impl From<&crate::types::Dependency> for Dependency {
             kind: value.kind as i32,
             side: value.side as i32,
             pointer_path: value.pointer_path.clone(),
+            mirrors: value.mirrors.clone(),
         }
     }
 }
@@ -96,6 +97,7 @@ impl TryFrom<Dependency> for crate::types::Dependency {
             kind,
             side,
             pointer_path: value.pointer_path,
+            mirrors: value.mirrors,
         })
     }
 }
@@ -122,6 +124,8 @@ impl TryFrom<Hash> for crate::types::Hash {
 
 impl From<&crate::platform::PlatformFilter> for PlatformFilter {
     fn from(value: &crate::platform::PlatformFilter) -> Self {
+        let mut value = value.clone();
+        value.normalize();
         Self {
             include: value
                 .include
@@ -141,10 +145,12 @@ impl TryFrom<PlatformFilter> for crate::platform::PlatformFilter {
     type Error = ProtocolError;
 
     fn try_from(value: PlatformFilter) -> Result<Self, Self::Error> {
-        Ok(Self {
+        let filter = Self {
             include: decode_platforms("platform_filter.include", value.include)?,
             exclude: decode_platforms("platform_filter.exclude", value.exclude)?,
-        })
+        };
+        filter.validate()?;
+        Ok(filter)
     }
 }
 
@@ -9,6 +9,9 @@ pub enum CoreError {
     #[error("unsupported protocol version: client={client} daemon={daemon}")]
     UnsupportedProtocol { client: u32, daemon: u32 },
 
+    #[error("protocol version mismatch: client={client} daemon={daemon}")]
+    VersionMismatch { client: u32, daemon: u32 },
+
     #[error("invalid configuration: {0}")]
     InvalidConfig(String),
 
@@ -55,6 +58,16 @@ impl From<CoreError> for RpcError {
                 .into_iter()
                 .collect(),
             },
+            CoreError::VersionMismatch { client, daemon } => RpcError {
+                code: ErrorCode::VersionMismatch,
+                message: e.to_string(),
+                details: [
+                    ("client_protocol".into(), client.to_string()),
+                    ("daemon_protocol".into(), daemon.to_string()),
+                ]
+                .into_iter()
+                .collect(),
+            },
             CoreError::InvalidConfig(_) => RpcError {
                 code: ErrorCode::InvalidConfig,
                 message: e.to_string(),
@@ -1,8 +1,14 @@
 mod errors;
 pub mod proto;
 
+/// Wire protocol major version. Additive changes keep it; breaking changes bump it.
 pub const PROTOCOL_VERSION: u32 = 1;
 
+/// Clients and daemons can talk to each other when they share a major version.
+pub fn protocol_compatible(client: u32, daemon: u32) -> bool {
+    client == daemon
+}
+
 pub fn pkg_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
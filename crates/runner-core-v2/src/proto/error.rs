@@ -10,18 +10,27 @@ pub struct RpcError {
     pub details: BTreeMap<String, String>,
 }
 
+impl RpcError {
+    pub fn version_mismatch(client: u32, daemon: u32) -> Self {
+        crate::errors::CoreError::VersionMismatch { client, daemon }.into()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ErrorCode {
     BadRequest,
     UnsupportedProtocol,
+    VersionMismatch,
 
     DaemonBusy,
     ServerAlreadyRunning,
     ServerNotRunning,
+    EulaNotAccepted,
 
     UnknownProfile,
     InvalidConfig,
     IoError,
+    PermissionDenied,
 
     Internal,
 }
@@ -9,8 +9,29 @@ pub enum Event {
     Log(LogLine),
     Status(ServerStatus),
     Lifecycle(LifecycleEvent),
-    RconOut { session: SessionId, text: String },
-    RconErr { session: SessionId, text: String },
+    RconOut {
+        session: SessionId,
+        text: String,
+    },
+    RconErr {
+        session: SessionId,
+        text: String,
+    },
+    /// Provisioning progress for an in-flight `Start`; the `Response` still marks completion.
+    StartProgress {
+        phase: String,
+        message: String,
+        current: u64,
+        total: u64,
+    },
+    PlayerJoined {
+        name: String,
+        online: u32,
+    },
+    PlayerLeft {
+        name: String,
+        online: u32,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +44,9 @@ pub enum Outbound {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Envelope<T> {
     pub id: RequestId,
+    /// Server profile a request targets; `None` means `DEFAULT_PROFILE`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<ProfileId>,
     pub payload: T,
 }
 
@@ -36,6 +60,9 @@ pub enum Request {
 
     Status {},
 
+    /// Every profile the daemon manages, from cached state only.
+    ListServers {},
+
     Start {
         profile: ProfileId,
         #[serde(default)]
@@ -49,12 +76,25 @@ pub enum Request {
 
     Shutdown {},
 
+    /// `lines` caps the result; `since_ms`/`until_ms` bound `LogLine::at_ms` (inclusive).
     LogsTail {
         lines: usize,
+        #[serde(default)]
+        since_ms: Option<UnixMillis>,
+        #[serde(default)]
+        until_ms: Option<UnixMillis>,
+        #[serde(default)]
+        filter: Option<LogFilter>,
     },
 
     DaemonLogsTail {
         lines: usize,
+        #[serde(default)]
+        since_ms: Option<UnixMillis>,
+        #[serde(default)]
+        until_ms: Option<UnixMillis>,
+        #[serde(default)]
+        filter: Option<LogFilter>,
     },
 
     /// Request the daemon to create a manual backup of the current server (if configured).
@@ -79,6 +119,39 @@ pub enum Request {
         session: SessionId,
     },
 
+    AcceptEula {
+        #[serde(default)]
+        server_root: Option<String>,
+    },
+
+    SyncWhitelist {},
+
+    Metrics {},
+
+    /// Clears the crash-loop flag without starting the server.
+    ClearCrashLoop {},
+
+    /// Holds the server stopped across daemon restarts, auto-start and crash recovery.
+    SetMaintenance {
+        on: bool,
+    },
+
+    /// Switches the deploy channel and reconciles a running server to its latest build.
+    SetChannel {
+        channel: String,
+    },
+
+    /// Pins the deployed build to `version`; `None` follows the channel again.
+    SetPinnedVersion {
+        #[serde(default)]
+        version: Option<String>,
+    },
+
+    GetServerProperties {},
+    SetServerProperties {
+        changes: BTreeMap<String, String>,
+    },
+
     SaveDeployKey {
         hub_url: String,
         pack_id: String,
@@ -89,11 +162,25 @@ pub enum Request {
     },
 }
 
+/// Server-side filter for log tails. A line matches when it satisfies every
+/// set criterion; `context` keeps that many lines around each match, like
+/// `grep -C`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogFilter {
+    #[serde(default)]
+    pub contains: Option<String>,
+    #[serde(default)]
+    pub regex: Option<String>,
+    #[serde(default)]
+    pub context: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Topic {
     Logs,
     Status,
     Lifecycle,
+    Players,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +194,28 @@ pub enum Response {
     Status {
         daemon: DaemonStatus,
         server: ServerStatus,
+        #[serde(default)]
+        players: PlayersOnline,
+        #[serde(default)]
+        performance: Box<PerformanceSummary>,
+        #[serde(default)]
+        maintenance: bool,
+        #[serde(default)]
+        next_restart_at_ms: Option<UnixMillis>,
+        #[serde(default)]
+        pinned_version: Option<String>,
+        #[serde(default)]
+        crash_looping: bool,
+        #[serde(default)]
+        last_crash: Option<Box<CrashInfo>>,
+        #[serde(default)]
+        last_provision: Option<Box<ProvisionSummary>>,
+        #[serde(default)]
+        last_exit: Option<Box<LastExit>>,
+    },
+
+    ServerList {
+        servers: Vec<ServerSummary>,
     },
 
     Started {
@@ -117,6 +226,9 @@ pub enum Response {
     Stopped {
         exit: Option<ExitInfo>,
         stopped_at_ms: UnixMillis,
+        /// The server was killed, either on request or after the grace period ran out.
+        #[serde(default)]
+        forced: bool,
     },
 
     LogsTail {
@@ -144,6 +256,48 @@ pub enum Response {
 
     DeployKeySaved {},
 
+    EulaAccepted {
+        path: String,
+        recorded: bool,
+    },
+
+    CrashLoopCleared {},
+
+    MaintenanceSet {
+        on: bool,
+        starting: bool,
+    },
+    PinnedVersionSet {
+        version: Option<String>,
+    },
+    ChannelSet {
+        channel: String,
+        reconciling: bool,
+    },
+
+    Metrics {
+        summary: PerformanceSummary,
+        history: Vec<PerformanceSample>,
+    },
+
+    WhitelistSynced {
+        added: Vec<String>,
+        removed: Vec<String>,
+        written_directly: Vec<String>,
+        #[serde(default)]
+        operators_added: Vec<String>,
+        #[serde(default)]
+        operators_removed: Vec<String>,
+    },
+
+    ServerProperties {
+        properties: BTreeMap<String, String>,
+    },
+    ServerPropertiesUpdated {
+        changed: Vec<String>,
+        restart_required: bool,
+    },
+
     /// Response for a manual backup request containing the created backup path.
     BackupCreated {
         path: String,
@@ -152,6 +306,96 @@ pub enum Response {
     Error(RpcError),
 }
 
+/// One row of `ListServers`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerSummary {
+    pub profile: ProfileId,
+    /// `idle`, `starting`, `running`, `stopping`, `exited` or `crashed`.
+    pub state: String,
+    pub pid: Option<i32>,
+    pub uptime_ms: Option<u64>,
+    pub pack_id: Option<String>,
+    pub channel: Option<String>,
+    pub version: Option<String>,
+    pub port: Option<u16>,
+    pub players: u32,
+    pub maintenance: bool,
+    pub crash_looping: bool,
+}
+
+/// Players currently online, as tracked from join/leave log lines.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayersOnline {
+    pub count: u32,
+    pub names: Vec<String>,
+}
+
+/// Details of the most recent unexpected server exit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashInfo {
+    pub exit_code: Option<i32>,
+    pub at_ms: UnixMillis,
+    pub uptime_ms: u64,
+    pub log_tail: Vec<String>,
+}
+
+/// Why the server process exited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitReason {
+    /// Stopped on request, or exited on its own with code 0.
+    Graceful,
+    Crash,
+    OutOfMemory,
+    /// Killed by the daemon, on request or after the stop grace period.
+    ForceKilled,
+}
+
+/// The most recent server process exit, kept until the next one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastExit {
+    pub exit: ExitInfo,
+    pub reason: ExitReason,
+    pub at_ms: UnixMillis,
+}
+
+/// Where the time went during the most recent pack apply.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvisionSummary {
+    pub at_ms: UnixMillis,
+    pub total_ms: u64,
+    /// Phases in the order they ran (`decode`, `java`, `write_files`, ...).
+    pub phases: Vec<ProvisionPhase>,
+    pub files_written: u64,
+    pub artifacts_linked: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvisionPhase {
+    pub name: String,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceSample {
+    pub at_ms: UnixMillis,
+    pub tps: f64,
+    /// Not every loader's command reports tick time (e.g. Paper `tps`).
+    pub mspt: Option<f64>,
+}
+
+/// Latest TPS/MSPT sample plus averages over the daemon's rolling history.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PerformanceSummary {
+    pub latest: Option<PerformanceSample>,
+    pub avg_tps: Option<f64>,
+    pub avg_mspt: Option<f64>,
+    /// RCON command that produced the samples.
+    pub command: Option<String>,
+    /// Set when no known performance command worked for this server.
+    pub unsupported: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DaemonStatus {
     pub daemon_version: String,
@@ -5,6 +5,9 @@ pub type UnixMillis = u64;
 
 pub type ProfileId = String;
 
+/// Profile used when a request names none, so single-server clients keep working.
+pub const DEFAULT_PROFILE: &str = "default";
+
 pub type SessionId = u64;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -19,3 +22,70 @@ pub struct LogLine {
     pub stream: LogStream,
     pub line: String,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLine {
+    /// Level from the usual Minecraft/log4j markers (`[ERROR]`,
+    /// `[Server thread/WARN]`, `[12:00:00 INFO]`). Unmarked stderr counts as
+    /// an error; other unmarked lines have no level.
+    pub fn level(&self) -> Option<LogLevel> {
+        const MARKERS: &[(&str, LogLevel)] = &[
+            ("FATAL", LogLevel::Error),
+            ("ERROR", LogLevel::Error),
+            ("WARN", LogLevel::Warn),
+            ("WARNING", LogLevel::Warn),
+            ("INFO", LogLevel::Info),
+            ("DEBUG", LogLevel::Debug),
+            ("TRACE", LogLevel::Debug),
+        ];
+        for bracket in self.line.split('[').skip(1) {
+            let Some(tag) = bracket.split(']').next() else {
+                continue;
+            };
+            let tag = tag.rsplit(['/', ' ']).next().unwrap_or(tag);
+            if let Some((_, level)) = MARKERS.iter().find(|(marker, _)| *marker == tag) {
+                return Some(*level);
+            }
+        }
+        match self.stream {
+            LogStream::Stderr => Some(LogLevel::Error),
+            LogStream::Stdout => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(stream: LogStream, text: &str) -> LogLine {
+        LogLine {
+            at_ms: 0,
+            stream,
+            line: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn detects_levels_from_common_log_formats() {
+        let level = |text: &str| line(LogStream::Stdout, text).level();
+        assert_eq!(
+            level("[12:00:00] [Server thread/WARN]: Can't keep up!"),
+            Some(LogLevel::Warn)
+        );
+        assert_eq!(level("[12:00:00 ERROR]: boom"), Some(LogLevel::Error));
+        assert_eq!(level("[INFO] Done (3.2s)!"), Some(LogLevel::Info));
+        assert_eq!(level("plain output"), None);
+        assert_eq!(
+            line(LogStream::Stderr, "at java.base/Thread.run").level(),
+            Some(LogLevel::Error)
+        );
+    }
+}
@@ -1,13 +1,17 @@
 use futures_util::{SinkExt, StreamExt};
-use tokio::io;
+use tokio::io::{self, AsyncRead, AsyncWrite};
 use tokio_util::codec::{Framed, LengthDelimitedCodec};
 
 use runner_core_v2::proto::{Envelope, Outbound, Request, Response};
 
-pub type FramedStream = Framed<tokio::net::UnixStream, LengthDelimitedCodec>;
+pub trait IpcStream: AsyncRead + AsyncWrite + Unpin + Send {}
 
-pub fn framed(stream: tokio::net::UnixStream) -> FramedStream {
-    Framed::new(stream, LengthDelimitedCodec::new())
+impl<T: AsyncRead + AsyncWrite + Unpin + Send> IpcStream for T {}
+
+pub type FramedStream = Framed<Box<dyn IpcStream>, LengthDelimitedCodec>;
+
+pub fn framed(stream: impl IpcStream + 'static) -> FramedStream {
+    Framed::new(Box::new(stream), LengthDelimitedCodec::new())
 }
 
 pub async fn send_request(framed: &mut FramedStream, req: &Envelope<Request>) -> io::Result<()> {
@@ -0,0 +1,211 @@
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use tokio::net::{TcpListener, TcpStream, UnixListener};
+
+use runner_v2_utils::{ipc_endpoint, IpcEndpoint, RuntimePaths};
+
+use crate::framing::{framed, FramedStream};
+use crate::socket;
+
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+const HANDSHAKE_ACK: &[u8] = b"ok";
+
+pub fn endpoint(paths: &RuntimePaths) -> io::Result<IpcEndpoint> {
+    ipc_endpoint(paths).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+}
+
+pub fn describe(endpoint: &IpcEndpoint) -> String {
+    match endpoint {
+        IpcEndpoint::Unix(path) => path.display().to_string(),
+        IpcEndpoint::Tcp(addr) => format!("tcp://{addr}"),
+    }
+}
+
+/// Connects to the daemon, presenting the token first on TCP.
+pub async fn connect(paths: &RuntimePaths) -> io::Result<FramedStream> {
+    match endpoint(paths)? {
+        IpcEndpoint::Unix(path) => Ok(framed(socket::connect(&path).await?)),
+        IpcEndpoint::Tcp(addr) => {
+            let mut stream = framed(TcpStream::connect(addr).await?);
+            let token = read_token(&paths.token_path)?;
+            let ack = tokio::time::timeout(HANDSHAKE_TIMEOUT, async {
+                stream.send(token.into_bytes().into()).await?;
+                stream.next().await.transpose()
+            })
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "IPC handshake timed out"))??;
+            match ack {
+                Some(frame) if frame.as_ref() == HANDSHAKE_ACK => Ok(stream),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    "daemon rejected the IPC token",
+                )),
+            }
+        }
+    }
+}
+
+/// Whether something is accepting connections on the endpoint.
+pub async fn alive(paths: &RuntimePaths) -> bool {
+    match endpoint(paths) {
+        Ok(IpcEndpoint::Unix(path)) => socket::socket_alive(&path).await,
+        Ok(IpcEndpoint::Tcp(addr)) => TcpStream::connect(addr).await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+pub enum Listener {
+    Unix(UnixListener),
+    Tcp {
+        listener: TcpListener,
+        token: String,
+    },
+}
+
+/// An accepted connection that has not been authenticated yet. The handshake
+/// runs on the connection's own task so a slow client cannot stall `accept`.
+pub struct Incoming {
+    stream: FramedStream,
+    token: Option<String>,
+}
+
+impl Listener {
+    /// Binds the endpoint. TCP listeners write a fresh token (mode 0600) that
+    /// clients must send as their first frame.
+    pub async fn bind(paths: &RuntimePaths) -> io::Result<Self> {
+        match endpoint(paths)? {
+            IpcEndpoint::Unix(path) => Ok(Self::Unix(socket::bind(&path).await?)),
+            IpcEndpoint::Tcp(addr) => {
+                let token = generate_token()?;
+                write_token(&paths.token_path, &token)?;
+                Ok(Self::Tcp {
+                    listener: TcpListener::bind(addr).await?,
+                    token,
+                })
+            }
+        }
+    }
+
+    pub async fn accept(&self) -> io::Result<Incoming> {
+        match self {
+            Self::Unix(listener) => {
+                let (stream, _addr) = listener.accept().await?;
+                Ok(Incoming {
+                    stream: framed(stream),
+                    token: None,
+                })
+            }
+            Self::Tcp { listener, token } => {
+                let (stream, _addr) = listener.accept().await?;
+                let _ = stream.set_nodelay(true);
+                Ok(Incoming {
+                    stream: framed(stream),
+                    token: Some(token.clone()),
+                })
+            }
+        }
+    }
+}
+
+impl Incoming {
+    pub async fn handshake(self) -> io::Result<FramedStream> {
+        let Self { mut stream, token } = self;
+        let Some(token) = token else {
+            return Ok(stream);
+        };
+
+        let frame = tokio::time::timeout(HANDSHAKE_TIMEOUT, stream.next())
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "IPC handshake timed out"))?
+            .transpose()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "socket closed"))?;
+        if !tokens_match(&frame, token.as_bytes()) {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "client presented an invalid IPC token",
+            ));
+        }
+        stream.send(HANDSHAKE_ACK.to_vec().into()).await?;
+        Ok(stream)
+    }
+}
+
+fn tokens_match(presented: &[u8], expected: &[u8]) -> bool {
+    presented.len() == expected.len()
+        && presented
+            .iter()
+            .zip(expected)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn generate_token() -> io::Result<String> {
+    let mut bytes = [0u8; 32];
+    std::fs::File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+fn write_token(path: &Path, token: &str) -> io::Result<()> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    // Replace rather than truncate so a pre-existing file's looser mode is not kept.
+    let _ = std::fs::remove_file(path);
+    let mut file = std::fs::OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .mode(0o600)
+        .open(path)?;
+    file.write_all(token.as_bytes())
+}
+
+fn read_token(path: &Path) -> io::Result<String> {
+    std::fs::read_to_string(path)
+        .map(|token| token.trim().to_string())
+        .map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("failed to read IPC token at {}: {}", path.display(), err),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_comparison_requires_exact_match() {
+        assert!(tokens_match(b"abcd", b"abcd"));
+        assert!(!tokens_match(b"abce", b"abcd"));
+        assert!(!tokens_match(b"abc", b"abcd"));
+    }
+
+    #[tokio::test]
+    async fn tcp_handshake_accepts_the_token_and_rejects_others() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = Listener::Tcp {
+            listener,
+            token: "secret".to_string(),
+        };
+
+        for (presented, accepted) in [("secret", true), ("guess", false)] {
+            let client = tokio::spawn(async move {
+                let mut stream = framed(TcpStream::connect(addr).await.unwrap());
+                stream
+                    .send(presented.as_bytes().to_vec().into())
+                    .await
+                    .unwrap();
+                stream.next().await.transpose().ok().flatten()
+            });
+            let result = server.accept().await.unwrap().handshake().await;
+            assert_eq!(result.is_ok(), accepted);
+            drop(result);
+            let ack = client.await.unwrap();
+            assert_eq!(ack.as_deref() == Some(HANDSHAKE_ACK), accepted);
+        }
+    }
+}
@@ -2,7 +2,12 @@ use std::path::Path;
 
 use crate::errors::ProvisionError;
 
-pub async fn ensure_eula(runtime_dir: &Path) -> Result<(), ProvisionError> {
+// Only writes `eula=true` once acceptance is recorded; otherwise an existing
+// eula.txt is left as it is so the daemon's start gate decides.
+pub async fn ensure_eula(runtime_dir: &Path, accepted: bool) -> Result<(), ProvisionError> {
+    if !accepted {
+        return Ok(());
+    }
     let eula_path = runtime_dir.join("eula.txt");
     let content = "# Generated by atlas-runnerd\neula=true\n";
     tokio::fs::write(&eula_path, content).await?;
@@ -1,12 +1,15 @@
 use std::path::Path;
 
-use protocol::{PackBlob, decode_blob};
+use protocol::{DecodeLimits, PackBlob, decode_blob_with_limits};
 
 use crate::{
-    deps::{provider::DependencyProvider, verify},
+    deps::{mirrors, provider::DependencyProvider},
     errors::ProvisionError,
     java,
-    launch::{self, LaunchPlan},
+    launch::{self, JvmSettings, LaunchPlan},
+    now_millis,
+    progress::{ProgressFn, report},
+    report::ProvisionReport,
 };
 
 mod eula;
@@ -22,25 +25,65 @@ pub async fn ensure_applied_from_packblob_bytes(
     server_root: &Path,
     pack_blob_bytes: &[u8],
     dep_provider: &dyn DependencyProvider,
-) -> Result<LaunchPlan, ProvisionError> {
+    jvm: &JvmSettings,
+    eula_accepted: bool,
+) -> Result<(LaunchPlan, ProvisionReport), ProvisionError> {
+    ensure_applied_from_packblob_bytes_with_progress(
+        server_root,
+        pack_blob_bytes,
+        dep_provider,
+        jvm,
+        eula_accepted,
+        &|_| {},
+    )
+    .await
+}
+
+pub async fn ensure_applied_from_packblob_bytes_with_progress(
+    server_root: &Path,
+    pack_blob_bytes: &[u8],
+    dep_provider: &dyn DependencyProvider,
+    jvm: &JvmSettings,
+    eula_accepted: bool,
+    progress: &ProgressFn,
+) -> Result<(LaunchPlan, ProvisionReport), ProvisionError> {
+    let mut timings = ProvisionReport::default();
+
     // 1) Decode PackBlob
+    let started = now_millis();
     let pack = decode_packblob(pack_blob_bytes)?;
+    timings.record("decode", started);
 
     // 2) Ensure java runtime is available
+    report(
+        progress,
+        "java",
+        format!(
+            "Preparing Java for Minecraft {}",
+            pack.metadata.minecraft_version
+        ),
+        0,
+        1,
+    );
+    let started = now_millis();
     let java_bin =
         java::ensure_java_for_minecraft(server_root, &pack.metadata.minecraft_version, None)
             .await?;
+    timings.record("java", started);
 
     // 3) Short-circuit if already applied
     if marker::is_pack_applied(server_root, &pack).await? {
+        report(progress, "assemble", "Pack already applied", 1, 1);
+        launch::check_java_version(&pack, &java_bin).await?;
         let mut plan = launch::read_launch_plan(server_root).await?;
         launch::apply_java_path_to_plan(&mut plan, &java_bin);
+        plan.apply_jvm_settings(jvm);
         let current_dir = server_root.join("current");
-        eula::ensure_eula(&current_dir).await?;
+        eula::ensure_eula(&current_dir, eula_accepted).await?;
         server_properties::ensure_whitelist_enforced(&current_dir).await?;
         server_properties::ensure_rcon_configured(&current_dir).await?;
         launch::write_launch_plan_to_dir(&current_dir, &plan).await?;
-        return Ok(plan);
+        return Ok((plan, timings));
     }
 
     // 4) Build apply plan (what files to write where)
@@ -48,44 +91,322 @@ pub async fn ensure_applied_from_packblob_bytes(
 
     // 5) Stage everything into staging dir
     let staging_dir = staging::create_staging_dir(server_root).await?;
-    let staging_current = staging_dir.join("current"); // staging/current/...
+    let result = stage_and_promote(
+        server_root,
+        &staging_dir,
+        &pack,
+        plan,
+        &java_bin,
+        dep_provider,
+        jvm,
+        eula_accepted,
+        progress,
+        &mut timings,
+    )
+    .await;
+    // After promotion only the empty nonce dir is left; after a failure it
+    // holds a half-built tree. Remove it either way so a retry starts clean.
+    if let Err(err) = tokio::fs::remove_dir_all(&staging_dir).await
+        && err.kind() != std::io::ErrorKind::NotFound
+    {
+        log::warn!(
+            "failed to remove staging dir {}: {err}",
+            staging_dir.display()
+        );
+    }
+    result.map(|launch_plan| (launch_plan, timings))
+}
 
+#[allow(clippy::too_many_arguments)]
+async fn stage_and_promote(
+    server_root: &Path,
+    staging_dir: &Path,
+    pack: &PackBlob,
+    plan: plan::ApplyPlan,
+    java_bin: &Path,
+    dep_provider: &dyn DependencyProvider,
+    jvm: &JvmSettings,
+    eula_accepted: bool,
+    progress: &ProgressFn,
+    timings: &mut ProvisionReport,
+) -> Result<LaunchPlan, ProvisionError> {
+    let staging_current = staging_dir.join("current"); // staging/current/...
     staging::ensure_dir(&staging_current).await?;
 
     // 5a) Write inline files
-    plan::write_inline_files(&pack, &staging_current).await?;
+    let started = now_millis();
+    timings.files_written = plan::write_inline_files(pack, &staging_current).await?;
+    timings.record("write_files", started);
 
     // 5b) Fetch+verify+write dependencies
-    for item in plan.deps {
-        let bytes = dep_provider.fetch(&item.dep).await?;
-        verify::verify_dependency_bytes(&item.dep, &bytes)?;
+    let total = plan.deps.len() as u64;
+    for (index, item) in plan.deps.into_iter().enumerate() {
+        report(
+            progress,
+            "fetch",
+            format!("Downloading {}", item.dep.pointer_path),
+            index as u64,
+            total,
+        );
+        let started = now_millis();
+        let bytes = mirrors::fetch_verified(dep_provider, &item.dep).await?;
+        timings.record("fetch_artifacts", started);
+
+        let started = now_millis();
         plan::write_dependency_bytes(&item, &bytes, &staging_current).await?;
+        timings.record("link_artifacts", started);
+        timings.artifacts_linked += 1;
     }
 
+    report(progress, "fetch", "Dependencies downloaded", total, total);
+
     // 5c) Ensure server loader is installed
-    loader::ensure_loader_installed(server_root, &staging_current, &pack.metadata, &java_bin)
+    report(progress, "loader", "Installing server loader", 0, 1);
+    let started = now_millis();
+    loader::ensure_loader_installed(server_root, &staging_current, &pack.metadata, java_bin)
         .await?;
+    timings.record("loader", started);
 
     // 6) Preserve selected files from existing current -> staging/current
+    report(progress, "assemble", "Assembling server runtime", 0, 1);
+    let started = now_millis();
     preserve::preserve_from_existing(server_root, &staging_current).await?;
 
     // 7) Ensure EULA + whitelist + RCON are enforced
-    eula::ensure_eula(&staging_current).await?;
+    eula::ensure_eula(&staging_current, eula_accepted).await?;
     server_properties::ensure_whitelist_enforced(&staging_current).await?;
     server_properties::ensure_rcon_configured(&staging_current).await?;
 
     // 8) Write launch plan + applied marker into staging/current/.runner/
-    let launch_plan = launch::derive_launch_plan(&pack, &staging_current, &java_bin)?;
+    let launch_plan = launch::derive_launch_plan(pack, &staging_current, java_bin, jvm).await?;
     launch::write_launch_plan_to_dir(&staging_current, &launch_plan).await?;
-    marker::write_applied_marker_to_dir(&staging_current, &pack).await?;
+    marker::write_applied_marker_to_dir(&staging_current, pack).await?;
 
     // 9) Promote staging/current to server_root/current atomically
     staging::promote(server_root, &staging_current).await?;
+    timings.record("assemble", started);
+    report(progress, "assemble", "Server runtime ready", 1, 1);
 
     Ok(launch_plan)
 }
 
 fn decode_packblob(bytes: &[u8]) -> Result<PackBlob, ProvisionError> {
-    decode_blob(bytes)
+    decode_blob_with_limits(bytes, &DecodeLimits::untrusted())
         .map_err(|e| ProvisionError::Invalid(format!("Failed to decode PackBlob: {e}")))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::{Path, PathBuf};
+
+    use protocol::{
+        Dependency, DependencyKind, DependencySide, Hash, HashAlgorithm, Loader, Manifest,
+        PackBlob, PackMetadata, PlatformFilter, encode_blob_default,
+    };
+    use sha2::Digest;
+
+    use super::ensure_applied_from_packblob_bytes;
+    use crate::deps::memory::InMemoryDependencyProvider;
+    use crate::hashing::sha256_extracted_tree;
+    use crate::launch::JvmSettings;
+
+    const MOD_URL: &str = "https://cdn.example.invalid/fabric-api.jar";
+
+    fn temp_server_root(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("atlas-apply-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // A verified "JDK 17" whose java only prints a version banner, so the
+    // runtime install is skipped.
+    fn install_fake_java(server_root: &Path) -> PathBuf {
+        let install_dir = server_root.join(".runner/java/jdk-17");
+        let java_bin = install_dir.join("bin/java");
+        std::fs::create_dir_all(java_bin.parent().unwrap()).unwrap();
+        std::fs::write(
+            &java_bin,
+            "#!/bin/sh\necho 'openjdk version \"17.0.2\" 2022-01-18' >&2\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&java_bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let checksum_path = install_dir.join("java.hash");
+        let hash =
+            sha256_extracted_tree(&install_dir, std::slice::from_ref(&checksum_path)).unwrap();
+        std::fs::write(checksum_path, hash).unwrap();
+        java_bin
+    }
+
+    fn pack_blob(mod_bytes: &[u8]) -> Vec<u8> {
+        let pack = PackBlob {
+            metadata: PackMetadata {
+                pack_id: "pack".into(),
+                version: "1".into(),
+                minecraft_version: "1.20.1".into(),
+                loader: Loader::Fabric,
+                loader_version: "0.15.0".into(),
+                name: "Pack".into(),
+                description: String::new(),
+            },
+            manifest: Manifest {
+                dependencies: vec![Dependency {
+                    url: MOD_URL.into(),
+                    hash: Hash {
+                        algorithm: HashAlgorithm::Sha256,
+                        hex: hex::encode(sha2::Sha256::digest(mod_bytes)),
+                    },
+                    platform: PlatformFilter::default(),
+                    kind: DependencyKind::Mod,
+                    side: DependencySide::Both,
+                    pointer_path: "mods/fabric-api.mod.toml".into(),
+                    mirrors: Vec::new(),
+                }],
+            },
+            files: [
+                ("server.jar".to_string(), b"server".to_vec()),
+                ("config/pack.toml".to_string(), b"motd = 1".to_vec()),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        encode_blob_default(&pack).unwrap()
+    }
+
+    #[tokio::test]
+    async fn applies_pack_from_in_memory_artifacts() {
+        let server_root = temp_server_root("offline");
+        let java_bin = install_fake_java(&server_root);
+        let provider = InMemoryDependencyProvider::new().with_artifact(MOD_URL, b"mod".to_vec());
+        let blob = pack_blob(b"mod");
+
+        let (plan, timings) = ensure_applied_from_packblob_bytes(
+            &server_root,
+            &blob,
+            &provider,
+            &JvmSettings::default(),
+            true,
+        )
+        .await
+        .expect("offline apply");
+
+        let current = server_root.join("current");
+        assert_eq!(
+            std::fs::read(current.join("mods/fabric-api.jar")).unwrap(),
+            b"mod"
+        );
+        assert_eq!(
+            std::fs::read(current.join("config/pack.toml")).unwrap(),
+            b"motd = 1"
+        );
+        assert!(current.join(".runner/applied.json").exists());
+        assert!(
+            std::fs::read_to_string(current.join("eula.txt"))
+                .unwrap()
+                .contains("eula=true")
+        );
+        assert_eq!(
+            plan.argv,
+            [
+                java_bin.to_string_lossy().as_ref(),
+                "-jar",
+                "server.jar",
+                "nogui"
+            ]
+        );
+        assert_eq!(provider.requested(), [MOD_URL]);
+        assert_eq!((timings.files_written, timings.artifacts_linked), (2, 1));
+        let phases: Vec<&str> = timings
+            .phases
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(
+            phases,
+            [
+                "decode",
+                "java",
+                "write_files",
+                "fetch_artifacts",
+                "link_artifacts",
+                "loader",
+                "assemble"
+            ]
+        );
+
+        // An applied pack is reused without fetching again.
+        let (_, timings) = ensure_applied_from_packblob_bytes(
+            &server_root,
+            &blob,
+            &provider,
+            &JvmSettings::default(),
+            true,
+        )
+        .await
+        .expect("reapply");
+        assert_eq!((timings.files_written, timings.artifacts_linked), (0, 0));
+        assert_eq!(provider.requested(), [MOD_URL]);
+
+        let _ = std::fs::remove_dir_all(&server_root);
+    }
+
+    #[tokio::test]
+    async fn unaccepted_eula_is_not_written() {
+        let server_root = temp_server_root("eula");
+        install_fake_java(&server_root);
+        let provider = InMemoryDependencyProvider::new().with_artifact(MOD_URL, b"mod".to_vec());
+        let blob = pack_blob(b"mod");
+
+        for _ in 0..2 {
+            ensure_applied_from_packblob_bytes(
+                &server_root,
+                &blob,
+                &provider,
+                &JvmSettings::default(),
+                false,
+            )
+            .await
+            .expect("apply");
+            assert!(!server_root.join("current/eula.txt").exists());
+        }
+
+        let _ = std::fs::remove_dir_all(&server_root);
+    }
+
+    #[tokio::test]
+    async fn missing_or_mismatched_artifacts_fail_without_promoting() {
+        let server_root = temp_server_root("mismatch");
+        install_fake_java(&server_root);
+        let provider =
+            InMemoryDependencyProvider::new().with_artifact(MOD_URL, b"tampered".to_vec());
+
+        let err = ensure_applied_from_packblob_bytes(
+            &server_root,
+            &pack_blob(b"mod"),
+            &provider,
+            &JvmSettings::default(),
+            true,
+        )
+        .await
+        .expect_err("hash mismatch");
+        assert!(matches!(
+            err,
+            crate::errors::ProvisionError::Integrity { .. }
+        ));
+        assert!(!server_root.join("current").exists());
+
+        let err = ensure_applied_from_packblob_bytes(
+            &server_root,
+            &pack_blob(b"mod"),
+            &InMemoryDependencyProvider::new(),
+            &JvmSettings::default(),
+            true,
+        )
+        .await
+        .expect_err("no artifact");
+        assert!(err.to_string().contains(MOD_URL));
+
+        let _ = std::fs::remove_dir_all(&server_root);
+    }
+}
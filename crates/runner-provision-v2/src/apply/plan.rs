@@ -79,7 +79,8 @@ pub(crate) async fn write_dependency_bytes(
 pub(crate) async fn write_inline_files(
     pack: &PackBlob,
     staging_current: &Path,
-) -> Result<(), ProvisionError> {
+) -> Result<usize, ProvisionError> {
+    let mut written = 0;
     for (rel_path_str, bytes) in &pack.files {
         let rel_path = sanitize_rel_path(&*rel_path_str)?;
 
@@ -93,9 +94,10 @@ pub(crate) async fn write_inline_files(
             tokio::fs::create_dir_all(parent).await?;
         }
         tokio::fs::write(dest, bytes).await?;
+        written += 1;
     }
 
-    Ok(())
+    Ok(written)
 }
 
 fn dependency_pointer_path(dep: &Dependency) -> PathBuf {
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use protocol::Dependency;
+
+use super::provider::DependencyProvider;
+use crate::errors::ProvisionError;
+
+/// Serves canned artifacts by URL, for provisioning without network access.
+/// Unknown URLs fail like an unreachable host.
+#[derive(Default)]
+pub struct InMemoryDependencyProvider {
+    artifacts: HashMap<String, Vec<u8>>,
+    requested: Mutex<Vec<String>>,
+}
+
+impl InMemoryDependencyProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_artifact(mut self, url: impl Into<String>, bytes: impl Into<Vec<u8>>) -> Self {
+        self.insert(url, bytes);
+        self
+    }
+
+    pub fn insert(&mut self, url: impl Into<String>, bytes: impl Into<Vec<u8>>) {
+        self.artifacts.insert(url.into(), bytes.into());
+    }
+
+    /// URLs fetched so far, in request order.
+    pub fn requested(&self) -> Vec<String> {
+        self.requested
+            .lock()
+            .map(|requested| requested.clone())
+            .unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl DependencyProvider for InMemoryDependencyProvider {
+    async fn fetch(&self, dep: &Dependency) -> Result<Vec<u8>, ProvisionError> {
+        if let Ok(mut requested) = self.requested.lock() {
+            requested.push(dep.url.clone());
+        }
+        self.artifacts
+            .get(&dep.url)
+            .cloned()
+            .ok_or_else(|| ProvisionError::Invalid(format!("no artifact for {}", dep.url)))
+    }
+}
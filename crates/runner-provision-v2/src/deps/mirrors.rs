@@ -0,0 +1,91 @@
+use protocol::Dependency;
+
+use crate::errors::ProvisionError;
+
+use super::{provider::DependencyProvider, verify};
+
+/// Fetches the primary URL, then each mirror in order, returning the first
+/// download whose bytes match the dependency hash.
+pub async fn fetch_verified(
+    dep_provider: &dyn DependencyProvider,
+    dep: &Dependency,
+) -> Result<Vec<u8>, ProvisionError> {
+    let mut candidate = dep.clone();
+    let mut last_error = None;
+    for url in std::iter::once(&dep.url).chain(dep.mirrors.iter()) {
+        candidate.url = url.clone();
+        let result = match dep_provider.fetch(&candidate).await {
+            Ok(bytes) => verify::verify_dependency_bytes(&candidate, &bytes).map(|_| bytes),
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(bytes) => return Ok(bytes),
+            Err(err) => {
+                log::warn!("download from {url} failed: {err}");
+                last_error = Some(err);
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        ProvisionError::Invalid(format!("no download URL for {}", dep.pointer_path))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use protocol::{
+        Dependency, DependencyKind, DependencySide, Hash, HashAlgorithm, PlatformFilter,
+    };
+    use sha2::Digest;
+
+    use super::fetch_verified;
+    use crate::deps::provider::DependencyProvider;
+    use crate::errors::ProvisionError;
+
+    const PRIMARY: &str = "https://cdn.example.invalid/mod.jar";
+    const MIRROR: &str = "https://mirror.example.invalid/mod.jar";
+
+    struct PrimaryDown;
+
+    #[async_trait]
+    impl DependencyProvider for PrimaryDown {
+        async fn fetch(&self, dep: &Dependency) -> Result<Vec<u8>, ProvisionError> {
+            if dep.url == MIRROR {
+                Ok(b"mod-bytes".to_vec())
+            } else {
+                Err(ProvisionError::Invalid(format!("{} unreachable", dep.url)))
+            }
+        }
+    }
+
+    fn dependency(bytes: &[u8], mirrors: Vec<String>) -> Dependency {
+        Dependency {
+            url: PRIMARY.to_string(),
+            hash: Hash {
+                algorithm: HashAlgorithm::Sha256,
+                hex: hex::encode(sha2::Sha256::digest(bytes)),
+            },
+            platform: PlatformFilter::default(),
+            kind: DependencyKind::Mod,
+            side: DependencySide::Both,
+            pointer_path: "mods/mod.jar".to_string(),
+            mirrors,
+        }
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_mirror_when_primary_fails() {
+        let dep = dependency(b"mod-bytes", vec![MIRROR.to_string()]);
+        let bytes = fetch_verified(&PrimaryDown, &dep)
+            .await
+            .expect("mirror fetch");
+        assert_eq!(bytes, b"mod-bytes");
+
+        let dep = dependency(b"other-bytes", vec![MIRROR.to_string()]);
+        let err = fetch_verified(&PrimaryDown, &dep)
+            .await
+            .expect_err("hash mismatch on mirror");
+        assert!(matches!(err, ProvisionError::Integrity { .. }));
+    }
+}
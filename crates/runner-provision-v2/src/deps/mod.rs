@@ -1,2 +1,4 @@
+pub(crate) mod memory;
+pub(crate) mod mirrors;
 pub(crate) mod provider;
 pub(crate) mod verify;
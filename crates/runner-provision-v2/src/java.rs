@@ -31,7 +31,14 @@ pub async fn ensure_java_for_minecraft_with_root(
         Some(_) => minimum,
         None => minimum,
     };
+    ensure_java_major_with_root(install_root, major).await
+}
 
+/// Installs (or verifies) the runtime for exactly `major` under `install_root`.
+pub async fn ensure_java_major_with_root(
+    install_root: &Path,
+    major: u32,
+) -> Result<PathBuf, ProvisionError> {
     let os = std::env::consts::OS;
     if os != "linux" && os != "macos" {
         return Err(ProvisionError::Invalid(
@@ -97,7 +104,8 @@ pub async fn ensure_java_for_minecraft_with_root(
     Ok(java_bin)
 }
 
-fn java_version_for_minecraft(version: &str) -> u32 {
+/// Minimum Java major the given Minecraft version runs on.
+pub fn java_version_for_minecraft(version: &str) -> u32 {
     let (major, minor) = parse_minecraft_version(version);
     if major > 1 || minor >= 20 {
         if minor >= 20 && version_at_least(version, (1, 20, 5)) {
@@ -304,6 +312,11 @@ fn extract_jdk_tar(archive_path: &Path, install_dir: &Path) -> Result<(), Provis
     Ok(())
 }
 
+/// Path of an already installed runtime for `major` under `install_root`, if any.
+pub fn installed_java_bin(install_root: &Path, major: u32) -> Option<PathBuf> {
+    Some(java_bin_path(&install_root.join(format!("jdk-{major}")))).filter(|path| path.exists())
+}
+
 fn java_bin_path(install_dir: &Path) -> PathBuf {
     if std::env::consts::OS == "macos" {
         return install_dir
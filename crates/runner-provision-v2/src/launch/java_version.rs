@@ -0,0 +1,103 @@
+use crate::errors::ProvisionError;
+use crate::java::java_version_for_minecraft;
+use protocol::PackBlob;
+use std::path::Path;
+use tokio::process::Command;
+
+/// Fails when `java_bin` is older than the Java the pack's Minecraft version
+/// needs. Returns the probed major.
+pub async fn check_java_version(pack: &PackBlob, java_bin: &Path) -> Result<u32, ProvisionError> {
+    let minecraft = &pack.metadata.minecraft_version;
+    let required = java_version_for_minecraft(minecraft);
+    let major = probe_java_major(java_bin).await?;
+    if major < required {
+        return Err(ProvisionError::Invalid(format!(
+            "{} is Java {major}, but Minecraft {minecraft} requires Java {required} or newer",
+            java_bin.display()
+        )));
+    }
+    Ok(major)
+}
+
+/// Runs `java_bin -version` and parses the major from its banner.
+pub async fn probe_java_major(java_bin: &Path) -> Result<u32, ProvisionError> {
+    let output = Command::new(java_bin)
+        .arg("-version")
+        .output()
+        .await
+        .map_err(|err| {
+            ProvisionError::Invalid(format!(
+                "failed to run {} -version: {err}",
+                java_bin.display()
+            ))
+        })?;
+    if !output.status.success() {
+        return Err(ProvisionError::Invalid(format!(
+            "{} -version exited with {}",
+            java_bin.display(),
+            output.status
+        )));
+    }
+
+    // The banner goes to stderr; some wrappers print it to stdout instead.
+    let mut banner = String::from_utf8_lossy(&output.stderr).into_owned();
+    banner.push_str(&String::from_utf8_lossy(&output.stdout));
+    parse_java_major(&banner).ok_or_else(|| {
+        ProvisionError::Invalid(format!(
+            "could not read the Java version from {} -version",
+            java_bin.display()
+        ))
+    })
+}
+
+// `openjdk version "21.0.5" 2024-10-15`, `java version "1.8.0_432"`,
+// `openjdk 17.0.2 2022-01-18` or `openjdk version "23-ea"`.
+fn parse_java_major(output: &str) -> Option<u32> {
+    output.lines().find_map(|line| {
+        let (head, rest) = line.split_once(' ')?;
+        if !head.ends_with("java") && !head.ends_with("jdk") {
+            return None;
+        }
+        let rest = rest.trim_start();
+        let rest = rest.strip_prefix("version").unwrap_or(rest).trim_start();
+        let token = match rest.strip_prefix('"') {
+            Some(quoted) => quoted.split('"').next()?,
+            None => rest.split_whitespace().next()?,
+        };
+        major_from_version(token)
+    })
+}
+
+fn major_from_version(version: &str) -> Option<u32> {
+    let mut parts = version.split(['.', '_', '-', '+']);
+    let first = parts.next()?.parse::<u32>().ok()?;
+    if first == 1 {
+        return parts.next()?.parse().ok();
+    }
+    Some(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_java_major;
+
+    #[test]
+    fn parses_modern_legacy_and_early_access_banners() {
+        let modern = "openjdk version \"21.0.5\" 2024-10-15 LTS\nOpenJDK Runtime Environment Temurin-21.0.5+11 (build 21.0.5+11-LTS)\n";
+        assert_eq!(parse_java_major(modern), Some(21));
+        assert_eq!(
+            parse_java_major("java version \"1.8.0_432\"\nJava(TM) SE Runtime Environment"),
+            Some(8)
+        );
+        assert_eq!(
+            parse_java_major("openjdk version \"23-ea\" 2024-09-17"),
+            Some(23)
+        );
+        assert_eq!(parse_java_major("openjdk 17.0.2 2022-01-18"), Some(17));
+        assert_eq!(
+            parse_java_major("Picked up JAVA_TOOL_OPTIONS: -Xss4m\nopenjdk version \"17.0.9\""),
+            Some(17)
+        );
+        assert_eq!(parse_java_major("this is not java -version output"), None);
+    }
+}
@@ -3,14 +3,32 @@ use protocol::PackBlob;
 use std::fs;
 use std::path::Path;
 
+mod java_version;
 mod plan;
-pub use plan::LaunchPlan;
+pub use java_version::{check_java_version, probe_java_major};
+pub use plan::{JvmSettings, LaunchPlan};
 
-pub fn derive_launch_plan(
-    _pack: &PackBlob,
+pub async fn derive_launch_plan(
+    pack: &PackBlob,
     staging_current: &Path,
     java_bin: &Path,
+    jvm: &JvmSettings,
 ) -> Result<LaunchPlan, ProvisionError> {
+    check_java_version(pack, java_bin).await?;
+    let argv = base_launch_argv(staging_current, java_bin)?;
+    let mut plan = LaunchPlan {
+        cwd_rel: ".".into(),
+        argv,
+        injected_jvm_args: Vec::new(),
+    };
+    plan.apply_jvm_settings(jvm);
+    Ok(plan)
+}
+
+fn base_launch_argv(
+    staging_current: &Path,
+    java_bin: &Path,
+) -> Result<Vec<String>, ProvisionError> {
     let run_sh = staging_current.join("run.sh");
     if run_sh.exists() {
         let mut argv = extract_run_sh_command(&run_sh)?;
@@ -18,36 +36,18 @@ pub fn derive_launch_plan(
         if !argv.iter().any(|arg| arg.eq_ignore_ascii_case("nogui")) {
             argv.push("nogui".to_string());
         }
-        return Ok(LaunchPlan {
-            cwd_rel: ".".into(),
-            argv,
-        });
-    }
-
-    let fabric_launch = staging_current.join("fabric-server-launch.jar");
-    if fabric_launch.exists() {
-        return Ok(LaunchPlan {
-            cwd_rel: ".".into(),
-            argv: vec![
-                java_bin.to_string_lossy().to_string(),
-                "-jar".into(),
-                "fabric-server-launch.jar".into(),
-                "nogui".into(),
-            ],
-        });
+        return Ok(argv);
     }
 
-    let server_jar = staging_current.join("server.jar");
-    if server_jar.exists() {
-        return Ok(LaunchPlan {
-            cwd_rel: ".".into(),
-            argv: vec![
+    for jar in ["fabric-server-launch.jar", "server.jar"] {
+        if staging_current.join(jar).exists() {
+            return Ok(vec![
                 java_bin.to_string_lossy().to_string(),
                 "-jar".into(),
-                "server.jar".into(),
+                jar.into(),
                 "nogui".into(),
-            ],
-        });
+            ]);
+        }
     }
 
     Err(ProvisionError::Invalid(
@@ -57,8 +57,8 @@ pub fn derive_launch_plan(
 
 fn extract_run_sh_command(path: &Path) -> Result<Vec<String>, ProvisionError> {
     let contents = fs::read_to_string(path)?;
-    for raw_line in contents.lines() {
-        let line = raw_line.trim();
+    for line in shell_logical_lines(&contents) {
+        let line = line.trim();
         if line.is_empty() || line.starts_with('#') {
             continue;
         }
@@ -75,24 +75,15 @@ fn extract_run_sh_command(path: &Path) -> Result<Vec<String>, ProvisionError> {
             continue;
         }
 
-        let tokens = split_shell_words(line);
-        if tokens.is_empty() {
-            continue;
-        }
-
         let mut argv = Vec::new();
-        for token in tokens {
-            let normalized = token.trim_matches('"').trim_matches('\'');
-            if normalized.is_empty() || normalized == "$@" || normalized == "\"$@\"" {
-                continue;
-            }
-
-            let normalized = match normalized {
-                "${JAVA}" | "$JAVA" => "java",
-                "exec" => continue,
-                other => other,
+        for word in split_shell_words(line) {
+            let value = match word.value.as_str() {
+                "$@" | "${@}" | "$*" | "${*}" if word.expands => continue,
+                "exec" if argv.is_empty() && !word.quoted => continue,
+                "$JAVA" | "${JAVA}" if word.expands => "java".to_string(),
+                _ => word.value,
             };
-            argv.push(normalized.to_string());
+            argv.push(value);
         }
 
         if argv.is_empty() {
@@ -121,30 +112,103 @@ pub fn apply_java_path_to_plan(plan: &mut LaunchPlan, java_bin: &Path) {
     apply_java_path(&mut plan.argv, java_bin);
 }
 
-fn split_shell_words(input: &str) -> Vec<String> {
-    let mut out = Vec::new();
+// Joins backslash-continued lines the way sh does before they are tokenized.
+// Comment lines never continue.
+fn shell_logical_lines(contents: &str) -> Vec<String> {
+    let mut lines = Vec::new();
     let mut current = String::new();
-    let mut in_quotes = false;
+    for raw_line in contents.lines() {
+        let line = raw_line.trim_end_matches('\r');
+        if current.is_empty() && line.trim_start().starts_with('#') {
+            lines.push(line.to_string());
+            continue;
+        }
+        match line.strip_suffix('\\') {
+            Some(head) if !head.ends_with('\\') => {
+                current.push_str(head);
+                current.push(' ');
+            }
+            _ => {
+                current.push_str(line);
+                lines.push(std::mem::take(&mut current));
+            }
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+struct ShellWord {
+    value: String,
+    // Any part of the word was quoted or escaped.
+    quoted: bool,
+    // `$` was left unquoted or inside double quotes, so sh would expand it.
+    expands: bool,
+}
 
-    for ch in input.chars() {
+fn split_shell_words(input: &str) -> Vec<ShellWord> {
+    let mut out = Vec::new();
+    let mut current: Option<ShellWord> = None;
+    let mut chars = input.chars();
+
+    while let Some(ch) = chars.next() {
+        if matches!(ch, ' ' | '\t') {
+            out.extend(current.take());
+            continue;
+        }
+        let word = current.get_or_insert_with(|| ShellWord {
+            value: String::new(),
+            quoted: false,
+            expands: false,
+        });
         match ch {
+            '\'' => {
+                word.quoted = true;
+                for inner in chars.by_ref() {
+                    if inner == '\'' {
+                        break;
+                    }
+                    word.value.push(inner);
+                }
+            }
             '"' => {
-                in_quotes = !in_quotes;
+                word.quoted = true;
+                while let Some(inner) = chars.next() {
+                    match inner {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some(next @ ('"' | '\\' | '$' | '`')) => word.value.push(next),
+                            Some(next) => {
+                                word.value.push('\\');
+                                word.value.push(next);
+                            }
+                            None => word.value.push('\\'),
+                        },
+                        '$' => {
+                            word.expands = true;
+                            word.value.push(inner);
+                        }
+                        _ => word.value.push(inner),
+                    }
+                }
             }
-            ' ' | '\t' if !in_quotes => {
-                if !current.is_empty() {
-                    out.push(current.clone());
-                    current.clear();
+            '\\' => {
+                word.quoted = true;
+                if let Some(next) = chars.next() {
+                    word.value.push(next);
                 }
             }
-            _ => current.push(ch),
+            '$' => {
+                word.expands = true;
+                word.value.push(ch);
+            }
+            _ => word.value.push(ch),
         }
     }
 
-    if !current.is_empty() {
-        out.push(current);
-    }
-
+    out.extend(current);
     out
 }
 
@@ -168,3 +232,170 @@ pub async fn read_launch_plan(server_root: &Path) -> Result<LaunchPlan, Provisio
     let bytes = tokio::fs::read(path).await?;
     Ok(serde_json::from_slice(&bytes)?)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{JvmSettings, LaunchPlan, base_launch_argv};
+    use std::path::Path;
+
+    const JAVA: &str = "/opt/jdk/bin/java";
+
+    fn launch_argv(name: &str, files: &[(&str, &str)], jvm: &JvmSettings) -> Vec<String> {
+        let dir = std::env::temp_dir().join(format!("atlas-launch-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for (file, contents) in files {
+            std::fs::write(dir.join(file), contents).unwrap();
+        }
+        let argv = base_launch_argv(&dir, Path::new(JAVA)).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let mut plan = LaunchPlan {
+            cwd_rel: ".".into(),
+            argv,
+            injected_jvm_args: Vec::new(),
+        };
+        plan.apply_jvm_settings(jvm);
+        plan.argv
+    }
+
+    fn memory(max_ram_mb: u32, extra_args: &[&str]) -> JvmSettings {
+        JvmSettings {
+            max_ram_mb: Some(max_ram_mb),
+            extra_args: extra_args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn vanilla_and_fabric_jars_get_flags_before_jar() {
+        for jar in ["server.jar", "fabric-server-launch.jar"] {
+            assert_eq!(
+                launch_argv(jar, &[(jar, "")], &JvmSettings::default()),
+                [JAVA, "-jar", jar, "nogui"]
+            );
+            assert_eq!(
+                launch_argv(jar, &[(jar, "")], &memory(4096, &["-XX:+UseG1GC"])),
+                [
+                    JAVA,
+                    "-Xms4096m",
+                    "-Xmx4096m",
+                    "-XX:+UseG1GC",
+                    "-jar",
+                    jar,
+                    "nogui"
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn run_sh_flags_are_replaced_and_nogui_kept() {
+        let run_sh = "#!/usr/bin/env sh\njava -Xmx2G -XX:-UseG1GC @user_jvm_args.txt @libraries/unix_args.txt \"$@\"\n";
+        assert_eq!(
+            launch_argv(
+                "run-sh-plain",
+                &[("run.sh", run_sh)],
+                &JvmSettings::default()
+            ),
+            [
+                JAVA,
+                "-Xmx2G",
+                "-XX:-UseG1GC",
+                "@user_jvm_args.txt",
+                "@libraries/unix_args.txt",
+                "nogui"
+            ]
+        );
+        assert_eq!(
+            launch_argv(
+                "run-sh-memory",
+                &[("run.sh", run_sh)],
+                &memory(8192, &["-XX:+UseG1GC"])
+            ),
+            [
+                JAVA,
+                "-Xms8192m",
+                "-Xmx8192m",
+                "-XX:+UseG1GC",
+                "@user_jvm_args.txt",
+                "@libraries/unix_args.txt",
+                "nogui"
+            ]
+        );
+    }
+
+    #[test]
+    fn neoforge_run_sh_with_arg_files() {
+        let run_sh = concat!(
+            "#!/usr/bin/env sh\n",
+            "# Forge requires a configured set of both JVM and program arguments.\n",
+            "# Add custom JVM arguments to the user_jvm_args.txt\n",
+            "# Add custom program arguments {such as nogui} to this file in the next line before the \"$@\" or\n",
+            "#  pass them to this script directly\n",
+            "java @user_jvm_args.txt @libraries/net/neoforged/neoforge/21.1.77/unix_args.txt \"$@\"\n",
+        );
+        assert_eq!(
+            launch_argv(
+                "run-sh-neoforge",
+                &[("run.sh", run_sh)],
+                &JvmSettings::default()
+            ),
+            [
+                JAVA,
+                "@user_jvm_args.txt",
+                "@libraries/net/neoforged/neoforge/21.1.77/unix_args.txt",
+                "nogui"
+            ]
+        );
+    }
+
+    #[test]
+    fn run_sh_continued_lines_and_quoted_args() {
+        let run_sh = concat!(
+            "#!/usr/bin/env sh\n",
+            "# trailing backslash in a comment \\\n",
+            "exec \"${JAVA}\" \\\n",
+            "    -Dlog4j.configurationFile='log config/log4j2.xml' \\\n",
+            "    \"-Dmotd=Hello \\\"world\\\"\" \\\n",
+            "    @user_jvm_args.txt @\"libraries/unix args.txt\" nogui \"$@\"\n",
+        );
+        assert_eq!(
+            launch_argv(
+                "run-sh-continued",
+                &[("run.sh", run_sh)],
+                &JvmSettings::default()
+            ),
+            [
+                JAVA,
+                "-Dlog4j.configurationFile=log config/log4j2.xml",
+                "-Dmotd=Hello \"world\"",
+                "@user_jvm_args.txt",
+                "@libraries/unix args.txt",
+                "nogui"
+            ]
+        );
+    }
+
+    #[test]
+    fn reapplying_replaces_previously_injected_flags() {
+        let mut plan = LaunchPlan {
+            cwd_rel: ".".into(),
+            argv: vec![JAVA.into(), "-jar".into(), "server.jar".into()],
+            injected_jvm_args: Vec::new(),
+        };
+        assert!(plan.apply_jvm_settings(&memory(4096, &["-Dlog4j2.formatMsgNoLookups=true"])));
+        assert!(plan.apply_jvm_settings(&memory(2048, &[])));
+        assert_eq!(
+            plan.argv,
+            [JAVA, "-Xms2048m", "-Xmx2048m", "-jar", "server.jar"]
+        );
+
+        let mut python = LaunchPlan {
+            cwd_rel: ".".into(),
+            argv: vec!["python".into(), "server.py".into()],
+            injected_jvm_args: Vec::new(),
+        };
+        assert!(!python.apply_jvm_settings(&memory(4096, &[])));
+        assert_eq!(python.argv, ["python", "server.py"]);
+    }
+}
@@ -5,4 +5,99 @@ use std::path::PathBuf;
 pub struct LaunchPlan {
     pub cwd_rel: PathBuf,  // usually "."
     pub argv: Vec<String>, // e.g. ["java","-Xmx8G","-jar","server.jar","nogui"]
+    /// Flags added by [`LaunchPlan::apply_jvm_settings`], so the next apply can
+    /// drop them before adding the current ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub injected_jvm_args: Vec<String>,
+}
+
+/// Operator JVM settings from the deploy config.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JvmSettings {
+    pub max_ram_mb: Option<u32>,
+    pub extra_args: Vec<String>,
+}
+
+impl LaunchPlan {
+    /// Inserts `-Xms`/`-Xmx` and the extra flags right after the java binary,
+    /// ahead of `-jar` and `@argfile`s. Flags the plan already sets for the same
+    /// option are replaced. Returns `false` when the command is not java.
+    pub fn apply_jvm_settings(&mut self, settings: &JvmSettings) -> bool {
+        let Some(java_pos) = self.argv.iter().position(|arg| is_java_command_token(arg)) else {
+            return false;
+        };
+
+        let mut flags = Vec::new();
+        if let Some(max_ram_mb) = settings.max_ram_mb {
+            flags.push(format!("-Xms{max_ram_mb}m"));
+            flags.push(format!("-Xmx{max_ram_mb}m"));
+        }
+        flags.extend(
+            settings
+                .extra_args
+                .iter()
+                .map(|arg| arg.trim())
+                .filter(|arg| arg.starts_with('-'))
+                .map(str::to_string),
+        );
+
+        let mut previous = std::mem::take(&mut self.injected_jvm_args);
+        let options_end = jvm_options_end(&self.argv, java_pos);
+        let mut kept = Vec::with_capacity(self.argv.len() + flags.len());
+        for (index, arg) in self.argv.drain(..).enumerate() {
+            if index > java_pos && index < options_end {
+                if let Some(pos) = previous.iter().position(|flag| *flag == arg) {
+                    previous.remove(pos);
+                    continue;
+                }
+                if flags
+                    .iter()
+                    .any(|flag| jvm_flag_key(flag) == jvm_flag_key(&arg))
+                {
+                    continue;
+                }
+            }
+            kept.push(arg);
+        }
+
+        let insert_at = java_pos + 1;
+        kept.splice(insert_at..insert_at, flags.iter().cloned());
+        self.argv = kept;
+        self.injected_jvm_args = flags;
+        true
+    }
+}
+
+pub(crate) fn is_java_command_token(value: &str) -> bool {
+    let token = value.rsplit(['/', '\\']).next().unwrap_or(value);
+    token.eq_ignore_ascii_case("java")
+        || token.eq_ignore_ascii_case("java.exe")
+        || token.eq_ignore_ascii_case("javaw.exe")
+}
+
+// JVM options run from after the binary up to `-jar`, the first `@argfile`
+// or the first argument that is not an option (the main class).
+fn jvm_options_end(argv: &[String], java_pos: usize) -> usize {
+    argv.iter()
+        .enumerate()
+        .skip(java_pos + 1)
+        .find(|(_, arg)| *arg == "-jar" || arg.starts_with('@') || !arg.starts_with('-'))
+        .map(|(index, _)| index)
+        .unwrap_or(argv.len())
+}
+
+/// The option a flag sets, ignoring its value: `-Xmx4g` and `-Xmx8g` both set
+/// `-Xmx`, `-XX:+UseG1GC` and `-XX:-UseG1GC` both set `UseG1GC`.
+fn jvm_flag_key(flag: &str) -> String {
+    let lower = flag.to_ascii_lowercase();
+    for prefix in ["-xms", "-xmx", "-xss", "-xmn"] {
+        if lower.starts_with(prefix) {
+            return prefix.to_string();
+        }
+    }
+    if let Some(option) = flag.strip_prefix("-XX:") {
+        let option = option.trim_start_matches(['+', '-']);
+        return option.split('=').next().unwrap_or(option).to_string();
+    }
+    flag.split('=').next().unwrap_or(flag).to_string()
 }
@@ -5,11 +5,18 @@ pub mod deps;
 mod hashing;
 pub mod java;
 pub mod launch;
+pub mod progress;
+mod report;
 
+pub use deps::memory::InMemoryDependencyProvider;
 pub use deps::provider::DependencyProvider;
 
-pub use apply::ensure_applied_from_packblob_bytes;
-pub use launch::LaunchPlan;
+pub use apply::{
+    ensure_applied_from_packblob_bytes, ensure_applied_from_packblob_bytes_with_progress,
+};
+pub use launch::{JvmSettings, LaunchPlan};
+pub use progress::ProvisionProgress;
+pub use report::ProvisionReport;
 
 pub(crate) fn now_millis() -> u128 {
     use std::time::{SystemTime, UNIX_EPOCH};
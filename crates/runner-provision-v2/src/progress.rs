@@ -0,0 +1,25 @@
+/// A provisioning step reported while a pack is applied.
+#[derive(Debug, Clone)]
+pub struct ProvisionProgress {
+    pub phase: &'static str,
+    pub message: String,
+    pub current: u64,
+    pub total: u64,
+}
+
+pub type ProgressFn = dyn Fn(ProvisionProgress) + Send + Sync;
+
+pub(crate) fn report(
+    progress: &ProgressFn,
+    phase: &'static str,
+    message: impl Into<String>,
+    current: u64,
+    total: u64,
+) {
+    progress(ProvisionProgress {
+        phase,
+        message: message.into(),
+        current,
+        total,
+    });
+}
@@ -0,0 +1,29 @@
+use crate::now_millis;
+
+/// Where the time went while a pack was applied. Phases are in the order they
+/// ran, with durations in milliseconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProvisionReport {
+    pub phases: Vec<(String, u128)>,
+    pub files_written: usize,
+    pub artifacts_linked: usize,
+}
+
+impl ProvisionReport {
+    pub fn total_ms(&self) -> u128 {
+        self.phases.iter().map(|(_, ms)| ms).sum()
+    }
+
+    /// Records `phase` as running from `started_ms` until now.
+    pub(crate) fn record(&mut self, phase: &str, started_ms: u128) {
+        self.add(phase, now_millis().saturating_sub(started_ms));
+    }
+
+    /// Adds `ms` to `phase`, appending it on first use.
+    pub(crate) fn add(&mut self, phase: &str, ms: u128) {
+        match self.phases.iter_mut().find(|(name, _)| name == phase) {
+            Some((_, total)) => *total += ms,
+            None => self.phases.push((phase.to_string(), ms)),
+        }
+    }
+}
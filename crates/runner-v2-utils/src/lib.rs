@@ -1,15 +1,36 @@
+use std::net::{Ipv4Addr, SocketAddr};
 use std::path::{Path, PathBuf};
 
 pub struct RuntimePaths {
     pub runtime_dir: PathBuf,
     pub socket_path: PathBuf,
     pub lock_path: PathBuf,
+    pub token_path: PathBuf,
 }
 
+/// How the CLI and daemon reach each other. Both sides resolve it from the same
+/// environment, so they always agree on the endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IpcEndpoint {
+    Unix(PathBuf),
+    /// Loopback TCP; every connection must present the token stored at
+    /// `RuntimePaths::token_path`.
+    Tcp(SocketAddr),
+}
+
+pub const SOCKET_ENV: &str = "ATLAS_RUNNER_SOCKET";
+pub const TRANSPORT_ENV: &str = "ATLAS_RUNNER_TRANSPORT";
+pub const TCP_PORT_ENV: &str = "ATLAS_RUNNER_TCP_PORT";
+pub const DEFAULT_TCP_PORT: u16 = 47625;
+
 /// Runtime namespace for v2 so it never collides with v1.
 const APP_ID_V2: &str = "runner2";
 
 pub fn runtime_paths_v2() -> RuntimePaths {
+    if let Some(socket) = std::env::var_os(SOCKET_ENV).filter(|value| !value.is_empty()) {
+        return runtime_paths_for_socket(PathBuf::from(socket));
+    }
+
     // Linux: prefer XDG_RUNTIME_DIR if present.
     if let Some(xdg) = std::env::var_os("XDG_RUNTIME_DIR") {
         let dir = PathBuf::from(xdg).join(APP_ID_V2);
@@ -30,10 +51,57 @@ fn mk(runtime_dir: PathBuf) -> RuntimePaths {
     RuntimePaths {
         socket_path: runtime_dir.join("runnerd.sock"),
         lock_path: runtime_dir.join("runnerd.lock"),
+        token_path: runtime_dir.join("runnerd.token"),
+        runtime_dir,
+    }
+}
+
+/// Runtime paths for an explicit socket (`--socket` / `ATLAS_RUNNER_SOCKET`).
+/// The socket's directory becomes the runtime namespace, holding the lock,
+/// token and server roots, so each daemon needs a directory of its own.
+pub fn runtime_paths_for_socket(socket_path: PathBuf) -> RuntimePaths {
+    let runtime_dir = socket_path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| PathBuf::from("."));
+    RuntimePaths {
+        socket_path,
+        lock_path: runtime_dir.join("runnerd.lock"),
+        token_path: runtime_dir.join("runnerd.token"),
         runtime_dir,
     }
 }
 
+/// Unix socket unless `ATLAS_RUNNER_TRANSPORT=tcp`, which listens on
+/// 127.0.0.1 at `ATLAS_RUNNER_TCP_PORT` (default 47625).
+pub fn ipc_endpoint(paths: &RuntimePaths) -> Result<IpcEndpoint, String> {
+    let transport = std::env::var(TRANSPORT_ENV).unwrap_or_default();
+    match transport.trim().to_ascii_lowercase().as_str() {
+        "" | "unix" => Ok(IpcEndpoint::Unix(paths.socket_path.clone())),
+        "tcp" => {
+            let port = match std::env::var(TCP_PORT_ENV) {
+                Ok(value) => value
+                    .trim()
+                    .parse::<u16>()
+                    .ok()
+                    .filter(|port| *port != 0)
+                    .ok_or_else(|| {
+                        format!("{TCP_PORT_ENV} must be a port number, got '{value}'")
+                    })?,
+                Err(_) => DEFAULT_TCP_PORT,
+            };
+            Ok(IpcEndpoint::Tcp(SocketAddr::from((
+                Ipv4Addr::LOCALHOST,
+                port,
+            ))))
+        }
+        other => Err(format!(
+            "{TRANSPORT_ENV} must be 'unix' or 'tcp', got '{other}'"
+        )),
+    }
+}
+
 pub fn ensure_dir(p: &Path) -> std::io::Result<()> {
     std::fs::create_dir_all(p)
 }